@@ -0,0 +1,49 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use bonsol_sdk::BonsolClient;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+
+use crate::command::AllowListArgs;
+
+pub async fn manage_allow_list(
+    rpc_url: String,
+    signer: Keypair,
+    allow_list_args: AllowListArgs,
+) -> Result<()> {
+    let rpc_client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let bonsol_client = BonsolClient::with_rpc_client(rpc_client);
+    let authority = signer.pubkey();
+    let (instructions, done) = match allow_list_args {
+        AllowListArgs::Set { id, claimers } => {
+            let claimers = claimers
+                .iter()
+                .map(|c| Pubkey::from_str(c).map_err(|_| anyhow::anyhow!("Invalid claimer {}", c)))
+                .collect::<Result<Vec<_>>>()?;
+            (
+                bonsol_client
+                    .set_allow_list_v1(&authority, &id, &claimers)
+                    .await?,
+                format!(
+                    "Allow list {} at {} holds {} provers",
+                    id,
+                    bonsol_sdk::allow_list_address(&authority, &id).0,
+                    claimers.len()
+                ),
+            )
+        }
+        AllowListArgs::Close { id } => (
+            bonsol_client.close_allow_list_v1(&authority, &id).await?,
+            format!("Allow list {} closed", id),
+        ),
+    };
+    bonsol_client
+        .send_txn_standard(signer, instructions)
+        .await?;
+    println!("{}", done);
+    Ok(())
+}
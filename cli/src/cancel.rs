@@ -0,0 +1,11 @@
+use anyhow::Result;
+use bonsol_sdk::BonsolClient;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+
+pub async fn cancel(sdk: &BonsolClient, signer: Keypair, execution_id: String) -> Result<()> {
+    let instructions = sdk.cancel_v1(&signer.pubkey(), &execution_id).await?;
+    sdk.send_txn_standard(signer, instructions).await?;
+    println!("Execution {} cancelled", execution_id);
+    Ok(())
+}
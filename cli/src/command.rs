@@ -172,6 +172,68 @@ pub struct SharedDeployArgs {
     pub auto_confirm: bool,
 }
 
+#[derive(Debug, Clone, Subcommand)]
+pub enum DeploymentArgs {
+    #[command(about = "Point a deployment at a new url serving the same program")]
+    UpdateUrl {
+        #[arg(help = "The image id of the deployment", short = 'p', long)]
+        program_id: String,
+
+        #[arg(help = "The new url of the program", long)]
+        url: String,
+    },
+
+    #[command(about = "Deprecate a deployment, it accepts no new executions afterwards")]
+    Deprecate {
+        #[arg(help = "The image id of the deployment", short = 'p', long)]
+        program_id: String,
+    },
+
+    #[command(about = "Close a deployment and reclaim its rent")]
+    Close {
+        #[arg(help = "The image id of the deployment", short = 'p', long)]
+        program_id: String,
+
+        #[arg(
+            help = "Whether to automatically confirm closing the deployment",
+            short = 'y',
+            long
+        )]
+        auto_confirm: bool,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum AllowListArgs {
+    #[command(about = "Create an allow list or replace the provers it holds")]
+    Set {
+        #[arg(
+            help = "The id of the allow list, unique per authority",
+            short = 'i',
+            long
+        )]
+        id: String,
+
+        #[arg(
+            help = "The provers allowed to claim, comma delimited",
+            short = 'c',
+            long,
+            value_delimiter = ','
+        )]
+        claimers: Vec<String>,
+    },
+
+    #[command(about = "Close an allow list and reclaim its rent")]
+    Close {
+        #[arg(
+            help = "The id of the allow list, unique per authority",
+            short = 'i',
+            long
+        )]
+        id: String,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Command {
     #[command(
@@ -182,6 +244,18 @@ pub enum Command {
         deploy_args: DeployArgs,
     },
 
+    #[command(about = "Manage a deployed program, only its owner can change it")]
+    Deployment {
+        #[clap(subcommand)]
+        deployment_args: DeploymentArgs,
+    },
+
+    #[command(about = "Manage an allow list of the provers that can claim executions")]
+    AllowList {
+        #[clap(subcommand)]
+        allow_list_args: AllowListArgs,
+    },
+
     #[command(about = "Build a ZK program")]
     Build {
         #[arg(
@@ -258,6 +332,30 @@ pub enum Command {
         output_location: Option<String>,
     },
 
+    #[command(about = "Cancel a pending execution request and refund its rent and tip")]
+    Cancel {
+        #[arg(help = "The id of the execution request", short = 'e', long)]
+        execution_id: String,
+    },
+
+    #[command(about = "Register the signer as a prover or update its registration")]
+    RegisterProver {
+        #[arg(
+            help = "Where the operator publishes its name, contact and hardware",
+            short = 'u',
+            long
+        )]
+        metadata_uri: String,
+
+        #[arg(
+            help = "The prover versions the node can prove, e.g. V1_2_1",
+            short = 'v',
+            long,
+            value_delimiter = ','
+        )]
+        prover_versions: Vec<String>,
+    },
+
     #[command(about = "Initialize a new project")]
     Init {
         #[arg(short = 'd', long)]
@@ -6,7 +6,7 @@ use std::str::FromStr;
 use anyhow::{Context, Result};
 use bonsol_prover::input_resolver::{ProgramInput, ResolvedInput};
 use bonsol_sdk::instructions::CallbackConfig;
-use bonsol_sdk::{InputT, InputType, ProgramInputType};
+use bonsol_sdk::{prior_execution_input, InputT, InputType, ProgramInputType};
 use clap::Args;
 use rand::distributions::Alphanumeric;
 use rand::Rng;
@@ -71,6 +71,7 @@ impl ToString for CliInputType {
             InputType::Private => "Private".to_string(),
             InputType::PublicProof => "PublicProof".to_string(),
             InputType::PrivateLocal => "PrivateUrl".to_string(),
+            InputType::PriorExecution => "PriorExecution".to_string(),
             _ => "InvalidInputType".to_string(),
         }
     }
@@ -87,6 +88,7 @@ impl FromStr for CliInputType {
             "Private" => Ok(CliInputType(InputType::Private)),
             "PublicProof" => Ok(CliInputType(InputType::PublicProof)),
             "PrivateUrl" => Ok(CliInputType(InputType::PrivateLocal)),
+            "PriorExecution" => Ok(CliInputType(InputType::PriorExecution)),
             _ => Err(anyhow::anyhow!("Invalid input type")),
         }
     }
@@ -110,6 +112,13 @@ pub struct CliExecutionConfig {
     pub verify_input_hash: Option<bool>,
     pub input_hash: Option<String>,
     pub forward_output: Option<bool>,
+    pub min_reputation: Option<u16>,
+    pub min_proofs: Option<u64>,
+    #[serde(default, with = "bonsol_sdk::instructions::serde_helpers::pubkeys")]
+    pub allowed_claimers: Vec<Pubkey>,
+    #[serde(default, with = "bonsol_sdk::instructions::serde_helpers::optpubkey")]
+    pub claimer_allow_list: Option<Pubkey>,
+    pub auction_slots: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -295,6 +304,17 @@ pub fn execute_transform_cli_inputs(inputs: Vec<CliInput>) -> Result<Vec<InputT>
                 }
                 res.push(InputT::public(input.data.into_bytes()));
             }
+            InputType::PriorExecution => {
+                // <requester>/<execution id>
+                let (requester, execution_id) = input.data.split_once('/').ok_or_else(|| {
+                    anyhow::anyhow!("PriorExecution inputs are <requester>/<execution id>")
+                })?;
+                let requester = Pubkey::from_str(requester)?;
+                res.push(InputT::prior_execution(prior_execution_input(
+                    &requester.to_bytes(),
+                    execution_id,
+                )));
+            }
             _ => res.push(InputT::new(input_type, Some(input.data.into_bytes()))),
         }
     }
@@ -535,4 +555,26 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn test_execute_transform_prior_execution_inputs() {
+        let requester = Pubkey::new_unique();
+        let input = CliInput {
+            input_type: "PriorExecution".to_string(),
+            data: format!("{}/execution", requester),
+        };
+        let parsed_inputs = execute_transform_cli_inputs(vec![input]).unwrap();
+        assert_eq!(
+            parsed_inputs,
+            vec![InputT::prior_execution(prior_execution_input(
+                &requester.to_bytes(),
+                "execution"
+            ))]
+        );
+        let missing_requester = CliInput {
+            input_type: "PriorExecution".to_string(),
+            data: "execution".to_string(),
+        };
+        assert!(execute_transform_cli_inputs(vec![missing_requester]).is_err());
+    }
 }
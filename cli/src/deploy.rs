@@ -2,7 +2,7 @@ use std::fs::{self, File};
 use std::path::Path;
 
 use anyhow::Result;
-use bonsol_sdk::{BonsolClient, ProgramInputType};
+use bonsol_sdk::{image_checksum, BonsolClient, ProgramInputType};
 use indicatif::ProgressBar;
 use object_store::aws::AmazonS3Builder;
 use object_store::ObjectStore;
@@ -41,6 +41,7 @@ pub async fn deploy(rpc_url: String, signer: Keypair, deploy_args: DeployArgs) -
             err,
         })
     })?;
+    let checksum = image_checksum(&loaded_binary);
     let url: String = match deploy_args {
         DeployArgs::S3(s3_upload) => {
             let S3UploadArgs {
@@ -149,6 +150,7 @@ pub async fn deploy(rpc_url: String, signer: Keypair, deploy_args: DeployArgs) -
                     &signer.pubkey(),
                     &image_id,
                     manifest.size,
+                    &checksum,
                     &manifest.name,
                     &url,
                     manifest
@@ -0,0 +1,58 @@
+use anyhow::Result;
+use bonsol_sdk::BonsolClient;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+
+use crate::command::DeploymentArgs;
+
+pub async fn manage_deployment(
+    rpc_url: String,
+    signer: Keypair,
+    deployment_args: DeploymentArgs,
+) -> Result<()> {
+    let rpc_client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let bonsol_client = BonsolClient::with_rpc_client(rpc_client);
+    let owner = signer.pubkey();
+    let (instructions, done) = match deployment_args {
+        DeploymentArgs::UpdateUrl { program_id, url } => (
+            bonsol_client
+                .update_deployment_url_v1(&owner, &program_id, &url)
+                .await?,
+            format!("{} now served from {}", program_id, url),
+        ),
+        DeploymentArgs::Deprecate { program_id } => (
+            bonsol_client
+                .deprecate_deployment_v1(&owner, &program_id)
+                .await?,
+            format!("{} deprecated", program_id),
+        ),
+        DeploymentArgs::Close {
+            program_id,
+            auto_confirm,
+        } => {
+            if !auto_confirm {
+                println!("Closing {program_id} cannot be undone and provers will stop serving it. Are you sure you want to continue? (y/n)");
+                let mut input = String::new();
+                std::io::stdin().read_line(&mut input)?;
+                let response = input.trim();
+                if response != "y" {
+                    println!("Response: {response}\nAborting...");
+                    return Ok(());
+                }
+            }
+            (
+                bonsol_client
+                    .close_deployment_v1(&owner, &program_id)
+                    .await?,
+                format!("{} closed", program_id),
+            )
+        }
+    };
+    bonsol_client
+        .send_txn_standard(signer, instructions)
+        .await?;
+    println!("{}", done);
+    Ok(())
+}
@@ -164,6 +164,23 @@ pub async fn execute(
             .execution_config
             .forward_output
             .unwrap_or(false),
+        min_reputation: execution_request_file
+            .execution_config
+            .min_reputation
+            .unwrap_or(0),
+        min_proofs: execution_request_file
+            .execution_config
+            .min_proofs
+            .unwrap_or(0),
+        allowed_claimers: execution_request_file
+            .execution_config
+            .allowed_claimers
+            .clone(),
+        claimer_allow_list: execution_request_file.execution_config.claimer_allow_list,
+        auction_slots: execution_request_file
+            .execution_config
+            .auction_slots
+            .unwrap_or(0),
     };
     let current_block = sdk.get_current_slot().await?;
     let expiry = expiry + current_block;
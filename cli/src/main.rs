@@ -14,12 +14,16 @@ use crate::command::{BonsolCli, Command};
 use crate::common::{execute_get_inputs, load_solana_config, sol_check, ZkProgramManifest};
 use crate::error::{BonsolCliError, ZkManifestError};
 
+mod allow_list;
 mod build;
+mod cancel;
 mod deploy;
+mod deployment;
 mod estimate;
 mod execute;
 mod init;
 mod prove;
+mod register_prover;
 
 #[cfg(all(test, feature = "integration-tests"))]
 mod tests;
@@ -50,6 +54,22 @@ async fn main() -> anyhow::Result<()> {
 
             deploy::deploy(rpc_url, keypair, deploy_args).await
         }
+        Command::AllowList { allow_list_args } => {
+            let (rpc_url, keypair) = load_solana_config(config, rpc_url, keypair)?;
+            if !sol_check(rpc_url.clone(), keypair.pubkey()).await {
+                return Err(BonsolCliError::InsufficientFunds(keypair.pubkey().to_string()).into());
+            }
+
+            allow_list::manage_allow_list(rpc_url, keypair, allow_list_args).await
+        }
+        Command::Deployment { deployment_args } => {
+            let (rpc_url, keypair) = load_solana_config(config, rpc_url, keypair)?;
+            if !sol_check(rpc_url.clone(), keypair.pubkey()).await {
+                return Err(BonsolCliError::InsufficientFunds(keypair.pubkey().to_string()).into());
+            }
+
+            deployment::manage_deployment(rpc_url, keypair, deployment_args).await
+        }
         Command::Estimate {
             manifest_path,
             input_file,
@@ -154,6 +174,21 @@ async fn main() -> anyhow::Result<()> {
             )
             .await
         }
+        Command::Cancel { execution_id } => {
+            let (rpc_url, keypair) = load_solana_config(config, rpc_url, keypair)?;
+            let sdk = BonsolClient::new(rpc_url);
+
+            cancel::cancel(&sdk, keypair, execution_id).await
+        }
+        Command::RegisterProver {
+            metadata_uri,
+            prover_versions,
+        } => {
+            let (rpc_url, keypair) = load_solana_config(config, rpc_url, keypair)?;
+            let sdk = BonsolClient::new(rpc_url);
+
+            register_prover::register_prover(&sdk, keypair, metadata_uri, prover_versions).await
+        }
         Command::Init { project_name, dir } => init::init_project(&project_name, dir),
     }
 }
@@ -0,0 +1,36 @@
+use anyhow::Result;
+use bonsol_sdk::prover_version::{ProverVersion, VERSION_V1_0_1, VERSION_V1_2_1};
+use bonsol_sdk::{prover_address, BonsolClient};
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+
+pub async fn register_prover(
+    sdk: &BonsolClient,
+    signer: Keypair,
+    metadata_uri: String,
+    prover_versions: Vec<String>,
+) -> Result<()> {
+    let prover_versions = prover_versions
+        .iter()
+        .map(|version| parse_prover_version(version))
+        .collect::<Result<Vec<_>>>()?;
+    let operator = signer.pubkey();
+    let instructions = sdk
+        .register_prover_v1(&operator, &metadata_uri, &prover_versions)
+        .await?;
+    sdk.send_txn_standard(signer, instructions).await?;
+    println!(
+        "Prover {} registered at {}",
+        operator,
+        prover_address(&operator).0
+    );
+    Ok(())
+}
+
+fn parse_prover_version(version: &str) -> Result<ProverVersion> {
+    match version {
+        "V1_0_1" => Ok(VERSION_V1_0_1),
+        "V1_2_1" => Ok(VERSION_V1_2_1),
+        _ => Err(anyhow::anyhow!("Unknown prover version {}", version)),
+    }
+}
@@ -0,0 +1,321 @@
+//! Http api for operators to inspect a running node and steer it.
+//! * `GET /inflight` lists the inflight proofs with their claim status and expiry
+//! * `GET /images` lists the loaded images with their size and when they were last used
+//! * `GET /health` reports the ingester's connection, the current block and whether claiming is
+//!   paused
+//! * `POST /claiming/pause` and `POST /claiming/resume` stop and restart claiming new executions
+//! * `DELETE /images/{image_id}` evicts an image from memory
+//! * `POST /images/{image_id}/load` downloads an image from its deployment and loads it
+use {
+    crate::{
+        ingest::{IngesterHealth, IngesterStatus},
+        risc0_runner::{Eviction, ImageSummary, InflightProofSummary, RunnerHandle},
+    },
+    anyhow::{bail, Result},
+    axum::{
+        extract::{Path, Request, State},
+        http::{header::AUTHORIZATION, StatusCode},
+        middleware::{self, Next},
+        response::{IntoResponse, Response},
+        routing::{delete, get, post},
+        Json, Router,
+    },
+    serde::Serialize,
+    std::sync::Arc,
+    subtle::ConstantTimeEq,
+    tokio::{net::TcpListener, task::JoinHandle},
+    tracing::{error, info, warn},
+};
+
+#[derive(Clone)]
+pub struct AdminState {
+    runner: RunnerHandle,
+    ingester: Arc<IngesterHealth>,
+    auth_token: Option<String>,
+}
+
+impl AdminState {
+    pub const fn new(
+        runner: RunnerHandle,
+        ingester: Arc<IngesterHealth>,
+        auth_token: Option<String>,
+    ) -> Self {
+        AdminState {
+            runner,
+            ingester,
+            auth_token,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct NodeHealth {
+    /// None when the rpc could not be reached
+    current_block: Option<u64>,
+    claiming_paused: bool,
+    ingester: IngesterStatus,
+}
+
+pub fn router(state: AdminState) -> Router {
+    Router::new()
+        .route("/inflight", get(inflight_proofs))
+        .route("/images", get(images))
+        .route("/images/:image_id", delete(evict_image))
+        .route("/images/:image_id/load", post(load_image))
+        .route("/health", get(health))
+        .route("/claiming/pause", post(pause_claiming))
+        .route("/claiming/resume", post(resume_claiming))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authorize))
+        .with_state(state)
+}
+
+/// Serves the admin api on `bind_address` until the node exits. Without an auth token it only
+/// listens on a loopback address.
+pub async fn serve(bind_address: &str, state: AdminState) -> Result<JoinHandle<()>> {
+    let listener = TcpListener::bind(bind_address).await?;
+    let local_addr = listener.local_addr()?;
+    if state.auth_token.is_none() && !local_addr.ip().is_loopback() {
+        bail!(
+            "The admin api needs an auth_token to listen on {}",
+            local_addr
+        );
+    }
+    info!("Admin api listening on {}", local_addr);
+    let app = router(state);
+    Ok(tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            error!("Admin api stopped: {:?}", e);
+        }
+    }))
+}
+
+async fn authorize(State(state): State<AdminState>, request: Request, next: Next) -> Response {
+    if let Some(token) = &state.auth_token {
+        let authorized = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|bearer| bool::from(bearer.as_bytes().ct_eq(token.as_bytes())));
+        if !authorized {
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+    }
+    next.run(request).await
+}
+
+async fn inflight_proofs(State(state): State<AdminState>) -> Json<Vec<InflightProofSummary>> {
+    Json(state.runner.inflight_proofs())
+}
+
+async fn images(State(state): State<AdminState>) -> Json<Vec<ImageSummary>> {
+    Json(state.runner.loaded_images().await)
+}
+
+async fn health(State(state): State<AdminState>) -> Json<NodeHealth> {
+    let current_block = state
+        .runner
+        .current_block()
+        .await
+        .map_err(|e| warn!("Failed to get the current block: {:?}", e))
+        .ok();
+    Json(NodeHealth {
+        current_block,
+        claiming_paused: state.runner.claiming_paused(),
+        ingester: state.ingester.status(),
+    })
+}
+
+async fn pause_claiming(State(state): State<AdminState>) -> StatusCode {
+    state.runner.set_claiming_paused(true);
+    StatusCode::NO_CONTENT
+}
+
+async fn resume_claiming(State(state): State<AdminState>) -> StatusCode {
+    state.runner.set_claiming_paused(false);
+    StatusCode::NO_CONTENT
+}
+
+async fn evict_image(State(state): State<AdminState>, Path(image_id): Path<String>) -> StatusCode {
+    match state.runner.evict_image(&image_id).await {
+        Eviction::Evicted => {
+            info!("Evicted image {}", image_id);
+            StatusCode::NO_CONTENT
+        }
+        Eviction::NotLoaded => StatusCode::NOT_FOUND,
+        Eviction::InUse => StatusCode::CONFLICT,
+    }
+}
+
+async fn load_image(
+    State(state): State<AdminState>,
+    Path(image_id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state.runner.load_image(&image_id).await.map_err(|e| {
+        error!("Failed to load image {}: {:?}", image_id, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+    })?;
+    info!("Loaded image {}", image_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            claim_policy::ClaimPolicy,
+            config::ProverNodeConfig,
+            risc0_runner::{ClaimStatus, ImageCache, ImageFetcher, InflightProof},
+            transaction_sender::{RpcTransactionSender, TransactionSender},
+        },
+        axum::body::{to_bytes, Body},
+        dashmap::DashMap,
+        solana_rpc_client::nonblocking::rpc_client::RpcClient,
+        solana_sdk::{
+            pubkey::Pubkey,
+            signature::{Keypair, Signature},
+        },
+        tower::ServiceExt,
+    };
+
+    fn admin(auth_token: Option<&str>) -> (Router, RunnerHandle) {
+        let (state, runner) = admin_state(auth_token);
+        (router(state), runner)
+    }
+
+    fn admin_state(auth_token: Option<&str>) -> (AdminState, RunnerHandle) {
+        let config = ProverNodeConfig::default();
+        let txn_sender: Arc<dyn TransactionSender> =
+            Arc::new(RpcTransactionSender::with_rpc_client(
+                Arc::new(RpcClient::new_mock("succeeds".to_string())),
+                Pubkey::new_unique(),
+                Arc::new(Keypair::new()),
+                config.transaction_fee_config.clone(),
+            ));
+        let inflight_proofs = Arc::new(DashMap::new());
+        inflight_proofs.insert(
+            "execution".to_string(),
+            InflightProof {
+                execution_id: "execution".to_string(),
+                image_id: "image".to_string(),
+                status: ClaimStatus::Claiming,
+                claim_signature: Signature::new_unique(),
+                submission_signature: None,
+                expiry: 2000,
+                requester: Pubkey::new_unique(),
+                program_callback: None,
+                additional_accounts: vec![],
+                prover_version: Default::default(),
+                prior_executions: vec![],
+            },
+        );
+        let runner = RunnerHandle::new(
+            Arc::new(config.clone()),
+            inflight_proofs,
+            Arc::new(ImageCache::new(1024, 5)),
+            txn_sender,
+            Arc::new(ImageFetcher::new(
+                reqwest::Client::new(),
+                config.image_source_config.clone(),
+                1024,
+            )),
+            Arc::new(ClaimPolicy::new(&config)),
+        );
+        let state = AdminState::new(
+            runner.clone(),
+            Arc::new(IngesterHealth::new("test")),
+            auth_token.map(str::to_string),
+        );
+        (state, runner)
+    }
+
+    fn request(method: &str, uri: &str, token: Option<&str>) -> Request {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        request.body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn lists_inflight_proofs_with_token() {
+        let (app, _) = admin(Some("secret"));
+
+        let response = app
+            .clone()
+            .oneshot(request("GET", "/inflight", None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .oneshot(request("GET", "/inflight", Some("secret")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let proofs: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(proofs[0]["execution_id"], "execution");
+        assert_eq!(proofs[0]["status"], "Claiming");
+        assert_eq!(proofs[0]["expiry"], 2000);
+    }
+
+    #[tokio::test]
+    async fn requires_a_token_off_loopback() {
+        let (state, _) = admin_state(None);
+        assert!(serve("0.0.0.0:0", state).await.is_err());
+        let (state, _) = admin_state(None);
+        serve("127.0.0.1:0", state).await.unwrap().abort();
+        let (state, _) = admin_state(Some("secret"));
+        serve("0.0.0.0:0", state).await.unwrap().abort();
+    }
+
+    #[tokio::test]
+    async fn pauses_and_resumes_claiming() {
+        let (app, runner) = admin(None);
+
+        let response = app
+            .clone()
+            .oneshot(request("POST", "/claiming/pause", None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert!(runner.claiming_paused());
+
+        let response = app
+            .clone()
+            .oneshot(request("GET", "/health", None))
+            .await
+            .unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let health: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(health["claiming_paused"], true);
+        // the mock rpc reports 1234 as the block height
+        assert_eq!(health["current_block"], 1234);
+        assert_eq!(health["ingester"]["connected"], false);
+
+        app.oneshot(request("POST", "/claiming/resume", None))
+            .await
+            .unwrap();
+        assert!(!runner.claiming_paused());
+    }
+
+    #[tokio::test]
+    async fn keeps_images_needed_by_inflight_proofs() {
+        let (app, _) = admin(None);
+
+        let response = app
+            .clone()
+            .oneshot(request("DELETE", "/images/image", None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = app
+            .oneshot(request("DELETE", "/images/other", None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,139 @@
+//! Decides what the node bids for auctioned execution requests.
+//! A request with `auction_slots` is claimed by the lowest bid once its auction ends. The node
+//! only bids for requests the claim policy decided to claim, the bid commits to proving the
+//! execution within the blocks the claim policy needs, counted from the end of the auction.
+use crate::config::{BiddingStrategyConfig, ProverNodeConfig};
+
+const BPS: u128 = 10_000;
+
+/// The facts about an auctioned execution request a bidding strategy decides on
+#[derive(Debug, Clone)]
+pub struct BidRequest {
+    pub tip: u64,
+    /// Lamports the claim policy estimates the proof costs
+    pub cost: u64,
+    pub current_block: u64,
+    /// Block the claim policy commits to proving the execution by when it is claimed now
+    pub computable_by: u64,
+    /// Last block the auction takes bids at
+    pub auction_end: u64,
+    pub max_block_height: u64,
+}
+
+impl BidRequest {
+    /// The block the proof is committed to when the execution is claimed right after the
+    /// auction, None when that is past the max block height
+    pub fn completion_slot(&self) -> Option<u64> {
+        let proving_blocks = self.computable_by.saturating_sub(self.current_block);
+        let completion_slot = self
+            .auction_end
+            .saturating_add(1)
+            .saturating_add(proving_blocks);
+        (completion_slot <= self.max_block_height).then_some(completion_slot)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bid {
+    /// Lamports of the tip asked for the proof
+    pub price: u64,
+    /// Block the proof is committed to
+    pub completion_slot: u64,
+}
+
+/// Decides the node's bids
+pub trait BiddingStrategy: Send + Sync {
+    /// The bid for `request`, None to stay out of the auction
+    fn bid(&self, request: &BidRequest) -> Option<Bid>;
+}
+
+/// The bidding strategy of the node's configuration
+pub struct ConfiguredBidding {
+    config: BiddingStrategyConfig,
+}
+
+impl ConfiguredBidding {
+    pub fn new(config: &ProverNodeConfig) -> Self {
+        ConfiguredBidding {
+            config: config.bidding_strategy_config.clone(),
+        }
+    }
+
+    fn price(&self, request: &BidRequest) -> Option<u64> {
+        if request.cost > request.tip {
+            return None;
+        }
+        let price = match self.config {
+            BiddingStrategyConfig::Tip => request.tip,
+            BiddingStrategyConfig::TipShare { share_bps } => {
+                let share = request.tip as u128 * share_bps as u128 / BPS;
+                (share as u64).max(request.cost)
+            }
+            BiddingStrategyConfig::CostPlus { margin_bps } => {
+                let price = request.cost as u128 * (BPS + margin_bps as u128) / BPS;
+                price.min(request.tip as u128) as u64
+            }
+        };
+        Some(price.min(request.tip))
+    }
+}
+
+impl BiddingStrategy for ConfiguredBidding {
+    fn bid(&self, request: &BidRequest) -> Option<Bid> {
+        Some(Bid {
+            price: self.price(request)?,
+            completion_slot: request.completion_slot()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn bidding(config: BiddingStrategyConfig) -> ConfiguredBidding {
+        ConfiguredBidding::new(&ProverNodeConfig {
+            bidding_strategy_config: config,
+            ..Default::default()
+        })
+    }
+
+    fn request(cost: u64) -> BidRequest {
+        BidRequest {
+            tip: 10_000,
+            cost,
+            current_block: 100,
+            computable_by: 160,
+            auction_end: 120,
+            max_block_height: 200,
+        }
+    }
+
+    #[test]
+    fn bids_commit_to_proving_after_the_auction() {
+        let bid = bidding(BiddingStrategyConfig::Tip).bid(&request(0));
+        assert_eq!(
+            bid,
+            Some(Bid {
+                price: 10_000,
+                completion_slot: 181,
+            })
+        );
+        let late = BidRequest {
+            max_block_height: 180,
+            ..request(0)
+        };
+        assert_eq!(bidding(BiddingStrategyConfig::Tip).bid(&late), None);
+    }
+
+    #[test]
+    fn prices_stay_between_the_cost_and_the_tip() {
+        let share = bidding(BiddingStrategyConfig::TipShare { share_bps: 5_000 });
+        assert_eq!(share.bid(&request(1_000)).unwrap().price, 5_000);
+        assert_eq!(share.bid(&request(6_000)).unwrap().price, 6_000);
+        let cost_plus = bidding(BiddingStrategyConfig::CostPlus { margin_bps: 2_000 });
+        assert_eq!(cost_plus.bid(&request(1_000)).unwrap().price, 1_200);
+        assert_eq!(cost_plus.bid(&request(9_000)).unwrap().price, 10_000);
+        assert_eq!(cost_plus.bid(&request(10_001)), None);
+    }
+}
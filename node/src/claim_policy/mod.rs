@@ -0,0 +1,324 @@
+//! Decides whether an execution request is worth claiming.
+//! The cost of a proof is driven by its cycle count, which is estimated per image from the proofs
+//! this node has already generated or from a dry run of the image when there is no history.
+mod bidding;
+
+pub use bidding::*;
+
+use {
+    crate::{
+        config::{ClaimPolicyConfig, ProverNodeConfig},
+        observe::*,
+    },
+    anyhow::Result,
+    bonsol_prover::{input_resolver::ProgramInput, prover::new_risc0_exec_env},
+    dashmap::DashMap,
+    risc0_binfmt::MemoryImage,
+    std::{
+        collections::VecDeque,
+        fmt,
+        sync::atomic::{AtomicBool, Ordering},
+    },
+    thiserror::Error,
+};
+
+/// Number of proof cycle observations kept per image
+const HISTORY_WINDOW: usize = 16;
+
+/// The facts about an execution request the policy decides on
+#[derive(Debug, Clone)]
+pub struct ClaimRequest<'a> {
+    pub execution_id: &'a str,
+    pub image_id: &'a str,
+    pub tip: u64,
+    pub current_block: u64,
+    pub max_block_height: u64,
+    pub estimated_cycles: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClaimDecision {
+    /// Claim and commit to submitting the proof before `computable_by`
+    Claim {
+        computable_by: u64,
+    },
+    Reject(RejectReason),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RejectReason {
+    #[error("execution expired at block {max_block_height}")]
+    Expired { max_block_height: u64 },
+    #[error("no cycle estimate for the image")]
+    NoCycleEstimate,
+    #[error("tip of {tip} lamports is below the {required} lamports asked for {cycles} cycles")]
+    Unprofitable {
+        tip: u64,
+        required: u64,
+        cycles: u64,
+    },
+    #[error("proving needs {blocks_needed} blocks but only {blocks_left} are left")]
+    InsufficientTime {
+        blocks_needed: u64,
+        blocks_left: u64,
+    },
+}
+
+impl RejectReason {
+    /// Short label used for metrics
+    pub const fn label(&self) -> &'static str {
+        match self {
+            RejectReason::Expired { .. } => "expired",
+            RejectReason::NoCycleEstimate => "no_cycle_estimate",
+            RejectReason::Unprofitable { .. } => "unprofitable",
+            RejectReason::InsufficientTime { .. } => "insufficient_time",
+        }
+    }
+}
+
+impl fmt::Display for ClaimDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClaimDecision::Claim { computable_by } => {
+                write!(f, "claim, committing to block {}", computable_by)
+            }
+            ClaimDecision::Reject(reason) => write!(f, "reject, {}", reason),
+        }
+    }
+}
+
+pub struct ClaimPolicy {
+    config: ClaimPolicyConfig,
+    cycle_history: DashMap<String, VecDeque<u64>>,
+    paused: AtomicBool,
+}
+
+impl ClaimPolicy {
+    pub fn new(config: &ProverNodeConfig) -> Self {
+        ClaimPolicy {
+            config: config.claim_policy_config.clone(),
+            cycle_history: DashMap::new(),
+            paused: AtomicBool::new(false),
+        }
+    }
+
+    /// Stops or restarts claiming new executions, executions already claimed are still proven
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+        info!("Claiming {}", if paused { "paused" } else { "resumed" });
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Records the total cycles of a proof generated for `image_id`
+    pub fn record_cycles(&self, image_id: &str, cycles: u64) {
+        let mut history = self.cycle_history.entry(image_id.to_string()).or_default();
+        if history.len() == HISTORY_WINDOW {
+            history.pop_front();
+        }
+        history.push_back(cycles);
+    }
+
+    /// The largest cycle count seen in the recent proofs of `image_id`, inputs change the cycle
+    /// count of a program so the estimate stays on the expensive side
+    pub fn estimate_cycles(&self, image_id: &str) -> Option<u64> {
+        self.cycle_history
+            .get(image_id)
+            .and_then(|history| history.iter().max().copied())
+    }
+
+    /// Whether a dry run should be used to estimate the cycles of an image without history
+    pub fn should_dry_run(&self, image_id: &str) -> bool {
+        matches!(
+            self.config,
+            ClaimPolicyConfig::CostAware { dry_run: true, .. }
+        ) && !self.cycle_history.contains_key(image_id)
+    }
+
+    /// Executes the image without proving to count its cycles, all inputs must be resolved
+    pub async fn dry_run(
+        &self,
+        image_id: &str,
+        memory_image: MemoryImage,
+        inputs: Vec<ProgramInput>,
+    ) -> Result<u64> {
+        let cycles = emit_event_with_duration!(MetricEvents::ClaimDryRun, {
+            tokio::task::spawn_blocking(move || -> Result<u64> {
+                let session = new_risc0_exec_env(memory_image, inputs)?.run()?;
+                Ok(session.total_cycles)
+            })
+            .await?
+        }, image_id => image_id)?;
+        self.record_cycles(image_id, cycles);
+        Ok(cycles)
+    }
+
+    pub fn evaluate(&self, request: &ClaimRequest) -> ClaimDecision {
+        if request.current_block >= request.max_block_height {
+            return ClaimDecision::Reject(RejectReason::Expired {
+                max_block_height: request.max_block_height,
+            });
+        }
+        match &self.config {
+            ClaimPolicyConfig::AcceptAll => ClaimDecision::Claim {
+                computable_by: request.max_block_height,
+            },
+            ClaimPolicyConfig::CostAware {
+                lamports_per_megacycle,
+                cycles_per_block,
+                overhead_blocks,
+                default_cycle_estimate,
+                ..
+            } => {
+                let cycles = match request.estimated_cycles.or(*default_cycle_estimate) {
+                    Some(cycles) => cycles,
+                    None => return ClaimDecision::Reject(RejectReason::NoCycleEstimate),
+                };
+                let required = proving_cost(cycles, *lamports_per_megacycle);
+                if request.tip < required {
+                    return ClaimDecision::Reject(RejectReason::Unprofitable {
+                        tip: request.tip,
+                        required,
+                        cycles,
+                    });
+                }
+                let blocks_needed = cycles
+                    .div_ceil((*cycles_per_block).max(1))
+                    .saturating_add(*overhead_blocks);
+                let blocks_left = request.max_block_height - request.current_block;
+                if blocks_needed > blocks_left {
+                    return ClaimDecision::Reject(RejectReason::InsufficientTime {
+                        blocks_needed,
+                        blocks_left,
+                    });
+                }
+                ClaimDecision::Claim {
+                    computable_by: request.current_block + blocks_needed,
+                }
+            }
+        }
+    }
+
+    /// Lamports the `CostAware` policy asks for proving `estimated_cycles`, zero for the other
+    /// policies and without an estimate
+    pub fn cost(&self, estimated_cycles: Option<u64>) -> u64 {
+        match &self.config {
+            ClaimPolicyConfig::AcceptAll => 0,
+            ClaimPolicyConfig::CostAware {
+                lamports_per_megacycle,
+                default_cycle_estimate,
+                ..
+            } => estimated_cycles
+                .or(*default_cycle_estimate)
+                .map_or(0, |cycles| proving_cost(cycles, *lamports_per_megacycle)),
+        }
+    }
+
+    /// Evaluates the request and logs the decision along with its reason
+    pub fn decide(&self, request: &ClaimRequest) -> ClaimDecision {
+        let decision = self.evaluate(request);
+        info!(
+            "Claim decision for execution {} of image {}: {}",
+            request.execution_id, request.image_id, decision
+        );
+        if let ClaimDecision::Reject(reason) = &decision {
+            emit_counter!(MetricEvents::ClaimRejected, 1, "reason" => reason.label());
+        }
+        decision
+    }
+}
+
+fn proving_cost(cycles: u64, lamports_per_megacycle: u64) -> u64 {
+    (cycles as u128 * lamports_per_megacycle as u128)
+        .div_ceil(1_000_000)
+        .min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cost_aware() -> ClaimPolicy {
+        let config = ProverNodeConfig {
+            claim_policy_config: ClaimPolicyConfig::CostAware {
+                lamports_per_megacycle: 1000,
+                cycles_per_block: 1_000_000,
+                overhead_blocks: 10,
+                default_cycle_estimate: None,
+                dry_run: false,
+            },
+            ..Default::default()
+        };
+        ClaimPolicy::new(&config)
+    }
+
+    fn request(tip: u64, estimated_cycles: Option<u64>) -> ClaimRequest<'static> {
+        ClaimRequest {
+            execution_id: "test",
+            image_id: "image",
+            tip,
+            current_block: 100,
+            max_block_height: 200,
+            estimated_cycles,
+        }
+    }
+
+    #[test]
+    fn claims_profitable_request_in_time() {
+        let policy = cost_aware();
+        assert_eq!(
+            policy.evaluate(&request(50_000, Some(50_000_000))),
+            ClaimDecision::Claim { computable_by: 160 }
+        );
+    }
+
+    #[test]
+    fn rejects_low_tip() {
+        let policy = cost_aware();
+        assert_eq!(
+            policy.evaluate(&request(49_999, Some(50_000_000))),
+            ClaimDecision::Reject(RejectReason::Unprofitable {
+                tip: 49_999,
+                required: 50_000,
+                cycles: 50_000_000,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_when_proof_cannot_finish() {
+        let policy = cost_aware();
+        assert_eq!(
+            policy.evaluate(&request(u64::MAX, Some(100_000_000))),
+            ClaimDecision::Reject(RejectReason::InsufficientTime {
+                blocks_needed: 110,
+                blocks_left: 100,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_without_estimate() {
+        let policy = cost_aware();
+        assert_eq!(
+            policy.evaluate(&request(u64::MAX, None)),
+            ClaimDecision::Reject(RejectReason::NoCycleEstimate)
+        );
+    }
+
+    #[test]
+    fn estimate_uses_recent_maximum() {
+        let policy = cost_aware();
+        assert_eq!(policy.estimate_cycles("image"), None);
+        policy.record_cycles("image", 10);
+        for _ in 0..HISTORY_WINDOW {
+            policy.record_cycles("image", 5);
+        }
+        // the larger observation has left the window
+        assert_eq!(policy.estimate_cycles("image"), Some(5));
+        policy.record_cycles("image", 7);
+        assert_eq!(policy.estimate_cycles("image"), Some(7));
+    }
+}
@@ -3,7 +3,7 @@ use {
         providers::{Format, Toml},
         Figment,
     },
-    serde::Deserialize,
+    serde::{Deserialize, Serialize},
     std::path::Path,
 };
 
@@ -18,19 +18,45 @@ pub enum IngesterConfig {
         timeout_secs: u32,
         token: String,
     },
-    WebsocketSub, //not implemented
+    /// Subscribes to the program's logs and fetches each transaction from `rpc_url`, for rpc
+    /// providers that do not support `blockSubscribe`
+    WebsocketSub {
+        wss_rpc_url: String,
+        rpc_url: String,
+    },
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub enum TransactionSenderConfig {
-    Rpc { rpc_url: String },
-    //--- below not implemented yet
-    Tpu,
+    Rpc {
+        rpc_url: String,
+    },
+    /// Sends transactions to the TPU of the upcoming leaders over QUIC, `rpc_url` and
+    /// `websocket_url` are used to follow the leader schedule and track signatures
+    Tpu {
+        rpc_url: String,
+        websocket_url: String,
+        /// Number of upcoming slots whose leaders receive each transaction
+        #[serde(default = "default_tpu_fanout_slots")]
+        fanout_slots: u64,
+    },
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub enum SignerConfig {
-    KeypairFile { path: String }, //--- below not implemented yet maybe hsm, signer server or some weird sig agg shiz
+    KeypairFile {
+        path: String,
+    },
+    /// Signs over http with a signer service, so that the node's key does not have to be kept on
+    /// the prover. See `RemoteSigner` for the protocol.
+    Remote {
+        url: String,
+        /// Sent as a bearer token with every request
+        #[serde(default)]
+        auth_token: Option<String>,
+        #[serde(default = "default_remote_signer_timeout_secs")]
+        timeout_secs: u32,
+    },
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -52,6 +78,9 @@ pub struct ProverNodeConfig {
     pub max_image_size_mb: u32,
     #[serde(default = "default_image_compression_ttl_hours")]
     pub image_compression_ttl_hours: u32,
+    /// Memory the loaded images may take before the least recently used ones are compressed
+    #[serde(default = "default_image_cache_memory_budget_mb")]
+    pub image_cache_memory_budget_mb: u32,
     #[serde(default = "default_max_input_size_mb")]
     pub max_input_size_mb: u32,
     #[serde(default = "default_image_download_timeout_secs")]
@@ -72,6 +101,35 @@ pub struct ProverNodeConfig {
     pub metrics_config: MetricsConfig,
     #[serde(default)]
     pub missing_image_strategy: MissingImageStrategy,
+    #[serde(default = "default_job_store_config")]
+    pub job_store_config: JobStoreConfig,
+    #[serde(default = "default_proof_compression_config")]
+    pub proof_compression_config: ProofCompressionConfig,
+    /// The prover versions the node serves, without any the node serves `V1_2_1` with the
+    /// `proof_compression_config` and `stark_compression_tools_path` above
+    #[serde(default)]
+    pub prover_backends: Vec<ProverBackendConfig>,
+    #[serde(default = "default_claim_policy_config")]
+    pub claim_policy_config: ClaimPolicyConfig,
+    #[serde(default = "default_bidding_strategy_config")]
+    pub bidding_strategy_config: BiddingStrategyConfig,
+    #[serde(default = "default_transaction_fee_config")]
+    pub transaction_fee_config: TransactionFeeConfig,
+    #[serde(default = "default_admin_api_config")]
+    pub admin_api_config: AdminApiConfig,
+    #[serde(default = "default_image_source_config")]
+    pub image_source_config: ImageSourceConfig,
+    #[serde(default = "default_expiry_crank_config")]
+    pub expiry_crank_config: ExpiryCrankConfig,
+    #[serde(default = "default_proof_batch_config")]
+    pub proof_batch_config: ProofBatchConfig,
+    #[serde(default = "default_receipt_archive_config")]
+    pub receipt_archive_config: ReceiptArchiveConfig,
+    /// Base urls of the nodes asked for the receipts of prior executions this node did not prove
+    #[serde(default)]
+    pub receipt_peers: Vec<String>,
+    #[serde(default = "default_receipt_api_config")]
+    pub receipt_api_config: ReceiptApiConfig,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -84,6 +142,342 @@ const fn default_metrics_config() -> MetricsConfig {
     MetricsConfig::None
 }
 
+/// Where the node persists the executions it has claimed so that they survive a restart
+#[derive(Debug, Deserialize, Clone)]
+pub enum JobStoreConfig {
+    File { path: String },
+    None,
+}
+
+const fn default_job_store_config() -> JobStoreConfig {
+    JobStoreConfig::None
+}
+
+/// Where the node keeps the receipts, journals and seals of the executions it proved, executions
+/// assuming them are proven with these receipts
+#[derive(Debug, Deserialize, Clone)]
+pub enum ReceiptArchiveConfig {
+    File {
+        path: String,
+    },
+    /// An S3 compatible bucket, the credentials are read from the `AWS_*` environment variables
+    S3 {
+        bucket: String,
+        region: String,
+        /// Endpoint of an S3 compatible store other than AWS
+        #[serde(default)]
+        endpoint: Option<String>,
+        /// Path the receipts are kept under in the bucket
+        #[serde(default)]
+        prefix: Option<String>,
+    },
+    None,
+}
+
+const fn default_receipt_archive_config() -> ReceiptArchiveConfig {
+    ReceiptArchiveConfig::None
+}
+
+/// A public http api serving the archived receipts and seals
+#[derive(Debug, Deserialize, Clone)]
+pub enum ReceiptApiConfig {
+    Http {
+        /// Address the api listens on, e.g. `0.0.0.0:8091`
+        bind_address: String,
+    },
+    None,
+}
+
+const fn default_receipt_api_config() -> ReceiptApiConfig {
+    ReceiptApiConfig::None
+}
+
+/// An http api to inspect the node and steer it while it runs
+#[derive(Debug, Deserialize, Clone)]
+pub enum AdminApiConfig {
+    Http {
+        /// Address the api listens on, e.g. `127.0.0.1:8090`
+        bind_address: String,
+        /// When set every request must carry it as a bearer token, it is required unless the api
+        /// listens on a loopback address
+        #[serde(default)]
+        auth_token: Option<String>,
+    },
+    None,
+}
+
+const fn default_admin_api_config() -> AdminApiConfig {
+    AdminApiConfig::None
+}
+
+/// Closes the executions that expire without being settled with `ExpireV1`, the node is paid the
+/// crank fee for each
+#[derive(Debug, Deserialize, Clone)]
+pub enum ExpiryCrankConfig {
+    Enabled {
+        /// Time between two checks for expired executions
+        #[serde(default = "default_expiry_crank_interval_secs")]
+        interval_secs: u64,
+    },
+    Disabled,
+}
+
+const fn default_expiry_crank_config() -> ExpiryCrankConfig {
+    ExpiryCrankConfig::Disabled
+}
+
+const fn default_expiry_crank_interval_secs() -> u64 {
+    10
+}
+
+/// Settles the proofs that are ready within a window with one `StatusBatchV1` instead of a
+/// `StatusV1` each. The proofs are aggregated by the batch image, it has to be the batch image of
+/// the verifier config of the proofs' prover version and be in the image folder.
+#[derive(Debug, Deserialize, Clone)]
+pub enum ProofBatchConfig {
+    Enabled {
+        /// Hex image id of the batch image
+        image_id: String,
+        /// Time a ready proof waits for others to batch with
+        #[serde(default = "default_proof_batch_window_ms")]
+        window_ms: u64,
+        /// Most proofs in one batch, a batch settling executions with many callback accounts
+        /// may still not fit in a transaction and is then submitted proof by proof
+        #[serde(default = "default_max_proof_batch_size")]
+        max_batch_size: usize,
+    },
+    Disabled,
+}
+
+const fn default_proof_batch_config() -> ProofBatchConfig {
+    ProofBatchConfig::Disabled
+}
+
+const fn default_proof_batch_window_ms() -> u64 {
+    2_000
+}
+
+const fn default_max_proof_batch_size() -> usize {
+    2
+}
+
+/// Where images are downloaded from besides the url of their deployment
+#[derive(Debug, Deserialize, Clone)]
+pub struct ImageSourceConfig {
+    /// Gateways `ipfs://` urls are resolved through, in the order they are tried
+    #[serde(default = "default_ipfs_gateways")]
+    pub ipfs_gateways: Vec<String>,
+    /// Gateways `ar://` urls are resolved through, in the order they are tried
+    #[serde(default = "default_arweave_gateways")]
+    pub arweave_gateways: Vec<String>,
+    /// Base urls serving images at `{mirror}/{image_id}`, tried after the deployment's url
+    #[serde(default)]
+    pub mirrors: Vec<String>,
+}
+
+fn default_image_source_config() -> ImageSourceConfig {
+    ImageSourceConfig {
+        ipfs_gateways: default_ipfs_gateways(),
+        arweave_gateways: default_arweave_gateways(),
+        mirrors: vec![],
+    }
+}
+
+fn default_ipfs_gateways() -> Vec<String> {
+    vec![
+        "https://ipfs.io".to_string(),
+        "https://dweb.link".to_string(),
+    ]
+}
+
+fn default_arweave_gateways() -> Vec<String> {
+    vec!["https://arweave.net".to_string()]
+}
+
+/// How the stark receipt is compressed into a groth16 proof, `Subprocess` runs the x86 binaries in
+/// `stark_compression_tools_path` while `Native` proves in process from the circuit wasm and zkey
+#[derive(Debug, Deserialize, Clone)]
+pub enum ProofCompressionConfig {
+    Subprocess,
+    Native {
+        witness_wasm_path: String,
+        zkey_path: String,
+    },
+}
+
+const fn default_proof_compression_config() -> ProofCompressionConfig {
+    ProofCompressionConfig::Subprocess
+}
+
+/// A prover backend serving the execution requests pinned to its `version`, every version is
+/// proven by a prover of its own risc0 release and compresses its proofs with its own circuit
+/// artifacts
+#[derive(Debug, Deserialize, Clone)]
+pub struct ProverBackendConfig {
+    pub version: ProverVersionConfig,
+    #[serde(default = "default_prover_config")]
+    pub prover_config: ProverConfig,
+    #[serde(default = "default_proof_compression_config")]
+    pub proof_compression_config: ProofCompressionConfig,
+    #[serde(default = "default_stark_compression_tools_path")]
+    pub stark_compression_tools_path: String,
+}
+
+/// How a backend proves its executions, `Linked` proves in process with the risc0 release the
+/// node is built with and only serves that release's version. `Subprocess` runs the prover at
+/// `prover_path`, built against the release of the backend's version.
+#[derive(Debug, Deserialize, Clone)]
+pub enum ProverConfig {
+    Linked,
+    Subprocess { prover_path: String },
+}
+
+const fn default_prover_config() -> ProverConfig {
+    ProverConfig::Linked
+}
+
+/// The prover versions a node can serve
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ProverVersionConfig {
+    V1_0_1,
+    #[default]
+    V1_2_1,
+}
+
+/// Decides which execution requests the node claims
+#[derive(Debug, Deserialize, Clone)]
+pub enum ClaimPolicyConfig {
+    /// Claims every request while the node has capacity
+    AcceptAll,
+    /// Claims a request only when the tip pays for the estimated cycles and the proof can be
+    /// submitted before the request expires
+    CostAware {
+        /// Price of proving one million cycles
+        lamports_per_megacycle: u64,
+        /// Cycles this node proves per block, used to estimate the blocks needed to prove
+        #[serde(default = "default_cycles_per_block")]
+        cycles_per_block: u64,
+        /// Blocks reserved for compression and submitting the proof
+        #[serde(default = "default_overhead_blocks")]
+        overhead_blocks: u64,
+        /// Estimate used for images without proof history
+        #[serde(default)]
+        default_cycle_estimate: Option<u64>,
+        /// Execute images without proof history to count their cycles
+        #[serde(default)]
+        dry_run: bool,
+    },
+}
+
+const fn default_claim_policy_config() -> ClaimPolicyConfig {
+    ClaimPolicyConfig::AcceptAll
+}
+
+const fn default_cycles_per_block() -> u64 {
+    250_000
+}
+
+const fn default_overhead_blocks() -> u64 {
+    150
+}
+
+/// What the node bids for auctioned execution requests it decided to claim
+#[derive(Debug, Deserialize, Clone)]
+pub enum BiddingStrategyConfig {
+    /// Bids the full tip
+    Tip,
+    /// Bids a share of the tip in basis points, never less than the cost of the proof
+    TipShare { share_bps: u16 },
+    /// Bids the cost of the proof estimated by the `CostAware` claim policy plus a margin in
+    /// basis points, never more than the tip
+    CostPlus { margin_bps: u16 },
+}
+
+const fn default_bidding_strategy_config() -> BiddingStrategyConfig {
+    BiddingStrategyConfig::Tip
+}
+
+/// Compute budget of the transactions the node submits
+#[derive(Debug, Deserialize, Clone)]
+pub struct TransactionFeeConfig {
+    /// Compute unit limit of claim transactions
+    #[serde(default = "default_claim_compute_units")]
+    pub claim_compute_units: u32,
+    /// Compute unit limit of the bids for auctioned executions
+    #[serde(default = "default_bid_compute_units")]
+    pub bid_compute_units: u32,
+    /// Compute unit limit of proof submissions, which verify the groth16 proof and run the
+    /// callback
+    #[serde(default = "default_submit_proof_compute_units")]
+    pub submit_proof_compute_units: u32,
+    /// Compute unit limit of the transactions closing expired executions
+    #[serde(default = "default_expire_compute_units")]
+    pub expire_compute_units: u32,
+    #[serde(default = "default_priority_fee_config")]
+    pub priority_fee: PriorityFeeConfig,
+    /// Share of the execution's tip, in basis points, that may be spent on a claim's priority fee
+    #[serde(default)]
+    pub claim_tip_share_bps: Option<u16>,
+    /// Upper bound on the compute unit price in micro lamports
+    #[serde(default = "default_max_compute_unit_price")]
+    pub max_compute_unit_price: u64,
+}
+
+/// Compute unit price of the transactions the node submits, in micro lamports
+#[derive(Debug, Deserialize, Clone)]
+pub enum PriorityFeeConfig {
+    Fixed {
+        compute_unit_price: u64,
+    },
+    /// Pays a percentile of the prices recently paid for the node's and the program's accounts
+    Dynamic {
+        #[serde(default = "default_priority_fee_percentile")]
+        percentile: u8,
+    },
+}
+
+fn default_transaction_fee_config() -> TransactionFeeConfig {
+    TransactionFeeConfig {
+        claim_compute_units: default_claim_compute_units(),
+        bid_compute_units: default_bid_compute_units(),
+        submit_proof_compute_units: default_submit_proof_compute_units(),
+        expire_compute_units: default_expire_compute_units(),
+        priority_fee: default_priority_fee_config(),
+        claim_tip_share_bps: None,
+        max_compute_unit_price: default_max_compute_unit_price(),
+    }
+}
+
+const fn default_claim_compute_units() -> u32 {
+    50_000
+}
+
+const fn default_bid_compute_units() -> u32 {
+    40_000
+}
+
+const fn default_submit_proof_compute_units() -> u32 {
+    1_400_000
+}
+
+const fn default_expire_compute_units() -> u32 {
+    20_000
+}
+
+const fn default_priority_fee_config() -> PriorityFeeConfig {
+    PriorityFeeConfig::Fixed {
+        compute_unit_price: 0,
+    }
+}
+
+const fn default_priority_fee_percentile() -> u8 {
+    75
+}
+
+const fn default_max_compute_unit_price() -> u64 {
+    1_000_000
+}
+
 fn default_stark_compression_tools_path() -> String {
     std::env::current_dir()
         .unwrap_or(Path::new("./").into())
@@ -108,6 +502,10 @@ const fn default_image_compression_ttl_hours() -> u32 {
     5
 }
 
+const fn default_image_cache_memory_budget_mb() -> u32 {
+    4096
+}
+
 const fn default_max_input_size_mb() -> u32 {
     1
 }
@@ -136,6 +534,14 @@ fn default_transaction_sender_config() -> TransactionSenderConfig {
     }
 }
 
+const fn default_tpu_fanout_slots() -> u64 {
+    12
+}
+
+const fn default_remote_signer_timeout_secs() -> u32 {
+    10
+}
+
 fn default_signer_config() -> SignerConfig {
     SignerConfig::KeypairFile {
         path: "./node-keypair.json".to_string(),
@@ -150,6 +556,7 @@ impl Default for ProverNodeConfig {
             risc0_image_folder: default_risc0_image_folder(),
             max_image_size_mb: default_max_image_size_mb(),
             image_compression_ttl_hours: default_image_compression_ttl_hours(),
+            image_cache_memory_budget_mb: default_image_cache_memory_budget_mb(),
             max_input_size_mb: default_max_input_size_mb(),
             image_download_timeout_secs: default_image_download_timeout_secs(),
             input_download_timeout_secs: default_input_download_timeout_secs(),
@@ -160,6 +567,19 @@ impl Default for ProverNodeConfig {
             stark_compression_tools_path: default_stark_compression_tools_path(),
             metrics_config: default_metrics_config(),
             missing_image_strategy: MissingImageStrategy::default(),
+            job_store_config: default_job_store_config(),
+            proof_compression_config: default_proof_compression_config(),
+            prover_backends: vec![],
+            claim_policy_config: default_claim_policy_config(),
+            bidding_strategy_config: default_bidding_strategy_config(),
+            transaction_fee_config: default_transaction_fee_config(),
+            admin_api_config: default_admin_api_config(),
+            image_source_config: default_image_source_config(),
+            expiry_crank_config: default_expiry_crank_config(),
+            proof_batch_config: default_proof_batch_config(),
+            receipt_archive_config: default_receipt_archive_config(),
+            receipt_peers: vec![],
+            receipt_api_config: default_receipt_api_config(),
         }
     }
 }
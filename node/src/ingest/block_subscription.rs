@@ -1,16 +1,22 @@
 use {
-    super::{Ingester, IngesterResult, TxChannel},
+    super::{
+        recovery::{recover, Backoff, IngestCursor},
+        Ingester, IngesterHealth, IngesterResult, TxChannel,
+    },
     crate::{
         ingest::{IngestError, IngestErrorType},
+        observe::*,
         types::BonsolInstruction,
     },
     anyhow::Result,
     solana_pubsub_client::nonblocking::pubsub_client::PubsubClient,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
     solana_rpc_client_api::config::{RpcBlockSubscribeConfig, RpcBlockSubscribeFilter},
     solana_sdk::{bs58, commitment_config::CommitmentConfig, pubkey::Pubkey},
     solana_transaction_status::{
         EncodedTransactionWithStatusMeta, UiInnerInstructions, UiInstruction, UiTransactionEncoding,
     },
+    std::sync::Arc,
     tokio::{sync::mpsc::UnboundedSender, task::JoinHandle},
     tracing::error,
 };
@@ -19,14 +25,18 @@ use futures_util::StreamExt;
 
 pub struct RpcIngester {
     rpc_url: String,
+    backfill_rpc: Option<Arc<RpcClient>>,
     op_handle: Option<JoinHandle<()>>,
+    health: Arc<IngesterHealth>,
 }
 
 impl RpcIngester {
-    pub const fn new(rpc_url: String) -> RpcIngester {
+    pub fn new(rpc_url: String, backfill_rpc: Option<Arc<RpcClient>>) -> RpcIngester {
         RpcIngester {
             op_handle: None,
             rpc_url,
+            backfill_rpc,
+            health: Arc::new(IngesterHealth::new("rpc")),
         }
     }
 }
@@ -36,10 +46,15 @@ fn filter_txs(
     program: &Pubkey,
     last_known_block: u64,
     tx: EncodedTransactionWithStatusMeta,
+    cursor: &mut IngestCursor,
 ) -> Vec<BonsolInstruction> {
     let mut res = vec![];
 
     if let Some(dtx) = tx.transaction.decode() {
+        // a transaction can be seen again when it was backfilled after a reconnect
+        if !cursor.mark_forwarded(dtx.signatures[0]) {
+            return res;
+        }
         let scc = dtx.message.static_account_keys();
         if let Some(meta) = tx.meta {
             if meta.err.is_some() {
@@ -88,8 +103,10 @@ fn filter_txs(
 
 async fn ingest(
     rpc_url: String,
+    backfill_rpc: Option<&RpcClient>,
     program: Pubkey,
     txchan: UnboundedSender<Vec<BonsolInstruction>>,
+    cursor: &mut IngestCursor,
 ) -> IngesterResult {
     let c = PubsubClient::new(&rpc_url).await.map_err(|e| IngestError {
         code: IngestErrorType::RpcError,
@@ -112,18 +129,31 @@ async fn ingest(
             code: IngestErrorType::RpcError,
             message: e.to_string(),
         })?;
+    recover("rpc", backfill_rpc, &program, cursor, &txchan)
+        .await
+        .map_err(|e| IngestError {
+            code: IngestErrorType::RpcError,
+            message: e.to_string(),
+        })?;
     while let Some(msg) = stream.next().await {
+        let slot = msg.value.slot;
         if let Some(blk) = msg.value.block {
             if let Some(txs) = blk.transactions {
                 let ix = txs
                     .into_iter()
                     .flat_map(|tx| {
-                        filter_txs(&program, blk.block_height.unwrap_or(blk.parent_slot), tx)
+                        filter_txs(
+                            &program,
+                            blk.block_height.unwrap_or(blk.parent_slot),
+                            tx,
+                            cursor,
+                        )
                     })
                     .collect::<Vec<BonsolInstruction>>();
                 txchan.send(ix).unwrap();
             }
         }
+        cursor.observe_slot(slot);
     }
     Ok(())
 }
@@ -132,18 +162,33 @@ impl Ingester for RpcIngester {
     fn start(&mut self, program: Pubkey) -> Result<TxChannel> {
         let (txchan, rx) = tokio::sync::mpsc::unbounded_channel();
         let rpc_url = self.rpc_url.clone();
+        let backfill_rpc = self.backfill_rpc.clone();
+        let health = self.health.clone();
         self.op_handle = Some(tokio::spawn(async move {
-            let mut retry = 10;
+            let mut cursor = IngestCursor::new(health);
+            let mut backoff = Backoff::default();
             loop {
-                let res = ingest(rpc_url.clone(), program, txchan.clone()).await;
-                if let Err(e) = res {
-                    error!("Error in ingester: {:?} retrying ", e);
-                    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                    if retry == 0 {
-                        break;
+                let res = ingest(
+                    rpc_url.clone(),
+                    backfill_rpc.as_deref(),
+                    program,
+                    txchan.clone(),
+                    &mut cursor,
+                )
+                .await;
+                match res {
+                    Ok(_) => {
+                        error!("Block subscription closed, reconnecting");
+                        cursor.health().disconnected(None);
+                        backoff.reset();
+                    }
+                    Err(e) => {
+                        error!("Error in ingester: {:?} retrying ", e);
+                        cursor.health().disconnected(Some(e.to_string()));
                     }
-                    retry -= 1;
                 }
+                tokio::time::sleep(backoff.next_delay()).await;
+                emit_counter!(MetricEvents::IngesterReconnect, 1, "ingester" => "rpc");
             }
         }));
         Ok(rx)
@@ -155,4 +200,8 @@ impl Ingester for RpcIngester {
         }
         Ok(())
     }
+
+    fn health(&self) -> Arc<IngesterHealth> {
+        self.health.clone()
+    }
 }
@@ -12,10 +12,16 @@ use {
 use crate::types::{filter_bonsol_instructions, BonsolInstruction};
 
 use {
-    super::{Ingester, TxChannel},
+    super::{
+        recovery::{recover, Backoff, IngestCursor},
+        Ingester, IngesterHealth, TxChannel,
+    },
+    crate::observe::*,
     anyhow::Result,
     futures::stream::StreamExt,
-    solana_sdk::{message::VersionedMessage, pubkey::Pubkey},
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::{message::VersionedMessage, pubkey::Pubkey, signature::Signature},
+    std::sync::Arc,
     tokio::sync::mpsc::UnboundedSender,
     yellowstone_grpc_client::{GeyserGrpcBuilder, GeyserGrpcClient},
     yellowstone_grpc_proto::{
@@ -31,22 +37,27 @@ pub struct GrpcIngester {
     token: String,
     connection_timeout_secs: Option<u32>,
     timeout_secs: Option<u32>,
+    backfill_rpc: Option<Arc<RpcClient>>,
     op_handle: Option<tokio::task::JoinHandle<Result<()>>>,
+    health: Arc<IngesterHealth>,
 }
 
 impl GrpcIngester {
-    pub const fn new(
+    pub fn new(
         url: String,
         token: String,
         connection_timeout_secs: Option<u32>,
         timeout_secs: Option<u32>,
+        backfill_rpc: Option<Arc<RpcClient>>,
     ) -> Self {
         GrpcIngester {
             url,
             token,
             connection_timeout_secs,
             timeout_secs,
+            backfill_rpc,
             op_handle: None,
+            health: Arc::new(IngesterHealth::new("grpc")),
         }
     }
     pub fn url(&self) -> &str {
@@ -57,21 +68,71 @@ impl GrpcIngester {
 impl<'a> TryFrom<&'a mut GrpcIngester> for GeyserGrpcBuilder {
     type Error = anyhow::Error;
     fn try_from(value: &'a mut GrpcIngester) -> Result<Self, Self::Error> {
-        Ok(GeyserGrpcClient::build_from_shared(value.url.clone())?
-            .x_token(Some(value.token.clone()))?
-            .connect_timeout(Duration::from_secs(
-                value.connection_timeout_secs.unwrap_or(10) as u64,
-            ))
-            .timeout(Duration::from_secs(value.timeout_secs.unwrap_or(10) as u64)))
+        grpc_builder(
+            &value.url,
+            &value.token,
+            value.connection_timeout_secs,
+            value.timeout_secs,
+        )
     }
 }
 
+fn grpc_builder(
+    url: &str,
+    token: &str,
+    connection_timeout_secs: Option<u32>,
+    timeout_secs: Option<u32>,
+) -> Result<GeyserGrpcBuilder> {
+    Ok(GeyserGrpcClient::build_from_shared(url.to_string())?
+        .x_token(Some(token.to_string()))?
+        .connect_timeout(Duration::from_secs(
+            connection_timeout_secs.unwrap_or(10) as u64
+        ))
+        .timeout(Duration::from_secs(timeout_secs.unwrap_or(10) as u64)))
+}
+
 impl Ingester for GrpcIngester {
     fn start(&mut self, program: Pubkey) -> Result<TxChannel> {
         let (txchan, rx) = tokio::sync::mpsc::unbounded_channel();
-        let stream_client = GeyserGrpcBuilder::try_from(&mut *self)?;
+        // fail fast on an invalid endpoint, the builder is recreated for every connection
+        GeyserGrpcBuilder::try_from(&mut *self)?;
+        let url = self.url.clone();
+        let token = self.token.clone();
+        let connection_timeout_secs = self.connection_timeout_secs;
+        let timeout_secs = self.timeout_secs;
+        let backfill_rpc = self.backfill_rpc.clone();
+        let health = self.health.clone();
         self.op_handle = Some(tokio::spawn(async move {
-            ingest(program, txchan, stream_client).await
+            let mut cursor = IngestCursor::new(health);
+            let mut backoff = Backoff::default();
+            loop {
+                let res = match grpc_builder(&url, &token, connection_timeout_secs, timeout_secs) {
+                    Ok(stream_client) => {
+                        ingest(
+                            program,
+                            &txchan,
+                            stream_client,
+                            backfill_rpc.as_deref(),
+                            &mut cursor,
+                        )
+                        .await
+                    }
+                    Err(e) => Err(e),
+                };
+                match res {
+                    Ok(_) => {
+                        error!("Grpc stream closed, reconnecting");
+                        cursor.health().disconnected(None);
+                        backoff.reset();
+                    }
+                    Err(e) => {
+                        error!("Error in ingester: {e:?} retrying");
+                        cursor.health().disconnected(Some(e.to_string()));
+                    }
+                }
+                tokio::time::sleep(backoff.next_delay()).await;
+                emit_counter!(MetricEvents::IngesterReconnect, 1, "ingester" => "grpc");
+            }
         }));
         Ok(rx)
     }
@@ -82,12 +143,18 @@ impl Ingester for GrpcIngester {
         }
         Ok(())
     }
+
+    fn health(&self) -> Arc<IngesterHealth> {
+        self.health.clone()
+    }
 }
 
 async fn ingest(
     program: Pubkey,
-    txchan: UnboundedSender<Vec<BonsolInstruction>>,
+    txchan: &UnboundedSender<Vec<BonsolInstruction>>,
     stream_client: GeyserGrpcBuilder,
+    backfill_rpc: Option<&RpcClient>,
+    cursor: &mut IngestCursor,
 ) -> Result<()> {
     let mut client = stream_client.connect().await?;
     let mut txmap = HashMap::new();
@@ -106,15 +173,17 @@ async fn ingest(
             ..Default::default()
         }))
         .await?;
+    recover("grpc", backfill_rpc, &program, cursor, txchan).await?;
 
     while let Some(message) = stream.next().await {
         match message {
             Ok(msg) => {
-                if let Err(e) = handle_msg(msg, program, &txchan) {
+                if let Err(e) = handle_msg(msg, program, txchan, cursor) {
                     error!("Error in stream: {e:?}")
                 }
             }
-            Err(e) => error!("Error in stream: {e:?}"),
+            // the stream does not recover from errors, reconnect
+            Err(e) => return Err(anyhow!("Error in stream: {e:?}")),
         }
     }
     Ok(())
@@ -124,9 +193,17 @@ fn handle_msg(
     msg: SubscribeUpdate,
     program: Pubkey,
     txchan: &UnboundedSender<Vec<BonsolInstruction>>,
+    cursor: &mut IngestCursor,
 ) -> Result<()> {
     if let Some(UpdateOneof::Transaction(txw)) = msg.update_oneof {
+        cursor.observe_slot(txw.slot);
         txw.transaction.map(|tx| -> Result<()> {
+            // a transaction can be seen again when it was backfilled after a reconnect
+            if let Ok(signature) = Signature::try_from(tx.signature.as_slice()) {
+                if !cursor.mark_forwarded(signature) {
+                    return Ok(());
+                }
+            }
             create_tx_with_meta(tx)
                 .map(|soltxn| {
                     try_send_instructions(
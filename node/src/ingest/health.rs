@@ -0,0 +1,87 @@
+//! Connection state of an ingester, shared with the admin api.
+use {serde::Serialize, std::sync::RwLock};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IngesterStatus {
+    pub ingester: &'static str,
+    /// Whether the ingester is currently subscribed
+    pub connected: bool,
+    /// Highest slot the ingester has processed
+    pub last_slot: Option<u64>,
+    pub reconnects: u64,
+    pub last_error: Option<String>,
+}
+
+pub struct IngesterHealth {
+    status: RwLock<IngesterStatus>,
+}
+
+impl IngesterHealth {
+    pub const fn new(ingester: &'static str) -> Self {
+        IngesterHealth {
+            status: RwLock::new(IngesterStatus {
+                ingester,
+                connected: false,
+                last_slot: None,
+                reconnects: 0,
+                last_error: None,
+            }),
+        }
+    }
+
+    pub fn connected(&self) {
+        self.update(|status| status.connected = true);
+    }
+
+    /// Records a dropped subscription, `error` is kept until the next error replaces it
+    pub fn disconnected(&self, error: Option<String>) {
+        self.update(|status| {
+            status.connected = false;
+            status.reconnects += 1;
+            if error.is_some() {
+                status.last_error = error;
+            }
+        });
+    }
+
+    pub fn observe_slot(&self, slot: u64) {
+        self.update(|status| {
+            status.last_slot = Some(status.last_slot.map_or(slot, |s| s.max(slot)))
+        });
+    }
+
+    pub fn status(&self) -> IngesterStatus {
+        match self.status.read() {
+            Ok(status) => status.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut IngesterStatus)) {
+        match self.status.write() {
+            Ok(mut status) => f(&mut status),
+            Err(poisoned) => f(&mut poisoned.into_inner()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tracks_reconnects_and_keeps_last_error() {
+        let health = IngesterHealth::new("test");
+        health.connected();
+        health.observe_slot(12);
+        health.observe_slot(10);
+        health.disconnected(Some("stream closed".to_string()));
+        health.disconnected(None);
+
+        let status = health.status();
+        assert!(!status.connected);
+        assert_eq!(status.last_slot, Some(12));
+        assert_eq!(status.reconnects, 2);
+        assert_eq!(status.last_error.as_deref(), Some("stream closed"));
+    }
+}
@@ -0,0 +1,372 @@
+use {
+    super::{
+        recovery::{recover, Backoff, IngestCursor},
+        Ingester, IngesterHealth, IngesterResult, TxChannel,
+    },
+    crate::{
+        ingest::{IngestError, IngestErrorType},
+        observe::*,
+        types::{filter_bonsol_instructions, BonsolInstruction},
+    },
+    anyhow::{anyhow, Result},
+    futures_util::StreamExt,
+    solana_pubsub_client::nonblocking::pubsub_client::PubsubClient,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_rpc_client_api::config::{
+        RpcTransactionConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter,
+    },
+    solana_sdk::{
+        bs58,
+        commitment_config::CommitmentConfig,
+        instruction::CompiledInstruction,
+        message::{v0::LoadedAddresses, AccountKeys},
+        pubkey::Pubkey,
+        signature::Signature,
+    },
+    solana_transaction_status::{
+        option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+        InnerInstruction, UiInnerInstructions, UiInstruction, UiLoadedAddresses,
+        UiTransactionEncoding,
+    },
+    std::{str::FromStr, sync::Arc, time::Duration},
+    tokio::{sync::mpsc::UnboundedSender, task::JoinHandle},
+    tracing::{error, warn},
+};
+
+/// Attempts to fetch a transaction that was just reported in the logs, the transaction can lag
+/// behind the notification on some rpc providers
+const FETCH_ATTEMPTS: u32 = 5;
+
+/// Ingests bonsol instructions from `logsSubscribe`, which unlike `blockSubscribe` is supported by
+/// standard rpc endpoints. Every transaction mentioning the program is fetched by signature.
+pub struct WebsocketIngester {
+    wss_rpc_url: String,
+    rpc_url: String,
+    op_handle: Option<JoinHandle<()>>,
+    health: Arc<IngesterHealth>,
+}
+
+impl WebsocketIngester {
+    pub fn new(wss_rpc_url: String, rpc_url: String) -> WebsocketIngester {
+        WebsocketIngester {
+            wss_rpc_url,
+            rpc_url,
+            op_handle: None,
+            health: Arc::new(IngesterHealth::new("websocket")),
+        }
+    }
+}
+
+fn parse_loaded_addresses(loaded: UiLoadedAddresses) -> Result<LoadedAddresses> {
+    let parse = |keys: Vec<String>| -> Result<Vec<Pubkey>> {
+        keys.iter()
+            .map(|k| Pubkey::from_str(k).map_err(|e| anyhow!("invalid loaded address: {e}")))
+            .collect()
+    };
+    Ok(LoadedAddresses {
+        writable: parse(loaded.writable)?,
+        readonly: parse(loaded.readonly)?,
+    })
+}
+
+/// Extracts the bonsol instructions, including the ones invoked through CPI, from a transaction
+/// fetched from the rpc
+pub(super) fn transaction_instructions(
+    program: &Pubkey,
+    tx: EncodedConfirmedTransactionWithStatusMeta,
+) -> Result<Vec<BonsolInstruction>> {
+    let last_known_block = tx.slot;
+    let meta = tx
+        .transaction
+        .meta
+        .ok_or(anyhow!("transaction is missing its status meta"))?;
+    if meta.err.is_some() {
+        return Ok(vec![]);
+    }
+    let vtx = tx
+        .transaction
+        .transaction
+        .decode()
+        .ok_or(anyhow!("failed to decode transaction"))?;
+    // versioned transactions can reference accounts from address lookup tables
+    let loaded_addresses = match meta.loaded_addresses {
+        OptionSerializer::Some(loaded) => parse_loaded_addresses(loaded)?,
+        _ => LoadedAddresses::default(),
+    };
+    let acc = AccountKeys::new(vtx.message.static_account_keys(), Some(&loaded_addresses));
+    let program_filter = |acc: &AccountKeys, program: &Pubkey, index: usize| -> bool {
+        acc.get(index).is_some_and(|p| p == program)
+    };
+
+    let mut bonsolixs: Vec<BonsolInstruction> = filter_bonsol_instructions(
+        vtx.message.instructions().to_vec(),
+        &acc,
+        program,
+        last_known_block,
+        program_filter,
+    )
+    .collect();
+    let inner_ix_groups: Option<Vec<UiInnerInstructions>> = meta.inner_instructions.into();
+    for group in inner_ix_groups.unwrap_or_default() {
+        let instructions: Vec<InnerInstruction> = group
+            .instructions
+            .into_iter()
+            .filter_map(|ix| match ix {
+                UiInstruction::Compiled(ix) => match bs58::decode(&ix.data).into_vec() {
+                    Ok(data) => Some(InnerInstruction {
+                        instruction: CompiledInstruction::new_from_raw_parts(
+                            ix.program_id_index,
+                            data,
+                            ix.accounts,
+                        ),
+                        stack_height: ix.stack_height,
+                    }),
+                    Err(_) => {
+                        error!("Failed to decode bs58 data for inner instruction");
+                        None
+                    }
+                },
+                _ => None,
+            })
+            .collect();
+        bonsolixs.extend(filter_bonsol_instructions(
+            instructions,
+            &acc,
+            program,
+            last_known_block,
+            program_filter,
+        ));
+    }
+    Ok(bonsolixs)
+}
+
+pub(super) async fn fetch_transaction(
+    rpc_client: &RpcClient,
+    signature: &Signature,
+) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    let mut attempt = 0;
+    loop {
+        match rpc_client
+            .get_transaction_with_config(signature, config)
+            .await
+        {
+            Ok(tx) => return Ok(tx),
+            Err(e) if attempt + 1 < FETCH_ATTEMPTS => {
+                warn!("Transaction {} not available yet: {:?}", signature, e);
+                attempt += 1;
+                tokio::time::sleep(Duration::from_millis(500 * attempt as u64)).await;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+async fn ingest(
+    wss_rpc_url: String,
+    rpc_client: &RpcClient,
+    program: Pubkey,
+    txchan: UnboundedSender<Vec<BonsolInstruction>>,
+    cursor: &mut IngestCursor,
+) -> IngesterResult {
+    let c = PubsubClient::new(&wss_rpc_url)
+        .await
+        .map_err(|e| IngestError {
+            code: IngestErrorType::RpcError,
+            message: e.to_string(),
+        })?;
+
+    let (mut stream, _unsub) = c
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![program.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )
+        .await
+        .map_err(|e| IngestError {
+            code: IngestErrorType::RpcError,
+            message: e.to_string(),
+        })?;
+    recover("websocket", Some(rpc_client), &program, cursor, &txchan)
+        .await
+        .map_err(|e| IngestError {
+            code: IngestErrorType::RpcError,
+            message: e.to_string(),
+        })?;
+    while let Some(msg) = stream.next().await {
+        if msg.value.err.is_some() {
+            continue;
+        }
+        let signature = match Signature::from_str(&msg.value.signature) {
+            Ok(signature) => signature,
+            Err(e) => {
+                error!("Invalid signature in logs notification: {:?}", e);
+                continue;
+            }
+        };
+        // a transaction can be seen again when it was backfilled after a reconnect
+        if cursor.is_forwarded(&signature) {
+            continue;
+        }
+        let ixs = fetch_transaction(rpc_client, &signature)
+            .await
+            .and_then(|tx| transaction_instructions(&program, tx));
+        match ixs {
+            Ok(ixs) => {
+                cursor.mark_forwarded(signature);
+                cursor.observe_slot(msg.context.slot);
+                if !ixs.is_empty() {
+                    txchan.send(ixs).map_err(|e| IngestError {
+                        code: IngestErrorType::IoError,
+                        message: e.to_string(),
+                    })?;
+                }
+            }
+            Err(e) => error!("Error fetching transaction {}: {:?}", signature, e),
+        }
+    }
+    Ok(())
+}
+
+impl Ingester for WebsocketIngester {
+    fn start(&mut self, program: Pubkey) -> Result<TxChannel> {
+        let (txchan, rx) = tokio::sync::mpsc::unbounded_channel();
+        let wss_rpc_url = self.wss_rpc_url.clone();
+        let rpc_client = RpcClient::new(self.rpc_url.clone());
+        let health = self.health.clone();
+        self.op_handle = Some(tokio::spawn(async move {
+            let mut cursor = IngestCursor::new(health);
+            let mut backoff = Backoff::default();
+            loop {
+                let res = ingest(
+                    wss_rpc_url.clone(),
+                    &rpc_client,
+                    program,
+                    txchan.clone(),
+                    &mut cursor,
+                )
+                .await;
+                match res {
+                    Ok(_) => {
+                        error!("Logs subscription closed, reconnecting");
+                        cursor.health().disconnected(None);
+                        backoff.reset();
+                    }
+                    Err(e) => {
+                        error!("Error in ingester: {:?} retrying ", e);
+                        cursor.health().disconnected(Some(e.to_string()));
+                    }
+                }
+                tokio::time::sleep(backoff.next_delay()).await;
+                emit_counter!(MetricEvents::IngesterReconnect, 1, "ingester" => "websocket");
+            }
+        }));
+        Ok(rx)
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        if let Some(t) = self.op_handle.as_mut() {
+            t.abort()
+        }
+        Ok(())
+    }
+
+    fn health(&self) -> Arc<IngesterHealth> {
+        self.health.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        solana_sdk::{
+            hash::Hash,
+            message::{
+                v0::{Message, MessageAddressTableLookup},
+                MessageHeader, VersionedMessage,
+            },
+            transaction::VersionedTransaction,
+        },
+        solana_transaction_status::{
+            EncodableWithMeta, EncodedTransactionWithStatusMeta, InnerInstructions,
+            TransactionStatusMeta, UiTransactionStatusMeta,
+        },
+    };
+
+    fn encoded_transaction(
+        message: Message,
+        meta: TransactionStatusMeta,
+    ) -> EncodedConfirmedTransactionWithStatusMeta {
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::V0(message),
+        };
+        EncodedConfirmedTransactionWithStatusMeta {
+            slot: 42,
+            transaction: EncodedTransactionWithStatusMeta {
+                transaction: tx.encode_with_meta(UiTransactionEncoding::Base64, &meta),
+                meta: Some(UiTransactionStatusMeta::from(meta)),
+                version: None,
+            },
+            block_time: None,
+        }
+    }
+
+    #[test]
+    fn extracts_outer_and_cpi_instructions() {
+        let program = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let loaded = Pubkey::new_unique();
+        let message = Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![payer, account, program],
+            recent_blockhash: Hash::default(),
+            instructions: vec![CompiledInstruction::new_from_raw_parts(
+                2,
+                vec![1, 2, 3],
+                vec![0, 1, 3],
+            )],
+            address_table_lookups: vec![MessageAddressTableLookup {
+                account_key: Pubkey::new_unique(),
+                writable_indexes: vec![0],
+                readonly_indexes: vec![],
+            }],
+        };
+        let meta = TransactionStatusMeta {
+            inner_instructions: Some(vec![InnerInstructions {
+                index: 0,
+                instructions: vec![InnerInstruction {
+                    instruction: CompiledInstruction::new_from_raw_parts(2, vec![4], vec![3]),
+                    stack_height: Some(2),
+                }],
+            }]),
+            loaded_addresses: LoadedAddresses {
+                writable: vec![loaded],
+                readonly: vec![],
+            },
+            ..Default::default()
+        };
+
+        let ixs = transaction_instructions(&program, encoded_transaction(message, meta))
+            .expect("failed to extract instructions");
+        assert_eq!(ixs.len(), 2);
+        assert!(!ixs[0].cpi);
+        assert_eq!(ixs[0].accounts, vec![payer, account, loaded]);
+        assert_eq!(ixs[0].data, vec![1, 2, 3]);
+        assert_eq!(ixs[0].last_known_block, 42);
+        assert!(ixs[1].cpi);
+        assert_eq!(ixs[1].accounts, vec![loaded]);
+        assert_eq!(ixs[1].data, vec![4]);
+    }
+}
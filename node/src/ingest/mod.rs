@@ -1,11 +1,19 @@
 mod block_subscription;
 mod grpc_stream;
+mod health;
+mod log_subscription;
+mod recovery;
 
 use anyhow::Result;
-pub use {block_subscription::RpcIngester, grpc_stream::GrpcIngester};
+pub use {
+    block_subscription::RpcIngester,
+    grpc_stream::GrpcIngester,
+    health::{IngesterHealth, IngesterStatus},
+    log_subscription::WebsocketIngester,
+};
 
 use {
-    crate::types::BonsolInstruction, solana_sdk::pubkey::Pubkey,
+    crate::types::BonsolInstruction, solana_sdk::pubkey::Pubkey, std::sync::Arc,
     tokio::sync::mpsc::UnboundedReceiver,
 };
 
@@ -32,6 +40,9 @@ pub trait Ingester {
     fn start(&mut self, program: Pubkey) -> Result<TxChannel>;
 
     fn stop(&mut self) -> Result<()>;
+
+    /// Connection state of the ingester, updated while it runs
+    fn health(&self) -> Arc<IngesterHealth>;
 }
 
 #[cfg(test)]
@@ -0,0 +1,259 @@
+//! Keeps ingesters from silently losing instructions when their stream drops.
+//! Each ingester remembers the last slot it processed and the transactions it forwarded, after a
+//! reconnect the slots it missed are backfilled from the rpc with `getSignaturesForAddress`.
+use {
+    super::{
+        log_subscription::{fetch_transaction, transaction_instructions},
+        IngesterHealth,
+    },
+    crate::{observe::*, types::BonsolInstruction},
+    anyhow::{anyhow, Result},
+    solana_rpc_client::{
+        nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+    },
+    solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature},
+    std::{
+        collections::{HashSet, VecDeque},
+        str::FromStr,
+        sync::Arc,
+        time::Duration,
+    },
+    tokio::sync::mpsc::UnboundedSender,
+    tracing::{error, warn},
+};
+
+/// Number of forwarded transaction signatures remembered for deduplication
+const SEEN_SIGNATURES: usize = 10_000;
+/// Page size of `getSignaturesForAddress`
+const SIGNATURE_PAGE: usize = 1000;
+/// Upper bound on the transactions fetched by a single backfill
+const MAX_BACKFILL: usize = 5000;
+
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Exponential delay between reconnect attempts
+pub struct Backoff {
+    current: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff {
+            current: MIN_BACKOFF,
+        }
+    }
+}
+
+impl Backoff {
+    /// The delay before the next attempt, every call doubles the following delay
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.current;
+        self.current = std::cmp::min(self.current * 2, MAX_BACKOFF);
+        delay
+    }
+
+    pub fn reset(&mut self) {
+        self.current = MIN_BACKOFF;
+    }
+}
+
+/// What an ingester has processed so far, it outlives the individual connections
+pub struct IngestCursor {
+    last_slot: Option<u64>,
+    seen: HashSet<Signature>,
+    order: VecDeque<Signature>,
+    health: Arc<IngesterHealth>,
+}
+
+impl IngestCursor {
+    pub fn new(health: Arc<IngesterHealth>) -> Self {
+        IngestCursor {
+            last_slot: None,
+            seen: HashSet::new(),
+            order: VecDeque::new(),
+            health,
+        }
+    }
+
+    pub fn health(&self) -> &IngesterHealth {
+        &self.health
+    }
+
+    pub const fn last_slot(&self) -> Option<u64> {
+        self.last_slot
+    }
+
+    pub fn observe_slot(&mut self, slot: u64) {
+        self.last_slot = Some(self.last_slot.map_or(slot, |last| last.max(slot)));
+        self.health.observe_slot(slot);
+    }
+
+    pub fn is_forwarded(&self, signature: &Signature) -> bool {
+        self.seen.contains(signature)
+    }
+
+    /// Marks a transaction as forwarded, returns false if it already was
+    pub fn mark_forwarded(&mut self, signature: Signature) -> bool {
+        if !self.seen.insert(signature) {
+            return false;
+        }
+        self.order.push_back(signature);
+        if self.order.len() > SEEN_SIGNATURES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+/// Called once a subscription is established. When the ingester has processed slots before, the
+/// gap since the last processed slot is reported and backfilled.
+pub async fn recover(
+    ingester: &'static str,
+    rpc_client: Option<&RpcClient>,
+    program: &Pubkey,
+    cursor: &mut IngestCursor,
+    txchan: &UnboundedSender<Vec<BonsolInstruction>>,
+) -> Result<()> {
+    cursor.health().connected();
+    let last_slot = match cursor.last_slot() {
+        Some(slot) => slot,
+        None => return Ok(()),
+    };
+    let rpc_client = match rpc_client {
+        Some(rpc_client) => rpc_client,
+        None => {
+            warn!(
+                "{} ingester reconnected after slot {} without an rpc to backfill from",
+                ingester, last_slot
+            );
+            return Ok(());
+        }
+    };
+    let current_slot = rpc_client
+        .get_slot_with_commitment(CommitmentConfig::confirmed())
+        .await?;
+    let gap = current_slot.saturating_sub(last_slot);
+    emit_histogram!(MetricEvents::IngesterSlotGap, gap as f64, ingester => ingester);
+    let forwarded = backfill(rpc_client, program, last_slot, cursor, txchan).await?;
+    emit_counter!(MetricEvents::IngesterBackfill, forwarded as u64, "ingester" => ingester);
+    Ok(())
+}
+
+/// Forwards the program's transactions from `from_slot` onwards that have not been forwarded yet,
+/// oldest first. Returns the number of transactions forwarded.
+async fn backfill(
+    rpc_client: &RpcClient,
+    program: &Pubkey,
+    from_slot: u64,
+    cursor: &mut IngestCursor,
+    txchan: &UnboundedSender<Vec<BonsolInstruction>>,
+) -> Result<usize> {
+    let mut signatures = Vec::new();
+    let mut before = None;
+    'pages: loop {
+        let page = rpc_client
+            .get_signatures_for_address_with_config(
+                program,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until: None,
+                    limit: Some(SIGNATURE_PAGE),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )
+            .await?;
+        let last_page = page.len() < SIGNATURE_PAGE;
+        for status in page {
+            // the last processed slot may only be partially processed, dedup handles the overlap
+            if status.slot < from_slot {
+                break 'pages;
+            }
+            let signature = Signature::from_str(&status.signature)?;
+            before = Some(signature);
+            if status.err.is_none() {
+                signatures.push(signature);
+            }
+            if signatures.len() >= MAX_BACKFILL {
+                warn!(
+                    "Backfill from slot {} truncated to {} transactions",
+                    from_slot, MAX_BACKFILL
+                );
+                break 'pages;
+            }
+        }
+        if last_page {
+            break;
+        }
+    }
+
+    let mut forwarded = 0;
+    for signature in signatures.into_iter().rev() {
+        if cursor.is_forwarded(&signature) {
+            continue;
+        }
+        let tx = match fetch_transaction(rpc_client, &signature).await {
+            Ok(tx) => tx,
+            Err(e) => {
+                error!("Failed to backfill transaction {}: {:?}", signature, e);
+                continue;
+            }
+        };
+        let slot = tx.slot;
+        let ixs = transaction_instructions(program, tx)?;
+        cursor.mark_forwarded(signature);
+        cursor.observe_slot(slot);
+        if !ixs.is_empty() {
+            txchan
+                .send(ixs)
+                .map_err(|_| anyhow!("failed to send backfilled instructions"))?;
+            forwarded += 1;
+        }
+    }
+    Ok(forwarded)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cursor() -> IngestCursor {
+        IngestCursor::new(Arc::new(IngesterHealth::new("test")))
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let mut backoff = Backoff::default();
+        assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+        assert_eq!(backoff.next_delay(), Duration::from_secs(2));
+        for _ in 0..10 {
+            backoff.next_delay();
+        }
+        assert_eq!(backoff.next_delay(), MAX_BACKOFF);
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), MIN_BACKOFF);
+    }
+
+    #[test]
+    fn cursor_dedups_and_forgets_oldest() {
+        let mut cursor = cursor();
+        let first = Signature::new_unique();
+        assert!(cursor.mark_forwarded(first));
+        assert!(!cursor.mark_forwarded(first));
+        for _ in 0..SEEN_SIGNATURES {
+            cursor.mark_forwarded(Signature::new_unique());
+        }
+        assert!(cursor.mark_forwarded(first));
+    }
+
+    #[test]
+    fn cursor_keeps_highest_slot() {
+        let mut cursor = cursor();
+        assert_eq!(cursor.last_slot(), None);
+        cursor.observe_slot(10);
+        cursor.observe_slot(8);
+        assert_eq!(cursor.last_slot(), Some(10));
+    }
+}
@@ -0,0 +1,168 @@
+use {
+    super::{JobRecord, JobStore},
+    anyhow::Result,
+    std::{
+        fs::{self, File},
+        io::{ErrorKind, Write},
+        path::{Path, PathBuf},
+        sync::Mutex,
+    },
+    tracing::warn,
+};
+
+const JOB_EXTENSION: &str = "json";
+
+/// A job store that keeps one JSON file per execution in a directory.
+/// Records are written to a temporary file, synced and then renamed over the previous record, so a
+/// crash at any point leaves either the old or the new state on disk, never a partial one.
+pub struct FileJobStore {
+    path: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl FileJobStore {
+    pub fn new(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        fs::create_dir_all(&path)?;
+        Ok(FileJobStore {
+            path,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    // execution ids are chosen by requesters, hex keeps them safe to use as file names
+    fn record_path(&self, execution_id: &str) -> PathBuf {
+        self.path
+            .join(hex::encode(execution_id))
+            .with_extension(JOB_EXTENSION)
+    }
+
+    fn sync_dir(&self) -> Result<()> {
+        File::open(&self.path)?.sync_all()?;
+        Ok(())
+    }
+}
+
+impl JobStore for FileJobStore {
+    fn put(&self, record: &JobRecord) -> Result<()> {
+        let data = serde_json::to_vec(record)?;
+        let target = self.record_path(&record.proof.execution_id);
+        let tmp = target.with_extension("tmp");
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        {
+            let mut file = File::create(&tmp)?;
+            file.write_all(&data)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp, &target)?;
+        self.sync_dir()
+    }
+
+    fn get(&self, execution_id: &str) -> Result<Option<JobRecord>> {
+        match fs::read(self.record_path(execution_id)) {
+            Ok(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn remove(&self, execution_id: &str) -> Result<()> {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        match fs::remove_file(self.record_path(execution_id)) {
+            Ok(_) => self.sync_dir(),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn list(&self) -> Result<Vec<JobRecord>> {
+        let mut records = Vec::new();
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(JOB_EXTENSION) {
+                continue;
+            }
+            let record = fs::read(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|data| serde_json::from_slice(&data).map_err(anyhow::Error::from));
+            match record {
+                Ok(record) => records.push(record),
+                Err(e) => warn!("Skipping unreadable job record {:?}: {:?}", path, e),
+            }
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{job_store::test::inflight_proof, risc0_runner::ClaimStatus},
+        bonsol_interface::bonsol_schema::ProgramInputType,
+        bonsol_prover::input_resolver::{ProgramInput, ResolvedInput},
+        tempfile::tempdir,
+    };
+
+    #[test]
+    fn records_survive_reopening_the_store() {
+        let dir = tempdir().unwrap();
+        let inputs = vec![ProgramInput::Resolved(ResolvedInput {
+            index: 0,
+            data: vec![1, 2, 3],
+            input_type: ProgramInputType::Public,
+        })];
+        let record = JobRecord::new(inflight_proof("exec/1", ClaimStatus::Claiming), &inputs);
+        FileJobStore::new(dir.path()).unwrap().put(&record).unwrap();
+
+        let store = FileJobStore::new(dir.path()).unwrap();
+        assert_eq!(store.get("exec/1").unwrap(), Some(record.clone()));
+        assert_eq!(store.list().unwrap(), vec![record]);
+    }
+
+    #[test]
+    fn put_replaces_previous_state() {
+        let dir = tempdir().unwrap();
+        let store = FileJobStore::new(dir.path()).unwrap();
+        let mut proof = inflight_proof("eid", ClaimStatus::Claiming);
+        store.put(&JobRecord::new(proof.clone(), &[])).unwrap();
+        proof.status = ClaimStatus::Confirmed;
+        store.transition(&proof).unwrap();
+
+        let records = store.list().unwrap();
+        assert_eq!(records.len(), 1);
+        assert!(records[0].is_terminal());
+        // no temporary files are left behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn remove_and_missing_records() {
+        let dir = tempdir().unwrap();
+        let store = FileJobStore::new(dir.path()).unwrap();
+        store
+            .put(&JobRecord::new(
+                inflight_proof("eid", ClaimStatus::Submitted),
+                &[],
+            ))
+            .unwrap();
+        store.remove("eid").unwrap();
+        store.remove("eid").unwrap();
+        assert_eq!(store.get("eid").unwrap(), None);
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn corrupt_records_are_skipped() {
+        let dir = tempdir().unwrap();
+        let store = FileJobStore::new(dir.path()).unwrap();
+        fs::write(dir.path().join("broken.json"), b"{not json").unwrap();
+        store
+            .put(&JobRecord::new(
+                inflight_proof("eid", ClaimStatus::Claiming),
+                &[],
+            ))
+            .unwrap();
+        assert_eq!(store.list().unwrap().len(), 1);
+    }
+}
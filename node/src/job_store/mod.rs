@@ -0,0 +1,262 @@
+mod file;
+
+pub use file::FileJobStore;
+
+use {
+    crate::risc0_runner::{ClaimStatus, InflightProof},
+    anyhow::Result,
+    bonsol_interface::bonsol_schema::ProgramInputType,
+    bonsol_prover::input_resolver::{ProgramInput, ResolvedInput, UnresolvedInput},
+    dashmap::DashMap,
+    reqwest::Url,
+    serde::{Deserialize, Serialize},
+    tracing::error,
+};
+
+/// A serializable copy of a staged `ProgramInput`.
+/// Private inputs are only ever stored unresolved, they are fetched again when a job is resumed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StagedInput {
+    Empty,
+    Resolved {
+        index: u8,
+        input_type: u8,
+        data: Vec<u8>,
+    },
+    Unresolved {
+        index: u8,
+        input_type: u8,
+        url: String,
+    },
+}
+
+impl From<&ProgramInput> for StagedInput {
+    fn from(input: &ProgramInput) -> Self {
+        match input {
+            ProgramInput::Empty => StagedInput::Empty,
+            ProgramInput::Resolved(ri) => StagedInput::Resolved {
+                index: ri.index,
+                input_type: ri.input_type.0,
+                data: ri.data.clone(),
+            },
+            ProgramInput::Unresolved(ui) => StagedInput::Unresolved {
+                index: ui.index,
+                input_type: ui.input_type.0,
+                url: ui.url.to_string(),
+            },
+        }
+    }
+}
+
+impl TryFrom<StagedInput> for ProgramInput {
+    type Error = anyhow::Error;
+
+    fn try_from(input: StagedInput) -> Result<Self> {
+        Ok(match input {
+            StagedInput::Empty => ProgramInput::Empty,
+            StagedInput::Resolved {
+                index,
+                input_type,
+                data,
+            } => ProgramInput::Resolved(ResolvedInput {
+                index,
+                data,
+                input_type: ProgramInputType(input_type),
+            }),
+            StagedInput::Unresolved {
+                index,
+                input_type,
+                url,
+            } => ProgramInput::Unresolved(UnresolvedInput {
+                index,
+                url: Url::parse(&url)?,
+                input_type: ProgramInputType(input_type),
+            }),
+        })
+    }
+}
+
+/// A job is an inflight proof together with the inputs that were staged when it was claimed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub proof: InflightProof,
+    pub inputs: Vec<StagedInput>,
+}
+
+impl JobRecord {
+    pub fn new(proof: InflightProof, inputs: &[ProgramInput]) -> Self {
+        let inputs = inputs
+            .iter()
+            .map(|i| match i {
+                // never write resolved private data to disk
+                ProgramInput::Resolved(ri) if ri.input_type == ProgramInputType::Private => {
+                    StagedInput::Empty
+                }
+                _ => StagedInput::from(i),
+            })
+            .collect();
+        JobRecord { proof, inputs }
+    }
+
+    pub const fn is_terminal(&self) -> bool {
+        matches!(
+            self.proof.status,
+            ClaimStatus::Confirmed | ClaimStatus::Expired
+        )
+    }
+
+    pub fn program_inputs(&self) -> Result<Vec<ProgramInput>> {
+        self.inputs
+            .iter()
+            .cloned()
+            .map(ProgramInput::try_from)
+            .collect()
+    }
+}
+
+/// Durable storage for the jobs a node has claimed.
+/// Every `ClaimStatus` transition of an `InflightProof` is written through the store so that a
+/// node can resume its unexpired claims after a restart.
+pub trait JobStore: Send + Sync {
+    /// Insert or replace the record for `record.proof.execution_id`
+    fn put(&self, record: &JobRecord) -> Result<()>;
+    fn get(&self, execution_id: &str) -> Result<Option<JobRecord>>;
+    fn remove(&self, execution_id: &str) -> Result<()>;
+    fn list(&self) -> Result<Vec<JobRecord>>;
+
+    /// Records a new state for an existing job, keeping the inputs that were staged with it.
+    fn transition(&self, proof: &InflightProof) -> Result<()> {
+        let record = match self.get(&proof.execution_id)? {
+            Some(mut record) => {
+                record.proof = proof.clone();
+                record
+            }
+            None => JobRecord::new(proof.clone(), &[]),
+        };
+        self.put(&record)
+    }
+}
+
+/// Job store used when persistence is disabled, jobs are lost when the node stops.
+#[derive(Default)]
+pub struct InMemoryJobStore {
+    jobs: DashMap<String, JobRecord>,
+}
+
+impl JobStore for InMemoryJobStore {
+    fn put(&self, record: &JobRecord) -> Result<()> {
+        self.jobs
+            .insert(record.proof.execution_id.clone(), record.clone());
+        Ok(())
+    }
+
+    fn get(&self, execution_id: &str) -> Result<Option<JobRecord>> {
+        Ok(self.jobs.get(execution_id).map(|r| r.value().clone()))
+    }
+
+    fn remove(&self, execution_id: &str) -> Result<()> {
+        self.jobs.remove(execution_id);
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<JobRecord>> {
+        Ok(self.jobs.iter().map(|r| r.value().clone()).collect())
+    }
+}
+
+/// Store failures are logged rather than propagated, losing durability must not stop a proof.
+pub fn record_job(store: &dyn JobStore, record: &JobRecord) {
+    if let Err(e) = store.put(record) {
+        error!(
+            "Failed to persist job {}: {:?}",
+            record.proof.execution_id, e
+        );
+    }
+}
+
+pub fn record_transition(store: &dyn JobStore, proof: &InflightProof) {
+    if let Err(e) = store.transition(proof) {
+        error!(
+            "Failed to persist {:?} for job {}: {:?}",
+            proof.status, proof.execution_id, e
+        );
+    }
+}
+
+pub fn forget_job(store: &dyn JobStore, execution_id: &str) {
+    if let Err(e) = store.remove(execution_id) {
+        error!("Failed to remove job {}: {:?}", execution_id, e);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        solana_sdk::{instruction::AccountMeta, pubkey::Pubkey, signature::Signature},
+    };
+
+    pub fn inflight_proof(execution_id: &str, status: ClaimStatus) -> InflightProof {
+        InflightProof {
+            execution_id: execution_id.to_string(),
+            image_id: "image".to_string(),
+            status,
+            claim_signature: Signature::new_unique(),
+            submission_signature: None,
+            expiry: 100,
+            requester: Pubkey::new_unique(),
+            program_callback: None,
+            additional_accounts: vec![AccountMeta::new(Pubkey::new_unique(), false)],
+            prover_version: Default::default(),
+            prior_executions: vec![],
+        }
+    }
+
+    #[test]
+    fn staged_inputs_round_trip() {
+        let inputs = vec![
+            ProgramInput::Resolved(ResolvedInput {
+                index: 0,
+                data: vec![1, 2, 3],
+                input_type: ProgramInputType::Public,
+            }),
+            ProgramInput::Unresolved(UnresolvedInput {
+                index: 1,
+                url: Url::parse("https://example.com/input").unwrap(),
+                input_type: ProgramInputType::Private,
+            }),
+        ];
+        let record = JobRecord::new(inflight_proof("eid", ClaimStatus::Claiming), &inputs);
+        assert_eq!(record.program_inputs().unwrap(), inputs);
+    }
+
+    #[test]
+    fn resolved_private_inputs_are_not_staged() {
+        let inputs = vec![ProgramInput::Resolved(ResolvedInput {
+            index: 0,
+            data: vec![42],
+            input_type: ProgramInputType::Private,
+        })];
+        let record = JobRecord::new(inflight_proof("eid", ClaimStatus::Claiming), &inputs);
+        assert_eq!(record.inputs, vec![StagedInput::Empty]);
+    }
+
+    #[test]
+    fn transition_keeps_staged_inputs() {
+        let store = InMemoryJobStore::default();
+        let mut proof = inflight_proof("eid", ClaimStatus::Claiming);
+        let inputs = vec![ProgramInput::Resolved(ResolvedInput {
+            index: 0,
+            data: vec![7],
+            input_type: ProgramInputType::Public,
+        })];
+        store.put(&JobRecord::new(proof.clone(), &inputs)).unwrap();
+        proof.status = ClaimStatus::Submitted;
+        proof.submission_signature = Some(Signature::new_unique());
+        store.transition(&proof).unwrap();
+        let record = store.get("eid").unwrap().unwrap();
+        assert_eq!(record.proof, proof);
+        assert_eq!(record.program_inputs().unwrap(), inputs);
+        assert!(!record.is_terminal());
+    }
+}
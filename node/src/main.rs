@@ -1,29 +1,40 @@
 pub mod types;
 #[macro_use]
 pub mod observe;
+mod admin;
+mod claim_policy;
 mod ingest;
+mod job_store;
 
 pub mod config;
+mod receipt_archive;
 mod risc0_runner;
+mod signer;
 mod transaction_sender;
 use {
+    admin::AdminState,
     anyhow::Result,
     bonsol_prover::input_resolver::DefaultInputResolver,
     config::*,
-    ingest::{GrpcIngester, Ingester, RpcIngester},
+    ingest::{GrpcIngester, Ingester, RpcIngester, WebsocketIngester},
+    job_store::{FileJobStore, InMemoryJobStore, JobStore},
     metrics::counter,
     metrics_exporter_prometheus::PrometheusBuilder,
     observe::MetricEvents,
+    receipt_archive::{
+        FileReceiptArchive, NoReceiptArchive, ObjectStoreReceiptArchive, PeerReceiptSource,
+        ReceiptArchive,
+    },
     risc0_runner::Risc0Runner,
     rlimit::Resource,
     solana_rpc_client::nonblocking::rpc_client::RpcClient,
-    solana_sdk::{pubkey::Pubkey, signature::read_keypair_file, signer::Signer},
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
     std::{process::exit, str::FromStr, sync::Arc, time::Duration},
     thiserror::Error,
     tokio::{select, signal},
     tracing::{error, info},
     tracing_subscriber,
-    transaction_sender::{RpcTransactionSender, TransactionSender},
+    transaction_sender::{RpcTransactionSender, TpuTransactionSender, TransactionSender},
 };
 
 #[derive(Error, Debug)]
@@ -40,6 +51,14 @@ pub enum CliError {
     InvalidIngester,
     #[error("Invalid Transaction Sender")]
     InvalidTransactionSender,
+    #[error("Invalid Job Store")]
+    InvalidJobStore,
+    #[error("Invalid Admin API")]
+    InvalidAdminApi,
+    #[error("Invalid Receipt Archive")]
+    InvalidReceiptArchive,
+    #[error("Invalid Receipt API")]
+    InvalidReceiptApi,
 }
 
 #[tokio::main]
@@ -69,19 +88,24 @@ async fn main() -> Result<()> {
         info!("Prometheus exporter installed");
     }
     emit_event!(MetricEvents::BonsolStartup, up => true);
-    //todo use traits for signer
-    let signer = match config.signer_config.clone() {
-        SignerConfig::KeypairFile { path } => {
-            info!("Using Keypair File");
-            read_keypair_file(&path).map_err(|_| CliError::InvalidSigner)?
+    let signer = signer::load_signer(&config.signer_config)
+        .await
+        .map_err(|e| {
+            error!("Failed to load signer: {:?}", e);
+            CliError::InvalidSigner
+        })?;
+    let signer_identity = signer.pubkey();
+    // ingesters backfill the slots they missed while reconnecting from the node's rpc
+    let backfill_rpc = match &config.transaction_sender_config {
+        TransactionSenderConfig::Rpc { rpc_url } | TransactionSenderConfig::Tpu { rpc_url, .. } => {
+            Some(Arc::new(RpcClient::new(rpc_url.clone())))
         }
     };
-    let signer_identity = signer.pubkey();
     //Todo traitify ingester
     let mut ingester: Box<dyn Ingester> = match config.ingester_config.clone() {
         IngesterConfig::RpcBlockSubscription { wss_rpc_url } => {
             info!("Using RPC Block Subscription");
-            Box::new(RpcIngester::new(wss_rpc_url))
+            Box::new(RpcIngester::new(wss_rpc_url, backfill_rpc))
         }
         IngesterConfig::GrpcSubscription {
             grpc_url,
@@ -95,38 +119,132 @@ async fn main() -> Result<()> {
                 token,
                 Some(connection_timeout_secs),
                 Some(timeout_secs),
+                backfill_rpc,
             ))
         }
-        _ => return Err(CliError::InvalidIngester.into()),
+        IngesterConfig::WebsocketSub {
+            wss_rpc_url,
+            rpc_url,
+        } => {
+            info!("Using Websocket Logs Subscription");
+            Box::new(WebsocketIngester::new(wss_rpc_url, rpc_url))
+        }
     };
 
-    let (mut transaction_sender, solana_rpc_client) = match config.transaction_sender_config.clone()
-    {
-        TransactionSenderConfig::Rpc { rpc_url } => (
-            RpcTransactionSender::new(rpc_url.clone(), program, signer),
-            RpcClient::new(rpc_url),
-        ),
-        _ => return Err(CliError::InvalidRpcUrl.into()),
-    };
+    let (mut transaction_sender, solana_rpc_client): (Box<dyn TransactionSender>, RpcClient) =
+        match config.transaction_sender_config.clone() {
+            TransactionSenderConfig::Rpc { rpc_url } => (
+                Box::new(RpcTransactionSender::new(
+                    rpc_url.clone(),
+                    program,
+                    signer,
+                    config.transaction_fee_config.clone(),
+                )),
+                RpcClient::new(rpc_url),
+            ),
+            TransactionSenderConfig::Tpu {
+                rpc_url,
+                websocket_url,
+                fanout_slots,
+            } => {
+                info!("Using TPU Transaction Sender");
+                let sender = TpuTransactionSender::new(
+                    rpc_url.clone(),
+                    websocket_url,
+                    fanout_slots,
+                    program,
+                    signer,
+                    config.transaction_fee_config.clone(),
+                )
+                .await
+                .map_err(|e| {
+                    error!("Failed to start TPU transaction sender: {:?}", e);
+                    CliError::InvalidTransactionSender
+                })?;
+                (Box::new(sender), RpcClient::new(rpc_url))
+            }
+        };
     transaction_sender.start();
+    let receipt_archive: Arc<dyn ReceiptArchive> = match config.receipt_archive_config.clone() {
+        ReceiptArchiveConfig::File { path } => {
+            info!("Using File Receipt Archive at {}", path);
+            Arc::new(FileReceiptArchive::new(path).map_err(|_| CliError::InvalidReceiptArchive)?)
+        }
+        ReceiptArchiveConfig::S3 {
+            bucket,
+            region,
+            endpoint,
+            prefix,
+        } => {
+            info!("Using S3 Receipt Archive in {}", bucket);
+            let archive =
+                ObjectStoreReceiptArchive::s3(&bucket, &region, endpoint.as_deref(), prefix)
+                    .map_err(|e| {
+                        error!("Failed to build the S3 receipt archive: {:?}", e);
+                        CliError::InvalidReceiptArchive
+                    })?;
+            Arc::new(archive)
+        }
+        ReceiptArchiveConfig::None => Arc::new(NoReceiptArchive),
+    };
+    let http_client = Arc::new(reqwest::Client::new());
+    let receipt_source = PeerReceiptSource::new(
+        receipt_archive.clone(),
+        http_client.clone(),
+        &config.receipt_peers,
+        Duration::from_secs(config.input_download_timeout_secs as u64),
+    )
+    .map_err(|e| {
+        error!("Invalid receipt peers: {:?}", e);
+        CliError::InvalidReceiptArchive
+    })?;
     let input_resolver = DefaultInputResolver::new_with_opts(
-        Arc::new(reqwest::Client::new()),
+        http_client,
         Arc::new(solana_rpc_client),
         Some(config.max_input_size_mb),
         Some(Duration::from_secs(
             config.image_download_timeout_secs as u64,
         )),
-    );
+    )
+    .with_receipt_source(Arc::new(receipt_source));
+    let job_store: Arc<dyn JobStore> = match config.job_store_config.clone() {
+        JobStoreConfig::File { path } => {
+            info!("Using File Job Store at {}", path);
+            Arc::new(FileJobStore::new(path).map_err(|_| CliError::InvalidJobStore)?)
+        }
+        JobStoreConfig::None => Arc::new(InMemoryJobStore::default()),
+    };
     //may take time to load images, depending on the number of images TODO put limit
     let mut runner = Risc0Runner::new(
         config.clone(),
         signer_identity,
-        Arc::new(transaction_sender),
+        Arc::from(transaction_sender),
         Arc::new(input_resolver),
+        job_store,
+        receipt_archive.clone(),
     )
     .await?;
     let runner_chan = runner.start()?;
     let mut ingester_chan = ingester.start(program)?;
+    if let AdminApiConfig::Http {
+        bind_address,
+        auth_token,
+    } = &config.admin_api_config
+    {
+        let state = AdminState::new(runner.handle(), ingester.health(), auth_token.clone());
+        admin::serve(bind_address, state).await.map_err(|e| {
+            error!("Failed to start admin api: {:?}", e);
+            CliError::InvalidAdminApi
+        })?;
+    }
+    if let ReceiptApiConfig::Http { bind_address } = &config.receipt_api_config {
+        receipt_archive::api::serve(bind_address, receipt_archive)
+            .await
+            .map_err(|e| {
+                error!("Failed to start receipt api: {:?}", e);
+                CliError::InvalidReceiptApi
+            })?;
+    }
     let handle = tokio::spawn(async move {
         while let Some(bix) = ingester_chan.recv().await {
             for ix in bix {
@@ -12,15 +12,25 @@ pub enum MetricEvents {
     ClaimAttempt,
     ClaimMissed,
     ClaimReceived,
+    ClaimRejected,
+    ClaimDryRun,
+    BidPlaced,
     ImageDeployment,
     ImageDownload,
     ImageCompressed,
     ImageLoaded,
+    ImageCacheHit,
+    ImageCacheMiss,
+    ImageSourceFailure,
+    DeploymentUpdated,
+    ExecutionCancelled,
+    ExecutionExpiryCranked,
     ImageComputeEstimate,
     ExecutionRequest,
     ProofGeneration,
     ProofCompression,
     ProofConversion,
+    ProofAggregation,
     InputDownload,
     ProofCycles,
     ProofSegments,
@@ -28,7 +38,11 @@ pub enum MetricEvents {
     SignaturesInFlight,
     IncompatibleProverVersion,
     ProofSubmissionError,
+    ProofBatchSubmitted,
     TransactionExpired,
+    IngesterReconnect,
+    IngesterSlotGap,
+    IngesterBackfill,
 }
 
 macro_rules! emit_event {
@@ -0,0 +1,129 @@
+//! Public http api serving the receipt archive.
+//! * `GET /receipts/{requester}/{execution_id}` returns the bincode encoded `Receipt` of an
+//!   execution, its succinct receipt with the journal
+//! * `GET /receipts/{requester}/{execution_id}/seal` returns the compressed seal the proof was
+//!   submitted with
+use {
+    super::{Artifact, ReceiptArchive},
+    anyhow::Result,
+    axum::{
+        extract::{Path, State},
+        http::{header::CONTENT_TYPE, StatusCode},
+        response::{IntoResponse, Response},
+        routing::get,
+        Router,
+    },
+    solana_sdk::pubkey::Pubkey,
+    std::{str::FromStr, sync::Arc},
+    tokio::{net::TcpListener, task::JoinHandle},
+    tracing::{error, info},
+};
+
+pub fn router(archive: Arc<dyn ReceiptArchive>) -> Router {
+    Router::new()
+        .route("/receipts/:requester/:execution_id", get(receipt))
+        .route("/receipts/:requester/:execution_id/seal", get(seal))
+        .with_state(archive)
+}
+
+/// Serves the receipt api on `bind_address` until the node exits
+pub async fn serve(bind_address: &str, archive: Arc<dyn ReceiptArchive>) -> Result<JoinHandle<()>> {
+    let listener = TcpListener::bind(bind_address).await?;
+    info!("Receipt api listening on {}", listener.local_addr()?);
+    let app = router(archive);
+    Ok(tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            error!("Receipt api stopped: {:?}", e);
+        }
+    }))
+}
+
+async fn receipt(
+    State(archive): State<Arc<dyn ReceiptArchive>>,
+    Path((requester, execution_id)): Path<(String, String)>,
+) -> Response {
+    artifact(
+        archive.as_ref(),
+        &requester,
+        &execution_id,
+        Artifact::Receipt,
+    )
+    .await
+}
+
+async fn seal(
+    State(archive): State<Arc<dyn ReceiptArchive>>,
+    Path((requester, execution_id)): Path<(String, String)>,
+) -> Response {
+    artifact(archive.as_ref(), &requester, &execution_id, Artifact::Seal).await
+}
+
+async fn artifact(
+    archive: &dyn ReceiptArchive,
+    requester: &str,
+    execution_id: &str,
+    artifact: Artifact,
+) -> Response {
+    let Ok(requester) = Pubkey::from_str(requester) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+    match archive.get(&requester, execution_id, artifact).await {
+        Ok(Some(bytes)) => ([(CONTENT_TYPE, "application/octet-stream")], bytes).into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            error!(
+                "Failed to read the {} of {}: {:?}",
+                artifact.name(),
+                execution_id,
+                e
+            );
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::receipt_archive::FileReceiptArchive,
+        axum::body::{to_bytes, Body},
+        axum::extract::Request,
+        tempfile::tempdir,
+        tower::ServiceExt,
+    };
+
+    #[tokio::test]
+    async fn serves_archived_receipts_and_seals() {
+        let dir = tempdir().unwrap();
+        let archive = Arc::new(FileReceiptArchive::new(dir.path()).unwrap());
+        let requester = Pubkey::new_unique();
+        archive
+            .put(&requester, "execution", Artifact::Receipt, &[1, 2, 3])
+            .await
+            .unwrap();
+        let app = router(archive);
+
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = app
+            .clone()
+            .oneshot(get(&format!("/receipts/{}/execution", requester)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body.as_ref(), &[1, 2, 3]);
+
+        let response = app
+            .clone()
+            .oneshot(get(&format!("/receipts/{}/execution/seal", requester)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = app
+            .oneshot(get(&format!("/receipts/{}/unknown", requester)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,147 @@
+use {
+    super::{Artifact, ReceiptArchive},
+    anyhow::Result,
+    async_trait::async_trait,
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        io::ErrorKind,
+        path::{Path, PathBuf},
+    },
+    tokio::{fs, io::AsyncWriteExt},
+};
+
+/// A receipt archive that keeps one file per artifact of an execution in a directory per
+/// requester, named after the execution with the artifact as extension.
+/// Artifacts are written to a temporary file, synced and then renamed, a crash never leaves a
+/// partial receipt behind.
+pub struct FileReceiptArchive {
+    path: PathBuf,
+}
+
+impl FileReceiptArchive {
+    pub fn new(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        std::fs::create_dir_all(&path)?;
+        Ok(FileReceiptArchive { path })
+    }
+
+    // execution ids are chosen by requesters, hex keeps them safe to use as file names
+    fn artifact_path(&self, requester: &Pubkey, execution_id: &str, artifact: Artifact) -> PathBuf {
+        self.path
+            .join(requester.to_string())
+            .join(hex::encode(execution_id))
+            .with_extension(artifact.name())
+    }
+}
+
+#[async_trait]
+impl ReceiptArchive for FileReceiptArchive {
+    async fn put(
+        &self,
+        requester: &Pubkey,
+        execution_id: &str,
+        artifact: Artifact,
+        bytes: &[u8],
+    ) -> Result<()> {
+        let target = self.artifact_path(requester, execution_id, artifact);
+        let tmp = target.with_extension("tmp");
+        if let Some(dir) = target.parent() {
+            fs::create_dir_all(dir).await?;
+        }
+        {
+            let mut file = fs::File::create(&tmp).await?;
+            file.write_all(bytes).await?;
+            file.sync_all().await?;
+        }
+        fs::rename(&tmp, &target).await?;
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        requester: &Pubkey,
+        execution_id: &str,
+        artifact: Artifact,
+    ) -> Result<Option<Vec<u8>>> {
+        match fs::read(self.artifact_path(requester, execution_id, artifact)).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, tempfile::tempdir};
+
+    #[tokio::test]
+    async fn receipts_survive_reopening_the_archive() {
+        let dir = tempdir().unwrap();
+        let requester = Pubkey::new_unique();
+        FileReceiptArchive::new(dir.path())
+            .unwrap()
+            .put(&requester, "exec/1", Artifact::Receipt, &[1, 2, 3])
+            .await
+            .unwrap();
+
+        let archive = FileReceiptArchive::new(dir.path()).unwrap();
+        archive
+            .put(&requester, "exec/1", Artifact::Seal, &[4])
+            .await
+            .unwrap();
+        assert_eq!(
+            archive
+                .get(&requester, "exec/1", Artifact::Receipt)
+                .await
+                .unwrap(),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(
+            archive
+                .get(&requester, "exec/1", Artifact::Seal)
+                .await
+                .unwrap(),
+            Some(vec![4])
+        );
+        assert_eq!(
+            archive
+                .get(&requester, "exec/2", Artifact::Receipt)
+                .await
+                .unwrap(),
+            None
+        );
+        // no temporary files are left behind
+        let requester_dir = dir.path().join(requester.to_string());
+        assert_eq!(std::fs::read_dir(requester_dir).unwrap().count(), 2);
+    }
+
+    #[tokio::test]
+    async fn execution_ids_are_scoped_to_their_requester() {
+        let dir = tempdir().unwrap();
+        let archive = FileReceiptArchive::new(dir.path()).unwrap();
+        let (requester, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        archive
+            .put(&requester, "exec", Artifact::Receipt, &[1])
+            .await
+            .unwrap();
+        archive
+            .put(&other, "exec", Artifact::Receipt, &[2])
+            .await
+            .unwrap();
+        assert_eq!(
+            archive
+                .get(&requester, "exec", Artifact::Receipt)
+                .await
+                .unwrap(),
+            Some(vec![1])
+        );
+        assert_eq!(
+            archive
+                .get(&other, "exec", Artifact::Receipt)
+                .await
+                .unwrap(),
+            Some(vec![2])
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Archive of the receipts of the executions a node proved.
+//! Once a proof is submitted its receipt, bincode encoded with its journal, and its compressed
+//! seal are archived under the requester and id of the execution. The archive proves executions that assume a prior
+//! execution and can be served to requesters over http, see `api`.
+pub mod api;
+mod file;
+mod object;
+
+pub use {file::FileReceiptArchive, object::ObjectStoreReceiptArchive};
+
+use {
+    anyhow::Result,
+    async_trait::async_trait,
+    bonsol_prover::input_resolver::ReceiptSource,
+    reqwest::{StatusCode, Url},
+    risc0_zkvm::{InnerReceipt, Journal, Receipt, ReceiptClaim, SuccinctReceipt},
+    solana_sdk::pubkey::Pubkey,
+    std::{sync::Arc, time::Duration},
+    tracing::{error, warn},
+};
+
+/// What is archived of a proven execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Artifact {
+    /// The succinct receipt before its identity recursion with the journal, a bincode encoded
+    /// `Receipt`
+    Receipt,
+    /// The compressed groth16 seal submitted on chain
+    Seal,
+}
+
+impl Artifact {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Artifact::Receipt => "receipt",
+            Artifact::Seal => "seal",
+        }
+    }
+}
+
+/// Storage for the artifacts of the executions a node proved, keyed by requester and execution
+/// id like the execution accounts, execution ids are only unique per requester.
+/// Executions that assume a prior execution are proven with its receipt, the node resolves them
+/// from its own archive or the archives of its peers.
+#[async_trait]
+pub trait ReceiptArchive: Send + Sync {
+    /// Insert or replace the `artifact` of the execution `execution_id` of `requester`
+    async fn put(
+        &self,
+        requester: &Pubkey,
+        execution_id: &str,
+        artifact: Artifact,
+        bytes: &[u8],
+    ) -> Result<()>;
+    async fn get(
+        &self,
+        requester: &Pubkey,
+        execution_id: &str,
+        artifact: Artifact,
+    ) -> Result<Option<Vec<u8>>>;
+}
+
+/// The archive of a node that keeps no receipts
+#[derive(Default)]
+pub struct NoReceiptArchive;
+
+#[async_trait]
+impl ReceiptArchive for NoReceiptArchive {
+    async fn put(
+        &self,
+        _requester: &Pubkey,
+        _execution_id: &str,
+        _artifact: Artifact,
+        _bytes: &[u8],
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        _requester: &Pubkey,
+        _execution_id: &str,
+        _artifact: Artifact,
+    ) -> Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+}
+
+/// Archives the receipt of a submitted proof before its identity recursion, the receipt an
+/// execution assuming it is proven with, and the seal it was submitted with. Executions settled
+/// in a batch have no seal of their own. A failed write is logged, the proof is already on its
+/// way.
+pub async fn archive_receipt(
+    archive: &dyn ReceiptArchive,
+    requester: &Pubkey,
+    execution_id: &str,
+    receipt: SuccinctReceipt<ReceiptClaim>,
+    journal: Journal,
+    seal: Option<&[u8]>,
+) {
+    let receipt = Receipt::new(InnerReceipt::Succinct(receipt), journal.bytes);
+    let stored = match bincode::serialize(&receipt) {
+        Ok(bytes) => {
+            archive
+                .put(requester, execution_id, Artifact::Receipt, &bytes)
+                .await
+        }
+        Err(e) => Err(e.into()),
+    };
+    if let Err(e) = stored {
+        error!("Failed to archive the receipt of {}: {:?}", execution_id, e);
+    }
+    if let Some(seal) = seal {
+        if let Err(e) = archive
+            .put(requester, execution_id, Artifact::Seal, seal)
+            .await
+        {
+            error!("Failed to archive the seal of {}: {:?}", execution_id, e);
+        }
+    }
+}
+
+/// Resolves the receipts of prior executions from the node's archive, then from its peers in
+/// order. Peers serve the receipts they archived at `/receipts/<requester>/<execution id>`.
+pub struct PeerReceiptSource {
+    archive: Arc<dyn ReceiptArchive>,
+    http_client: Arc<reqwest::Client>,
+    peers: Vec<Url>,
+    timeout: Duration,
+}
+
+impl PeerReceiptSource {
+    pub fn new(
+        archive: Arc<dyn ReceiptArchive>,
+        http_client: Arc<reqwest::Client>,
+        peers: &[String],
+        timeout: Duration,
+    ) -> Result<Self> {
+        let peers = peers
+            .iter()
+            .map(|peer| Url::parse(peer))
+            .collect::<Result<_, _>>()?;
+        Ok(PeerReceiptSource {
+            archive,
+            http_client,
+            peers,
+            timeout,
+        })
+    }
+
+    async fn fetch(
+        &self,
+        peer: &Url,
+        requester: &Pubkey,
+        execution_id: &str,
+    ) -> Result<Option<Vec<u8>>> {
+        let mut url = peer.clone();
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("Invalid peer {}", peer))?
+            .pop_if_empty()
+            .extend(["receipts", &requester.to_string(), execution_id]);
+        let resp = self
+            .http_client
+            .get(url)
+            .timeout(self.timeout)
+            .send()
+            .await?;
+        if resp.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(resp.error_for_status()?.bytes().await?.to_vec()))
+    }
+}
+
+#[async_trait]
+impl ReceiptSource for PeerReceiptSource {
+    async fn receipt(&self, requester: &Pubkey, execution_id: &str) -> Result<Option<Vec<u8>>> {
+        if let Some(receipt) = self
+            .archive
+            .get(requester, execution_id, Artifact::Receipt)
+            .await?
+        {
+            return Ok(Some(receipt));
+        }
+        for peer in &self.peers {
+            match self.fetch(peer, requester, execution_id).await {
+                Ok(Some(receipt)) => return Ok(Some(receipt)),
+                Ok(None) => {}
+                Err(e) => warn!(
+                    "Failed to fetch the receipt of {} from {}: {:?}",
+                    execution_id, peer, e
+                ),
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,131 @@
+use {
+    super::{Artifact, ReceiptArchive},
+    anyhow::Result,
+    async_trait::async_trait,
+    object_store::{aws::AmazonS3Builder, path::Path, ObjectStore},
+    solana_sdk::pubkey::Pubkey,
+    std::sync::Arc,
+};
+
+/// A receipt archive in an object store, one object per artifact of an execution named after the
+/// execution with the artifact as extension, under its requester and an optional prefix.
+pub struct ObjectStoreReceiptArchive {
+    store: Arc<dyn ObjectStore>,
+    prefix: Option<String>,
+}
+
+impl ObjectStoreReceiptArchive {
+    pub const fn new(store: Arc<dyn ObjectStore>, prefix: Option<String>) -> Self {
+        ObjectStoreReceiptArchive { store, prefix }
+    }
+
+    /// An archive in an S3 compatible bucket, the credentials are read from the environment
+    pub fn s3(
+        bucket: &str,
+        region: &str,
+        endpoint: Option<&str>,
+        prefix: Option<String>,
+    ) -> Result<Self> {
+        let mut builder = AmazonS3Builder::from_env()
+            .with_bucket_name(bucket)
+            .with_region(region);
+        if let Some(endpoint) = endpoint {
+            builder = builder.with_endpoint(endpoint);
+        }
+        Ok(Self::new(Arc::new(builder.build()?), prefix))
+    }
+
+    // execution ids are chosen by requesters, hex keeps them safe to use as object names
+    fn artifact_path(&self, requester: &Pubkey, execution_id: &str, artifact: Artifact) -> Path {
+        let name = format!(
+            "{}/{}.{}",
+            requester,
+            hex::encode(execution_id),
+            artifact.name()
+        );
+        match &self.prefix {
+            Some(prefix) => Path::from(format!("{}/{}", prefix, name)),
+            None => Path::from(name),
+        }
+    }
+}
+
+#[async_trait]
+impl ReceiptArchive for ObjectStoreReceiptArchive {
+    async fn put(
+        &self,
+        requester: &Pubkey,
+        execution_id: &str,
+        artifact: Artifact,
+        bytes: &[u8],
+    ) -> Result<()> {
+        self.store
+            .put(
+                &self.artifact_path(requester, execution_id, artifact),
+                bytes.to_vec().into(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        requester: &Pubkey,
+        execution_id: &str,
+        artifact: Artifact,
+    ) -> Result<Option<Vec<u8>>> {
+        match self
+            .store
+            .get(&self.artifact_path(requester, execution_id, artifact))
+            .await
+        {
+            Ok(object) => Ok(Some(object.bytes().await?.to_vec())),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, object_store::memory::InMemory};
+
+    #[tokio::test]
+    async fn artifacts_are_kept_under_the_prefix() {
+        let store = Arc::new(InMemory::new());
+        let archive = ObjectStoreReceiptArchive::new(store.clone(), Some("receipts".to_string()));
+        let requester = Pubkey::new_unique();
+        archive
+            .put(&requester, "exec/1", Artifact::Receipt, &[1, 2, 3])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            archive
+                .get(&requester, "exec/1", Artifact::Receipt)
+                .await
+                .unwrap(),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(
+            archive
+                .get(&requester, "exec/1", Artifact::Seal)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            archive
+                .get(&Pubkey::new_unique(), "exec/1", Artifact::Receipt)
+                .await
+                .unwrap(),
+            None
+        );
+        let path = Path::from(format!(
+            "receipts/{}/{}.receipt",
+            requester,
+            hex::encode("exec/1")
+        ));
+        assert!(store.head(&path).await.is_ok());
+    }
+}
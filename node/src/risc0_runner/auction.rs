@@ -0,0 +1,140 @@
+//! Bids for auctioned execution requests.
+//! Instead of claiming an auctioned request right away the node bids for it while its auction is
+//! open, waits for the auction to end and claims the execution in its bid's turn. The claim commits
+//! to the completion slot of the bid, the program rejects a claim committing to a later block.
+use {
+    crate::{
+        claim_policy::{BidRequest, BiddingStrategy},
+        observe::*,
+        transaction_sender::TransactionSender,
+    },
+    anyhow::Result,
+    bonsol_interface::{bid_state::BidStateV1, util::execution_bids_address},
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    std::time::Duration,
+    thiserror::Error,
+    tracing::info,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The auctioned execution request the node bids for
+#[derive(Debug, Clone)]
+pub struct Auction<'a> {
+    pub execution_id: &'a str,
+    pub requester: Pubkey,
+    pub execution_account: Pubkey,
+    pub tip: u64,
+    /// Lamports the claim policy estimates the proof costs
+    pub cost: u64,
+    /// Blocks the claim policy needs to prove the execution
+    pub proving_blocks: u64,
+    pub max_block_height: u64,
+    pub claimer_allow_list: Option<Pubkey>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuctionOutcome {
+    /// Claim in the node's turn, committing to submitting the proof by `block_commitment`
+    Claim {
+        block_commitment: u64,
+    },
+    Lost(AuctionLoss),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum AuctionLoss {
+    #[error("the auction has closed")]
+    Closed,
+    #[error("the bidding strategy does not bid")]
+    NoBid,
+    #[error("the node was outbid")]
+    Outbid,
+}
+
+impl AuctionLoss {
+    /// Short label used for metrics
+    pub const fn label(&self) -> &'static str {
+        match self {
+            AuctionLoss::Closed => "auction_closed",
+            AuctionLoss::NoBid => "no_bid",
+            AuctionLoss::Outbid => "outbid",
+        }
+    }
+}
+
+/// Bids for the execution while its auction is open and waits for the node's turn to claim it
+pub async fn bid_and_wait(
+    strategy: &dyn BiddingStrategy,
+    transaction_sender: &dyn TransactionSender,
+    auction: &Auction<'_>,
+) -> Result<AuctionOutcome> {
+    let bids_address = execution_bids_address(auction.execution_account.as_ref()).0;
+    let Some(bids) = load_bids(transaction_sender, bids_address).await? else {
+        return Ok(AuctionOutcome::Lost(AuctionLoss::Closed));
+    };
+    let current_slot = transaction_sender.get_current_slot().await?;
+    if current_slot > bids.auction_end {
+        return Ok(AuctionOutcome::Lost(AuctionLoss::Closed));
+    }
+    let bid = strategy.bid(&BidRequest {
+        tip: auction.tip,
+        cost: auction.cost,
+        current_block: current_slot,
+        computable_by: current_slot + auction.proving_blocks,
+        auction_end: bids.auction_end,
+        max_block_height: auction.max_block_height,
+    });
+    let Some(bid) = bid else {
+        return Ok(AuctionOutcome::Lost(AuctionLoss::NoBid));
+    };
+    transaction_sender
+        .bid(
+            auction.execution_id,
+            auction.requester,
+            bid.price,
+            bid.completion_slot,
+            auction.claimer_allow_list,
+        )
+        .await?;
+    emit_event!(MetricEvents::BidPlaced, execution_id => auction.execution_id, price => bid.price);
+
+    wait_for_slot(transaction_sender, bids.auction_end + 1).await?;
+    // the bids are closed when the execution is cancelled
+    let Some(bids) = load_bids(transaction_sender, bids_address).await? else {
+        return Ok(AuctionOutcome::Lost(AuctionLoss::Closed));
+    };
+    let node = transaction_sender.signer().pubkey();
+    let placed = bids.bids().iter().find(|b| b.bidder == node.to_bytes());
+    let (Some(placed), Some((start, end))) = (placed, bids.claim_window(&node)) else {
+        return Ok(AuctionOutcome::Lost(AuctionLoss::Outbid));
+    };
+    if wait_for_slot(transaction_sender, start).await? > end {
+        return Ok(AuctionOutcome::Lost(AuctionLoss::Outbid));
+    }
+    Ok(AuctionOutcome::Claim {
+        block_commitment: placed.completion_slot,
+    })
+}
+
+/// The bids of an auction, None once the bids account was closed
+async fn load_bids(
+    transaction_sender: &dyn TransactionSender,
+    bids_address: Pubkey,
+) -> Result<Option<BidStateV1>> {
+    let account = transaction_sender.get_account(bids_address).await?;
+    Ok(account
+        .filter(|account| account.owner == bonsol_interface::ID)
+        .and_then(|account| BidStateV1::load_bids_owned(&account.data).ok()))
+}
+
+/// Waits until the chain reaches `slot`, the slot it reached is returned
+async fn wait_for_slot(transaction_sender: &dyn TransactionSender, slot: u64) -> Result<u64> {
+    loop {
+        let current_slot = transaction_sender.get_current_slot().await?;
+        if current_slot >= slot {
+            return Ok(current_slot);
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
@@ -0,0 +1,442 @@
+//! Prover backends serving execution requests by the prover version they are pinned to.
+//! Every version is proven by a prover of its own risc0 release under the version's recursion
+//! control root and compresses its receipts into groth16 proofs with its own circuit artifacts,
+//! the program verifies them against the version's verifier parameters. A node can serve several
+//! versions at once so requesters pinned to an older version are still served while they migrate.
+use {
+    super::{
+        compression::{CompressedReciept, NativeCompressor, ProofCompressor, SubprocessCompressor},
+        proving::{ExecutionProver, LinkedProver, ProvenExecution, ProvingImage, SubprocessProver},
+    },
+    crate::config::{
+        ProofCompressionConfig, ProverBackendConfig, ProverConfig, ProverNodeConfig,
+        ProverVersionConfig,
+    },
+    anyhow::{anyhow, Result},
+    bonsol_interface::{
+        bonsol_schema::{ExecutionRequestV1, ProverVersion as FBSProverVersion},
+        prover_version::{ProverVersion, VERSION_V1_0_1, VERSION_V1_2_1},
+    },
+    bonsol_prover::input_resolver::ProgramInput,
+    hex_literal::hex,
+    risc0_zkvm::{sha::Digest, Groth16ReceiptVerifierParameters, ReceiptClaim, SuccinctReceipt},
+    std::{collections::HashMap, sync::Arc},
+};
+
+/// The recursion control root and bn254 identity control id a prover version proves under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProverConstants {
+    pub control_root: [u8; 32],
+    pub bn254_control_id: [u8; 32],
+}
+
+pub const PROVER_CONSTANTS_V1_0_1: ProverConstants = ProverConstants {
+    control_root: hex!("a516a057c9fbf5629106300934d48e0e775d4230e41e503347cad96fcbde7e2e"),
+    bn254_control_id: hex!("51b54a62f2aa599aef768744c95de8c7d89bf716e11b1179f05d6cf0bcfeb60e"),
+};
+
+pub const PROVER_CONSTANTS_V1_2_1: ProverConstants = ProverConstants {
+    control_root: hex!("8cdad9242664be3112aba377c5425a4df735eb1c6966472b561d2855932c0469"),
+    bn254_control_id: hex!("c07a65145c3cb48b6101962ea607a4dd93c753bb26975cb47feb00d3666e4404"),
+};
+
+impl ProverVersionConfig {
+    pub const fn prover_version(self) -> ProverVersion {
+        match self {
+            ProverVersionConfig::V1_0_1 => VERSION_V1_0_1,
+            ProverVersionConfig::V1_2_1 => VERSION_V1_2_1,
+        }
+    }
+
+    /// The version execution requests pinned to this one carry
+    pub const fn schema_version(self) -> FBSProverVersion {
+        match self {
+            ProverVersionConfig::V1_0_1 => FBSProverVersion::V1_0_1,
+            ProverVersionConfig::V1_2_1 => FBSProverVersion::V1_2_1,
+        }
+    }
+
+    pub const fn constants(self) -> ProverConstants {
+        match self {
+            ProverVersionConfig::V1_0_1 => PROVER_CONSTANTS_V1_0_1,
+            ProverVersionConfig::V1_2_1 => PROVER_CONSTANTS_V1_2_1,
+        }
+    }
+}
+
+impl TryFrom<&ProverVersion> for ProverVersionConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(version: &ProverVersion) -> Result<Self> {
+        match version {
+            ProverVersion::V1_0_1 { .. } => Ok(ProverVersionConfig::V1_0_1),
+            ProverVersion::V1_2_1 { .. } => Ok(ProverVersionConfig::V1_2_1),
+            ProverVersion::UnsupportedVersion => Err(anyhow!("Unsupported prover version")),
+        }
+    }
+}
+
+/// Proves and compresses the executions pinned to one prover version
+pub struct ProverBackend {
+    version: ProverVersionConfig,
+    prover: Arc<dyn ExecutionProver>,
+    compressor: Arc<dyn ProofCompressor>,
+}
+
+impl ProverBackend {
+    pub fn new(
+        version: ProverVersionConfig,
+        prover: Arc<dyn ExecutionProver>,
+        compressor: Arc<dyn ProofCompressor>,
+    ) -> Self {
+        ProverBackend {
+            version,
+            prover,
+            compressor,
+        }
+    }
+
+    /// Fails for a version the node has no prover for, proofs of another release's prover would
+    /// not verify against the version's control root
+    fn from_config(config: &ProverBackendConfig) -> Result<Self> {
+        let prover: Arc<dyn ExecutionProver> = match &config.prover_config {
+            ProverConfig::Linked => Arc::new(LinkedProver::new(config.version)?),
+            ProverConfig::Subprocess { prover_path } => {
+                Arc::new(SubprocessProver::new(prover_path)?)
+            }
+        };
+        let compressor: Arc<dyn ProofCompressor> = match &config.proof_compression_config {
+            ProofCompressionConfig::Subprocess => Arc::new(SubprocessCompressor::new(
+                &config.stark_compression_tools_path,
+            )?),
+            ProofCompressionConfig::Native {
+                witness_wasm_path,
+                zkey_path,
+            } => Arc::new(NativeCompressor::new(witness_wasm_path, zkey_path)?),
+        };
+        Ok(ProverBackend::new(config.version, prover, compressor))
+    }
+
+    pub fn version(&self) -> ProverVersionConfig {
+        self.version
+    }
+
+    /// The groth16 verifier parameters the program checks this backend's proofs against
+    pub fn verifier_parameters(&self) -> Groth16ReceiptVerifierParameters {
+        let constants = self.version.constants();
+        Groth16ReceiptVerifierParameters {
+            control_root: Digest::from_bytes(constants.control_root),
+            bn254_control_id: Digest::from_bytes(constants.bn254_control_id),
+            ..Default::default()
+        }
+    }
+
+    /// Proves an execution with the prover of the backend's version, see
+    /// `ExecutionProver::prove`
+    pub fn prove(
+        &self,
+        image: &ProvingImage,
+        inputs: Vec<ProgramInput>,
+        conditional: bool,
+    ) -> Result<ProvenExecution> {
+        self.prover.prove(image, inputs, conditional)
+    }
+
+    pub fn identity_p254(
+        &self,
+        receipt: &SuccinctReceipt<ReceiptClaim>,
+    ) -> Result<SuccinctReceipt<ReceiptClaim>> {
+        self.prover.identity_p254(receipt)
+    }
+
+    pub async fn compress(
+        &self,
+        succinct_receipt: SuccinctReceipt<ReceiptClaim>,
+    ) -> Result<CompressedReciept> {
+        self.compressor.compress(succinct_receipt).await
+    }
+}
+
+/// The node's prover backends keyed by the version they serve
+pub struct ProverBackends {
+    backends: HashMap<ProverVersionConfig, Arc<ProverBackend>>,
+}
+
+impl ProverBackends {
+    /// The backends of the node's `prover_backends`, or a single `V1_2_1` backend with the linked
+    /// prover and the top level compression config when none are configured
+    pub fn new(config: &ProverNodeConfig) -> Result<Self> {
+        let configs = if config.prover_backends.is_empty() {
+            vec![ProverBackendConfig {
+                version: ProverVersionConfig::default(),
+                prover_config: ProverConfig::Linked,
+                proof_compression_config: config.proof_compression_config.clone(),
+                stark_compression_tools_path: config.stark_compression_tools_path.clone(),
+            }]
+        } else {
+            config.prover_backends.clone()
+        };
+        let backends = configs
+            .iter()
+            .map(ProverBackend::from_config)
+            .collect::<Result<Vec<_>>>()?;
+        let configured = backends.len();
+        let backends = ProverBackends::from_backends(backends);
+        if backends.backends.len() != configured {
+            return Err(anyhow!(
+                "A prover version is configured in several prover backends"
+            ));
+        }
+        Ok(backends)
+    }
+
+    pub fn from_backends(backends: impl IntoIterator<Item = ProverBackend>) -> Self {
+        ProverBackends {
+            backends: backends
+                .into_iter()
+                .map(|backend| (backend.version(), Arc::new(backend)))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, version: ProverVersionConfig) -> Option<Arc<ProverBackend>> {
+        self.backends.get(&version).cloned()
+    }
+
+    /// The backend serving the prover version the request is pinned to, None when the node does
+    /// not serve it
+    pub fn for_request(&self, exec: &ExecutionRequestV1) -> Option<Arc<ProverBackend>> {
+        let version: ProverVersion = exec.prover_version().try_into().ok()?;
+        self.get(ProverVersionConfig::try_from(&version).ok()?)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<ProverBackend>> {
+        self.backends.values()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        async_trait::async_trait,
+        bonsol_interface::bonsol_schema::ProgramInputType,
+        bonsol_prover::{image::Image, input_resolver::ResolvedInput},
+        risc0_zkvm::{sha::Digestible, Journal, MaybePruned, SuccinctReceiptVerifierParameters},
+        serde_json::json,
+        std::{fs, os::unix::fs::PermissionsExt, path::Path},
+        tempfile::{tempdir, TempDir},
+    };
+
+    struct NoCompression;
+
+    #[async_trait]
+    impl ProofCompressor for NoCompression {
+        async fn compress(
+            &self,
+            _succint_receipt: SuccinctReceipt<ReceiptClaim>,
+        ) -> Result<CompressedReciept> {
+            Err(anyhow!("no compression"))
+        }
+    }
+
+    struct NoProver;
+
+    impl ExecutionProver for NoProver {
+        fn prove(
+            &self,
+            _image: &ProvingImage,
+            _inputs: Vec<ProgramInput>,
+            _conditional: bool,
+        ) -> Result<ProvenExecution> {
+            Err(anyhow!("no prover"))
+        }
+
+        fn identity_p254(
+            &self,
+            _receipt: &SuccinctReceipt<ReceiptClaim>,
+        ) -> Result<SuccinctReceipt<ReceiptClaim>> {
+            Err(anyhow!("no prover"))
+        }
+    }
+
+    fn backend(version: ProverVersionConfig) -> ProverBackend {
+        ProverBackend::new(version, Arc::new(NoProver), Arc::new(NoCompression))
+    }
+
+    /// Digest of the succinct verifier parameters of the version's control root
+    fn succinct_parameters(version: ProverVersionConfig) -> Digest {
+        SuccinctReceiptVerifierParameters {
+            control_root: Digest::from_bytes(version.constants().control_root),
+            ..Default::default()
+        }
+        .digest()
+    }
+
+    /// A prover binary standing in for the prover of `version`, it records its arguments and
+    /// answers with a receipt carrying the version's verifier parameters
+    fn stub_prover(dir: &TempDir, version: ProverVersionConfig) -> SubprocessProver {
+        let claim = MaybePruned::Value(ReceiptClaim::ok(Digest::ZERO, Vec::<u8>::new()));
+        let receipt: SuccinctReceipt<ReceiptClaim> = serde_json::from_value(json!({
+            "seal": [],
+            "control_id": Digest::ZERO,
+            "claim": claim,
+            "hashfn": "poseidon2",
+            "verifier_parameters": succinct_parameters(version),
+            "control_inclusion_proof": { "index": 0, "digests": [] },
+        }))
+        .unwrap();
+        let proven = ProvenExecution {
+            journal: Journal::new(Vec::new()),
+            receipt,
+            segments: 1,
+            total_cycles: 1 << 16,
+            user_cycles: 1,
+        };
+        let proof = dir.path().join("proof.bin");
+        fs::write(&proof, bincode::serialize(&proven).unwrap()).unwrap();
+        let prover = dir.path().join("prover");
+        fs::write(
+            &prover,
+            format!(
+                "#!/bin/sh\necho \"$@\" > {}\ncp {} \"$4\"\n",
+                dir.path().join("args").display(),
+                proof.display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&prover, fs::Permissions::from_mode(0o755)).unwrap();
+        SubprocessProver::new(prover.to_str().unwrap()).unwrap()
+    }
+
+    /// The simple image with an attestation it accepts
+    async fn simple_image() -> (ProvingImage, Vec<ProgramInput>) {
+        let elf_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../elf/simple");
+        let image = Image::new(elf_path.clone()).await.unwrap();
+        let input = |index: u8, data: &[u8], input_type| {
+            ProgramInput::Resolved(ResolvedInput {
+                index,
+                data: data.to_vec(),
+                input_type,
+            })
+        };
+        let inputs = vec![
+            input(0, br#"{"attestation":"test"}"#, ProgramInputType::Public),
+            input(1, b"test", ProgramInputType::Private),
+        ];
+        let image = ProvingImage {
+            memory_image: image.get_memory_image().unwrap(),
+            elf_path,
+        };
+        (image, inputs)
+    }
+
+    #[test]
+    fn backends_match_the_verifier_digest_of_their_version() {
+        for version in [ProverVersionConfig::V1_0_1, ProverVersionConfig::V1_2_1] {
+            let digest = backend(version).verifier_parameters().digest().to_string();
+            assert_eq!(
+                ProverVersion::try_from(version.schema_version()),
+                Ok(version.prover_version())
+            );
+            match version.prover_version() {
+                ProverVersion::V1_0_1 { verifier_digest } => assert_eq!(digest, verifier_digest),
+                ProverVersion::V1_2_1 { verifier_digest } => assert_eq!(digest, verifier_digest),
+                ProverVersion::UnsupportedVersion => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn latest_backend_matches_the_linked_prover() {
+        let parameters = backend(ProverVersionConfig::V1_2_1).verifier_parameters();
+        assert_eq!(
+            parameters.digest(),
+            Groth16ReceiptVerifierParameters::default().digest()
+        );
+    }
+
+    #[test]
+    fn backends_are_found_by_version() {
+        let backends = ProverBackends::from_backends([backend(ProverVersionConfig::V1_0_1)]);
+        assert!(backends.get(ProverVersionConfig::V1_0_1).is_some());
+        assert!(backends.get(ProverVersionConfig::V1_2_1).is_none());
+    }
+
+    #[test]
+    fn backends_without_a_prover_of_their_version_are_rejected() {
+        let config = |prover_config| ProverNodeConfig {
+            prover_backends: vec![ProverBackendConfig {
+                version: ProverVersionConfig::V1_0_1,
+                prover_config,
+                proof_compression_config: ProofCompressionConfig::Subprocess,
+                stark_compression_tools_path: String::new(),
+            }],
+            ..Default::default()
+        };
+        let linked = ProverBackends::new(&config(ProverConfig::Linked));
+        assert!(linked
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("Subprocess prover"));
+        let missing = ProverBackends::new(&config(ProverConfig::Subprocess {
+            prover_path: "/nonexistent/prover".to_string(),
+        }));
+        assert!(missing.is_err());
+        assert!(LinkedProver::new(ProverVersionConfig::V1_2_1).is_ok());
+    }
+
+    #[tokio::test]
+    async fn subprocess_backends_prove_with_their_own_prover() {
+        let dir = tempdir().unwrap();
+        let version = ProverVersionConfig::V1_0_1;
+        let backend = ProverBackend::new(
+            version,
+            Arc::new(stub_prover(&dir, version)),
+            Arc::new(NoCompression),
+        );
+        let (image, inputs) = simple_image().await;
+
+        let proven = backend.prove(&image, inputs, false).unwrap();
+
+        assert_eq!(
+            proven.receipt.verifier_parameters,
+            succinct_parameters(version)
+        );
+        let args = fs::read_to_string(dir.path().join("args")).unwrap();
+        let args: Vec<&str> = args.split_whitespace().collect();
+        assert_eq!(args[0], "prove");
+        assert_eq!(args[1], image.elf_path.to_str().unwrap());
+        assert_eq!(args.len(), 4);
+    }
+
+    #[tokio::test]
+    #[ignore = "proves with the linked risc0 prover, which takes minutes"]
+    async fn backends_prove_under_the_control_root_of_their_version() {
+        let dir = tempdir().unwrap();
+        let backends = ProverBackends::from_backends([
+            ProverBackend::new(
+                ProverVersionConfig::V1_2_1,
+                Arc::new(LinkedProver::new(ProverVersionConfig::V1_2_1).unwrap()),
+                Arc::new(NoCompression),
+            ),
+            ProverBackend::new(
+                ProverVersionConfig::V1_0_1,
+                Arc::new(stub_prover(&dir, ProverVersionConfig::V1_0_1)),
+                Arc::new(NoCompression),
+            ),
+        ]);
+        let (image, inputs) = simple_image().await;
+
+        for backend in backends.iter() {
+            let proven = backend.prove(&image, inputs.clone(), false).unwrap();
+            assert_eq!(
+                proven.receipt.verifier_parameters,
+                succinct_parameters(backend.version()),
+                "{:?} proved under another control root",
+                backend.version()
+            );
+        }
+    }
+}
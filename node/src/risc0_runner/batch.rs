@@ -0,0 +1,319 @@
+//! Batches the proofs that are ready within a window into one `StatusBatchV1`.
+//! A proven execution waits up to the batch window for other proofs of its prover version. The
+//! batch image verifies the receipt of every execution of the batch as an assumption, its receipt
+//! is compressed into the one groth16 proof that settles all of them. A proof that is ready alone,
+//! or a batch that fails to aggregate or to fit in a transaction, is submitted proof by proof.
+use {
+    super::{
+        compression::claim_outcome, proving::ProvingImage, submit_proof, ClaimStatus,
+        InflightProof, JobContext, ProverBackend, Risc0RunnerError,
+    },
+    crate::{
+        config::ProverVersionConfig,
+        job_store::record_transition,
+        observe::*,
+        receipt_archive::archive_receipt,
+        transaction_sender::{BatchSubmission, ProofSubmission},
+    },
+    anyhow::{anyhow, Result},
+    bonsol_interface::bonsol_schema::ProgramInputType,
+    bonsol_prover::input_resolver::{ProgramInput, ResolvedInput},
+    risc0_zkvm::{
+        sha::{Digest, Digestible},
+        InnerReceipt, Journal, Receipt, ReceiptClaim, SuccinctReceipt,
+    },
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
+    tokio::task::JoinHandle,
+    tracing::{error, warn},
+};
+
+/// A proven execution waiting to be submitted
+pub struct ReadyProof {
+    pub claim: InflightProof,
+    pub backend: Arc<ProverBackend>,
+    pub journal: Journal,
+    pub assumptions_digest: Digest,
+    /// The receipt of the execution before its identity recursion, batches take it as an
+    /// assumption
+    pub receipt: SuccinctReceipt<ReceiptClaim>,
+}
+
+pub struct ProofBatcher {
+    image_id: String,
+    window: Duration,
+    max_batch_size: usize,
+    pending: Mutex<Vec<(Instant, ReadyProof)>>,
+    jobs: JobContext,
+}
+
+impl ProofBatcher {
+    pub fn new(image_id: String, window_ms: u64, max_batch_size: usize, jobs: JobContext) -> Self {
+        ProofBatcher {
+            image_id,
+            window: Duration::from_millis(window_ms),
+            max_batch_size,
+            pending: Mutex::new(Vec::new()),
+            jobs,
+        }
+    }
+
+    /// Queues a proof to be submitted with the next batch of its prover version
+    pub fn add(&self, proof: ReadyProof) {
+        self.pending.lock().unwrap().push((Instant::now(), proof));
+    }
+
+    /// Periodically submits the batches that are full or whose window has passed
+    pub fn start(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval((self.window / 2).max(Duration::from_millis(1)));
+            loop {
+                interval.tick().await;
+                let due = take_due(
+                    &mut self.pending.lock().unwrap(),
+                    |proof| proof.claim.prover_version,
+                    Instant::now(),
+                    self.window,
+                    self.max_batch_size,
+                );
+                for batch in due {
+                    let batcher = self.clone();
+                    tokio::spawn(async move { batcher.submit(batch).await });
+                }
+            }
+        })
+    }
+
+    async fn submit(&self, batch: Vec<ReadyProof>) {
+        if batch.len() > 1 {
+            match self.submit_batch(&batch).await {
+                Ok(()) => return,
+                Err(e) => warn!(
+                    "Failed to submit a batch of {} proofs, submitting them one by one: {:?}",
+                    batch.len(),
+                    e
+                ),
+            }
+        }
+        for proof in batch {
+            let execution_id = proof.claim.execution_id.clone();
+            if let Err(e) = submit_proof(&self.jobs, proof).await {
+                error!("Error submitting proof of {}: {:?}", execution_id, e);
+            }
+        }
+    }
+
+    async fn submit_batch(&self, batch: &[ReadyProof]) -> Result<()> {
+        let backend = batch[0].backend.clone();
+        let image = self
+            .jobs
+            .loaded_images
+            .proving_image(&self.image_id)
+            .await?
+            .ok_or(Risc0RunnerError::ImageDataUnavailable)?;
+        let claims: Vec<u8> = batch
+            .iter()
+            .flat_map(|proof| proof.receipt.claim.digest().as_bytes().to_vec())
+            .collect();
+        let assumptions: Vec<Receipt> = batch
+            .iter()
+            .map(|proof| {
+                Receipt::new(
+                    InnerReceipt::Succinct(proof.receipt.clone()),
+                    proof.journal.bytes.clone(),
+                )
+            })
+            .collect();
+        let receipt = tokio::task::spawn_blocking({
+            let backend = backend.clone();
+            move || aggregate(&backend, image, claims, assumptions)
+        })
+        .await??;
+        let compressed_receipt = backend.compress(receipt).await?;
+        if compressed_receipt.exit_code_system != 0 || compressed_receipt.exit_code_user != 0 {
+            return Err(anyhow!("The batch image did not halt"));
+        }
+
+        let submissions = batch
+            .iter()
+            .map(|proof| {
+                let (execution_digest, exit_code_system, exit_code_user) =
+                    claim_outcome(&proof.receipt.claim)?;
+                let (input_digest, committed_outputs) = proof.journal.bytes.split_at(32);
+                Ok(ProofSubmission {
+                    execution_id: proof.claim.execution_id.clone(),
+                    requester_account: proof.claim.requester,
+                    callback_exec: proof.claim.program_callback.clone(),
+                    proof: Vec::new(),
+                    execution_digest,
+                    input_digest: input_digest.to_vec(),
+                    assumption_digest: proof.assumptions_digest.as_bytes().to_vec(),
+                    committed_outputs: committed_outputs.to_vec(),
+                    additional_accounts: proof.claim.additional_accounts.clone(),
+                    prior_executions: Vec::new(),
+                    exit_code_system,
+                    exit_code_user,
+                    execution_expiry: proof.claim.expiry,
+                    prover_version: backend.version().schema_version(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let sig = self
+            .jobs
+            .txn_sender
+            .submit_proof_batch(BatchSubmission {
+                proof: compressed_receipt.proof,
+                execution_digest: compressed_receipt.execution_digest,
+                submissions,
+            })
+            .await?;
+
+        emit_event!(MetricEvents::ProofBatchSubmitted, size => batch.len(), sig => sig.to_string());
+        for proof in batch {
+            let mut claim = proof.claim.clone();
+            claim.status = ClaimStatus::Submitted;
+            // the executions of the batch settle with the one transaction
+            claim.submission_signature = Some(sig);
+            record_transition(self.jobs.job_store.as_ref(), &claim);
+            archive_receipt(
+                self.jobs.receipt_archive.as_ref(),
+                &claim.requester,
+                &claim.execution_id,
+                proof.receipt.clone(),
+                proof.journal.clone(),
+                // the batch seal proves the batch image, not the execution
+                None,
+            )
+            .await;
+            self.jobs
+                .inflight_proofs
+                .insert(claim.execution_id.clone(), claim);
+        }
+        Ok(())
+    }
+}
+
+/// Removes the batches that are due from `pending`. Proofs are batched with the proofs of their
+/// prover version in the order they were ready, a batch is due once it is full or its oldest
+/// proof waited for the whole window.
+fn take_due<T>(
+    pending: &mut Vec<(Instant, T)>,
+    prover_version: impl Fn(&T) -> ProverVersionConfig,
+    now: Instant,
+    window: Duration,
+    max_batch_size: usize,
+) -> Vec<Vec<T>> {
+    let max_batch_size = max_batch_size.max(1);
+    let mut by_version: HashMap<ProverVersionConfig, Vec<(Instant, T)>> = HashMap::new();
+    for (ready_at, proof) in pending.drain(..) {
+        by_version
+            .entry(prover_version(&proof))
+            .or_default()
+            .push((ready_at, proof));
+    }
+    let mut due = Vec::new();
+    for (_, mut proofs) in by_version {
+        while let Some((oldest, _)) = proofs.first() {
+            if proofs.len() < max_batch_size && now.duration_since(*oldest) < window {
+                pending.append(&mut proofs);
+                break;
+            }
+            let size = proofs.len().min(max_batch_size);
+            due.push(proofs.drain(..size).map(|(_, proof)| proof).collect());
+        }
+    }
+    due
+}
+
+// proving function, no async this is cpu/gpu intesive
+fn aggregate(
+    backend: &ProverBackend,
+    image: ProvingImage,
+    claims: Vec<u8>,
+    assumptions: Vec<Receipt>,
+) -> Result<SuccinctReceipt<ReceiptClaim>> {
+    let mut count_and_claims = (assumptions.len() as u32).to_le_bytes().to_vec();
+    count_and_claims.extend(claims);
+    let mut inputs = vec![ProgramInput::Resolved(ResolvedInput {
+        index: 0,
+        data: count_and_claims,
+        input_type: ProgramInputType::Public,
+    })];
+    for assumption in assumptions {
+        // the executor takes the inputs in order, the index is only kept for the requests' inputs
+        inputs.push(ProgramInput::Resolved(ResolvedInput {
+            index: inputs.len() as u8,
+            data: bincode::serialize(&assumption)?,
+            input_type: ProgramInputType::PublicProof,
+        }));
+    }
+    // resolves the assumptions of the batch, the batch image's claim has none left
+    let proven = emit_event_with_duration!(MetricEvents::ProofAggregation, {
+        backend.prove(&image, inputs, false)
+    }, system => "risc0")?;
+    backend.identity_p254(&proven.receipt)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn pending(
+        proofs: &[(u64, ProverVersionConfig)],
+        now: Instant,
+    ) -> Vec<(Instant, ProverVersionConfig)> {
+        proofs
+            .iter()
+            .map(|(age_ms, version)| (now - Duration::from_millis(*age_ms), *version))
+            .collect()
+    }
+
+    #[test]
+    fn batches_are_due_when_full_or_after_the_window() {
+        let now = Instant::now();
+        let window = Duration::from_millis(2_000);
+        let mut waiting = pending(
+            &[
+                (100, ProverVersionConfig::V1_2_1),
+                (50, ProverVersionConfig::V1_2_1),
+                (10, ProverVersionConfig::V1_2_1),
+            ],
+            now,
+        );
+        let due = take_due(&mut waiting, |v| *v, now, window, 2);
+        assert_eq!(due, vec![vec![ProverVersionConfig::V1_2_1; 2]]);
+        assert_eq!(waiting.len(), 1);
+
+        let due = take_due(&mut waiting, |v| *v, now + window, window, 2);
+        assert_eq!(due, vec![vec![ProverVersionConfig::V1_2_1]]);
+        assert!(waiting.is_empty());
+    }
+
+    #[test]
+    fn batches_do_not_mix_prover_versions() {
+        let now = Instant::now();
+        let window = Duration::from_millis(2_000);
+        let mut waiting = pending(
+            &[
+                (3_000, ProverVersionConfig::V1_0_1),
+                (3_000, ProverVersionConfig::V1_2_1),
+                (10, ProverVersionConfig::V1_0_1),
+            ],
+            now,
+        );
+        let mut due = take_due(&mut waiting, |v| *v, now, window, 4);
+        due.sort_by_key(|batch| batch.len());
+        assert_eq!(
+            due,
+            vec![
+                vec![ProverVersionConfig::V1_2_1],
+                vec![ProverVersionConfig::V1_0_1; 2],
+            ]
+        );
+        assert!(waiting.is_empty());
+    }
+}
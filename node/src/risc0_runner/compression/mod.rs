@@ -0,0 +1,55 @@
+mod native;
+mod subprocess;
+
+pub use {native::NativeCompressor, subprocess::SubprocessCompressor};
+
+use {
+    super::Risc0RunnerError,
+    anyhow::Result,
+    async_trait::async_trait,
+    risc0_groth16::Seal,
+    risc0_zkvm::{sha::Digestible, ExitCode, MaybePruned, ReceiptClaim, SuccinctReceipt},
+};
+
+pub struct CompressedReciept {
+    pub execution_digest: Vec<u8>,
+    pub exit_code_system: u32,
+    pub exit_code_user: u32,
+    pub proof: Vec<u8>,
+}
+
+/// Compresses a succinct stark receipt into a groth16 proof that can be verified on chain.
+#[async_trait]
+pub trait ProofCompressor: Send + Sync {
+    async fn compress(
+        &self,
+        succint_receipt: SuccinctReceipt<ReceiptClaim>,
+    ) -> Result<CompressedReciept>;
+}
+
+/// Every compressor produces a `Seal`, the rest of the receipt comes from the claim.
+fn compressed_receipt(seal: Seal, claim: MaybePruned<ReceiptClaim>) -> Result<CompressedReciept> {
+    let (execution_digest, exit_code_system, exit_code_user) = claim_outcome(&claim)?;
+    Ok(CompressedReciept {
+        execution_digest,
+        exit_code_system,
+        exit_code_user,
+        proof: seal.to_vec(),
+    })
+}
+
+/// The post state digest and the system and user exit codes of a claim as the program takes
+/// them, the executions settled by a batch are submitted with these and no proof of their own
+pub fn claim_outcome(claim: &MaybePruned<ReceiptClaim>) -> Result<(Vec<u8>, u32, u32)> {
+    if let MaybePruned::Value(rc) = claim {
+        let (system, user) = match rc.exit_code {
+            ExitCode::Halted(user_exit) => (0, user_exit),
+            ExitCode::Paused(user_exit) => (1, user_exit),
+            ExitCode::SystemSplit => (2, 0),
+            ExitCode::SessionLimit => (2, 2),
+        };
+        Ok((rc.post.digest().as_bytes().to_vec(), system, user))
+    } else {
+        Err(Risc0RunnerError::ProofCompressionError.into())
+    }
+}
@@ -0,0 +1,175 @@
+//! In process STARK to SNARK compression.
+//! The witness for the `stark_verify` circuit is computed by its circom wasm witness generator and
+//! the groth16 proof is created with arkworks from the same zkey `rapidsnark` uses.
+mod reduction;
+mod witness;
+mod zkey;
+
+use {
+    super::{compressed_receipt, CompressedReciept, ProofCompressor},
+    crate::{observe::*, risc0_runner::utils::seal_to_iop},
+    anyhow::{anyhow, Result},
+    ark_bn254::{Bn254, Fq, Fr, G1Affine, G2Affine},
+    ark_ff::PrimeField,
+    ark_groth16::{Groth16, ProvingKey},
+    ark_relations::r1cs::ConstraintMatrices,
+    async_trait::async_trait,
+    num_bigint::{BigInt, BigUint},
+    rand::Rng,
+    reduction::CircomReduction,
+    risc0_groth16::{ProofJson, Seal},
+    risc0_zkvm::{ReceiptClaim, SuccinctReceipt},
+    std::{
+        fs::File,
+        io::BufReader,
+        path::Path,
+        sync::{Arc, Mutex},
+    },
+    witness::WitnessCalculator,
+};
+
+/// Compresses proofs without any external binaries, this works on any architecture wasmer supports.
+pub struct NativeCompressor {
+    inner: Arc<NativeProver>,
+}
+
+struct NativeProver {
+    witness_calculator: Mutex<WitnessCalculator>,
+    proving_key: ProvingKey<Bn254>,
+    matrices: ConstraintMatrices<Fr>,
+}
+
+impl NativeCompressor {
+    /// Loads the `stark_verify` witness generator wasm and the final zkey, the zkey is large so
+    /// this is done once when the node starts.
+    pub fn new(witness_wasm_path: impl AsRef<Path>, zkey_path: impl AsRef<Path>) -> Result<Self> {
+        let witness_calculator = WitnessCalculator::new(witness_wasm_path)?;
+        let mut reader = BufReader::new(File::open(zkey_path)?);
+        let (proving_key, matrices) = zkey::read_zkey(&mut reader)?;
+        Ok(NativeCompressor {
+            inner: Arc::new(NativeProver {
+                witness_calculator: Mutex::new(witness_calculator),
+                proving_key,
+                matrices,
+            }),
+        })
+    }
+}
+
+impl NativeProver {
+    fn prove(&self, iop: Vec<BigUint>) -> Result<ark_groth16::Proof<Bn254>> {
+        let inputs = vec![(
+            "iop".to_string(),
+            iop.into_iter().map(BigInt::from).collect::<Vec<_>>(),
+        )];
+        let full_assignment = {
+            let mut calculator = self
+                .witness_calculator
+                .lock()
+                .map_err(|_| anyhow!("Witness calculator poisoned"))?;
+            calculator.calculate_witness_element(inputs)?
+        };
+        let r = random_fr();
+        let s = random_fr();
+        Groth16::<Bn254, CircomReduction>::create_proof_with_reduction_and_matrices(
+            &self.proving_key,
+            r,
+            s,
+            &self.matrices,
+            self.matrices.num_instance_variables,
+            self.matrices.num_constraints,
+            &full_assignment,
+        )
+        .map_err(|e| anyhow!("Groth16 proving failed: {:?}", e))
+    }
+}
+
+#[async_trait]
+impl ProofCompressor for NativeCompressor {
+    async fn compress(
+        &self,
+        succint_receipt: SuccinctReceipt<ReceiptClaim>,
+    ) -> Result<CompressedReciept> {
+        let iop = emit_event_with_duration!(MetricEvents::ProofConversion, {
+            seal_to_iop(&succint_receipt.seal)
+        }, system => "groth16native")?;
+        let prover = self.inner.clone();
+        let proof = emit_event_with_duration!(MetricEvents::ProofCompression, {
+            tokio::task::spawn_blocking(move || prover.prove(iop)).await?
+        }, system => "risc0native")?;
+        let seal: Seal = proof_to_json(&proof)?.try_into()?;
+        compressed_receipt(seal, succint_receipt.claim)
+    }
+}
+
+// 64 random bytes reduced into the field keeps the bias negligible
+fn random_fr() -> Fr {
+    let mut bytes = [0u8; 64];
+    rand::thread_rng().fill(&mut bytes[..]);
+    Fr::from_le_bytes_mod_order(&bytes)
+}
+
+fn fq_to_decimal(f: &Fq) -> String {
+    BigUint::from(f.into_bigint()).to_str_radix(10)
+}
+
+fn g1_to_json(p: &G1Affine) -> Vec<String> {
+    vec![fq_to_decimal(&p.x), fq_to_decimal(&p.y), "1".to_string()]
+}
+
+fn g2_to_json(p: &G2Affine) -> Vec<Vec<String>> {
+    vec![
+        vec![fq_to_decimal(&p.x.c0), fq_to_decimal(&p.x.c1)],
+        vec![fq_to_decimal(&p.y.c0), fq_to_decimal(&p.y.c1)],
+        vec!["1".to_string(), "0".to_string()],
+    ]
+}
+
+/// Encodes the proof exactly as `rapidsnark` writes `proof.json` so both compressors share the
+/// same `Seal` conversion.
+fn proof_to_json(proof: &ark_groth16::Proof<Bn254>) -> Result<ProofJson> {
+    let json = serde_json::json!({
+        "pi_a": g1_to_json(&proof.a),
+        "pi_b": g2_to_json(&proof.b),
+        "pi_c": g1_to_json(&proof.c),
+        "protocol": "groth16",
+        "curve": "bn128",
+    });
+    Ok(serde_json::from_value(json)?)
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        ark_ec::{AffineRepr, CurveGroup},
+    };
+
+    #[test]
+    fn proof_json_matches_rapidsnark_layout() {
+        let a = (G1Affine::generator() * Fr::from(3u64)).into_affine();
+        let b = (G2Affine::generator() * Fr::from(5u64)).into_affine();
+        let c = (G1Affine::generator() * Fr::from(7u64)).into_affine();
+        let proof = ark_groth16::Proof::<Bn254> { a, b, c };
+        let seal: Seal = proof_to_json(&proof).unwrap().try_into().unwrap();
+        let bytes = seal.to_vec();
+        assert_eq!(bytes.len(), 256);
+
+        let be = |f: &Fq| {
+            let mut v = BigUint::from(f.into_bigint()).to_bytes_be();
+            while v.len() < 32 {
+                v.insert(0, 0);
+            }
+            v
+        };
+        assert_eq!(&bytes[0..32], be(&a.x).as_slice());
+        assert_eq!(&bytes[32..64], be(&a.y).as_slice());
+        // g2 coordinates are written with the imaginary part first
+        assert_eq!(&bytes[64..96], be(&b.x.c1).as_slice());
+        assert_eq!(&bytes[96..128], be(&b.x.c0).as_slice());
+        assert_eq!(&bytes[128..160], be(&b.y.c1).as_slice());
+        assert_eq!(&bytes[160..192], be(&b.y.c0).as_slice());
+        assert_eq!(&bytes[192..224], be(&c.x).as_slice());
+        assert_eq!(&bytes[224..256], be(&c.y).as_slice());
+    }
+}
@@ -0,0 +1,205 @@
+//! Witness generation for circom 2 circuits compiled to wasm, adapted from ark-circom.
+use {
+    anyhow::{anyhow, Result},
+    ark_bn254::Fr,
+    ark_ff::PrimeField,
+    fnv::FnvHasher,
+    num_bigint::{BigInt, BigUint, Sign},
+    num_traits::{ToPrimitive, Zero},
+    std::{hash::Hasher, path::Path},
+    wasmer::{imports, Function, Instance, Memory, MemoryType, Module, RuntimeError, Store, Value},
+};
+
+pub struct WitnessCalculator {
+    instance: Instance,
+    n32: u32,
+}
+
+#[derive(thiserror::Error, Debug, Clone, Copy)]
+#[error("witness generation exited with {0}")]
+struct ExitCode(u32);
+
+impl WitnessCalculator {
+    pub fn new(path: impl AsRef<Path>) -> Result<Self> {
+        let store = Store::default();
+        let module = Module::from_file(&store, path)?;
+        let memory = Memory::new(&store, MemoryType::new(2000, None, false))?;
+        let import_object = imports! {
+            "env" => {
+                "memory" => memory,
+            },
+            // host callbacks used by the circom runtime for logging and errors
+            "runtime" => {
+                "error" => Function::new_native(&store, runtime_error),
+                "exceptionHandler" => Function::new_native(&store, noop_1),
+                "showSharedRWMemory" => Function::new_native(&store, noop),
+                "printErrorMessage" => Function::new_native(&store, noop),
+                "writeBufferMessage" => Function::new_native(&store, noop),
+                "logSetSignal" => Function::new_native(&store, noop_2),
+                "logGetSignal" => Function::new_native(&store, noop_2),
+                "logFinishComponent" => Function::new_native(&store, noop_1),
+                "logStartComponent" => Function::new_native(&store, noop_1),
+                "log" => Function::new_native(&store, noop_1),
+            }
+        };
+        let instance = Instance::new(&module, &import_object)?;
+        let mut calculator = WitnessCalculator { instance, n32: 0 };
+        let version = calculator.get_u32("getVersion")?;
+        if version != 2 {
+            return Err(anyhow!("Unsupported circom version {}", version));
+        }
+        calculator.n32 = calculator.get_u32("getFieldNumLen32")?;
+        calculator.call("getRawPrime", &[])?;
+        let mut arr = vec![0; calculator.n32 as usize];
+        for i in 0..calculator.n32 {
+            arr[(calculator.n32 - i - 1) as usize] = calculator.read_shared_rw_memory(i)?;
+        }
+        let prime = from_array32(arr);
+        if prime != BigInt::from(BigUint::from(Fr::MODULUS)) {
+            return Err(anyhow!("Witness generator is not defined over bn254"));
+        }
+        Ok(calculator)
+    }
+
+    /// Computes the full assignment of the circuit for the given named input signals
+    pub fn calculate_witness_element<I: IntoIterator<Item = (String, Vec<BigInt>)>>(
+        &mut self,
+        inputs: I,
+    ) -> Result<Vec<Fr>> {
+        let modulus = BigInt::from(BigUint::from(Fr::MODULUS));
+        Ok(self
+            .calculate_witness(inputs)?
+            .into_iter()
+            .map(|w| {
+                // negative elements are represented relative to the modulus
+                let w = if w.sign() == Sign::Minus {
+                    &modulus + w
+                } else {
+                    w
+                };
+                Fr::from(w.to_biguint().unwrap_or_default())
+            })
+            .collect())
+    }
+
+    fn calculate_witness<I: IntoIterator<Item = (String, Vec<BigInt>)>>(
+        &mut self,
+        inputs: I,
+    ) -> Result<Vec<BigInt>> {
+        self.call("init", &[Value::I32(1)])?;
+        let n32 = self.n32 as usize;
+        for (name, values) in inputs.into_iter() {
+            let (msb, lsb) = fnv(&name);
+            for (i, value) in values.into_iter().enumerate() {
+                let f_arr = to_array32(&value, n32);
+                for j in 0..n32 {
+                    self.call(
+                        "writeSharedRWMemory",
+                        &[Value::I32(j as i32), Value::I32(f_arr[n32 - 1 - j] as i32)],
+                    )?;
+                }
+                self.call(
+                    "setInputSignal",
+                    &[
+                        Value::I32(msb as i32),
+                        Value::I32(lsb as i32),
+                        Value::I32(i as i32),
+                    ],
+                )?;
+            }
+        }
+
+        let witness_size = self.get_u32("getWitnessSize")?;
+        let mut w = Vec::with_capacity(witness_size as usize);
+        for i in 0..witness_size {
+            self.call("getWitness", &[Value::I32(i as i32)])?;
+            let mut arr = vec![0; n32];
+            for j in 0..n32 {
+                arr[n32 - 1 - j] = self.read_shared_rw_memory(j as u32)?;
+            }
+            w.push(from_array32(arr));
+        }
+        Ok(w)
+    }
+
+    fn call(&self, name: &str, params: &[Value]) -> Result<Box<[Value]>> {
+        let func = self.instance.exports.get_function(name)?;
+        Ok(func.call(params)?)
+    }
+
+    fn get_u32(&self, name: &str) -> Result<u32> {
+        let result = self.call(name, &[])?;
+        result
+            .first()
+            .and_then(Value::i32)
+            .map(|v| v as u32)
+            .ok_or(anyhow!("{} returned no value", name))
+    }
+
+    fn read_shared_rw_memory(&self, i: u32) -> Result<u32> {
+        let result = self.call("readSharedRWMemory", &[Value::I32(i as i32)])?;
+        result
+            .first()
+            .and_then(Value::i32)
+            .map(|v| v as u32)
+            .ok_or(anyhow!("readSharedRWMemory returned no value"))
+    }
+}
+
+fn runtime_error(a: i32, b: i32, c: i32, d: i32, e: i32, f: i32) -> Result<(), RuntimeError> {
+    tracing::error!("circom runtime error: {a} {b} {c} {d} {e} {f}");
+    Err(RuntimeError::user(Box::new(ExitCode(1))))
+}
+
+const fn noop() {}
+const fn noop_1(_: i32) {}
+const fn noop_2(_: i32, _: i32) {}
+
+// signal names are looked up by the 64 bit fnv hash of the name split in two
+fn fnv(inp: &str) -> (u32, u32) {
+    let mut hasher = FnvHasher::default();
+    hasher.write(inp.as_bytes());
+    let h = hasher.finish();
+    ((h >> 32) as u32, h as u32)
+}
+
+fn from_array32(arr: Vec<u32>) -> BigInt {
+    let radix = BigInt::from(0x100000000u64);
+    arr.iter()
+        .fold(BigInt::zero(), |res, &val| res * &radix + BigInt::from(val))
+}
+
+fn to_array32(s: &BigInt, size: usize) -> Vec<u32> {
+    let mut res = vec![0; size];
+    let mut rem = s.clone();
+    let radix = BigInt::from(0x100000000u64);
+    let mut c = size;
+    while !rem.is_zero() && c > 0 {
+        c -= 1;
+        res[c] = (&rem % &radix).to_u32().unwrap_or_default();
+        rem /= &radix;
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn array32_round_trip() {
+        let value = BigInt::from(BigUint::from(Fr::MODULUS)) - 1;
+        let arr = to_array32(&value, 8);
+        assert_eq!(arr.len(), 8);
+        assert_eq!(from_array32(arr), value);
+    }
+
+    #[test]
+    fn fnv_splits_hash() {
+        // fnv-1a 64 of "iop" as used by the circom runtime to locate input signals
+        let (msb, lsb) = fnv("iop");
+        let mut hasher = FnvHasher::default();
+        hasher.write(b"iop");
+        assert_eq!(((msb as u64) << 32) | lsb as u64, hasher.finish());
+    }
+}
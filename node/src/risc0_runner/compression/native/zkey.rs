@@ -0,0 +1,225 @@
+//! Reader for snarkjs groth16 zkey files, adapted from ark-circom.
+//!
+//! A zkey file is a list of sections:
+//!  Header(1)
+//!  HeaderGroth(2): n8q, q, n8r, r, NVars, NPub, DomainSize, alpha1, beta1, beta2, gamma2, delta1, delta2
+//!  IC(3)
+//!  Coefs(4)
+//!  PointsA(5)
+//!  PointsB1(6)
+//!  PointsB2(7)
+//!  PointsC(8)
+//!  PointsH(9)
+//!  Contributions(10)
+use {
+    anyhow::{anyhow, Result},
+    ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine},
+    ark_ff::{BigInteger256, PrimeField, Zero},
+    ark_groth16::{ProvingKey, VerifyingKey},
+    ark_relations::r1cs::ConstraintMatrices,
+    ark_serialize::CanonicalDeserialize,
+    byteorder::{LittleEndian, ReadBytesExt},
+    std::{
+        collections::HashMap,
+        io::{Read, Seek, SeekFrom},
+    },
+};
+
+/// Reads a snarkjs zkey into an arkworks proving key and the constraint matrices needed by the
+/// circom witness map.
+pub fn read_zkey<R: Read + Seek>(
+    reader: &mut R,
+) -> Result<(ProvingKey<Bn254>, ConstraintMatrices<Fr>)> {
+    let mut binfile = BinFile::new(reader)?;
+    let header = binfile.groth_header()?;
+    let proving_key = binfile.proving_key(&header)?;
+    let matrices = binfile.matrices(&header)?;
+    Ok((proving_key, matrices))
+}
+
+struct BinFile<'a, R> {
+    sections: HashMap<u32, u64>,
+    reader: &'a mut R,
+}
+
+struct HeaderGroth {
+    n_vars: usize,
+    n_public: usize,
+    domain_size: u32,
+    alpha_g1: G1Affine,
+    beta_g1: G1Affine,
+    beta_g2: G2Affine,
+    gamma_g2: G2Affine,
+    delta_g1: G1Affine,
+    delta_g2: G2Affine,
+}
+
+impl<'a, R: Read + Seek> BinFile<'a, R> {
+    fn new(reader: &'a mut R) -> Result<Self> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != b"zkey" {
+            return Err(anyhow!("Not a zkey file"));
+        }
+        let _version = reader.read_u32::<LittleEndian>()?;
+        let num_sections = reader.read_u32::<LittleEndian>()?;
+
+        let mut sections = HashMap::new();
+        for _ in 0..num_sections {
+            let section_id = reader.read_u32::<LittleEndian>()?;
+            let section_length = reader.read_u64::<LittleEndian>()?;
+            // only the first occurence of a section is used
+            let position = reader.stream_position()?;
+            sections.entry(section_id).or_insert(position);
+            reader.seek(SeekFrom::Current(section_length as i64))?;
+        }
+        Ok(BinFile { sections, reader })
+    }
+
+    fn seek_section(&mut self, id: u32) -> Result<()> {
+        let position = *self
+            .sections
+            .get(&id)
+            .ok_or(anyhow!("zkey section {} missing", id))?;
+        self.reader.seek(SeekFrom::Start(position))?;
+        Ok(())
+    }
+
+    fn groth_header(&mut self) -> Result<HeaderGroth> {
+        self.seek_section(2)?;
+        let reader = &mut self.reader;
+        let _n8q = u32::deserialize_uncompressed(&mut *reader)?;
+        let _q = BigInteger256::deserialize_uncompressed(&mut *reader)?;
+        let _n8r = u32::deserialize_uncompressed(&mut *reader)?;
+        let _r = BigInteger256::deserialize_uncompressed(&mut *reader)?;
+        let n_vars = u32::deserialize_uncompressed(&mut *reader)? as usize;
+        let n_public = u32::deserialize_uncompressed(&mut *reader)? as usize;
+        let domain_size = u32::deserialize_uncompressed(&mut *reader)?;
+        Ok(HeaderGroth {
+            n_vars,
+            n_public,
+            domain_size,
+            alpha_g1: deserialize_g1(reader)?,
+            beta_g1: deserialize_g1(reader)?,
+            beta_g2: deserialize_g2(reader)?,
+            gamma_g2: deserialize_g2(reader)?,
+            delta_g1: deserialize_g1(reader)?,
+            delta_g2: deserialize_g2(reader)?,
+        })
+    }
+
+    fn proving_key(&mut self, header: &HeaderGroth) -> Result<ProvingKey<Bn254>> {
+        // the range is non-inclusive so we do +1 to get all inputs
+        let ic = self.g1_section(header.n_public + 1, 3)?;
+        let a_query = self.g1_section(header.n_vars, 5)?;
+        let b_g1_query = self.g1_section(header.n_vars, 6)?;
+        let b_g2_query = self.g2_section(header.n_vars, 7)?;
+        let l_query = self.g1_section(header.n_vars - header.n_public - 1, 8)?;
+        let h_query = self.g1_section(header.domain_size as usize, 9)?;
+
+        Ok(ProvingKey::<Bn254> {
+            vk: VerifyingKey::<Bn254> {
+                alpha_g1: header.alpha_g1,
+                beta_g2: header.beta_g2,
+                gamma_g2: header.gamma_g2,
+                delta_g2: header.delta_g2,
+                gamma_abc_g1: ic,
+            },
+            beta_g1: header.beta_g1,
+            delta_g1: header.delta_g1,
+            a_query,
+            b_g1_query,
+            b_g2_query,
+            h_query,
+            l_query,
+        })
+    }
+
+    fn matrices(&mut self, header: &HeaderGroth) -> Result<ConstraintMatrices<Fr>> {
+        self.seek_section(4)?;
+        let num_coeffs = self.reader.read_u32::<LittleEndian>()?;
+
+        let mut matrices = vec![vec![vec![]; header.domain_size as usize]; 2];
+        let mut max_constraint_index = 0;
+        for _ in 0..num_coeffs {
+            let matrix = self.reader.read_u32::<LittleEndian>()? as usize;
+            let constraint = self.reader.read_u32::<LittleEndian>()? as usize;
+            let signal = self.reader.read_u32::<LittleEndian>()? as usize;
+            let value = deserialize_field_fr(&mut self.reader)?;
+            if matrix > 1 || constraint >= header.domain_size as usize {
+                return Err(anyhow!("Invalid zkey coefficient"));
+            }
+            max_constraint_index = std::cmp::max(max_constraint_index, constraint);
+            matrices[matrix][constraint].push((value, signal));
+        }
+
+        // arkworks adds the public input constraints itself
+        let num_constraints = max_constraint_index - header.n_public;
+        matrices
+            .iter_mut()
+            .for_each(|m| m.truncate(num_constraints));
+        let b = matrices.pop().unwrap_or_default();
+        let a = matrices.pop().unwrap_or_default();
+        let a_num_non_zero = a.iter().map(|lc| lc.len()).sum();
+        let b_num_non_zero = b.iter().map(|lc| lc.len()).sum();
+        Ok(ConstraintMatrices {
+            num_instance_variables: header.n_public + 1,
+            num_witness_variables: header.n_vars - header.n_public,
+            num_constraints,
+            a_num_non_zero,
+            b_num_non_zero,
+            c_num_non_zero: 0,
+            a,
+            b,
+            c: vec![],
+        })
+    }
+
+    fn g1_section(&mut self, num: usize, section_id: u32) -> Result<Vec<G1Affine>> {
+        self.seek_section(section_id)?;
+        (0..num).map(|_| deserialize_g1(self.reader)).collect()
+    }
+
+    fn g2_section(&mut self, num: usize, section_id: u32) -> Result<Vec<G2Affine>> {
+        self.seek_section(section_id)?;
+        (0..num).map(|_| deserialize_g2(self.reader)).collect()
+    }
+}
+
+// snarkjs writes the coefficients multiplied by R^2, so they need one division by R
+fn deserialize_field_fr<R: Read>(reader: &mut R) -> Result<Fr> {
+    let bigint = BigInteger256::deserialize_uncompressed(reader)?;
+    Ok(Fr::new_unchecked(Fr::new_unchecked(bigint).into_bigint()))
+}
+
+// points are already in montgomery form, `Fq::new` would multiply by R again
+fn deserialize_field<R: Read>(reader: &mut R) -> Result<Fq> {
+    let bigint = BigInteger256::deserialize_uncompressed(reader)?;
+    Ok(Fq::new_unchecked(bigint))
+}
+
+fn deserialize_field2<R: Read>(reader: &mut R) -> Result<Fq2> {
+    let c0 = deserialize_field(reader)?;
+    let c1 = deserialize_field(reader)?;
+    Ok(Fq2::new(c0, c1))
+}
+
+fn deserialize_g1<R: Read>(reader: &mut R) -> Result<G1Affine> {
+    let x = deserialize_field(reader)?;
+    let y = deserialize_field(reader)?;
+    if x.is_zero() && y.is_zero() {
+        Ok(G1Affine::identity())
+    } else {
+        Ok(G1Affine::new(x, y))
+    }
+}
+
+fn deserialize_g2<R: Read>(reader: &mut R) -> Result<G2Affine> {
+    let x = deserialize_field2(reader)?;
+    let y = deserialize_field2(reader)?;
+    if x.is_zero() && y.is_zero() {
+        Ok(G2Affine::identity())
+    } else {
+        Ok(G2Affine::new(x, y))
+    }
+}
@@ -0,0 +1,95 @@
+use {
+    super::{compressed_receipt, CompressedReciept, ProofCompressor},
+    crate::{
+        observe::*,
+        risc0_runner::{
+            utils::{async_to_json, check_stark_compression_tools_path},
+            Risc0RunnerError,
+        },
+    },
+    anyhow::{anyhow, Result},
+    async_trait::async_trait,
+    risc0_groth16::{ProofJson, Seal},
+    risc0_zkvm::{ReceiptClaim, SuccinctReceipt},
+    std::{
+        env::consts::ARCH,
+        io::Cursor,
+        path::{Path, PathBuf},
+    },
+    tempfile::tempdir,
+    tokio::{fs::File, io::AsyncReadExt, process::Command},
+};
+
+/// Compresses proofs with the `stark_verify` witness generator and `rapidsnark` binaries found in
+/// the `stark_compression_tools_path`. The binaries are only built for x86.
+pub struct SubprocessCompressor {
+    tools_path: PathBuf,
+}
+
+impl SubprocessCompressor {
+    pub fn new(tools_path: &str) -> Result<Self> {
+        if !(ARCH == "x86_64" || ARCH == "x86") {
+            return Err(anyhow!(
+                "Subprocess proof compression is only supported on x86, use Native compression on {}",
+                ARCH
+            ));
+        }
+        check_stark_compression_tools_path(tools_path)?;
+        Ok(SubprocessCompressor {
+            tools_path: PathBuf::from(tools_path),
+        })
+    }
+}
+
+#[async_trait]
+impl ProofCompressor for SubprocessCompressor {
+    async fn compress(
+        &self,
+        succint_receipt: SuccinctReceipt<ReceiptClaim>,
+    ) -> Result<CompressedReciept> {
+        let sealbytes = succint_receipt.get_seal_bytes();
+        let tmp = tempdir()?;
+        let prove_dir = tmp.path();
+        let root_path = Path::new(&self.tools_path);
+        let mut cursor = Cursor::new(&sealbytes);
+        let inputs = prove_dir.join("input.json");
+        let witness = prove_dir.join("out.wtns");
+        let input_file = File::create(&inputs).await?;
+        emit_event_with_duration!(MetricEvents::ProofConversion,{
+            async_to_json(&mut cursor, input_file).await
+        }, system => "groth16json")?;
+        let zkey = root_path.join("stark_verify_final.zkey");
+        let proof_out = prove_dir.join("proof.json");
+        let public = prove_dir.join("public.json");
+        emit_event_with_duration!(MetricEvents::ProofCompression,{
+        let status = Command::new(root_path.join("stark_verify"))
+            .arg(inputs.clone())
+            .arg(witness.clone())
+            .output()
+            .await?;
+        if !status.status.success() {
+            info!("witness {:?}", status);
+            return Err(Risc0RunnerError::ProofCompressionError.into());
+        }
+        let snark_status = Command::new(root_path.join("rapidsnark"))
+            .arg(zkey)
+            .arg(witness)
+            .arg(proof_out.clone())
+            .arg(public)
+            .output()
+            .await?;
+        if !snark_status.status.success() {
+            info!("snark {:?}", snark_status);
+            return Err(Risc0RunnerError::ProofCompressionError.into());
+        }
+        }, system => "risc0");
+
+        let mut proof_fd = File::open(proof_out).await?;
+        let mt = proof_fd.metadata().await?;
+        let mut bytes = Vec::with_capacity(mt.len() as usize);
+        proof_fd.read_to_end(&mut bytes).await?;
+        let proof: ProofJson = serde_json::from_slice(&bytes)?;
+        let seal: Seal = proof.try_into()?;
+        compressed_receipt(seal, succint_receipt.claim)
+    }
+}
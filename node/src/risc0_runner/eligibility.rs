@@ -0,0 +1,163 @@
+//! Whether the node can claim an execution request. A request can restrict its claimers to listed
+//! provers and to registered provers with a track record, the node skips the requests the program
+//! would reject its claim on instead of spending a claim transaction on them.
+use {
+    crate::transaction_sender::TransactionSender,
+    anyhow::Result,
+    bonsol_interface::{
+        allow_list::{claimer_allowed, restricts_claimers},
+        bonsol_schema::{root_as_allow_list_v1, ExecutionRequestV1},
+        prover_state::ProverV1,
+        util::prover_address,
+    },
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    thiserror::Error,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum Ineligibility {
+    #[error("the node is not an allowed claimer")]
+    NotAllowed,
+    #[error("the node does not have the required reputation")]
+    InsufficientReputation,
+}
+
+impl Ineligibility {
+    /// Short label used for metrics
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Ineligibility::NotAllowed => "not_allowed",
+            Ineligibility::InsufficientReputation => "insufficient_reputation",
+        }
+    }
+}
+
+/// The allow list account the request references, its claim passes it to the program
+pub fn claimer_allow_list(exec: &ExecutionRequestV1) -> Option<Pubkey> {
+    exec.claimer_allow_list()
+        .and_then(|address| <[u8; 32]>::try_from(address.bytes()).ok())
+        .map(Pubkey::from)
+}
+
+/// Checks the request's claimer restrictions against the node's identity, the allow list and the
+/// node's registration are only fetched when the request needs them
+pub async fn check_eligibility(
+    exec: &ExecutionRequestV1<'_>,
+    transaction_sender: &dyn TransactionSender,
+) -> Result<Option<Ineligibility>> {
+    let node = transaction_sender.signer().pubkey();
+    if restricts_claimers(exec) {
+        let allow_list = match claimer_allow_list(exec) {
+            Some(address) => program_account(transaction_sender, address).await?,
+            None => None,
+        };
+        if !allowed(exec, allow_list.as_deref(), &node) {
+            return Ok(Some(Ineligibility::NotAllowed));
+        }
+    }
+    let (min_reputation, min_proofs) = (exec.min_reputation(), exec.min_proofs());
+    if min_reputation > 0 || min_proofs > 0 {
+        let registration = program_account(transaction_sender, prover_address(&node).0)
+            .await?
+            .and_then(|data| ProverV1::load_prover_owned(&data).ok());
+        if !meets(registration.as_ref(), min_reputation, min_proofs) {
+            return Ok(Some(Ineligibility::InsufficientReputation));
+        }
+    }
+    Ok(None)
+}
+
+/// The data of an account the bonsol program owns, None when it is closed
+async fn program_account(
+    transaction_sender: &dyn TransactionSender,
+    address: Pubkey,
+) -> Result<Option<Vec<u8>>> {
+    let account = transaction_sender.get_account(address).await?;
+    Ok(account
+        .filter(|account| account.owner == bonsol_interface::ID)
+        .map(|account| account.data))
+}
+
+fn allowed(exec: &ExecutionRequestV1, allow_list: Option<&[u8]>, node: &Pubkey) -> bool {
+    let allow_list = allow_list.and_then(|data| root_as_allow_list_v1(data).ok());
+    claimer_allowed(exec, allow_list.as_ref(), &node.to_bytes())
+}
+
+fn meets(registration: Option<&ProverV1>, min_reputation: u16, min_proofs: u64) -> bool {
+    if min_reputation == 0 && min_proofs == 0 {
+        return true;
+    }
+    registration.is_some_and(|registration| registration.meets(min_reputation, min_proofs))
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        bonsol_interface::{
+            bonsol_schema::{parse_ix_data, root_as_execution_request_v1},
+            instructions::{execute_v1, set_allow_list_v1, ExecutionConfig, InputRef},
+        },
+    };
+
+    fn request(allowed_claimers: Vec<Pubkey>, claimer_allow_list: Option<Pubkey>) -> Vec<u8> {
+        let requester = Pubkey::new_unique();
+        let ix = execute_v1(
+            &requester,
+            &requester,
+            "image",
+            "execution",
+            vec![InputRef::public(b"input")],
+            1,
+            100,
+            ExecutionConfig {
+                verify_input_hash: false,
+                allowed_claimers,
+                claimer_allow_list,
+                ..Default::default()
+            },
+            None,
+            None,
+        )
+        .unwrap();
+        parse_ix_data(&ix.data)
+            .unwrap()
+            .execute_v1()
+            .unwrap()
+            .bytes()
+            .to_vec()
+    }
+
+    #[test]
+    fn node_must_be_listed_in_restricted_requests() {
+        let (node, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let data = request(vec![], None);
+        let exec = root_as_execution_request_v1(&data).unwrap();
+        assert!(allowed(&exec, None, &node));
+
+        let data = request(vec![other], None);
+        let exec = root_as_execution_request_v1(&data).unwrap();
+        assert!(!allowed(&exec, None, &node));
+
+        let address = Pubkey::new_unique();
+        let data = request(vec![other], Some(address));
+        let exec = root_as_execution_request_v1(&data).unwrap();
+        assert_eq!(claimer_allow_list(&exec), Some(address));
+        let ix = set_allow_list_v1(&Pubkey::new_unique(), "trusted", &[node]).unwrap();
+        let ix = parse_ix_data(&ix.data).unwrap();
+        let allow_list = ix.allow_list_v1().unwrap().bytes();
+        assert!(allowed(&exec, Some(allow_list), &node));
+        assert!(!allowed(&exec, None, &node));
+    }
+
+    #[test]
+    fn reputation_requirements_need_a_registration() {
+        let mut registration = ProverV1::new(&Pubkey::new_unique(), 1);
+        assert!(meets(None, 0, 0));
+        assert!(!meets(None, 1, 0));
+        assert!(!meets(Some(&registration), 0, 1));
+        registration.record_claim();
+        registration.record_proof();
+        assert!(meets(Some(&registration), 10_000, 1));
+    }
+}
@@ -0,0 +1,173 @@
+//! Closes the executions that pass their max block height without being settled.
+//! Every execution request the node sees is watched until it is settled, cancelled or expired. Once
+//! its max block height has passed the crank sends `ExpireV1`, which refunds the requester and pays
+//! the node the crank fee. A failed expiration is retried on the next sweep for as long as the
+//! execution account is still open.
+use {
+    crate::{
+        observe::*,
+        transaction_sender::{TransactionSender, TransactionStatus},
+    },
+    bonsol_interface::util::PROVEN_EXECUTION_LEN,
+    dashmap::DashMap,
+    solana_sdk::{pubkey::Pubkey, signature::Signature},
+    std::{sync::Arc, time::Duration},
+    tokio::task::JoinHandle,
+    tracing::{error, warn},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingExecution {
+    requester: Pubkey,
+    expiry: u64,
+    /// The last expiration sent for the execution
+    expire_signature: Option<Signature>,
+}
+
+pub struct ExpiryCrank {
+    pending: DashMap<String, PendingExecution>,
+    txn_sender: Arc<dyn TransactionSender>,
+    interval: Duration,
+}
+
+impl ExpiryCrank {
+    pub fn new(txn_sender: Arc<dyn TransactionSender>, interval_secs: u64) -> Self {
+        ExpiryCrank {
+            pending: DashMap::new(),
+            txn_sender,
+            interval: Duration::from_secs(interval_secs),
+        }
+    }
+
+    /// Watches an execution request until it is settled or expires at `expiry`
+    pub fn watch(&self, execution_id: &str, requester: Pubkey, expiry: u64) {
+        self.pending.insert(
+            execution_id.to_string(),
+            PendingExecution {
+                requester,
+                expiry,
+                expire_signature: None,
+            },
+        );
+    }
+
+    /// Stops watching an execution that was settled, cancelled or expired
+    pub fn forget(&self, execution_id: &str) {
+        if let Some((_, pending)) = self.pending.remove(execution_id) {
+            if let Some(sig) = pending.expire_signature {
+                self.txn_sender.clear_signature_status(&sig);
+            }
+        }
+    }
+
+    /// Periodically expires the watched executions past their max block height
+    pub fn start(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            loop {
+                interval.tick().await;
+                match self.txn_sender.get_current_block().await {
+                    Ok(current_block) => self.sweep(current_block).await,
+                    Err(e) => error!("Failed to get the current block: {:?}", e),
+                }
+            }
+        })
+    }
+
+    async fn sweep(&self, current_block: u64) {
+        for (execution_id, pending) in self.due(current_block) {
+            if let Some(sig) = pending.expire_signature {
+                if let Some(TransactionStatus::Pending { .. }) =
+                    self.txn_sender.get_signature_status(&sig)
+                {
+                    continue;
+                }
+                self.txn_sender.clear_signature_status(&sig);
+            }
+            match self
+                .txn_sender
+                .get_execution_account(pending.requester, &execution_id)
+                .await
+            {
+                // settled executions only keep their exit code and the digest of a proven claim
+                Ok(Some(account)) if account.data.len() > PROVEN_EXECUTION_LEN => {}
+                Ok(_) => {
+                    self.pending.remove(&execution_id);
+                    continue;
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to get the execution account of {}: {:?}",
+                        execution_id, e
+                    );
+                    continue;
+                }
+            }
+            match self
+                .txn_sender
+                .expire(&execution_id, pending.requester)
+                .await
+            {
+                Ok(sig) => {
+                    emit_event!(MetricEvents::ExecutionExpiryCranked, execution_id => execution_id.as_str());
+                    if let Some(mut pending) = self.pending.get_mut(&execution_id) {
+                        pending.expire_signature = Some(sig);
+                    }
+                }
+                Err(e) => warn!("Failed to expire execution {}: {:?}", execution_id, e),
+            }
+        }
+    }
+
+    /// The watched executions whose max block height has passed
+    fn due(&self, current_block: u64) -> Vec<(String, PendingExecution)> {
+        self.pending
+            .iter()
+            .filter(|entry| entry.expiry < current_block)
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{config::ProverNodeConfig, transaction_sender::RpcTransactionSender},
+        solana_rpc_client::nonblocking::rpc_client::RpcClient,
+        solana_sdk::signature::Keypair,
+    };
+
+    fn crank() -> ExpiryCrank {
+        let txn_sender = Arc::new(RpcTransactionSender::with_rpc_client(
+            Arc::new(RpcClient::new_mock("succeeds".to_string())),
+            Pubkey::new_unique(),
+            Arc::new(Keypair::new()),
+            ProverNodeConfig::default().transaction_fee_config,
+        ));
+        ExpiryCrank::new(txn_sender, 10)
+    }
+
+    #[test]
+    fn only_executions_past_their_max_block_height_are_due() {
+        let crank = crank();
+        crank.watch("expired", Pubkey::new_unique(), 100);
+        crank.watch("last_block", Pubkey::new_unique(), 200);
+        crank.watch("open", Pubkey::new_unique(), 300);
+
+        let due = crank.due(200);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0, "expired");
+    }
+
+    #[test]
+    fn settled_executions_are_forgotten() {
+        let crank = crank();
+        crank.watch("settled", Pubkey::new_unique(), 100);
+        crank.watch("open", Pubkey::new_unique(), 100);
+        crank.forget("settled");
+        let due = crank.due(1000);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0, "open");
+    }
+}
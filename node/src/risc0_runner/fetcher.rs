@@ -0,0 +1,195 @@
+//! Downloads images from every source that may serve them.
+//! A deployment's url can be an `ipfs://` or `ar://` uri, which is resolved through the configured
+//! gateways, or a plain http url. The operator's mirrors serve images by id and are tried after
+//! the deployment's own sources. Images are content addressed, so whatever a source serves is only
+//! accepted when it matches the deployment's checksum, before the elf is loaded, and its computed
+//! image id matches the deployment.
+use {
+    super::Risc0RunnerError,
+    crate::{config::ImageSourceConfig, observe::*},
+    anyhow::{anyhow, Result},
+    bonsol_interface::util::image_checksum,
+    bonsol_prover::{image::Image, util::get_body_max_size},
+    bytes::Bytes,
+    tracing::warn,
+};
+
+pub struct ImageFetcher {
+    http_client: reqwest::Client,
+    sources: ImageSourceConfig,
+    /// Largest image accepted from any source, in bytes
+    max_size: u64,
+}
+
+impl ImageFetcher {
+    pub const fn new(
+        http_client: reqwest::Client,
+        sources: ImageSourceConfig,
+        max_size: u64,
+    ) -> Self {
+        ImageFetcher {
+            http_client,
+            sources,
+            max_size,
+        }
+    }
+
+    /// The urls an image may be downloaded from, in the order they are tried
+    pub fn source_urls(&self, url: &str, image_id: &str) -> Vec<String> {
+        let mut urls = if let Some(path) = url.strip_prefix("ipfs://") {
+            gateway_urls(&self.sources.ipfs_gateways, &format!("ipfs/{}", path))
+        } else if let Some(path) = url.strip_prefix("ar://") {
+            gateway_urls(&self.sources.arweave_gateways, path)
+        } else {
+            vec![url.to_string()]
+        };
+        urls.extend(gateway_urls(&self.sources.mirrors, image_id));
+        urls
+    }
+
+    /// Downloads the image with `image_id` from the first source that serves it, `size` and
+    /// `checksum` are announced by the deployment. Deployments made before checksums were required
+    /// have none and are only checked by their image id.
+    pub async fn fetch(
+        &self,
+        url: &str,
+        image_id: &str,
+        size: u64,
+        checksum: Option<&[u8]>,
+    ) -> Result<Image> {
+        let max_size = size.min(self.max_size) as usize;
+        for source in self.source_urls(url, image_id) {
+            let result = emit_event_with_duration!(MetricEvents::ImageDownload, {
+                self.download(&source, max_size).await
+            }, url => source.as_str());
+            let result = result
+                .and_then(|data| verify_checksum(data, checksum))
+                .and_then(Image::from_bytes)
+                .and_then(|img| verify(img, image_id));
+            match result {
+                Ok(img) => return Ok(img),
+                Err(e) => {
+                    warn!(
+                        "Failed to fetch image {} from {}: {:?}",
+                        image_id, source, e
+                    );
+                    emit_counter!(MetricEvents::ImageSourceFailure, 1, "image_id" => image_id);
+                }
+            }
+        }
+        Err(anyhow!("No source served image {}", image_id))
+    }
+
+    async fn download(&self, url: &str, max_size: usize) -> Result<Bytes> {
+        let resp = self.http_client.get(url).send().await?.error_for_status()?;
+        let data = get_body_max_size(resp.bytes_stream(), max_size)
+            .await
+            .map_err(|_| Risc0RunnerError::ImgTooLarge)?;
+        Ok(data)
+    }
+}
+
+/// Rejects bytes that are not the deployed elf without parsing them
+fn verify_checksum(data: Bytes, checksum: Option<&[u8]>) -> Result<Bytes> {
+    match checksum {
+        Some(checksum) if image_checksum(&data).as_slice() != checksum => {
+            Err(anyhow!("Image does not match the deployment checksum"))
+        }
+        _ => Ok(data),
+    }
+}
+
+/// Rejects an image that is not the one the deployment is for
+fn verify(img: Image, image_id: &str) -> Result<Image> {
+    if img.id != image_id {
+        return Err(anyhow!(
+            "Source served image {} instead of {}",
+            img.id,
+            image_id
+        ));
+    }
+    Ok(img)
+}
+
+fn gateway_urls(gateways: &[String], path: &str) -> Vec<String> {
+    gateways
+        .iter()
+        .map(|gateway| format!("{}/{}", gateway.trim_end_matches('/'), path))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::config::ProverNodeConfig};
+
+    fn fetcher(mirrors: Vec<String>) -> ImageFetcher {
+        let mut sources = ProverNodeConfig::default().image_source_config;
+        sources.ipfs_gateways = vec![
+            "https://ipfs.io".to_string(),
+            "https://dweb.link/".to_string(),
+        ];
+        sources.arweave_gateways = vec!["https://arweave.net".to_string()];
+        sources.mirrors = mirrors;
+        ImageFetcher::new(reqwest::Client::new(), sources, 1024)
+    }
+
+    #[test]
+    fn resolves_uris_through_gateways_then_mirrors() {
+        let fetcher = fetcher(vec!["https://mirror.example/images".to_string()]);
+        assert_eq!(
+            fetcher.source_urls("ipfs://bafy/program", "abc"),
+            vec![
+                "https://ipfs.io/ipfs/bafy/program",
+                "https://dweb.link/ipfs/bafy/program",
+                "https://mirror.example/images/abc",
+            ]
+        );
+        assert_eq!(
+            fetcher.source_urls("ar://tx", "abc"),
+            vec![
+                "https://arweave.net/tx",
+                "https://mirror.example/images/abc"
+            ]
+        );
+        assert_eq!(
+            fetcher.source_urls("https://host.example/program", "abc"),
+            vec![
+                "https://host.example/program",
+                "https://mirror.example/images/abc"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn tries_every_source_until_one_serves_the_image() {
+        let mut server = mockito::Server::new_async().await;
+        let primary = server
+            .mock("GET", "/program")
+            .with_status(503)
+            .create_async()
+            .await;
+        // not an elf, the image id cannot be computed so the image is rejected
+        let mirror = server
+            .mock("GET", "/mirror/abc")
+            .with_body("not an elf")
+            .create_async()
+            .await;
+        let fetcher = fetcher(vec![format!("{}/mirror", server.url())]);
+
+        let result = fetcher
+            .fetch(&format!("{}/program", server.url()), "abc", 1024, None)
+            .await;
+        assert!(result.is_err());
+        primary.assert_async().await;
+        mirror.assert_async().await;
+    }
+
+    #[test]
+    fn rejects_bytes_not_matching_the_checksum() {
+        let elf = Bytes::from_static(b"elf");
+        let checksum = image_checksum(&elf);
+        assert!(verify_checksum(elf.clone(), Some(&checksum[..])).is_ok());
+        assert!(verify_checksum(elf.clone(), Some(&[0; 32][..])).is_err());
+        assert!(verify_checksum(elf, None).is_ok());
+    }
+}
@@ -0,0 +1,125 @@
+//! Inspects and steers a running `Risc0Runner`, used by the admin api.
+use {
+    super::{load_image, ClaimStatus, ImageFetcher, ImageSummary, InflightProofs, LoadedImageMap},
+    crate::{
+        claim_policy::ClaimPolicy, config::ProverNodeConfig, transaction_sender::TransactionSender,
+    },
+    anyhow::Result,
+    itertools::Itertools,
+    serde::Serialize,
+    std::sync::Arc,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InflightProofSummary {
+    pub execution_id: String,
+    pub image_id: String,
+    pub status: ClaimStatus,
+    pub expiry: u64,
+    pub requester: String,
+    pub claim_signature: String,
+    pub submission_signature: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eviction {
+    Evicted,
+    NotLoaded,
+    /// The image is needed by an inflight proof and was kept
+    InUse,
+}
+
+#[derive(Clone)]
+pub struct RunnerHandle {
+    config: Arc<ProverNodeConfig>,
+    inflight_proofs: InflightProofs,
+    loaded_images: LoadedImageMap,
+    txn_sender: Arc<dyn TransactionSender>,
+    image_fetcher: Arc<ImageFetcher>,
+    claim_policy: Arc<ClaimPolicy>,
+}
+
+impl RunnerHandle {
+    pub fn new(
+        config: Arc<ProverNodeConfig>,
+        inflight_proofs: InflightProofs,
+        loaded_images: LoadedImageMap,
+        txn_sender: Arc<dyn TransactionSender>,
+        image_fetcher: Arc<ImageFetcher>,
+        claim_policy: Arc<ClaimPolicy>,
+    ) -> Self {
+        RunnerHandle {
+            config,
+            inflight_proofs,
+            loaded_images,
+            txn_sender,
+            image_fetcher,
+            claim_policy,
+        }
+    }
+
+    /// The inflight proofs, the ones expiring first come first
+    pub fn inflight_proofs(&self) -> Vec<InflightProofSummary> {
+        self.inflight_proofs
+            .iter()
+            .map(|proof| InflightProofSummary {
+                execution_id: proof.execution_id.clone(),
+                image_id: proof.image_id.clone(),
+                status: proof.status,
+                expiry: proof.expiry,
+                requester: proof.requester.to_string(),
+                claim_signature: proof.claim_signature.to_string(),
+                submission_signature: proof.submission_signature.map(|sig| sig.to_string()),
+            })
+            .sorted_by_key(|proof| proof.expiry)
+            .collect()
+    }
+
+    pub async fn loaded_images(&self) -> Vec<ImageSummary> {
+        self.loaded_images.summaries().await
+    }
+
+    pub async fn current_block(&self) -> Result<u64> {
+        self.txn_sender.get_current_block().await
+    }
+
+    pub fn claiming_paused(&self) -> bool {
+        self.claim_policy.is_paused()
+    }
+
+    pub fn set_claiming_paused(&self, paused: bool) {
+        self.claim_policy.set_paused(paused);
+    }
+
+    /// Drops an image's program from memory, it is loaded again from disk when a claim needs it
+    pub async fn evict_image(&self, image_id: &str) -> Eviction {
+        if self
+            .inflight_proofs
+            .iter()
+            .any(|proof| proof.image_id == image_id)
+        {
+            return Eviction::InUse;
+        }
+        if self.loaded_images.compress(image_id).await {
+            Eviction::Evicted
+        } else {
+            Eviction::NotLoaded
+        }
+    }
+
+    /// Loads an image's program, images the node does not know yet are downloaded from their
+    /// deployment
+    pub async fn load_image(&self, image_id: &str) -> Result<()> {
+        if self.loaded_images.reload(image_id).await? {
+            return Ok(());
+        }
+        load_image(
+            &self.config,
+            self.txn_sender.as_ref(),
+            &self.image_fetcher,
+            image_id,
+            &self.loaded_images,
+        )
+        .await
+    }
+}
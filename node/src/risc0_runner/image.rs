@@ -0,0 +1,357 @@
+//! Keeps the parsed programs of the node's images within a memory budget.
+//! Every image the node knows about stays registered, but the program of the least recently used
+//! images is dropped with `Image::compress` when the loaded images exceed the budget or when an
+//! image has not been used for `image_compression_ttl_hours`. A compressed image is loaded again
+//! from disk the next time a claim needs it.
+use {
+    super::proving::ProvingImage,
+    crate::observe::*,
+    anyhow::Result,
+    bonsol_prover::image::Image,
+    dashmap::DashMap,
+    itertools::Itertools,
+    risc0_binfmt::MemoryImage,
+    serde::Serialize,
+    std::{
+        fs,
+        path::PathBuf,
+        sync::Arc,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+    tokio::{
+        sync::{Mutex, MutexGuard},
+        task::JoinHandle,
+    },
+};
+
+/// Time between two checks for images past the ttl
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImageSummary {
+    pub image_id: String,
+    pub size: u64,
+    /// Unix timestamp of the last time the image was loaded or used
+    pub last_used: u64,
+    /// Whether the program is held in memory
+    pub loaded: bool,
+}
+
+struct CachedImage {
+    /// Size of the image in memory, known without waiting for the image lock
+    size: u64,
+    image: Mutex<Image>,
+}
+
+/// A loaded image as seen by the eviction policy
+#[derive(Debug)]
+struct Usage {
+    image_id: String,
+    size: u64,
+    last_used: u64,
+    /// Images locked by a claim right now are counted but never evicted
+    evictable: bool,
+}
+
+pub struct ImageCache {
+    images: DashMap<String, Arc<CachedImage>>,
+    memory_budget: u64,
+    ttl_secs: u64,
+}
+
+impl ImageCache {
+    pub fn new(memory_budget_mb: u32, ttl_hours: u32) -> Self {
+        ImageCache {
+            images: DashMap::new(),
+            memory_budget: memory_budget_mb as u64 * 1024 * 1024,
+            ttl_secs: ttl_hours as u64 * 60 * 60,
+        }
+    }
+
+    /// Registers every image in `folder`, the ones that do not fit the budget are compressed as
+    /// they are loaded
+    pub async fn load_folder(&self, folder: &str) -> Result<()> {
+        for entry in fs::read_dir(folder)? {
+            let entry = entry?;
+            if entry.file_type()?.is_file() {
+                let img = Image::new(entry.path()).await?;
+                info!("Loaded image: {}", &img.id);
+                self.insert(img).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds a loaded image, replacing any previous image with the same id
+    pub async fn insert(&self, mut image: Image) {
+        image.last_used = now();
+        let image_id = image.id.clone();
+        self.images.insert(
+            image_id.clone(),
+            Arc::new(CachedImage {
+                size: image.size,
+                image: Mutex::new(image),
+            }),
+        );
+        self.enforce_budget(&image_id).await;
+    }
+
+    /// Forgets an image, returns the path its elf is stored at
+    pub async fn remove(&self, image_id: &str) -> Option<PathBuf> {
+        let (_, cached) = self.images.remove(image_id)?;
+        let image = cached.image.lock().await;
+        Some(image.path.clone())
+    }
+
+    pub fn contains(&self, image_id: &str) -> bool {
+        self.images.contains_key(image_id)
+    }
+
+    pub fn size(&self, image_id: &str) -> Option<u64> {
+        self.images.get(image_id).map(|cached| cached.size)
+    }
+
+    /// The memory image to execute an image with, its program is loaded again if it was
+    /// compressed. None when the image is unknown.
+    pub async fn memory_image(&self, image_id: &str) -> Result<Option<MemoryImage>> {
+        let cached = match self.cached(image_id) {
+            Some(cached) => cached,
+            None => return Ok(None),
+        };
+        let (memory_image, reloaded) = {
+            let (image, reloaded) = self.lock_loaded(image_id, &cached).await?;
+            (image.get_memory_image()?, reloaded)
+        };
+        if reloaded {
+            self.enforce_budget(image_id).await;
+        }
+        Ok(Some(memory_image))
+    }
+
+    /// The memory image and elf path to prove an image with, its program is loaded again if it
+    /// was compressed. None when the image is unknown.
+    pub async fn proving_image(&self, image_id: &str) -> Result<Option<ProvingImage>> {
+        let cached = match self.cached(image_id) {
+            Some(cached) => cached,
+            None => return Ok(None),
+        };
+        let (proving_image, reloaded) = {
+            let (image, reloaded) = self.lock_loaded(image_id, &cached).await?;
+            let proving_image = ProvingImage {
+                memory_image: image.get_memory_image()?,
+                elf_path: image.path.clone(),
+            };
+            (proving_image, reloaded)
+        };
+        if reloaded {
+            self.enforce_budget(image_id).await;
+        }
+        Ok(Some(proving_image))
+    }
+
+    /// Loads the program of a compressed image, returns false when the image is unknown
+    pub async fn reload(&self, image_id: &str) -> Result<bool> {
+        let cached = match self.cached(image_id) {
+            Some(cached) => cached,
+            None => return Ok(false),
+        };
+        let reloaded = self.lock_loaded(image_id, &cached).await?.1;
+        if reloaded {
+            self.enforce_budget(image_id).await;
+        }
+        Ok(true)
+    }
+
+    /// Drops the program of an image, returns false when it is unknown or already compressed
+    pub async fn compress(&self, image_id: &str) -> bool {
+        let cached = match self.cached(image_id) {
+            Some(cached) => cached,
+            None => return false,
+        };
+        let mut image = cached.image.lock().await;
+        if image.data.is_none() {
+            return false;
+        }
+        image.compress();
+        emit_event!(MetricEvents::ImageCompressed, image_id => image_id, reason => "manual");
+        true
+    }
+
+    pub async fn summaries(&self) -> Vec<ImageSummary> {
+        let cached = self
+            .images
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect_vec();
+        let mut summaries = Vec::with_capacity(cached.len());
+        for cached in cached {
+            let image = cached.image.lock().await;
+            summaries.push(ImageSummary {
+                image_id: image.id.clone(),
+                size: cached.size,
+                last_used: image.last_used,
+                loaded: image.data.is_some(),
+            });
+        }
+        summaries.sort_by(|a, b| a.image_id.cmp(&b.image_id));
+        summaries
+    }
+
+    /// Periodically compresses the images that were not used within the ttl
+    pub fn start(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+            loop {
+                interval.tick().await;
+                let usage = self.usage();
+                for image in expired(&usage, now(), self.ttl_secs) {
+                    self.compress_unused(image, "ttl").await;
+                }
+            }
+        })
+    }
+
+    fn cached(&self, image_id: &str) -> Option<Arc<CachedImage>> {
+        self.images.get(image_id).map(|entry| entry.value().clone())
+    }
+
+    /// Locks an image with its program loaded, the flag tells whether it had to be loaded
+    async fn lock_loaded<'a>(
+        &self,
+        image_id: &str,
+        cached: &'a CachedImage,
+    ) -> Result<(MutexGuard<'a, Image>, bool)> {
+        let mut image = cached.image.lock().await;
+        let reloaded = image.data.is_none();
+        if reloaded {
+            emit_counter!(MetricEvents::ImageCacheMiss, 1, "image_id" => image_id);
+            emit_event_with_duration!(MetricEvents::ImageLoaded, {
+                image.load().await
+            }, image_id => image_id)?;
+        } else {
+            emit_counter!(MetricEvents::ImageCacheHit, 1, "image_id" => image_id);
+        }
+        image.last_used = now();
+        Ok((image, reloaded))
+    }
+
+    /// Compresses the least recently used images until the loaded ones fit the budget, `keep` is
+    /// the image that was just loaded and stays
+    async fn enforce_budget(&self, keep: &str) {
+        let usage = self.usage();
+        for image in over_budget(&usage, self.memory_budget, keep) {
+            self.compress_unused(image, "budget").await;
+        }
+    }
+
+    /// Compresses an image unless it was used since `image` was observed
+    async fn compress_unused(&self, image: &Usage, reason: &str) {
+        if let Some(cached) = self.cached(&image.image_id) {
+            let mut current = cached.image.lock().await;
+            if current.data.is_some() && current.last_used == image.last_used {
+                current.compress();
+                emit_event!(MetricEvents::ImageCompressed, image_id => image.image_id.as_str(), reason => reason);
+            }
+        }
+    }
+
+    /// The images whose program is loaded
+    fn usage(&self) -> Vec<Usage> {
+        self.images
+            .iter()
+            .filter_map(|entry| match entry.value().image.try_lock() {
+                Ok(image) => image.data.as_ref().map(|_| Usage {
+                    image_id: image.id.clone(),
+                    size: entry.size,
+                    last_used: image.last_used,
+                    evictable: true,
+                }),
+                Err(_) => Some(Usage {
+                    image_id: entry.key().clone(),
+                    size: entry.size,
+                    last_used: u64::MAX,
+                    evictable: false,
+                }),
+            })
+            .collect()
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// The images to compress for the loaded ones to fit `budget`, least recently used first
+fn over_budget<'a>(usage: &'a [Usage], budget: u64, keep: &str) -> Vec<&'a Usage> {
+    let mut loaded: u64 = usage.iter().map(|image| image.size).sum();
+    let mut evicted = Vec::new();
+    for image in usage
+        .iter()
+        .filter(|image| image.evictable && image.image_id != keep)
+        .sorted_by_key(|image| image.last_used)
+    {
+        if loaded <= budget {
+            break;
+        }
+        loaded -= image.size;
+        evicted.push(image);
+    }
+    evicted
+}
+
+/// The images that were not used for more than `ttl_secs`
+fn expired(usage: &[Usage], now: u64, ttl_secs: u64) -> Vec<&Usage> {
+    usage
+        .iter()
+        .filter(|image| image.evictable && now.saturating_sub(image.last_used) > ttl_secs)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn usage(image_id: &str, size: u64, last_used: u64) -> Usage {
+        Usage {
+            image_id: image_id.to_string(),
+            size,
+            last_used,
+            evictable: true,
+        }
+    }
+
+    fn ids(images: Vec<&Usage>) -> Vec<&str> {
+        images.iter().map(|image| image.image_id.as_str()).collect()
+    }
+
+    #[test]
+    fn evicts_least_recently_used_until_within_budget() {
+        let mut busy = usage("busy", 40, 0);
+        busy.evictable = false;
+        let images = vec![
+            usage("new", 30, 300),
+            usage("old", 30, 100),
+            usage("older", 30, 50),
+            usage("recent", 30, 200),
+            busy,
+        ];
+        // 160 loaded, the busy and just loaded images stay
+        assert_eq!(
+            ids(over_budget(&images, 90, "new")),
+            vec!["older", "old", "recent"]
+        );
+        assert_eq!(ids(over_budget(&images, 100, "new")), vec!["older", "old"]);
+        assert!(over_budget(&images, 160, "new").is_empty());
+    }
+
+    #[test]
+    fn expires_images_unused_past_ttl() {
+        let mut busy = usage("busy", 10, 0);
+        busy.evictable = false;
+        let images = vec![usage("idle", 10, 100), usage("used", 10, 950), busy];
+        assert_eq!(ids(expired(&images, 1000, 60)), vec!["idle"]);
+    }
+}
@@ -1,58 +1,70 @@
+mod auction;
+mod backend;
+mod batch;
+mod compression;
+mod eligibility;
+mod expiry;
+mod fetcher;
+mod handle;
+mod image;
+mod proving;
 mod utils;
 pub mod verify_prover_version;
 
+pub use {
+    backend::{ProverBackend, ProverBackends},
+    fetcher::ImageFetcher,
+    handle::{Eviction, InflightProofSummary, RunnerHandle},
+    image::{ImageCache, ImageSummary},
+};
+
 use crate::transaction_sender::TransactionStatus;
 
-use {solana_sdk::instruction::AccountMeta, utils::check_stark_compression_tools_path};
+use solana_sdk::instruction::AccountMeta;
 
 use {
     crate::{
-        config::ProverNodeConfig,
+        claim_policy::{
+            BiddingStrategy, ClaimDecision, ClaimPolicy, ClaimRequest, ConfiguredBidding,
+        },
+        config::{ExpiryCrankConfig, ProofBatchConfig, ProverNodeConfig, ProverVersionConfig},
+        job_store::{forget_job, record_job, record_transition, JobRecord, JobStore},
         observe::*,
-        risc0_runner::utils::async_to_json,
-        transaction_sender::{RpcTransactionSender, TransactionSender},
+        receipt_archive::{archive_receipt, ReceiptArchive},
+        transaction_sender::TransactionSender,
         MissingImageStrategy,
     },
     bonsol_interface::{
-        bonsol_schema::{ClaimV1, DeployV1, ExecutionRequestV1},
-        prover_version::{ProverVersion, VERSION_V1_2_1},
+        bonsol_schema::{ClaimV1, DeployV1, ExecutionRequestV1, InputType, ProgramInputType},
+        util::prior_execution_address,
     },
     dashmap::DashMap,
-    risc0_binfmt::MemoryImage,
-    risc0_zkvm::{ExitCode, Journal, SuccinctReceipt},
-    solana_sdk::{pubkey::Pubkey, signature::Signature},
-    std::{
-        convert::TryInto, env::consts::ARCH, fs, io::Cursor, path::Path, sync::Arc, time::Duration,
-    },
+    risc0_zkvm::{Journal, SuccinctReceipt},
+    serde::{Deserialize, Serialize},
+    solana_sdk::{clock::MAX_PROCESSING_AGE, pubkey::Pubkey, signature::Signature},
+    std::{collections::HashMap, convert::TryInto, path::Path, sync::Arc, time::Duration},
 };
 
 use {
     crate::types::{BonsolInstruction, ProgramExec},
     anyhow::Result,
+    auction::{bid_and_wait, Auction, AuctionOutcome},
+    batch::{ProofBatcher, ReadyProof},
     bonsol_interface::bonsol_schema::{parse_ix_data, root_as_deploy_v1, ChannelInstructionIxType},
-    bonsol_prover::{
-        image::Image,
-        input_resolver::{InputResolver, ProgramInput},
-        prover::{get_risc0_prover, new_risc0_exec_env},
-        util::get_body_max_size,
-    },
-    risc0_groth16::{ProofJson, Seal},
+    bonsol_prover::input_resolver::{InputResolver, ProgramInput},
+    eligibility::{check_eligibility, claimer_allow_list},
+    expiry::ExpiryCrank,
+    proving::ProvingImage,
     risc0_zkvm::{
-        recursion::identity_p254,
         sha::{Digest, Digestible},
-        InnerReceipt, MaybePruned, ReceiptClaim, VerifierContext,
+        MaybePruned, ReceiptClaim,
     },
-    tempfile::tempdir,
     thiserror::Error,
-    tokio::{
-        fs::File, io::AsyncReadExt, process::Command, sync::mpsc::UnboundedSender, task::JoinHandle,
-    },
+    tokio::{sync::mpsc::UnboundedSender, task::JoinHandle},
     tracing::{error, info, warn},
-    verify_prover_version::verify_prover_version,
+    verify_prover_version::{verify_prover_version, verify_verifier_parameters},
 };
 
-const REQUIRED_PROVER: ProverVersion = VERSION_V1_2_1;
-
 #[derive(Debug, Error)]
 pub enum Risc0RunnerError {
     #[error("Empty instruction")]
@@ -73,17 +85,19 @@ pub enum Risc0RunnerError {
     ProofCompressionError,
     #[error("Error with proof generation")]
     ProofGenerationError,
-    #[error("Invalid prover version {0}, expected {1}")]
-    InvalidProverVersion(ProverVersion, ProverVersion),
+    #[error("No prover backend for version {0:?}")]
+    NoProverBackend(ProverVersionConfig),
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ClaimStatus {
     Claiming,
     Submitted,
+    Confirmed,
+    Expired,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InflightProof {
     pub execution_id: String,
     pub image_id: String,
@@ -94,58 +108,142 @@ pub struct InflightProof {
     pub requester: Pubkey,
     pub program_callback: Option<ProgramExec>,
     pub additional_accounts: Vec<AccountMeta>,
+    /// The prover version the execution is pinned to, it is proven by the matching backend
+    #[serde(default)]
+    pub prover_version: ProverVersionConfig,
+    /// The execution accounts of the prior executions the execution assumes, in input order
+    #[serde(default)]
+    pub prior_executions: Vec<Pubkey>,
 }
 
 type InflightProofs = Arc<DashMap<String, InflightProof>>;
 type InflightProofRef<'a> = &'a DashMap<String, InflightProof>;
 
-type LoadedImageMap = Arc<DashMap<String, Image>>;
-type LoadedImageMapRef<'a> = &'a DashMap<String, Image>;
+type LoadedImageMap = Arc<ImageCache>;
+type LoadedImageMapRef<'a> = &'a ImageCache;
 
 type InputStagingArea = Arc<DashMap<String, Vec<ProgramInput>>>;
 type InputStagingAreaRef<'a> = &'a DashMap<String, Vec<ProgramInput>>;
 
+/// The services an execution request uses from its claim to the submission of its proof, shared
+/// by the runner's workers
+#[derive(Clone)]
+pub struct JobContext {
+    pub config: Arc<ProverNodeConfig>,
+    pub self_identity: Pubkey,
+    pub backends: Arc<ProverBackends>,
+    pub claim_policy: Arc<ClaimPolicy>,
+    pub bidding_strategy: Arc<dyn BiddingStrategy>,
+    pub input_resolver: Arc<dyn InputResolver + 'static>,
+    pub image_fetcher: Arc<ImageFetcher>,
+    pub txn_sender: Arc<dyn TransactionSender>,
+    pub job_store: Arc<dyn JobStore>,
+    pub receipt_archive: Arc<dyn ReceiptArchive>,
+    pub inflight_proofs: InflightProofs,
+    pub loaded_images: LoadedImageMap,
+    pub input_staging_area: InputStagingArea,
+}
+
 pub struct Risc0Runner {
-    config: Arc<ProverNodeConfig>,
-    loaded_images: LoadedImageMap,
+    jobs: JobContext,
     worker_handle: Option<JoinHandle<Result<()>>>,
     inflight_proof_worker_handle: Option<JoinHandle<Result<()>>>,
-    txn_sender: Arc<RpcTransactionSender>,
-    input_staging_area: InputStagingArea,
-    self_identity: Arc<Pubkey>,
-    inflight_proofs: InflightProofs,
-    input_resolver: Arc<dyn InputResolver + 'static>,
+    image_cache_worker_handle: Option<JoinHandle<()>>,
+    expiry_crank_worker_handle: Option<JoinHandle<()>>,
+    proof_batch_worker_handle: Option<JoinHandle<()>>,
+    resumed_claims: Vec<String>,
+    expiry_crank: Option<Arc<ExpiryCrank>>,
+    proof_batcher: Option<Arc<ProofBatcher>>,
 }
 
 impl Risc0Runner {
     pub async fn new(
         config: ProverNodeConfig,
         self_identity: Pubkey,
-        txn_sender: Arc<RpcTransactionSender>,
+        txn_sender: Arc<dyn TransactionSender>,
         input_resolver: Arc<dyn InputResolver + 'static>,
+        job_store: Arc<dyn JobStore>,
+        receipt_archive: Arc<dyn ReceiptArchive>,
     ) -> Result<Risc0Runner> {
-        let dir = fs::read_dir(&config.risc0_image_folder)?;
-        let loaded_images = DashMap::new();
-        for entry in dir {
-            let entry = entry?;
-            if entry.file_type()?.is_file() {
-                let img = Image::new(entry.path()).await?;
-                info!("Loaded image: {}", &img.id);
-                loaded_images.insert(img.id.clone(), img);
-            }
-        }
-        check_stark_compression_tools_path(&config.stark_compression_tools_path)?;
+        let loaded_images = ImageCache::new(
+            config.image_cache_memory_budget_mb,
+            config.image_compression_ttl_hours,
+        );
+        loaded_images
+            .load_folder(&config.risc0_image_folder)
+            .await?;
+        let backends = Arc::new(ProverBackends::new(&config)?);
+        let inflight_proofs = Arc::new(DashMap::new());
+        let input_staging_area = Arc::new(DashMap::new());
+        let resumed_claims = restore_jobs(
+            job_store.as_ref(),
+            txn_sender.as_ref(),
+            &inflight_proofs,
+            &input_staging_area,
+        )
+        .await?;
 
-        Ok(Risc0Runner {
+        let claim_policy = Arc::new(ClaimPolicy::new(&config));
+        let bidding_strategy: Arc<dyn BiddingStrategy> = Arc::new(ConfiguredBidding::new(&config));
+        // TODO: move image handling out of prover
+        let image_fetcher = Arc::new(ImageFetcher::new(
+            reqwest::Client::builder()
+                .timeout(Duration::from_secs(
+                    config.image_download_timeout_secs as u64,
+                ))
+                .build()?,
+            config.image_source_config.clone(),
+            config.max_image_size_mb as u64 * 1024 * 1024,
+        ));
+
+        let expiry_crank = match config.expiry_crank_config {
+            ExpiryCrankConfig::Enabled { interval_secs } => Some(Arc::new(ExpiryCrank::new(
+                txn_sender.clone(),
+                interval_secs,
+            ))),
+            ExpiryCrankConfig::Disabled => None,
+        };
+
+        let proof_batch_config = config.proof_batch_config.clone();
+        let jobs = JobContext {
             config: Arc::new(config),
+            self_identity,
+            backends,
+            claim_policy,
+            bidding_strategy,
+            input_resolver,
+            image_fetcher,
+            txn_sender,
+            job_store,
+            receipt_archive,
+            inflight_proofs,
             loaded_images: Arc::new(loaded_images),
+            input_staging_area,
+        };
+        let proof_batcher = match proof_batch_config {
+            ProofBatchConfig::Enabled {
+                image_id,
+                window_ms,
+                max_batch_size,
+            } => Some(Arc::new(ProofBatcher::new(
+                image_id,
+                window_ms,
+                max_batch_size,
+                jobs.clone(),
+            ))),
+            ProofBatchConfig::Disabled => None,
+        };
+
+        Ok(Risc0Runner {
+            jobs,
             worker_handle: None,
             inflight_proof_worker_handle: None,
-            txn_sender,
-            input_staging_area: Arc::new(DashMap::new()),
-            self_identity: Arc::new(self_identity),
-            inflight_proofs: Arc::new(DashMap::new()),
-            input_resolver,
+            image_cache_worker_handle: None,
+            expiry_crank_worker_handle: None,
+            proof_batch_worker_handle: None,
+            resumed_claims,
+            expiry_crank,
+            proof_batcher,
         })
     }
 
@@ -153,46 +251,69 @@ impl Risc0Runner {
     // Break into Image handling, Input handling, Execution Request
     // Inputs and Image should be service used by this prover.
     pub fn start(&mut self) -> Result<UnboundedSender<BonsolInstruction>> {
-        verify_prover_version(REQUIRED_PROVER)
+        verify_prover_version(ProverVersionConfig::default().prover_version())
             .expect("Bonsol build conflict: prover version is not supported");
+        for backend in self.jobs.backends.iter() {
+            verify_verifier_parameters(
+                backend.version().prover_version(),
+                &backend.verifier_parameters(),
+            )
+            .expect("Prover backend constants do not match their prover version");
+        }
         let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<BonsolInstruction>();
-        let loaded_images = self.loaded_images.clone();
-        // TODO: move image handling out of prover
-        let img_client = Arc::new(
-            reqwest::Client::builder()
-                .timeout(Duration::from_secs(
-                    self.config.image_download_timeout_secs as u64,
-                ))
-                .build()?,
-        );
-        let config = self.config.clone();
-        let self_id = self.self_identity.clone();
-        let input_staging_area = self.input_staging_area.clone();
-        let inflight_proofs = self.inflight_proofs.clone();
-        let txn_sender = self.txn_sender.clone();
+        self.image_cache_worker_handle = Some(self.jobs.loaded_images.clone().start());
+        self.expiry_crank_worker_handle = self
+            .expiry_crank
+            .as_ref()
+            .map(|crank| crank.clone().start());
+        self.proof_batch_worker_handle = self
+            .proof_batcher
+            .as_ref()
+            .map(|batcher| batcher.clone().start());
+        let jobs = self.jobs.clone();
         self.inflight_proof_worker_handle = Some(tokio::spawn(async move {
+            let JobContext {
+                inflight_proofs,
+                txn_sender,
+                job_store,
+                ..
+            } = &jobs;
             let mut interval = tokio::time::interval(Duration::from_secs(1));
             loop {
                 interval.tick().await;
                 let current_block = txn_sender.get_current_block().await.unwrap_or(0);
+                // the proofs of a batch share their submission, its status is cleared with the last of them
+                let mut submissions: HashMap<Signature, usize> = HashMap::new();
+                for proof in inflight_proofs.iter() {
+                    if let (ClaimStatus::Submitted, Some(sig)) =
+                        (proof.status, proof.submission_signature)
+                    {
+                        *submissions.entry(sig).or_default() += 1;
+                    }
+                }
+                // the job store writes to disk, the jobs are persisted once the map is released
+                let mut transitions = Vec::new();
+                let mut finished = Vec::new();
                 inflight_proofs.retain(|_, v| {
                     if v.expiry < current_block {
                         emit_event!(MetricEvents::ProofExpired, execution_id => v.execution_id.clone());
+                        v.status = ClaimStatus::Expired;
+                        transitions.push(v.clone());
                         return false;
                     }
-                    match &v.status {
+                    match v.status {
                         ClaimStatus::Claiming => {
                             let sig = v.claim_signature;
                             let inner_status = txn_sender.get_signature_status(&sig);
-                            return match inner_status {
+                            let keep = match inner_status {
                                 None => false,
                                 Some(status) => {
                                     match status {
                                         TransactionStatus::Confirmed(status) => {
-                                            txn_sender.clear_signature_status(&sig);
+                                            // a landed claim stays tracked until its claim event is handled
                                             if status.err.is_some() {
                                                 info!("Claim Transaction Failed");
-
+                                                txn_sender.clear_signature_status(&sig);
                                             }
                                             status.err.is_none()
                                         },
@@ -200,20 +321,35 @@ impl Risc0Runner {
                                     }
                                 }
                             };
+                            if !keep {
+                                finished.push(v.execution_id.clone());
+                            }
+                            return keep;
                         }
                         ClaimStatus::Submitted => {
-                            if let Some(sig) = v.submission_signature.as_ref() {
-                                let inner_status = txn_sender.get_signature_status(sig);
+                            if let Some(sig) = v.submission_signature {
+                                let inner_status = txn_sender.get_signature_status(&sig);
                                 return match inner_status {
-                                    None => false,
+                                    None => {
+                                        finished.push(v.execution_id.clone());
+                                        false
+                                    },
                                     Some(status) => {
                                         match status {
                                             TransactionStatus::Confirmed(status) => {
-                                                txn_sender.clear_signature_status(sig);
+                                                let sharing = submissions.entry(sig).or_insert(1);
+                                                *sharing -= 1;
+                                                if *sharing == 0 {
+                                                    txn_sender.clear_signature_status(&sig);
+                                                }
                                                 if status.err.is_some() {
                                                     emit_event!(MetricEvents::ProofSubmissionError, sig => sig.to_string());
+                                                    finished.push(v.execution_id.clone());
+                                                } else {
+                                                    v.status = ClaimStatus::Confirmed;
+                                                    transitions.push(v.clone());
                                                 }
-                                                status.err.is_none()
+                                                false
                                             },
                                             _ => true
                                         }
@@ -221,25 +357,47 @@ impl Risc0Runner {
                                 };
                             }
                         }
+                        ClaimStatus::Confirmed | ClaimStatus::Expired => return false,
                     };
                     true
                 });
+                if transitions.is_empty() && finished.is_empty() {
+                    continue;
+                }
+                let job_store = job_store.clone();
+                let persisted = tokio::task::spawn_blocking(move || {
+                    for proof in &transitions {
+                        record_transition(job_store.as_ref(), proof);
+                    }
+                    for execution_id in &finished {
+                        forget_job(job_store.as_ref(), execution_id);
+                    }
+                })
+                .await;
+                if let Err(e) = persisted {
+                    error!("Failed to persist the inflight jobs: {:?}", e);
+                }
             }
         }));
 
-        let inflight_proofs = self.inflight_proofs.clone();
-        let txn_sender = self.txn_sender.clone();
-        let input_resolver = self.input_resolver.clone();
+        let jobs = self.jobs.clone();
+        let expiry_crank = self.expiry_crank.clone();
+        let proof_batcher = self.proof_batcher.clone();
+        for execution_id in self.resumed_claims.drain(..) {
+            let jobs = jobs.clone();
+            let proof_batcher = proof_batcher.clone();
+            tokio::spawn(async move {
+                let result = resume_claim(&jobs, proof_batcher.as_deref(), execution_id).await;
+                if result.is_err() {
+                    info!("Error: {:?}", result);
+                }
+            });
+        }
         self.worker_handle = Some(tokio::spawn(async move {
             while let Some(bix) = rx.recv().await {
-                let txn_sender = txn_sender.clone();
-                let loaded_images = loaded_images.clone();
-                let config = config.clone();
-                let img_client = img_client.clone();
-                let input_resolver = input_resolver.clone();
-                let self_id = self_id.clone();
-                let input_staging_area = input_staging_area.clone();
-                let inflight_proofs = inflight_proofs.clone();
+                let jobs = jobs.clone();
+                let expiry_crank = expiry_crank.clone();
+                let proof_batcher = proof_batcher.clone();
                 tokio::spawn(async move {
                     let bonsol_ix_type =
                         parse_ix_data(&bix.data).map_err(|_| Risc0RunnerError::InvalidData)?;
@@ -251,8 +409,13 @@ impl Risc0Runner {
                                 Risc0RunnerError::EmptyInstruction.into(),
                             )?;
                             emit_counter!(MetricEvents::ImageDeployment, 1, "image_id" => payload.image_id().unwrap_or_default());
-                            handle_image_deployment(&config, &img_client, payload, &loaded_images)
-                                .await
+                            handle_image_deployment(
+                                &jobs.config,
+                                &jobs.image_fetcher,
+                                payload,
+                                &jobs.loaded_images,
+                            )
+                            .await
                         }
                         ChannelInstructionIxType::ExecuteV1 => {
                             info!("Received execution request");
@@ -262,31 +425,17 @@ impl Risc0Runner {
                                 .ok_or::<anyhow::Error>(
                                 Risc0RunnerError::EmptyInstruction.into(),
                             )?;
-                            let er_prover_version: ProverVersion = payload
-                                .prover_version()
-                                .try_into()
-                                .map_err::<anyhow::Error, _>(|_| {
-                                    Risc0RunnerError::InvalidProverVersion(
-                                        ProverVersion::UnsupportedVersion,
-                                        REQUIRED_PROVER,
-                                    )
-                                    .into()
-                                })?;
-                            if er_prover_version != REQUIRED_PROVER {
-                                return Err(Risc0RunnerError::InvalidProverVersion(
-                                    er_prover_version,
-                                    REQUIRED_PROVER,
-                                )
-                                .into());
+                            if let (Some(crank), Some(execution_id)) =
+                                (&expiry_crank, payload.execution_id())
+                            {
+                                crank.watch(
+                                    execution_id,
+                                    bix.accounts[0],
+                                    payload.max_block_height(),
+                                );
                             }
                             handle_execution_request(
-                                &config,
-                                &inflight_proofs,
-                                input_resolver.clone(),
-                                img_client.clone(),
-                                &txn_sender,
-                                &loaded_images,
-                                &input_staging_area,
+                                &jobs,
                                 bix.last_known_block,
                                 payload,
                                 &bix.accounts,
@@ -300,20 +449,90 @@ impl Risc0Runner {
                                 .ok_or::<anyhow::Error>(
                                 Risc0RunnerError::EmptyInstruction.into(),
                             )?;
-                            handle_claim(
-                                &config,
-                                &self_id,
-                                &inflight_proofs,
-                                input_resolver.clone(),
-                                &txn_sender,
-                                &loaded_images,
-                                &input_staging_area,
-                                payload,
-                                &bix.accounts,
+                            handle_claim(&jobs, proof_batcher.as_deref(), payload, &bix.accounts)
+                                .await
+                        }
+                        ChannelInstructionIxType::UpdateDeploymentUrlV1 => {
+                            let payload = bonsol_ix_type
+                                .manage_deployment_v1_nested_flatbuffer()
+                                .ok_or::<anyhow::Error>(
+                                Risc0RunnerError::EmptyInstruction.into(),
+                            )?;
+                            let image_id =
+                                payload.image_id().ok_or(Risc0RunnerError::InvalidData)?;
+                            emit_event!(MetricEvents::DeploymentUpdated, image_id => image_id, action => "update_url");
+                            handle_deployment_url_update(
+                                &jobs.config,
+                                jobs.txn_sender.as_ref(),
+                                &jobs.image_fetcher,
+                                image_id,
+                                &jobs.loaded_images,
+                            )
+                            .await
+                        }
+                        ix_type @ (ChannelInstructionIxType::DeprecateDeploymentV1
+                        | ChannelInstructionIxType::CloseDeploymentV1) => {
+                            let payload = bonsol_ix_type
+                                .manage_deployment_v1_nested_flatbuffer()
+                                .ok_or::<anyhow::Error>(
+                                Risc0RunnerError::EmptyInstruction.into(),
+                            )?;
+                            let image_id =
+                                payload.image_id().ok_or(Risc0RunnerError::InvalidData)?;
+                            let closed = ix_type == ChannelInstructionIxType::CloseDeploymentV1;
+                            let action = if closed { "close" } else { "deprecate" };
+                            emit_event!(MetricEvents::DeploymentUpdated, image_id => image_id, action => action);
+                            handle_deployment_retired(
+                                &jobs.inflight_proofs,
+                                &jobs.loaded_images,
+                                image_id,
+                                closed,
                             )
                             .await
                         }
-                        ChannelInstructionIxType::StatusV1 => Ok(()),
+                        ChannelInstructionIxType::CancelV1 => {
+                            let payload = bonsol_ix_type
+                                .cancel_v1_nested_flatbuffer()
+                                .ok_or::<anyhow::Error>(
+                                Risc0RunnerError::EmptyInstruction.into(),
+                            )?;
+                            let execution_id = payload
+                                .execution_id()
+                                .ok_or(Risc0RunnerError::InvalidData)?;
+                            if let Some(crank) = &expiry_crank {
+                                crank.forget(execution_id);
+                            }
+                            handle_cancel(
+                                &jobs.inflight_proofs,
+                                jobs.job_store.as_ref(),
+                                jobs.txn_sender.as_ref(),
+                                &jobs.input_staging_area,
+                                execution_id,
+                            );
+                            Ok(())
+                        }
+                        ChannelInstructionIxType::StatusV1 => {
+                            if let (Some(crank), Some(execution_id)) = (
+                                &expiry_crank,
+                                bonsol_ix_type
+                                    .status_v1_nested_flatbuffer()
+                                    .and_then(|status| status.execution_id()),
+                            ) {
+                                crank.forget(execution_id);
+                            }
+                            Ok(())
+                        }
+                        ChannelInstructionIxType::ExpireV1 => {
+                            if let (Some(crank), Some(execution_id)) = (
+                                &expiry_crank,
+                                bonsol_ix_type
+                                    .expire_v1_nested_flatbuffer()
+                                    .and_then(|expire| expire.execution_id()),
+                            ) {
+                                crank.forget(execution_id);
+                            }
+                            Ok(())
+                        }
                         _ => {
                             info!("Unknown instruction type");
                             Ok(())
@@ -330,6 +549,18 @@ impl Risc0Runner {
         Ok(tx)
     }
 
+    /// A handle to inspect and steer the runner while it runs
+    pub fn handle(&self) -> RunnerHandle {
+        RunnerHandle::new(
+            self.jobs.config.clone(),
+            self.jobs.inflight_proofs.clone(),
+            self.jobs.loaded_images.clone(),
+            self.jobs.txn_sender.clone(),
+            self.jobs.image_fetcher.clone(),
+            self.jobs.claim_policy.clone(),
+        )
+    }
+
     pub fn stop(&mut self) -> Result<()> {
         self.worker_handle.take().unwrap().abort();
         Ok(())
@@ -337,22 +568,22 @@ impl Risc0Runner {
 }
 
 pub async fn handle_claim<'a>(
-    config: &ProverNodeConfig,
-    self_identity: &Pubkey,
-    in_flight_proofs: InflightProofRef<'a>,
-    input_resolver: Arc<dyn InputResolver + 'static>,
-    transaction_sender: &RpcTransactionSender,
-    loaded_images: LoadedImageMapRef<'a>,
-    input_staging_area: InputStagingAreaRef<'a>,
+    jobs: &JobContext,
+    proof_batcher: Option<&ProofBatcher>,
     claim: ClaimV1<'a>,
     accounts: &[Pubkey], // need to create cannonical parsing of accounts per instruction type for my flatbuffer model or use shank
 ) -> Result<()> {
+    let self_identity = &jobs.self_identity;
+    let in_flight_proofs = &jobs.inflight_proofs;
+    let transaction_sender = jobs.txn_sender.as_ref();
+    let job_store = jobs.job_store.as_ref();
     info!("Received claim event");
     let claimer = accounts[3];
     let execution_id = claim.execution_id().ok_or(Risc0RunnerError::InvalidData)?;
     if &claimer != self_identity {
         let attempt = in_flight_proofs.remove(execution_id);
         if let Some((ifp, claim)) = attempt {
+            forget_job(job_store, &ifp);
             if let ClaimStatus::Claiming = claim.status {
                 transaction_sender.clear_signature_status(&claim.claim_signature);
                 emit_event!(MetricEvents::ClaimMissed, execution_id => ifp, signature => &claim.claim_signature.to_string());
@@ -364,258 +595,549 @@ pub async fn handle_claim<'a>(
     let claim_status = in_flight_proofs
         .get(execution_id)
         .map(|v| v.value().to_owned());
-    if let Some(mut claim) = claim_status {
+    if let Some(claim) = claim_status {
         emit_event!(MetricEvents::ClaimReceived, execution_id => execution_id);
         if let ClaimStatus::Claiming = claim.status {
-            if let Some(image) = loaded_images.get(&claim.image_id) {
-                if image.data.is_none() {
-                    return Err(Risc0RunnerError::ImageDataUnavailable.into());
-                }
-                //if image is not loaded at claim, fail
-                let mut inputs = input_staging_area
-                    .get(execution_id)
-                    .ok_or(Risc0RunnerError::InvalidData)?
-                    .value()
-                    .clone(); //clone soe we dont hold a reference over http requests
-                let unresolved_count = inputs
-                    .iter()
-                    .filter(|i| match i {
-                        ProgramInput::Unresolved(_) => true,
-                        _ => false,
-                    })
-                    .count();
+            transaction_sender.clear_signature_status(&claim.claim_signature);
+            prove_and_submit(jobs, proof_batcher, claim).await?;
+        }
+    }
+    Ok(())
+}
 
-                if unresolved_count > 0 {
-                    info!("{} outstanding inputs", unresolved_count);
+/// The requester withdrew the execution, a lapsed claim of this node can no longer be proven
+fn handle_cancel<'a>(
+    in_flight_proofs: InflightProofRef<'a>,
+    job_store: &dyn JobStore,
+    transaction_sender: &dyn TransactionSender,
+    input_staging_area: InputStagingAreaRef<'a>,
+    execution_id: &str,
+) {
+    emit_event!(MetricEvents::ExecutionCancelled, execution_id => execution_id);
+    input_staging_area.remove(execution_id);
+    if let Some((eid, claim)) = in_flight_proofs.remove(execution_id) {
+        forget_job(job_store, &eid);
+        if let ClaimStatus::Claiming = claim.status {
+            transaction_sender.clear_signature_status(&claim.claim_signature);
+        }
+    }
+}
 
-                    emit_event_with_duration!(MetricEvents::InputDownload, {
-                        input_resolver.resolve_private_inputs(execution_id, &mut inputs, Arc::new(transaction_sender)).await?;
-                    }, execution_id => execution_id, stage => "private");
-                    input_staging_area.insert(execution_id.to_string(), inputs);
-                    // one of the huge problems with the claim system is that we are not guaranteed to have
-                    // the inputs we need at the time we claim and no way to
-                }
-                info!("{} inputs resolved", unresolved_count);
-
-                // drain the inputs and own them here, this is a bit of a hack but it works
-                let (eid, inputs) = input_staging_area
-                    .remove(execution_id)
-                    .ok_or(Risc0RunnerError::InvalidData)?;
-                let mem_image = image.get_memory_image()?;
-                let result: Result<
-                    (Journal, Digest, SuccinctReceipt<ReceiptClaim>),
-                    Risc0RunnerError,
-                > = tokio::task::spawn_blocking(move || {
-                    risc0_prove(mem_image, inputs).map_err(|e| {
-                        info!("Error generating proof: {:?}", e);
-                        Risc0RunnerError::ProofGenerationError
-                    })
+/// Picks up a job restored from the job store that was still claiming when the node stopped.
+/// The claim event may have been missed while the node was down so the claim account is checked directly.
+async fn resume_claim(
+    jobs: &JobContext,
+    proof_batcher: Option<&ProofBatcher>,
+    execution_id: String,
+) -> Result<()> {
+    let self_identity = &jobs.self_identity;
+    let in_flight_proofs = &jobs.inflight_proofs;
+    let transaction_sender = jobs.txn_sender.as_ref();
+    let job_store = jobs.job_store.as_ref();
+    let input_staging_area = &jobs.input_staging_area;
+    let claim = match in_flight_proofs.get(&execution_id) {
+        Some(claim) if claim.status == ClaimStatus::Claiming => claim.value().to_owned(),
+        _ => return Ok(()),
+    };
+    let claim_state = transaction_sender
+        .get_claim_state(claim.requester, &execution_id)
+        .await?;
+    match claim_state {
+        Some(state) if Pubkey::from(state.claimer) == *self_identity => {
+            info!("Resuming claimed execution {}", execution_id);
+            transaction_sender.clear_signature_status(&claim.claim_signature);
+            prove_and_submit(jobs, proof_batcher, claim).await
+        }
+        Some(_) => {
+            in_flight_proofs.remove(&execution_id);
+            input_staging_area.remove(&execution_id);
+            forget_job(job_store, &execution_id);
+            transaction_sender.clear_signature_status(&claim.claim_signature);
+            emit_event!(MetricEvents::ClaimMissed, execution_id => execution_id, signature => &claim.claim_signature.to_string());
+            Ok(())
+        }
+        // the claim has not landed yet, the claim event will pick it up
+        None => Ok(()),
+    }
+}
+
+async fn prove_and_submit(
+    jobs: &JobContext,
+    proof_batcher: Option<&ProofBatcher>,
+    claim: InflightProof,
+) -> Result<()> {
+    let backends = &jobs.backends;
+    let claim_policy = &jobs.claim_policy;
+    let in_flight_proofs = &jobs.inflight_proofs;
+    let job_store = jobs.job_store.as_ref();
+    let input_resolver = &jobs.input_resolver;
+    let transaction_sender = jobs.txn_sender.as_ref();
+    let loaded_images = &jobs.loaded_images;
+    let input_staging_area = &jobs.input_staging_area;
+    let execution_id = claim.execution_id.clone();
+    let execution_id = execution_id.as_str();
+    let backend = backends
+        .get(claim.prover_version)
+        .ok_or(Risc0RunnerError::NoProverBackend(claim.prover_version))?;
+    if loaded_images.contains(&claim.image_id) {
+        //if image is not loaded at claim, fail
+        let mut inputs = input_staging_area
+            .get(execution_id)
+            .ok_or(Risc0RunnerError::InvalidData)?
+            .value()
+            .clone(); //clone soe we dont hold a reference over http requests
+        let unresolved_count = inputs
+            .iter()
+            .filter(|i| match i {
+                ProgramInput::Unresolved(_) => true,
+                _ => false,
+            })
+            .count();
+
+        if unresolved_count > 0 {
+            info!("{} outstanding inputs", unresolved_count);
+
+            emit_event_with_duration!(MetricEvents::InputDownload, {
+                input_resolver.resolve_private_inputs(execution_id, &mut inputs, transaction_sender.signer()).await?;
+            }, execution_id => execution_id, stage => "private");
+            input_staging_area.insert(execution_id.to_string(), inputs);
+            // one of the huge problems with the claim system is that we are not guaranteed to have
+            // the inputs we need at the time we claim and no way to
+        }
+        info!("{} inputs resolved", unresolved_count);
+
+        // drain the inputs and own them here, this is a bit of a hack but it works
+        let (eid, inputs) = input_staging_area
+            .remove(execution_id)
+            .ok_or(Risc0RunnerError::InvalidData)?;
+        let image = loaded_images
+            .proving_image(&claim.image_id)
+            .await
+            .map_err(|e| {
+                error!("Failed to load image {}: {:?}", claim.image_id, e);
+                Risc0RunnerError::ImageDataUnavailable
+            })?
+            .ok_or(Risc0RunnerError::ImageDataUnavailable)?;
+        let result: Result<
+            (Journal, Digest, SuccinctReceipt<ReceiptClaim>, u64),
+            Risc0RunnerError,
+        > = tokio::task::spawn_blocking({
+            let backend = backend.clone();
+            move || {
+                risc0_prove(&backend, image, inputs).map_err(|e| {
+                    info!("Error generating proof: {:?}", e);
+                    Risc0RunnerError::ProofGenerationError
                 })
-                .await?;
-                match result {
-                    Ok((journal, assumptions_digest, reciept)) => {
-                        let compressed_receipt = risc0_compress_proof(
-                            config.stark_compression_tools_path.as_str(),
-                            reciept,
-                        )
-                        .await
-                        .map_err(|e| {
-                            info!("Error compressing proof: {:?}", e);
-                            Risc0RunnerError::ProofCompressionError
-                        })?;
-
-                        let (input_digest, committed_outputs) = journal.bytes.split_at(32);
-                        let sig = transaction_sender
-                            .submit_proof(
-                                &eid,
-                                claim.requester,
-                                claim.program_callback.clone(),
-                                &compressed_receipt.proof,
-                                &compressed_receipt.execution_digest,
-                                input_digest,
-                                assumptions_digest.as_bytes(),
-                                committed_outputs,
-                                claim.additional_accounts.clone(),
-                                compressed_receipt.exit_code_system,
-                                compressed_receipt.exit_code_user,
-                            )
-                            .await
-                            .map_err(|e| {
-                                error!("Error submitting proof: {:?}", e);
-                                Risc0RunnerError::TransactionError(e.to_string())
-                            })?;
-
-                        claim.status = ClaimStatus::Submitted;
-                        claim.submission_signature = Some(sig);
-                        in_flight_proofs.insert(eid.clone(), claim);
-                        info!("Proof submitted: {:?}", sig);
-                    }
-                    Err(e) => {
-                        info!("Error generating proof: {:?}", e);
-                    }
+            }
+        })
+        .await?;
+        match result {
+            Ok((journal, assumptions_digest, receipt, total_cycles)) => {
+                claim_policy.record_cycles(&claim.image_id, total_cycles);
+                let proof = ReadyProof {
+                    claim,
+                    backend,
+                    journal,
+                    assumptions_digest,
+                    receipt,
                 };
+                match proof_batcher {
+                    // the batch image resolves its assumptions, proofs assuming prior executions
+                    // are settled on their own
+                    Some(batcher) if proof.claim.prior_executions.is_empty() => batcher.add(proof),
+                    _ => submit_proof(jobs, proof).await?,
+                }
+            }
+            Err(e) => {
+                info!("Error generating proof: {:?}", e);
                 in_flight_proofs.remove(&eid);
-            } else {
-                info!("Image not loaded, fatal error aborting execution");
+                forget_job(job_store, &eid);
             }
-        }
+        };
+    } else {
+        info!("Image not loaded, fatal error aborting execution");
     }
     Ok(())
 }
 
-async fn handle_execution_request<'a>(
-    config: &ProverNodeConfig,
+/// Compresses the proof of an execution and submits it with a `StatusV1` of its own, its receipt
+/// is archived once submitted
+async fn submit_proof(jobs: &JobContext, proof: ReadyProof) -> Result<()> {
+    let in_flight_proofs = &jobs.inflight_proofs;
+    let job_store = jobs.job_store.as_ref();
+    let receipt_archive = jobs.receipt_archive.as_ref();
+    let transaction_sender = jobs.txn_sender.as_ref();
+    let ReadyProof {
+        mut claim,
+        backend,
+        journal,
+        assumptions_digest,
+        receipt,
+    } = proof;
+    let succinct_receipt = receipt.clone();
+    let receipt = tokio::task::spawn_blocking({
+        let backend = backend.clone();
+        move || backend.identity_p254(&receipt)
+    })
+    .await?
+    .map_err(|e| {
+        info!("Error generating proof: {:?}", e);
+        Risc0RunnerError::ProofGenerationError
+    })?;
+    let compressed_receipt = backend.compress(receipt).await.map_err(|e| {
+        info!("Error compressing proof: {:?}", e);
+        Risc0RunnerError::ProofCompressionError
+    })?;
+
+    let (input_digest, committed_outputs) = journal.bytes.split_at(32);
+    let sig = transaction_sender
+        .submit_proof(
+            &claim.execution_id,
+            claim.requester,
+            claim.program_callback.clone(),
+            &compressed_receipt.proof,
+            &compressed_receipt.execution_digest,
+            input_digest,
+            assumptions_digest.as_bytes(),
+            committed_outputs,
+            claim.additional_accounts.clone(),
+            claim.prior_executions.clone(),
+            compressed_receipt.exit_code_system,
+            compressed_receipt.exit_code_user,
+            claim.expiry,
+            backend.version().schema_version(),
+        )
+        .await
+        .map_err(|e| {
+            error!("Error submitting proof: {:?}", e);
+            Risc0RunnerError::TransactionError(e.to_string())
+        })?;
+
+    claim.status = ClaimStatus::Submitted;
+    claim.submission_signature = Some(sig);
+    // the inflight worker retires the job once the submission is confirmed
+    record_transition(job_store, &claim);
+    archive_receipt(
+        receipt_archive,
+        &claim.requester,
+        &claim.execution_id,
+        succinct_receipt,
+        journal,
+        Some(&compressed_receipt.proof),
+    )
+    .await;
+    in_flight_proofs.insert(claim.execution_id.clone(), claim);
+    info!("Proof submitted: {:?}", sig);
+    Ok(())
+}
+
+/// Restores the jobs this node had claimed before it was stopped.
+/// Finished and expired jobs are pruned, the execution ids still waiting on a claim are returned.
+async fn restore_jobs<'a>(
+    job_store: &dyn JobStore,
+    transaction_sender: &dyn TransactionSender,
     in_flight_proofs: InflightProofRef<'a>,
-    input_resolver: Arc<dyn InputResolver + 'static>,
-    img_client: Arc<reqwest::Client>,
-    transaction_sender: &RpcTransactionSender,
-    loaded_images: LoadedImageMapRef<'a>,
     input_staging_area: InputStagingAreaRef<'a>,
-    _execution_block: u64,
+) -> Result<Vec<String>> {
+    let current_block = transaction_sender.get_current_block().await?;
+    // the transactions were signed with a blockhash from before the restart, which stays valid for
+    // at most MAX_PROCESSING_AGE blocks from now. Their signatures are dropped after that unless
+    // they land.
+    let blockhash_expiry = current_block + MAX_PROCESSING_AGE as u64;
+    let mut claiming = Vec::new();
+    for record in job_store.list()? {
+        let eid = record.proof.execution_id.clone();
+        if record.is_terminal() {
+            job_store.remove(&eid)?;
+            continue;
+        }
+        if record.proof.expiry < current_block {
+            emit_event!(MetricEvents::ProofExpired, execution_id => eid.clone());
+            job_store.remove(&eid)?;
+            continue;
+        }
+        let inputs = match record.program_inputs() {
+            Ok(inputs) => inputs,
+            Err(e) => {
+                warn!("Dropping job {} with invalid staged inputs: {:?}", eid, e);
+                job_store.remove(&eid)?;
+                continue;
+            }
+        };
+        match record.proof.status {
+            ClaimStatus::Claiming => {
+                transaction_sender.track_signature(record.proof.claim_signature, blockhash_expiry);
+                claiming.push(eid.clone());
+            }
+            ClaimStatus::Submitted => {
+                if let Some(sig) = record.proof.submission_signature {
+                    transaction_sender.track_signature(sig, blockhash_expiry);
+                }
+            }
+            ClaimStatus::Confirmed | ClaimStatus::Expired => {}
+        }
+        info!("Resuming {:?} job {}", record.proof.status, eid);
+        input_staging_area.insert(eid.clone(), inputs);
+        in_flight_proofs.insert(eid, record.proof);
+    }
+    Ok(claiming)
+}
+
+async fn handle_execution_request<'a>(
+    jobs: &JobContext,
+    execution_block: u64,
     exec: ExecutionRequestV1<'a>,
     accounts: &[Pubkey],
 ) -> Result<()> {
-    if !can_execute(exec) {
+    let config = &jobs.config;
+    let backends = &jobs.backends;
+    let claim_policy = &jobs.claim_policy;
+    let bidding_strategy = jobs.bidding_strategy.as_ref();
+    let in_flight_proofs = &jobs.inflight_proofs;
+    let job_store = jobs.job_store.as_ref();
+    let input_resolver = &jobs.input_resolver;
+    let image_fetcher = &jobs.image_fetcher;
+    let transaction_sender = jobs.txn_sender.as_ref();
+    let loaded_images = &jobs.loaded_images;
+    let input_staging_area = &jobs.input_staging_area;
+    let Some(backend) = backends.for_request(&exec) else {
         warn!(
             "Execution request for incompatible prover version: {:?}",
             exec.prover_version()
         );
         emit_event!(MetricEvents::IncompatibleProverVersion, execution_id => exec.execution_id().unwrap_or_default());
         return Ok(());
-    }
+    };
 
-    // current naive implementation is to accept everything we have pending capacity for on this node, but this needs work
     let inflight = in_flight_proofs.len();
     emit_event!(MetricEvents::ExecutionRequest, execution_id => exec.execution_id().unwrap_or_default());
-    if inflight < config.maximum_concurrent_proofs as usize {
-        let eid = exec
-            .execution_id()
-            .map(|d| d.to_string())
-            .ok_or(Risc0RunnerError::InvalidData)?;
-        let image_id = exec
-            .image_id()
-            .map(|d| d.to_string())
-            .ok_or(Risc0RunnerError::InvalidData)?;
-        let expiry = exec.max_block_height();
-        let img = loaded_images.get(&image_id);
-        let img = if img.is_none() {
-            match config.missing_image_strategy {
-                MissingImageStrategy::DownloadAndClaim => {
-                    info!("Image not loaded, attempting to load and running claim");
-                    load_image(
-                        config,
-                        transaction_sender,
-                        &img_client,
-                        &image_id,
-                        loaded_images,
-                    )
-                    .await?;
-                    loaded_images.get(&image_id)
-                }
-                MissingImageStrategy::DownloadAndMiss => {
-                    info!("Image not loaded, loading and rejecting claim");
-                    load_image(
-                        config,
-                        transaction_sender,
-                        &img_client,
-                        &image_id,
-                        loaded_images,
-                    )
-                    .await?;
-                    None
-                }
-                MissingImageStrategy::Fail => {
-                    info!("Image not loaded, rejecting claim");
-                    None
-                }
+    if inflight >= config.maximum_concurrent_proofs as usize {
+        info!(
+            "Claim decision for execution {}: reject, {} proofs already inflight",
+            exec.execution_id().unwrap_or_default(),
+            inflight
+        );
+        emit_counter!(MetricEvents::ClaimRejected, 1, "reason" => "capacity");
+        return Ok(());
+    }
+    if claim_policy.is_paused() {
+        info!(
+            "Claim decision for execution {}: reject, claiming is paused",
+            exec.execution_id().unwrap_or_default()
+        );
+        emit_counter!(MetricEvents::ClaimRejected, 1, "reason" => "paused");
+        return Ok(());
+    }
+    if let Some(ineligibility) = check_eligibility(&exec, transaction_sender).await? {
+        info!(
+            "Claim decision for execution {}: reject, {}",
+            exec.execution_id().unwrap_or_default(),
+            ineligibility
+        );
+        emit_counter!(MetricEvents::ClaimRejected, 1, "reason" => ineligibility.label());
+        return Ok(());
+    }
+    let eid = exec
+        .execution_id()
+        .map(|d| d.to_string())
+        .ok_or(Risc0RunnerError::InvalidData)?;
+    let image_id = exec
+        .image_id()
+        .map(|d| d.to_string())
+        .ok_or(Risc0RunnerError::InvalidData)?;
+    let expiry = exec.max_block_height();
+    let image_size = loaded_images.size(&image_id);
+    let image_size = if image_size.is_none() {
+        match config.missing_image_strategy {
+            MissingImageStrategy::DownloadAndClaim => {
+                info!("Image not loaded, attempting to load and running claim");
+                load_image(
+                    config,
+                    transaction_sender,
+                    image_fetcher,
+                    &image_id,
+                    loaded_images,
+                )
+                .await?;
+                loaded_images.size(&image_id)
+            }
+            MissingImageStrategy::DownloadAndMiss => {
+                info!("Image not loaded, loading and rejecting claim");
+                load_image(
+                    config,
+                    transaction_sender,
+                    image_fetcher,
+                    &image_id,
+                    loaded_images,
+                )
+                .await?;
+                None
+            }
+            MissingImageStrategy::Fail => {
+                info!("Image not loaded, rejecting claim");
+                None
             }
-        } else {
-            img
         }
-        .ok_or(Risc0RunnerError::ImgLoadError)?;
-
-        // naive compute cost estimate which is YES WE CAN DO THIS in the default amount of time
-        emit_histogram!(MetricEvents::ImageComputeEstimate, img.size  as f64, image_id => image_id.clone());
-        //ensure compute can happen before expiry
-        //execution_block + (image_compute_estimate % config.max_compute_per_block) + 1 some bogus calc
-        let computable_by = expiry / 2;
-
-        if computable_by < expiry {
-            //the way this is done can cause race conditions where so many request come in a short time that we accept
-            // them before we change the value of g so we optimistically change to inflight and we will decrement if we dont win the claim
-            let inputs = exec.input().ok_or(Risc0RunnerError::InvalidData)?;
-            let program_inputs = emit_event_with_duration!(MetricEvents::InputDownload, {
-                input_resolver.resolve_public_inputs(
-                    inputs.iter().map(|i| i.unpack()).collect()
-                ).await?
-            }, execution_id => eid, stage => "public");
-            input_staging_area.insert(eid.clone(), program_inputs);
-            let sig = transaction_sender
-                .claim(&eid, accounts[0], accounts[2], computable_by)
-                .await
-                .map_err(|e| Risc0RunnerError::TransactionError(e.to_string()));
-            match sig {
-                Ok(sig) => {
-                    let callback_program = exec
-                        .callback_program_id()
-                        .and_then::<[u8; 32], _>(|v| v.bytes().try_into().ok())
-                        .map(Pubkey::from);
-                    let callback = if callback_program.is_some() {
-                        Some(ProgramExec {
-                            program_id: callback_program.unwrap(),
-                            instruction_prefix: exec
-                                .callback_instruction_prefix()
-                                .map(|v| v.bytes().to_vec())
-                                .unwrap_or(vec![0x1]),
-                        })
-                    } else {
-                        None
-                    };
+    } else {
+        image_size
+    }
+    .ok_or(Risc0RunnerError::ImgLoadError)?;
 
-                    in_flight_proofs.insert(
-                        eid.clone(),
-                        InflightProof {
-                            execution_id: eid.clone(),
-                            image_id: image_id.clone(),
-                            status: ClaimStatus::Claiming,
-                            expiry,
-                            claim_signature: sig,
-                            submission_signature: None,
-                            requester: accounts[0],
-                            program_callback: callback,
-                            additional_accounts: exec
-                                .callback_extra_accounts()
-                                .unwrap_or_default()
-                                .into_iter()
-                                .map(|a| {
-                                    let pkbytes: [u8; 32] = a.pubkey().into();
-                                    let pubkey = Pubkey::try_from(pkbytes).unwrap_or_default();
-                                    let writable = a.writable();
-                                    AccountMeta {
-                                        pubkey,
-                                        is_writable: writable == 1,
-                                        is_signer: false,
-                                    }
-                                })
-                                .collect(),
-                        },
-                    );
-                    emit_event!(MetricEvents::ClaimAttempt, execution_id => eid);
-                }
-                Err(e) => {
-                    info!("Error claiming: {:?}", e);
-                    in_flight_proofs.remove(&eid);
+    emit_histogram!(MetricEvents::ImageComputeEstimate, image_size as f64, image_id => image_id.clone());
+    let mut estimated_cycles = claim_policy.estimate_cycles(&image_id);
+    let mut program_inputs = None;
+    if estimated_cycles.is_none() && claim_policy.should_dry_run(&image_id) {
+        let inputs = resolve_public_inputs(input_resolver.as_ref(), &exec, &eid).await?;
+        let memory_image = loaded_images
+            .memory_image(&image_id)
+            .await?
+            .ok_or(Risc0RunnerError::ImgLoadError)?;
+        estimated_cycles = claim_policy
+            .dry_run(&image_id, memory_image, inputs.clone())
+            .await
+            .map_err(|e| info!("Dry run of image {} failed: {:?}", image_id, e))
+            .ok();
+        program_inputs = Some(inputs);
+    }
+    let decision = claim_policy.decide(&ClaimRequest {
+        execution_id: &eid,
+        image_id: &image_id,
+        tip: exec.tip(),
+        current_block: execution_block,
+        max_block_height: expiry,
+        estimated_cycles,
+    });
+    if let ClaimDecision::Claim { computable_by } = decision {
+        // an auctioned execution is claimed in the turn of the node's bid, for the block it bid
+        let block_commitment = if exec.auction_slots() > 0 {
+            let auction = Auction {
+                execution_id: &eid,
+                requester: accounts[0],
+                execution_account: accounts[2],
+                tip: exec.tip(),
+                cost: claim_policy.cost(estimated_cycles),
+                proving_blocks: computable_by.saturating_sub(execution_block),
+                max_block_height: expiry,
+                claimer_allow_list: claimer_allow_list(&exec),
+            };
+            match bid_and_wait(bidding_strategy, transaction_sender, &auction).await? {
+                AuctionOutcome::Claim { block_commitment } => block_commitment,
+                AuctionOutcome::Lost(loss) => {
+                    info!("Claim decision for execution {}: reject, {}", eid, loss);
+                    emit_counter!(MetricEvents::ClaimRejected, 1, "reason" => loss.label());
+                    return Ok(());
                 }
             }
+        } else {
+            computable_by
+        };
+        //the way this is done can cause race conditions where so many request come in a short time that we accept
+        // them before we change the value of g so we optimistically change to inflight and we will decrement if we dont win the claim
+        let program_inputs = match program_inputs {
+            Some(inputs) => inputs,
+            None => resolve_public_inputs(input_resolver.as_ref(), &exec, &eid).await?,
+        };
+        input_staging_area.insert(eid.clone(), program_inputs.clone());
+        let sig = transaction_sender
+            .claim(
+                &eid,
+                accounts[0],
+                accounts[2],
+                block_commitment,
+                exec.tip(),
+                claimer_allow_list(&exec),
+            )
+            .await
+            .map_err(|e| Risc0RunnerError::TransactionError(e.to_string()));
+        match sig {
+            Ok(sig) => {
+                let callback_program = exec
+                    .callback_program_id()
+                    .and_then::<[u8; 32], _>(|v| v.bytes().try_into().ok())
+                    .map(Pubkey::from);
+                let callback = if callback_program.is_some() {
+                    Some(ProgramExec {
+                        program_id: callback_program.unwrap(),
+                        instruction_prefix: exec
+                            .callback_instruction_prefix()
+                            .map(|v| v.bytes().to_vec())
+                            .unwrap_or(vec![0x1]),
+                    })
+                } else {
+                    None
+                };
+
+                let inflight_proof = InflightProof {
+                    execution_id: eid.clone(),
+                    image_id: image_id.clone(),
+                    status: ClaimStatus::Claiming,
+                    expiry,
+                    claim_signature: sig,
+                    submission_signature: None,
+                    requester: accounts[0],
+                    program_callback: callback,
+                    additional_accounts: exec
+                        .callback_extra_accounts()
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|a| {
+                            let pkbytes: [u8; 32] = a.pubkey().into();
+                            let pubkey = Pubkey::try_from(pkbytes).unwrap_or_default();
+                            let writable = a.writable();
+                            AccountMeta {
+                                pubkey,
+                                is_writable: writable == 1,
+                                is_signer: false,
+                            }
+                        })
+                        .collect(),
+                    prover_version: backend.version(),
+                    prior_executions: prior_executions(&exec),
+                };
+                record_job(
+                    job_store,
+                    &JobRecord::new(inflight_proof.clone(), &program_inputs),
+                );
+                in_flight_proofs.insert(eid.clone(), inflight_proof);
+                emit_event!(MetricEvents::ClaimAttempt, execution_id => eid);
+            }
+            Err(e) => {
+                info!("Error claiming: {:?}", e);
+                in_flight_proofs.remove(&eid);
+            }
         }
     }
     Ok(())
 }
 
+/// The execution accounts the `PriorExecution` inputs of the request name, in input order
+fn prior_executions(exec: &ExecutionRequestV1) -> Vec<Pubkey> {
+    exec.input()
+        .into_iter()
+        .flatten()
+        .filter(|i| i.input_type() == InputType::PriorExecution)
+        .filter_map(|i| i.data().and_then(|d| prior_execution_address(d.bytes())))
+        .collect()
+}
+
+async fn resolve_public_inputs(
+    input_resolver: &dyn InputResolver,
+    exec: &ExecutionRequestV1<'_>,
+    execution_id: &str,
+) -> Result<Vec<ProgramInput>> {
+    let inputs = exec.input().ok_or(Risc0RunnerError::InvalidData)?;
+    let program_inputs = emit_event_with_duration!(MetricEvents::InputDownload, {
+        input_resolver.resolve_public_inputs(
+            inputs.iter().map(|i| i.unpack()).collect()
+        ).await?
+    }, execution_id => execution_id, stage => "public");
+    Ok(program_inputs)
+}
+
 async fn load_image<'a>(
     config: &ProverNodeConfig,
-    transaction_sender: &RpcTransactionSender,
-    http_client: &reqwest::Client,
+    transaction_sender: &dyn TransactionSender,
+    image_fetcher: &ImageFetcher,
     image_id: &str,
     loaded_images: LoadedImageMapRef<'a>,
 ) -> Result<()> {
@@ -625,158 +1147,108 @@ async fn load_image<'a>(
         .map_err(Risc0RunnerError::ImageDownloadError)?;
     let deploy_data = root_as_deploy_v1(&account.data)
         .map_err(|_| anyhow::anyhow!("Failed to parse account data"))?;
-    handle_image_deployment(config, http_client, deploy_data, loaded_images).await?;
+    handle_image_deployment(config, image_fetcher, deploy_data, loaded_images).await?;
     Ok(())
 }
 
 async fn handle_image_deployment<'a>(
     config: &ProverNodeConfig,
-    http_client: &reqwest::Client,
+    image_fetcher: &ImageFetcher,
     deploy: DeployV1<'a>,
     loaded_images: LoadedImageMapRef<'a>,
 ) -> Result<()> {
     let url = deploy.url().ok_or(Risc0RunnerError::InvalidData)?;
+    let image_id = deploy.image_id().ok_or(Risc0RunnerError::InvalidData)?;
     let size = deploy.size_();
+    let checksum = deploy.image_checksum().map(|c| c.bytes());
     emit_histogram!(MetricEvents::ImageDownload, size as f64, url => url.to_string());
-    emit_event_with_duration!(MetricEvents::ImageDownload, {
-        let resp = http_client.get(url).send().await?.error_for_status()?;
-        let min = std::cmp::min(size, (config.max_image_size_mb * 1024 * 1024) as u64) as usize;
-        info!("Downloading image, size {} min {}", size, min);
-        if resp.status().is_success() {
-            let stream = resp.bytes_stream();
-            let resp_data = get_body_max_size(stream, min)
-                .await
-                .map_err(|_|Risc0RunnerError::ImgTooLarge)?;
-
-            let img = Image::from_bytes(resp_data)?;
-            if let Some(bytes) = img.bytes() {
-                tokio::fs::write(Path::new(&config.risc0_image_folder).join(img.id.clone()), bytes).await?;
-            }
-            if img.id != deploy.image_id().unwrap_or_default() {
-                return Err(Risc0RunnerError::InvalidData.into());
-            }
-            loaded_images.insert(img.id.clone(), img);
-        }
-        Ok(())
-    }, url => url.to_string())
+    info!("Downloading image {}, size {}", image_id, size);
+    // the checksum and image id are verified by the fetcher, nothing unverified is written to disk
+    let mut img = image_fetcher.fetch(url, image_id, size, checksum).await?;
+    // compressed images are loaded again from this path
+    img.path = Path::new(&config.risc0_image_folder).join(&img.id);
+    if let Some(bytes) = img.bytes() {
+        tokio::fs::write(&img.path, bytes).await?;
+    }
+    loaded_images.insert(img).await;
+    Ok(())
 }
 
-// proving function, no async this is cpu/gpu intesive
-fn risc0_prove(
-    memory_image: MemoryImage,
-    sorted_inputs: Vec<ProgramInput>,
-) -> Result<(Journal, Digest, SuccinctReceipt<ReceiptClaim>)> {
-    let image_id = memory_image.compute_id().to_string();
-    let mut exec = new_risc0_exec_env(memory_image, sorted_inputs)?;
-    let session = exec.run()?;
-    // Obtain the default prover.
-    let prover = get_risc0_prover()?;
-    let ctx = VerifierContext::default();
-    let info = emit_event_with_duration!(MetricEvents::ProofGeneration,{
-        prover.prove_session(&ctx, &session)
-    }, system => "risc0")?;
-    emit_histogram!(MetricEvents::ProofSegments, info.stats.segments as f64, system => "risc0", image_id => &image_id);
-    emit_histogram!(MetricEvents::ProofCycles, info.stats.total_cycles as f64, system => "risc0", cycle_type => "total", image_id => &image_id);
-    emit_histogram!(MetricEvents::ProofCycles, info.stats.user_cycles as f64, system => "risc0", cycle_type => "user", image_id => &image_id);
-    if let InnerReceipt::Composite(cr) = &info.receipt.inner {
-        let sr = emit_event_with_duration!(MetricEvents::ProofConversion,{ prover.composite_to_succinct(cr) }, system => "risc0")?;
-        let ident_receipt = identity_p254(&sr)?;
-        if let MaybePruned::Value(rc) = sr.claim {
-            if let MaybePruned::Value(Some(op)) = rc.output {
-                if let MaybePruned::Value(ass) = op.assumptions {
-                    return Ok((info.receipt.journal, ass.digest(), ident_receipt));
-                }
-            }
-        }
+/// The node fetches images it could not download before from the new url, images it already has
+/// are content addressed and stay as they are
+async fn handle_deployment_url_update<'a>(
+    config: &ProverNodeConfig,
+    transaction_sender: &dyn TransactionSender,
+    image_fetcher: &ImageFetcher,
+    image_id: &str,
+    loaded_images: LoadedImageMapRef<'a>,
+) -> Result<()> {
+    if loaded_images.contains(image_id) {
+        return Ok(());
     }
-    Err(Risc0RunnerError::ProofGenerationError.into())
+    info!("Deployment {} moved, downloading image", image_id);
+    load_image(
+        config,
+        transaction_sender,
+        image_fetcher,
+        image_id,
+        loaded_images,
+    )
+    .await
 }
 
-pub struct CompressedReciept {
-    pub execution_digest: Vec<u8>,
-    pub exit_code_system: u32,
-    pub exit_code_user: u32,
-    pub proof: Vec<u8>,
-}
-/// Compresses the proof to be sent to the blockchain
-/// This is a temporary solution until the wasm groth16 prover or a rust impl is working
-async fn risc0_compress_proof(
-    tools_path: &str,
-    succint_receipt: SuccinctReceipt<ReceiptClaim>,
-) -> Result<CompressedReciept> {
-    let sealbytes = succint_receipt.get_seal_bytes();
-    if !(ARCH == "x86_64" || ARCH == "x86") {
-        panic!("X86 only");
-    }
-    let tmp = tempdir()?;
-    let prove_dir = tmp.path();
-    let root_path = Path::new(tools_path);
-    let mut cursor = Cursor::new(&sealbytes);
-    let inputs = prove_dir.join("input.json");
-    let witness = prove_dir.join("out.wtns");
-    let input_file = File::create(&inputs).await?;
-    emit_event_with_duration!(MetricEvents::ProofConversion,{
-        async_to_json(&mut cursor, input_file).await
-    }, system => "groth16json")?;
-    let zkey = root_path.join("stark_verify_final.zkey");
-    let proof_out = prove_dir.join("proof.json");
-    let public = prove_dir.join("public.json");
-    emit_event_with_duration!(MetricEvents::ProofCompression,{
-    let status = Command::new(root_path.join("stark_verify"))
-        .arg(inputs.clone())
-        .arg(witness.clone())
-        .output()
-        .await?;
-    if !status.status.success() {
-        info!("witness {:?}", status);
-        return Err(Risc0RunnerError::ProofCompressionError.into());
-    }
-    let snark_status = Command::new(root_path.join("rapidsnark"))
-        .arg(zkey)
-        .arg(witness)
-        .arg(proof_out.clone())
-        .arg(public)
-        .output()
-        .await?;
-    if !snark_status.status.success() {
-        info!("snark {:?}", snark_status);
-        return Err(Risc0RunnerError::ProofCompressionError.into());
+/// Deprecated and closed deployments accept no new executions so their image is dropped from
+/// memory. A closed deployment's image is also deleted unless an inflight proof still needs it.
+async fn handle_deployment_retired<'a>(
+    in_flight_proofs: InflightProofRef<'a>,
+    loaded_images: LoadedImageMapRef<'a>,
+    image_id: &str,
+    closed: bool,
+) -> Result<()> {
+    let in_use = in_flight_proofs
+        .iter()
+        .any(|proof| proof.image_id == image_id);
+    if !closed || in_use {
+        loaded_images.compress(image_id).await;
+        return Ok(());
     }
-    }, system => "risc0");
-
-    let mut proof_fd = File::open(proof_out).await?;
-    let mt = proof_fd.metadata().await?;
-    let mut bytes = Vec::with_capacity(mt.len() as usize);
-    proof_fd.read_to_end(&mut bytes).await?;
-    let proof: ProofJson = serde_json::from_slice(&bytes)?;
-    let seal: Seal = proof.try_into()?;
-    let claim = succint_receipt.claim;
-    if let MaybePruned::Value(rc) = claim {
-        let (system, user) = match rc.exit_code {
-            ExitCode::Halted(user_exit) => (0, user_exit),
-            ExitCode::Paused(user_exit) => (1, user_exit),
-            ExitCode::SystemSplit => (2, 0),
-            ExitCode::SessionLimit => (2, 2),
-        };
-        Ok(CompressedReciept {
-            execution_digest: rc.post.digest().as_bytes().to_vec(),
-            exit_code_system: system,
-            exit_code_user: user,
-            proof: seal.to_vec(),
-        })
-    } else {
-        Err(Risc0RunnerError::ProofCompressionError.into())
+    if let Some(path) = loaded_images.remove(image_id).await {
+        info!("Deployment {} closed, deleting image", image_id);
+        match tokio::fs::remove_file(&path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
     }
+    Ok(())
 }
 
-fn can_execute(exec: ExecutionRequestV1) -> bool {
-    let version = exec.prover_version().try_into();
-    if version.is_ok() {
-        match version.unwrap() {
-            REQUIRED_PROVER => true,
-            _ => false,
+// proving function, no async this is cpu/gpu intesive
+fn risc0_prove(
+    backend: &ProverBackend,
+    image: ProvingImage,
+    sorted_inputs: Vec<ProgramInput>,
+) -> Result<(Journal, Digest, SuccinctReceipt<ReceiptClaim>, u64)> {
+    let image_id = image.memory_image.compute_id().to_string();
+    // the receipts of prior executions stay assumed, the program checks their claims on chain
+    let conditional = sorted_inputs.iter().any(|input| {
+        matches!(input, ProgramInput::Resolved(ri) if ri.input_type == ProgramInputType::PriorExecution)
+    });
+    let proven = emit_event_with_duration!(MetricEvents::ProofGeneration,{
+        backend.prove(&image, sorted_inputs, conditional)
+    }, system => "risc0")?;
+    emit_histogram!(MetricEvents::ProofSegments, proven.segments as f64, system => "risc0", image_id => &image_id);
+    emit_histogram!(MetricEvents::ProofCycles, proven.total_cycles as f64, system => "risc0", cycle_type => "total", image_id => &image_id);
+    emit_histogram!(MetricEvents::ProofCycles, proven.user_cycles as f64, system => "risc0", cycle_type => "user", image_id => &image_id);
+    if let MaybePruned::Value(rc) = &proven.receipt.claim {
+        if let MaybePruned::Value(Some(op)) = &rc.output {
+            // the identity recursion is left to the submission, batches take the receipt as it is
+            return Ok((
+                proven.journal,
+                op.assumptions.digest(),
+                proven.receipt,
+                proven.total_cycles,
+            ));
         }
-    } else {
-        false
     }
+    Err(Risc0RunnerError::ProofGenerationError.into())
 }
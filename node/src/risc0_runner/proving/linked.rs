@@ -0,0 +1,85 @@
+use {
+    super::{ExecutionProver, ProvenExecution, ProvingImage, LINKED_PROVER_VERSION},
+    crate::{config::ProverVersionConfig, observe::*, risc0_runner::Risc0RunnerError},
+    anyhow::{anyhow, Result},
+    bonsol_prover::{
+        input_resolver::ProgramInput,
+        prover::{get_risc0_prover, new_risc0_exec_env},
+    },
+    risc0_zkvm::{
+        recursion, CompositeReceipt, InnerReceipt, ProverServer, ReceiptClaim, SuccinctReceipt,
+        VerifierContext,
+    },
+};
+
+/// Proves with the risc0 release the node is built with, it only proves that release's version
+pub struct LinkedProver;
+
+impl LinkedProver {
+    pub fn new(version: ProverVersionConfig) -> Result<Self> {
+        if version != LINKED_PROVER_VERSION {
+            return Err(anyhow!(
+                "The node is built with the {:?} prover, {:?} needs a Subprocess prover",
+                LINKED_PROVER_VERSION,
+                version
+            ));
+        }
+        Ok(LinkedProver)
+    }
+}
+
+impl ExecutionProver for LinkedProver {
+    fn prove(
+        &self,
+        image: &ProvingImage,
+        inputs: Vec<ProgramInput>,
+        conditional: bool,
+    ) -> Result<ProvenExecution> {
+        let mut exec = new_risc0_exec_env(image.memory_image.clone(), inputs)?;
+        let session = exec.run()?;
+        let prover = get_risc0_prover()?;
+        // the linked release proves under its own control root
+        let info = prover.prove_session(&VerifierContext::default(), &session)?;
+        let InnerReceipt::Composite(cr) = &info.receipt.inner else {
+            return Err(Risc0RunnerError::ProofGenerationError.into());
+        };
+        let receipt = emit_event_with_duration!(MetricEvents::ProofConversion,{
+            if conditional {
+                conditional_succinct(prover.as_ref(), cr)
+            } else {
+                prover.composite_to_succinct(cr)
+            }
+        }, system => "risc0")?;
+        Ok(ProvenExecution {
+            journal: info.receipt.journal,
+            receipt,
+            segments: info.stats.segments as u64,
+            total_cycles: info.stats.total_cycles,
+            user_cycles: info.stats.user_cycles,
+        })
+    }
+
+    fn identity_p254(
+        &self,
+        receipt: &SuccinctReceipt<ReceiptClaim>,
+    ) -> Result<SuccinctReceipt<ReceiptClaim>> {
+        recursion::identity_p254(receipt)
+    }
+}
+
+/// Lifts and joins the segments of a receipt without resolving its assumptions, the claim of the
+/// receipt is conditional on them
+fn conditional_succinct(
+    prover: &dyn ProverServer,
+    cr: &CompositeReceipt,
+) -> Result<SuccinctReceipt<ReceiptClaim>> {
+    let mut segments = cr.segments.iter();
+    let first = segments
+        .next()
+        .ok_or(Risc0RunnerError::ProofGenerationError)?;
+    let mut receipt = prover.lift(first)?;
+    for segment in segments {
+        receipt = prover.join(&receipt, &prover.lift(segment)?)?;
+    }
+    Ok(receipt)
+}
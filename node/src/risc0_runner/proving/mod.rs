@@ -0,0 +1,59 @@
+//! Proves executions under the recursion control root of a prover version.
+//! The node links a single risc0 release and proves its version in process, every other version
+//! is proven by a prover built against that version's release which the node runs as a
+//! subprocess.
+mod linked;
+mod subprocess;
+
+pub use {linked::LinkedProver, subprocess::SubprocessProver};
+
+use {
+    crate::config::ProverVersionConfig,
+    anyhow::Result,
+    bonsol_prover::input_resolver::ProgramInput,
+    risc0_binfmt::MemoryImage,
+    risc0_zkvm::{Journal, ReceiptClaim, SuccinctReceipt},
+    serde::{Deserialize, Serialize},
+    std::path::PathBuf,
+};
+
+/// The version of the risc0 release the node is built with
+pub const LINKED_PROVER_VERSION: ProverVersionConfig = ProverVersionConfig::V1_2_1;
+
+/// An image ready to be proven, the linked prover executes its memory image while a subprocess
+/// prover loads the elf itself
+pub struct ProvingImage {
+    pub memory_image: MemoryImage,
+    pub elf_path: PathBuf,
+}
+
+/// A proven execution, subprocess provers write it out with bincode
+#[derive(Serialize, Deserialize)]
+pub struct ProvenExecution {
+    pub journal: Journal,
+    /// The lifted and joined receipt of the execution, before its identity recursion
+    pub receipt: SuccinctReceipt<ReceiptClaim>,
+    pub segments: u64,
+    pub total_cycles: u64,
+    pub user_cycles: u64,
+}
+
+/// Proves executions and their recursion under the control root of one prover version. Proving
+/// blocks, the node calls provers on blocking threads.
+pub trait ExecutionProver: Send + Sync {
+    /// Executes the image with its inputs and proves the execution into a succinct receipt. The
+    /// assumptions of a `conditional` proof stay unresolved, otherwise they are resolved with the
+    /// receipts among the inputs.
+    fn prove(
+        &self,
+        image: &ProvingImage,
+        inputs: Vec<ProgramInput>,
+        conditional: bool,
+    ) -> Result<ProvenExecution>;
+
+    /// The identity recursion over the bn254 control id, groth16 compression takes its receipt
+    fn identity_p254(
+        &self,
+        receipt: &SuccinctReceipt<ReceiptClaim>,
+    ) -> Result<SuccinctReceipt<ReceiptClaim>>;
+}
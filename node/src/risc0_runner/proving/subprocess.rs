@@ -0,0 +1,113 @@
+use {
+    super::{ExecutionProver, ProvenExecution, ProvingImage},
+    crate::{observe::*, risc0_runner::Risc0RunnerError},
+    anyhow::{anyhow, Result},
+    bonsol_prover::input_resolver::ProgramInput,
+    risc0_zkvm::{ReceiptClaim, SuccinctReceipt},
+    serde::{Deserialize, Serialize},
+    std::{
+        fs,
+        path::{Path, PathBuf},
+        process::Command,
+    },
+    tempfile::tempdir,
+};
+
+/// An input as the subprocess prover takes it, `input_type` is the `ProgramInputType` the input
+/// was resolved as
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubprocessInput {
+    pub input_type: u8,
+    pub data: Vec<u8>,
+}
+
+/// Proves with the prover binary at `prover_path`, built against the risc0 release of the version
+/// it serves. The binary is called as
+/// `prove <elf> <inputs> <out> [--conditional]` with the bincode `SubprocessInput`s, writing the
+/// bincode `ProvenExecution`, and as `identity <receipt> <out>` with a bincode succinct receipt,
+/// writing the receipt of its identity recursion.
+pub struct SubprocessProver {
+    prover_path: PathBuf,
+}
+
+impl SubprocessProver {
+    pub fn new(prover_path: &str) -> Result<Self> {
+        let prover_path = PathBuf::from(prover_path);
+        if !prover_path.is_file() {
+            return Err(anyhow!(
+                "Error: Prover not found at {}",
+                prover_path.to_string_lossy()
+            ));
+        }
+        Ok(SubprocessProver { prover_path })
+    }
+
+    fn run(&self, command: &mut Command) -> Result<()> {
+        let output = command.output()?;
+        if !output.status.success() {
+            info!(
+                "prover {}: {:?}",
+                self.prover_path.to_string_lossy(),
+                output
+            );
+            return Err(Risc0RunnerError::ProofGenerationError.into());
+        }
+        Ok(())
+    }
+}
+
+impl ExecutionProver for SubprocessProver {
+    fn prove(
+        &self,
+        image: &ProvingImage,
+        inputs: Vec<ProgramInput>,
+        conditional: bool,
+    ) -> Result<ProvenExecution> {
+        let inputs = inputs
+            .into_iter()
+            .map(|input| match input {
+                ProgramInput::Resolved(ri) => Ok(SubprocessInput {
+                    input_type: ri.input_type.0,
+                    data: ri.data,
+                }),
+                _ => Err(anyhow!("Invalid input type")),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let tmp = tempdir()?;
+        let inputs_path = tmp.path().join("inputs.bin");
+        let out = tmp.path().join("proof.bin");
+        fs::write(&inputs_path, bincode::serialize(&inputs)?)?;
+        let mut command = Command::new(&self.prover_path);
+        command
+            .arg("prove")
+            .arg(&image.elf_path)
+            .arg(&inputs_path)
+            .arg(&out);
+        if conditional {
+            command.arg("--conditional");
+        }
+        self.run(&mut command)?;
+        read_bincode(&out)
+    }
+
+    fn identity_p254(
+        &self,
+        receipt: &SuccinctReceipt<ReceiptClaim>,
+    ) -> Result<SuccinctReceipt<ReceiptClaim>> {
+        let tmp = tempdir()?;
+        let receipt_path = tmp.path().join("receipt.bin");
+        let out = tmp.path().join("identity.bin");
+        fs::write(&receipt_path, bincode::serialize(receipt)?)?;
+        self.run(
+            Command::new(&self.prover_path)
+                .arg("identity")
+                .arg(&receipt_path)
+                .arg(&out),
+        )?;
+        read_bincode(&out)
+    }
+}
+
+fn read_bincode<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T> {
+    Ok(bincode::deserialize(&fs::read(path)?)?)
+}
@@ -46,38 +46,51 @@ pub async fn async_to_json<
     let mut mem = Vec::new();
     writeln!(mem, "{{\n  \"iop\": [")?;
 
-    let mut pos = 0;
-    for seal_type in K_SEAL_TYPES.iter().take(K_SEAL_ELEMS) {
-        if pos != 0 {
+    for (i, value) in seal_to_iop(&iop)?.iter().enumerate() {
+        if i != 0 {
             writeln!(mem, ",")?;
         }
+        writeln!(mem, "    \"{}\"", value.to_str_radix(10))?;
+    }
+    write!(mem, "  ]\n}}")?;
+    writer.write_all(mem.as_slice()).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Decodes a recursion seal into the values of the `iop` input signal of the `stark_verify` circuit
+pub fn seal_to_iop(iop: &[u32]) -> Result<Vec<BigUint>> {
+    if iop.len() < K_SEAL_WORDS {
+        return Err(anyhow!(
+            "Seal too short: {} words, expected {}",
+            iop.len(),
+            K_SEAL_WORDS
+        ));
+    }
+    let mut values = Vec::with_capacity(K_SEAL_ELEMS);
+    let mut pos = 0;
+    for seal_type in K_SEAL_TYPES.iter().take(K_SEAL_ELEMS) {
         match seal_type {
             IopType::Fp => {
-                let value = BabyBearElem::new_raw(iop[pos]).as_u32();
+                values.push(BigUint::from(BabyBearElem::new_raw(iop[pos]).as_u32()));
                 pos += 1;
-                writeln!(mem, "    \"{value}\"")?;
             }
             _ => {
                 let digest = Digest::try_from(&iop[pos..pos + DIGEST_WORDS])?;
-                let value = digest_to_decimal(&digest)?;
+                values.push(digest_to_biguint(&digest)?);
                 pos += 8;
-                writeln!(mem, "    \"{value}\"")?;
             }
         }
     }
-    write!(mem, "  ]\n}}")?;
-    writer.write_all(mem.as_slice()).await?;
-    writer.flush().await?;
-    Ok(())
+    Ok(values)
 }
 
-fn digest_to_decimal(digest: &Digest) -> Result<String> {
-    to_decimal(&format!("{:?}", digest_to_fr(digest))).context("digest_to_decimal failed")
+fn digest_to_biguint(digest: &Digest) -> Result<BigUint> {
+    from_fr_debug(&format!("{:?}", digest_to_fr(digest))).context("digest_to_biguint failed")
 }
 
-fn to_decimal(s: &str) -> Option<String> {
+fn from_fr_debug(s: &str) -> Option<BigUint> {
     s.strip_prefix("Fr(0x")
         .and_then(|s| s.strip_suffix(')'))
         .and_then(|stripped| BigUint::from_str_radix(stripped, 16).ok())
-        .map(|n| n.to_str_radix(10))
 }
@@ -5,7 +5,15 @@ use risc0_zkvm::{sha::Digestible, Groth16ReceiptVerifierParameters};
 use bonsol_interface::prover_version::ProverVersion;
 
 pub fn verify_prover_version(required: ProverVersion) -> Result<()> {
-    let actual_digest = Groth16ReceiptVerifierParameters::default().digest();
+    verify_verifier_parameters(required, &Groth16ReceiptVerifierParameters::default())
+}
+
+/// Checks that `parameters` hash to the verifier digest of the `required` prover version
+pub fn verify_verifier_parameters(
+    required: ProverVersion,
+    parameters: &Groth16ReceiptVerifierParameters,
+) -> Result<()> {
+    let actual_digest = parameters.digest();
     let prover_digest = actual_digest.to_string();
 
     match required {
@@ -0,0 +1,39 @@
+mod remote;
+
+pub use remote::RemoteSigner;
+
+use {
+    crate::config::SignerConfig,
+    anyhow::{anyhow, Result},
+    solana_sdk::{signature::read_keypair_file, signer::Signer},
+    std::{sync::Arc, time::Duration},
+    tracing::info,
+};
+
+/// The identity the node claims executions and submits proofs with
+pub type NodeSigner = Arc<dyn Signer + Send + Sync>;
+
+pub async fn load_signer(config: &SignerConfig) -> Result<NodeSigner> {
+    match config {
+        SignerConfig::KeypairFile { path } => {
+            info!("Using Keypair File");
+            let keypair = read_keypair_file(path)
+                .map_err(|e| anyhow!("Failed to read keypair file {}: {:?}", path, e))?;
+            Ok(Arc::new(keypair))
+        }
+        SignerConfig::Remote {
+            url,
+            auth_token,
+            timeout_secs,
+        } => {
+            info!("Using Remote Signer at {}", url);
+            let signer = RemoteSigner::connect(
+                url,
+                auth_token.clone(),
+                Duration::from_secs(*timeout_secs as u64),
+            )
+            .await?;
+            Ok(Arc::new(signer))
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Signs with a signer service over http, the node's key never has to live on the prover.
+//! The service implements two json endpoints:
+//! * `GET {url}/pubkey` responds with `{"pubkey": "<base58 pubkey>"}`
+//! * `POST {url}/sign` with `{"pubkey": "<base58 pubkey>", "message": "<base64 message>"}` responds
+//!   with `{"signature": "<base58 signature>"}`
+//!
+//! When an auth token is configured it is sent as `Authorization: Bearer <token>`.
+//!
+//! Signing blocks until the service responds, the node signs on blocking threads and never on the
+//! async runtime.
+use {
+    anyhow::{anyhow, Result},
+    base64::{engine::general_purpose::STANDARD, Engine},
+    serde::{Deserialize, Serialize},
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::Signature,
+        signer::{Signer, SignerError},
+    },
+    std::{str::FromStr, time::Duration},
+};
+
+#[derive(Debug, Deserialize)]
+struct PubkeyResponse {
+    pubkey: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SignRequest {
+    pubkey: String,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SignResponse {
+    signature: String,
+}
+
+pub struct RemoteSigner {
+    url: String,
+    auth_token: Option<String>,
+    timeout: Duration,
+    pubkey: Pubkey,
+}
+
+impl RemoteSigner {
+    /// Fetches the signer's public key, which is fixed for the lifetime of the node
+    pub async fn connect(url: &str, auth_token: Option<String>, timeout: Duration) -> Result<Self> {
+        let url = url.trim_end_matches('/').to_string();
+        let mut request = reqwest::Client::new()
+            .get(format!("{}/pubkey", url))
+            .timeout(timeout);
+        if let Some(token) = &auth_token {
+            request = request.bearer_auth(token);
+        }
+        let response: PubkeyResponse = request.send().await?.error_for_status()?.json().await?;
+        let pubkey = Pubkey::from_str(&response.pubkey)
+            .map_err(|e| anyhow!("Remote signer returned an invalid pubkey: {:?}", e))?;
+        Ok(RemoteSigner {
+            url,
+            auth_token,
+            timeout,
+            pubkey,
+        })
+    }
+
+    fn request_signature(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(self.timeout)
+            .build()
+            .map_err(|e| SignerError::Connection(e.to_string()))?;
+        let mut request = client
+            .post(format!("{}/sign", self.url))
+            .json(&SignRequest {
+                pubkey: self.pubkey.to_string(),
+                message: STANDARD.encode(message),
+            });
+        if let Some(token) = &self.auth_token {
+            request = request.bearer_auth(token);
+        }
+        let response: SignResponse = request
+            .send()
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| SignerError::Connection(e.to_string()))?
+            .json()
+            .map_err(|e| SignerError::Protocol(e.to_string()))?;
+        let signature = Signature::from_str(&response.signature)
+            .map_err(|e| SignerError::Protocol(e.to_string()))?;
+        // a misconfigured signer must not get transactions sent with a signature that cannot land
+        if !signature.verify(self.pubkey.as_ref(), message) {
+            return Err(SignerError::Protocol(
+                "remote signer returned a signature that does not verify".to_string(),
+            ));
+        }
+        Ok(signature)
+    }
+}
+
+impl Signer for RemoteSigner {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        Ok(self.pubkey)
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        self.request_signature(message)
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, mockito::Matcher, serde_json::json, solana_sdk::signature::Keypair};
+
+    #[tokio::test]
+    async fn signs_with_remote_key() {
+        let keypair = Keypair::new();
+        let message = b"claim";
+        let signature = keypair.sign_message(message);
+        let mut server = mockito::Server::new_async().await;
+        let pubkey_mock = server
+            .mock("GET", "/pubkey")
+            .match_header("authorization", "Bearer secret")
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "pubkey": keypair.pubkey().to_string() }).to_string())
+            .create_async()
+            .await;
+        let sign_mock = server
+            .mock("POST", "/sign")
+            .match_header("authorization", "Bearer secret")
+            .match_body(Matcher::Json(json!({
+                "pubkey": keypair.pubkey().to_string(),
+                "message": STANDARD.encode(message),
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "signature": signature.to_string() }).to_string())
+            .create_async()
+            .await;
+
+        let signer = RemoteSigner::connect(
+            &server.url(),
+            Some("secret".to_string()),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("failed to connect to remote signer");
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+        let signed = tokio::task::spawn_blocking(move || signer.try_sign_message(message))
+            .await
+            .unwrap();
+        assert_eq!(signed, Ok(signature));
+        pubkey_mock.assert_async().await;
+        sign_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn rejects_signature_from_another_key() {
+        let keypair = Keypair::new();
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/pubkey")
+            .with_body(json!({ "pubkey": keypair.pubkey().to_string() }).to_string())
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/sign")
+            .with_body(
+                json!({ "signature": Keypair::new().sign_message(b"claim").to_string() })
+                    .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let signer = RemoteSigner::connect(&server.url(), None, Duration::from_secs(5))
+            .await
+            .expect("failed to connect to remote signer");
+        let signed = tokio::task::spawn_blocking(move || signer.try_sign_message(b"claim"))
+            .await
+            .unwrap();
+        assert!(matches!(signed, Err(SignerError::Protocol(_))));
+    }
+}
@@ -0,0 +1,612 @@
+use {
+    super::{FeeStrategy, TransactionKind},
+    crate::{signer::NodeSigner, types::ProgramExec},
+    anyhow::Result,
+    bonsol_interface::{
+        bonsol_schema::{
+            BidV1, BidV1Args, ChannelInstruction, ChannelInstructionArgs, ChannelInstructionIxType,
+            ClaimV1, ClaimV1Args, ExpireV1, ExpireV1Args, ProverVersion, StatusBatchV1,
+            StatusBatchV1Args, StatusTypes, StatusV1, StatusV1Args,
+        },
+        claim_state::ClaimStateV1,
+        util::{
+            execution_address, execution_bids_address, execution_claim_address, prover_address,
+            verifier_config_address,
+        },
+    },
+    flatbuffers::{FlatBufferBuilder, WIPOffset},
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        message::{v0, VersionedMessage},
+        packet::PACKET_DATA_SIZE,
+        pubkey::Pubkey,
+        signer::Signer,
+        system_program,
+        transaction::VersionedTransaction,
+    },
+    std::sync::Arc,
+};
+
+/// Everything needed to build a proof submission, kept so that it can be rebuilt with a new
+/// blockhash
+#[derive(Debug, Clone)]
+pub struct ProofSubmission {
+    pub execution_id: String,
+    pub requester_account: Pubkey,
+    pub callback_exec: Option<ProgramExec>,
+    pub proof: Vec<u8>,
+    pub execution_digest: Vec<u8>,
+    pub input_digest: Vec<u8>,
+    pub assumption_digest: Vec<u8>,
+    pub committed_outputs: Vec<u8>,
+    pub additional_accounts: Vec<AccountMeta>,
+    /// The accounts of the executions the execution assumes, in the order of its inputs
+    pub prior_executions: Vec<Pubkey>,
+    pub exit_code_system: u32,
+    pub exit_code_user: u32,
+    /// Block height after which the execution can no longer be proven
+    pub execution_expiry: u64,
+    /// The version the execution is pinned to, the program verifies the proof with its verifier
+    pub prover_version: ProverVersion,
+}
+
+impl ProofSubmission {
+    /// The status settling the execution, `StatusBatchV1` carries one without a proof per
+    /// execution
+    fn status<'b>(
+        &self,
+        fbb: &mut FlatBufferBuilder<'b>,
+        proof: Option<&[u8]>,
+    ) -> WIPOffset<StatusV1<'b>> {
+        let proof_vec = proof.map(|proof| fbb.create_vector(proof));
+        let execution_digest = fbb.create_vector(&self.execution_digest);
+        let input_digest = fbb.create_vector(&self.input_digest);
+        let assumption_digest = fbb.create_vector(&self.assumption_digest);
+        let eid = fbb.create_string(&self.execution_id);
+        let out = fbb.create_vector(&self.committed_outputs);
+        StatusV1::create(
+            fbb,
+            &StatusV1Args {
+                execution_id: Some(eid),                    //0-?? bytes lets say 16
+                status: StatusTypes::Completed,             //1 byte
+                proof: proof_vec,                           //256 bytes
+                execution_digest: Some(execution_digest),   //32 bytes
+                input_digest: Some(input_digest),           //32 bytes
+                assumption_digest: Some(assumption_digest), //32 bytes
+                committed_outputs: Some(out),               //0-?? bytes lets say 32
+                exit_code_system: self.exit_code_system,    //4 byte
+                exit_code_user: self.exit_code_user,        //4 byte
+            }, //total ~408 bytes plenty of room for more stuff
+        )
+    }
+}
+
+/// Proof submissions settled together by one proof of the batch image, the submissions carry
+/// everything but their proofs
+#[derive(Debug, Clone)]
+pub struct BatchSubmission {
+    pub proof: Vec<u8>,
+    /// Post state digest of the batch image's execution
+    pub execution_digest: Vec<u8>,
+    pub submissions: Vec<ProofSubmission>,
+}
+
+/// A submission the sender rebuilds with a new blockhash while its executions can be proven
+#[derive(Debug, Clone)]
+pub enum Submission {
+    Proof(ProofSubmission),
+    Batch(BatchSubmission),
+}
+
+impl Submission {
+    /// Slot after which the submission can no longer settle its executions, the program checks
+    /// the executions' expiry against the slot
+    pub fn expiry(&self) -> u64 {
+        match self {
+            Submission::Proof(submission) => submission.execution_expiry,
+            Submission::Batch(batch) => batch
+                .submissions
+                .iter()
+                .map(|submission| submission.execution_expiry)
+                .min()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Builds and signs the transactions the node submits
+#[derive(Clone)]
+pub struct TransactionBuilder {
+    pub rpc_client: Arc<RpcClient>,
+    pub bonsol_program: Pubkey,
+    pub signer: NodeSigner,
+    pub fees: Arc<FeeStrategy>,
+}
+
+impl TransactionBuilder {
+    /// The registry account of the node's prover, its counters are updated when its claims are
+    /// settled
+    fn prover_account(&self) -> Pubkey {
+        prover_address(&self.signer.pubkey()).0
+    }
+
+    /// The prover holding the claim on an execution, None while it is unclaimed
+    async fn current_claimer(&self, execution_claim_account: &Pubkey) -> Result<Option<Pubkey>> {
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(execution_claim_account, self.rpc_client.commitment())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get account: {:?}", e))?
+            .value;
+        match account {
+            Some(account) if !account.data.is_empty() => {
+                let claim = ClaimStateV1::load_claim_owned(&account.data)
+                    .map_err(|e| anyhow::anyhow!("Failed to parse claim account: {:?}", e))?;
+                Ok(Some(Pubkey::new_from_array(claim.claimer)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Compute budget instructions followed by `instruction`
+    async fn with_compute_budget(
+        &self,
+        instruction: Instruction,
+        kind: TransactionKind,
+    ) -> Vec<Instruction> {
+        let mut instructions = self
+            .fees
+            .compute_budget_instructions(
+                &self.rpc_client,
+                &[self.signer.pubkey(), self.bonsol_program],
+                kind,
+            )
+            .await;
+        instructions.push(instruction);
+        instructions
+    }
+
+    /// Compiles and signs `instructions` with the latest blockhash, returns the transaction with
+    /// the last block height it is valid for
+    async fn sign(&self, instructions: &[Instruction]) -> Result<(VersionedTransaction, u64)> {
+        let (blockhash, last_valid) = self
+            .rpc_client
+            .get_latest_blockhash_with_commitment(self.rpc_client.commitment())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get blockhash: {:?}", e))?;
+
+        let msg = v0::Message::try_compile(&self.signer.pubkey(), instructions, &[], blockhash)?;
+        // a remote signer blocks on its signing service, it is kept off the runtime
+        let signer = self.signer.clone();
+        let tx = tokio::task::spawn_blocking(move || {
+            VersionedTransaction::try_new(VersionedMessage::V0(msg), &[signer.as_ref()])
+        })
+        .await??;
+        Ok((tx, last_valid))
+    }
+
+    /// Builds and signs a claim transaction, returns it with the last block height it is valid for
+    pub async fn claim_transaction(
+        &self,
+        execution_id: &str,
+        requester: Pubkey,
+        execution_account: Pubkey,
+        block_commitment: u64,
+        tip: u64,
+        claimer_allow_list: Option<Pubkey>,
+    ) -> Result<(VersionedTransaction, u64)> {
+        let (execution_claim_account, _) = execution_claim_address(execution_account.as_ref());
+        let mut accounts = vec![
+            AccountMeta::new(execution_account, false),
+            AccountMeta::new(requester, false),
+            AccountMeta::new(execution_claim_account, false),
+            AccountMeta::new(self.signer.pubkey(), true),
+            AccountMeta::new(self.signer.pubkey(), true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(self.prover_account(), false),
+        ];
+        // taking over a lapsed claim records the missed commitment in the registry account of
+        // the lapsed claimer, it follows the claimer's
+        if let Some(lapsed_claimer) = self.current_claimer(&execution_claim_account).await? {
+            accounts.push(AccountMeta::new(prover_address(&lapsed_claimer).0, false));
+        }
+        if let Some(allow_list) = claimer_allow_list {
+            accounts.push(AccountMeta::new_readonly(allow_list, false));
+        }
+        // the bids of an auctioned execution are closed by its first claim
+        accounts.push(AccountMeta::new(
+            execution_bids_address(execution_account.as_ref()).0,
+            false,
+        ));
+        let mut fbb = FlatBufferBuilder::new();
+        let eid = fbb.create_string(execution_id);
+        let stat = ClaimV1::create(
+            &mut fbb,
+            &ClaimV1Args {
+                block_commitment,
+                execution_id: Some(eid),
+            },
+        );
+        fbb.finish(stat, None);
+        let statbytes = fbb.finished_data();
+        let mut fbb2 = FlatBufferBuilder::new();
+        let off = fbb2.create_vector(statbytes);
+        let root = ChannelInstruction::create(
+            &mut fbb2,
+            &ChannelInstructionArgs {
+                ix_type: ChannelInstructionIxType::ClaimV1,
+                claim_v1: Some(off),
+                ..Default::default()
+            },
+        );
+        fbb2.finish(root, None);
+        let ix_data = fbb2.finished_data();
+        let instruction = Instruction::new_with_bytes(self.bonsol_program, ix_data, accounts);
+        let instructions = self
+            .with_compute_budget(instruction, TransactionKind::Claim { tip })
+            .await;
+        self.sign(&instructions).await
+    }
+
+    /// Builds and signs a bid of `price` lamports for the claim on an auctioned execution,
+    /// committing to prove it by `completion_slot`. Returns it with the last block height it is
+    /// valid for.
+    pub async fn bid_transaction(
+        &self,
+        execution_id: &str,
+        requester: Pubkey,
+        price: u64,
+        completion_slot: u64,
+        claimer_allow_list: Option<Pubkey>,
+    ) -> Result<(VersionedTransaction, u64)> {
+        let (execution_account, _) = execution_address(&requester, execution_id.as_bytes());
+        let (bids_account, _) = execution_bids_address(execution_account.as_ref());
+        let mut accounts = vec![
+            AccountMeta::new_readonly(execution_account, false),
+            AccountMeta::new_readonly(requester, false),
+            AccountMeta::new(bids_account, false),
+            AccountMeta::new_readonly(self.signer.pubkey(), true),
+            AccountMeta::new_readonly(self.prover_account(), false),
+        ];
+        if let Some(allow_list) = claimer_allow_list {
+            accounts.push(AccountMeta::new_readonly(allow_list, false));
+        }
+        let mut fbb = FlatBufferBuilder::new();
+        let eid = fbb.create_string(execution_id);
+        let bid = BidV1::create(
+            &mut fbb,
+            &BidV1Args {
+                execution_id: Some(eid),
+                price,
+                completion_slot,
+            },
+        );
+        fbb.finish(bid, None);
+        let bidbytes = fbb.finished_data();
+        let mut fbb2 = FlatBufferBuilder::new();
+        let off = fbb2.create_vector(bidbytes);
+        let root = ChannelInstruction::create(
+            &mut fbb2,
+            &ChannelInstructionArgs {
+                ix_type: ChannelInstructionIxType::BidV1,
+                bid_v1: Some(off),
+                ..Default::default()
+            },
+        );
+        fbb2.finish(root, None);
+        let ix_data = fbb2.finished_data();
+        let instruction = Instruction::new_with_bytes(self.bonsol_program, ix_data, accounts);
+        let instructions = self
+            .with_compute_budget(instruction, TransactionKind::Bid)
+            .await;
+        self.sign(&instructions).await
+    }
+
+    /// Builds and signs a transaction closing an expired execution, the node is paid the crank fee.
+    /// `claimer` is the prover holding the claim on it, if any. Returns it with the last block
+    /// height it is valid for.
+    pub async fn expire_transaction(
+        &self,
+        execution_id: &str,
+        requester: Pubkey,
+        claimer: Option<Pubkey>,
+    ) -> Result<(VersionedTransaction, u64)> {
+        let (execution_account, _) = execution_address(&requester, execution_id.as_bytes());
+        let (execution_claim_account, _) = execution_claim_address(execution_account.as_ref());
+        let mut accounts = vec![
+            AccountMeta::new(self.signer.pubkey(), true),
+            AccountMeta::new(requester, false),
+            AccountMeta::new(execution_account, false),
+            AccountMeta::new(execution_claim_account, false),
+        ];
+        if let Some(claimer) = claimer {
+            accounts.push(AccountMeta::new(prover_address(&claimer).0, false));
+        }
+        accounts.push(AccountMeta::new(
+            execution_bids_address(execution_account.as_ref()).0,
+            false,
+        ));
+        let mut fbb = FlatBufferBuilder::new();
+        let eid = fbb.create_string(execution_id);
+        let expire = ExpireV1::create(
+            &mut fbb,
+            &ExpireV1Args {
+                execution_id: Some(eid),
+            },
+        );
+        fbb.finish(expire, None);
+        let expirebytes = fbb.finished_data();
+        let mut fbb2 = FlatBufferBuilder::new();
+        let off = fbb2.create_vector(expirebytes);
+        let root = ChannelInstruction::create(
+            &mut fbb2,
+            &ChannelInstructionArgs {
+                ix_type: ChannelInstructionIxType::ExpireV1,
+                expire_v1: Some(off),
+                ..Default::default()
+            },
+        );
+        fbb2.finish(root, None);
+        let ix_data = fbb2.finished_data();
+        let instruction = Instruction::new_with_bytes(self.bonsol_program, ix_data, accounts);
+        let instructions = self
+            .with_compute_budget(instruction, TransactionKind::Expire)
+            .await;
+        self.sign(&instructions).await
+    }
+
+    /// The callback program the submission passes and the callback's extra accounts, the
+    /// program itself when the execution has no callback
+    fn callback_accounts(&self, submission: &ProofSubmission) -> (Pubkey, Vec<AccountMeta>) {
+        match &submission.callback_exec {
+            None => (self.bonsol_program, vec![]),
+            Some(pe) => {
+                let prog = pe.program_id;
+                //todo: add read interface simulation on program to get other accounts
+                (prog, submission.additional_accounts.clone())
+            }
+        }
+    }
+
+    /// Builds and signs a proof submission, returns it with the last block height it is valid for
+    pub async fn proof_transaction(
+        &self,
+        submission: &ProofSubmission,
+    ) -> Result<(VersionedTransaction, u64)> {
+        let (execution_request_data_account, _) = execution_address(
+            &submission.requester_account,
+            submission.execution_id.as_bytes(),
+        );
+        let (execution_claim_account, _) =
+            execution_claim_address(execution_request_data_account.as_ref());
+        let (id, additional_accounts) = self.callback_accounts(submission);
+
+        let mut accounts = vec![
+            AccountMeta::new(submission.requester_account, false),
+            AccountMeta::new(execution_request_data_account, false),
+            AccountMeta::new_readonly(id, false),
+            AccountMeta::new(self.signer.pubkey(), true),
+            AccountMeta::new(execution_claim_account, false),
+            AccountMeta::new(self.prover_account(), false),
+            AccountMeta::new_readonly(verifier_config_address(submission.prover_version).0, false),
+        ];
+        // the program reads the claims the proof assumes from the prior executions
+        accounts.extend(
+            submission
+                .prior_executions
+                .iter()
+                .map(|prior| AccountMeta::new_readonly(*prior, false)),
+        );
+        accounts.extend(additional_accounts);
+        let mut fbb = FlatBufferBuilder::new();
+        let stat = submission.status(&mut fbb, Some(&submission.proof));
+        fbb.finish(stat, None);
+        let statbytes = fbb.finished_data();
+        let mut fbb2 = FlatBufferBuilder::new();
+        let off = fbb2.create_vector(statbytes);
+        let root = ChannelInstruction::create(
+            &mut fbb2,
+            &ChannelInstructionArgs {
+                ix_type: ChannelInstructionIxType::StatusV1,
+                status_v1: Some(off),
+                ..Default::default()
+            },
+        );
+        fbb2.finish(root, None);
+        let ix_data = fbb2.finished_data();
+        let instruction = Instruction::new_with_bytes(self.bonsol_program, ix_data, accounts);
+        let instructions = self
+            .with_compute_budget(instruction, TransactionKind::SubmitProof)
+            .await;
+        self.sign(&instructions).await
+    }
+
+    /// Builds and signs a batch submission settling every execution of the batch with one proof,
+    /// returns it with the last block height it is valid for. Fails when the batch does not fit
+    /// in a transaction.
+    pub async fn batch_transaction(
+        &self,
+        batch: &BatchSubmission,
+    ) -> Result<(VersionedTransaction, u64)> {
+        let prover_version = batch
+            .submissions
+            .first()
+            .map(|submission| submission.prover_version)
+            .ok_or(anyhow::anyhow!("Empty batch"))?;
+        let mut accounts = vec![
+            AccountMeta::new(self.signer.pubkey(), true),
+            AccountMeta::new_readonly(verifier_config_address(prover_version).0, false),
+        ];
+        let mut fbb = FlatBufferBuilder::new();
+        let mut statuses = Vec::with_capacity(batch.submissions.len());
+        for submission in &batch.submissions {
+            let (execution_account, _) = execution_address(
+                &submission.requester_account,
+                submission.execution_id.as_bytes(),
+            );
+            let (execution_claim_account, _) = execution_claim_address(execution_account.as_ref());
+            let (id, additional_accounts) = self.callback_accounts(submission);
+            accounts.extend([
+                AccountMeta::new(submission.requester_account, false),
+                AccountMeta::new(execution_account, false),
+                AccountMeta::new_readonly(id, false),
+                AccountMeta::new(execution_claim_account, false),
+                AccountMeta::new(self.prover_account(), false),
+            ]);
+            accounts.extend(additional_accounts);
+            statuses.push(submission.status(&mut fbb, None));
+        }
+        let proof = fbb.create_vector(&batch.proof);
+        let execution_digest = fbb.create_vector(&batch.execution_digest);
+        let statuses = fbb.create_vector(&statuses);
+        let stat = StatusBatchV1::create(
+            &mut fbb,
+            &StatusBatchV1Args {
+                proof: Some(proof),
+                execution_digest: Some(execution_digest),
+                statuses: Some(statuses),
+            },
+        );
+        fbb.finish(stat, None);
+        let statbytes = fbb.finished_data();
+        let mut fbb2 = FlatBufferBuilder::new();
+        let off = fbb2.create_vector(statbytes);
+        let root = ChannelInstruction::create(
+            &mut fbb2,
+            &ChannelInstructionArgs {
+                ix_type: ChannelInstructionIxType::StatusBatchV1,
+                status_batch_v1: Some(off),
+                ..Default::default()
+            },
+        );
+        fbb2.finish(root, None);
+        let ix_data = fbb2.finished_data();
+        let instruction = Instruction::new_with_bytes(self.bonsol_program, ix_data, accounts);
+        let instructions = self
+            .with_compute_budget(instruction, TransactionKind::SubmitProof)
+            .await;
+        let (tx, last_valid) = self.sign(&instructions).await?;
+        if bincode::serialized_size(&tx)? > PACKET_DATA_SIZE as u64 {
+            return Err(anyhow::anyhow!(
+                "A batch of {} proofs does not fit in a transaction",
+                batch.submissions.len()
+            ));
+        }
+        Ok((tx, last_valid))
+    }
+
+    /// Builds and signs the transaction of `submission`
+    pub async fn submission_transaction(
+        &self,
+        submission: &Submission,
+    ) -> Result<(VersionedTransaction, u64)> {
+        match submission {
+            Submission::Proof(submission) => self.proof_transaction(submission).await,
+            Submission::Batch(batch) => self.batch_transaction(batch).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::config::ProverNodeConfig,
+        base64::{engine::general_purpose::STANDARD, Engine},
+        serde_json::json,
+        solana_rpc_client::mock_sender::Mocks,
+        solana_rpc_client_api::request::RpcRequest,
+        solana_sdk::signature::Keypair,
+    };
+
+    /// A builder whose rpc reports the execution as claimed by `claimer`, or as unclaimed
+    fn mock_builder(claimer: Option<Pubkey>) -> TransactionBuilder {
+        let mut mocks = Mocks::default();
+        if let Some(claimer) = claimer {
+            let claim = ClaimStateV1::from_claim_ix(&claimer, 10, 20, 1_000);
+            let mut data = claim.claimer.to_vec();
+            for field in [claim.claimed_at, claim.block_commitment, claim.price] {
+                data.extend(field.to_le_bytes());
+            }
+            mocks.insert(
+                RpcRequest::GetAccountInfo,
+                json!({
+                    "context": { "slot": 1 },
+                    "value": {
+                        "lamports": 1_000_000,
+                        "data": [STANDARD.encode(&data), "base64"],
+                        "owner": Pubkey::new_unique().to_string(),
+                        "executable": false,
+                        "rentEpoch": 0,
+                        "space": data.len(),
+                    },
+                }),
+            );
+        }
+        TransactionBuilder {
+            rpc_client: Arc::new(RpcClient::new_mock_with_mocks(
+                "succeeds".to_string(),
+                mocks,
+            )),
+            bonsol_program: Pubkey::new_unique(),
+            signer: Arc::new(Keypair::new()),
+            fees: Arc::new(FeeStrategy::new(
+                ProverNodeConfig::default().transaction_fee_config,
+            )),
+        }
+    }
+
+    /// The accounts of the claim instruction, which follows the compute budget instructions
+    fn claim_accounts(tx: &VersionedTransaction) -> Vec<Pubkey> {
+        let keys = tx.message.static_account_keys();
+        let claim = tx.message.instructions().last().unwrap();
+        claim
+            .accounts
+            .iter()
+            .map(|index| keys[*index as usize])
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn takeover_claims_take_the_registry_of_the_lapsed_claimer() {
+        let requester = Pubkey::new_unique();
+        let (execution_account, _) = execution_address(&requester, b"execution");
+        let (bids_account, _) = execution_bids_address(execution_account.as_ref());
+        let allow_list = Pubkey::new_unique();
+        let lapsed_claimer = Pubkey::new_unique();
+
+        let builder = mock_builder(Some(lapsed_claimer));
+        let (tx, _) = builder
+            .claim_transaction(
+                "execution",
+                requester,
+                execution_account,
+                100,
+                1_000,
+                Some(allow_list),
+            )
+            .await
+            .unwrap();
+        let accounts = claim_accounts(&tx);
+        assert_eq!(accounts[6], prover_address(&builder.signer.pubkey()).0);
+        assert_eq!(
+            accounts[7..],
+            [prover_address(&lapsed_claimer).0, allow_list, bids_account]
+        );
+
+        let builder = mock_builder(None);
+        let (tx, _) = builder
+            .claim_transaction(
+                "execution",
+                requester,
+                execution_account,
+                100,
+                1_000,
+                Some(allow_list),
+            )
+            .await
+            .unwrap();
+        assert_eq!(claim_accounts(&tx)[7..], [allow_list, bids_account]);
+    }
+}
@@ -0,0 +1,192 @@
+//! Compute budget instructions for the transactions the node submits.
+//! Claims, proof submissions and expirations get their own compute unit limit, the compute unit
+//! price is either fixed or follows the fees recently paid for the node's and the bonsol program's
+//! accounts.
+use {
+    crate::config::{PriorityFeeConfig, TransactionFeeConfig},
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::{
+        compute_budget::ComputeBudgetInstruction, instruction::Instruction, pubkey::Pubkey,
+    },
+    std::{
+        sync::Mutex,
+        time::{Duration, Instant},
+    },
+    tracing::warn,
+};
+
+/// How long a dynamic compute unit price is reused before the recent fees are fetched again
+const PRICE_TTL: Duration = Duration::from_secs(10);
+
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+/// The instruction a transaction is built around
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionKind {
+    Claim { tip: u64 },
+    Bid,
+    SubmitProof,
+    Expire,
+}
+
+pub struct FeeStrategy {
+    config: TransactionFeeConfig,
+    dynamic_price: Mutex<Option<(Instant, u64)>>,
+}
+
+impl FeeStrategy {
+    pub const fn new(config: TransactionFeeConfig) -> Self {
+        FeeStrategy {
+            config,
+            dynamic_price: Mutex::new(None),
+        }
+    }
+
+    pub const fn compute_unit_limit(&self, kind: TransactionKind) -> u32 {
+        match kind {
+            TransactionKind::Claim { .. } => self.config.claim_compute_units,
+            TransactionKind::Bid => self.config.bid_compute_units,
+            TransactionKind::SubmitProof => self.config.submit_proof_compute_units,
+            TransactionKind::Expire => self.config.expire_compute_units,
+        }
+    }
+
+    /// The compute budget instructions to prepend to a transaction of `kind`
+    pub async fn compute_budget_instructions(
+        &self,
+        rpc_client: &RpcClient,
+        accounts: &[Pubkey],
+        kind: TransactionKind,
+    ) -> Vec<Instruction> {
+        let limit = self.compute_unit_limit(kind);
+        let base_price = match self.config.priority_fee {
+            PriorityFeeConfig::Fixed { compute_unit_price } => compute_unit_price,
+            PriorityFeeConfig::Dynamic { percentile } => {
+                self.dynamic_price(rpc_client, accounts, percentile).await
+            }
+        };
+        let price = match (kind, self.config.claim_tip_share_bps) {
+            (TransactionKind::Claim { tip }, Some(bps)) => {
+                base_price.max(tip_share_price(tip, bps, limit))
+            }
+            _ => base_price,
+        }
+        .min(self.config.max_compute_unit_price);
+        let mut ixs = vec![ComputeBudgetInstruction::set_compute_unit_limit(limit)];
+        if price > 0 {
+            ixs.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+        }
+        ixs
+    }
+
+    async fn dynamic_price(
+        &self,
+        rpc_client: &RpcClient,
+        accounts: &[Pubkey],
+        percentile: u8,
+    ) -> u64 {
+        if let Some((fetched_at, price)) = *self.dynamic_price.lock().unwrap() {
+            if fetched_at.elapsed() < PRICE_TTL {
+                return price;
+            }
+        }
+        match rpc_client.get_recent_prioritization_fees(accounts).await {
+            Ok(fees) => {
+                let fees = fees.into_iter().map(|f| f.prioritization_fee).collect();
+                let price = fee_percentile(fees, percentile);
+                *self.dynamic_price.lock().unwrap() = Some((Instant::now(), price));
+                price
+            }
+            Err(e) => {
+                // a stale price is better than no priority fee at all
+                warn!("Failed to get recent prioritization fees: {:?}", e);
+                self.dynamic_price
+                    .lock()
+                    .unwrap()
+                    .map(|(_, price)| price)
+                    .unwrap_or(0)
+            }
+        }
+    }
+}
+
+/// The `percentile` of the recently paid compute unit prices
+fn fee_percentile(mut fees: Vec<u64>, percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    let rank = (fees.len() - 1) * percentile.min(100) as usize / 100;
+    fees[rank]
+}
+
+/// The compute unit price that spends `bps` basis points of the tip on the priority fee
+fn tip_share_price(tip: u64, bps: u16, compute_units: u32) -> u64 {
+    if compute_units == 0 {
+        return 0;
+    }
+    let budget = tip as u128 * bps as u128 / 10_000;
+    (budget * MICRO_LAMPORTS_PER_LAMPORT / compute_units as u128).min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn percentile_of_recent_fees() {
+        assert_eq!(fee_percentile(vec![], 75), 0);
+        assert_eq!(fee_percentile(vec![50, 10, 40, 20, 30], 0), 10);
+        assert_eq!(fee_percentile(vec![50, 10, 40, 20, 30], 50), 30);
+        assert_eq!(fee_percentile(vec![50, 10, 40, 20, 30], 75), 40);
+        assert_eq!(fee_percentile(vec![50, 10, 40, 20, 30], 100), 50);
+    }
+
+    #[test]
+    fn claim_price_scales_with_tip() {
+        // 1% of a 1_000_000 lamport tip over 50_000 compute units
+        assert_eq!(tip_share_price(1_000_000, 100, 50_000), 200_000);
+        assert_eq!(tip_share_price(0, 100, 50_000), 0);
+        assert_eq!(tip_share_price(1_000_000, 100, 0), 0);
+    }
+
+    #[tokio::test]
+    async fn fixed_price_is_capped_and_tip_share_applies_to_claims() {
+        let strategy = FeeStrategy::new(TransactionFeeConfig {
+            claim_compute_units: 50_000,
+            bid_compute_units: 40_000,
+            submit_proof_compute_units: 1_400_000,
+            expire_compute_units: 20_000,
+            priority_fee: PriorityFeeConfig::Fixed {
+                compute_unit_price: 1_000,
+            },
+            claim_tip_share_bps: Some(100),
+            max_compute_unit_price: 100_000,
+        });
+        let rpc_client = RpcClient::new_mock("succeeds".to_string());
+        let claim = strategy
+            .compute_budget_instructions(
+                &rpc_client,
+                &[],
+                TransactionKind::Claim { tip: 1_000_000 },
+            )
+            .await;
+        assert_eq!(
+            claim,
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(50_000),
+                ComputeBudgetInstruction::set_compute_unit_price(100_000),
+            ]
+        );
+        let proof = strategy
+            .compute_budget_instructions(&rpc_client, &[], TransactionKind::SubmitProof)
+            .await;
+        assert_eq!(
+            proof,
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                ComputeBudgetInstruction::set_compute_unit_price(1_000),
+            ]
+        );
+    }
+}
@@ -1,3 +1,15 @@
+mod builder;
+mod fees;
+mod rebroadcast;
+mod tpu;
+
+pub use {
+    builder::{BatchSubmission, ProofSubmission, Submission, TransactionBuilder},
+    fees::{FeeStrategy, TransactionKind},
+    rebroadcast::{Broadcaster, RpcBroadcaster, SignatureTracker},
+    tpu::TpuTransactionSender,
+};
+
 use std::sync::Arc;
 
 use tracing::error;
@@ -5,23 +17,12 @@ use tracing::error;
 use {
     async_trait::async_trait,
     bonsol_interface::{
-        bonsol_schema::{
-            ChannelInstruction, ChannelInstructionArgs, ChannelInstructionIxType, ClaimV1,
-            ClaimV1Args, StatusTypes, StatusV1, StatusV1Args,
-        },
+        bonsol_schema::ProverVersion,
+        claim_state::ClaimStateV1,
         util::{deployment_address, execution_address, execution_claim_address},
     },
-    dashmap::DashMap,
-    flatbuffers::FlatBufferBuilder,
-    itertools::Itertools,
-    solana_rpc_client_api::config::RpcSendTransactionConfig,
     solana_sdk::{
-        account::Account,
-        commitment_config::CommitmentConfig,
-        message::{v0, VersionedMessage},
-        signature::Signature,
-        signer::SignerError,
-        system_program,
+        account::Account, signature::Signature, signer::SignerError,
         transaction::VersionedTransaction,
     },
     solana_transaction_status::TransactionStatus as TransactionConfirmationStatus,
@@ -29,16 +30,10 @@ use {
 };
 
 use {
-    crate::types::ProgramExec,
+    crate::{config::TransactionFeeConfig, signer::NodeSigner, types::ProgramExec},
     anyhow::Result,
     solana_rpc_client::nonblocking::rpc_client::RpcClient,
-    solana_sdk::{
-        instruction::{AccountMeta, Instruction},
-        pubkey::Pubkey,
-        signature::Keypair,
-        signer::Signer,
-    },
-    tracing::info,
+    solana_sdk::{instruction::AccountMeta, pubkey::Pubkey, signer::Signer},
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -48,15 +43,24 @@ pub enum TransactionStatus {
 }
 
 #[async_trait]
-pub trait TransactionSender {
+pub trait TransactionSender: Send + Sync {
     fn start(&mut self);
+    /// The identity the sender signs transactions with
+    fn signer(&self) -> NodeSigner;
+    /// Claims the execution, `tip` is the execution's tip which can scale the priority fee.
+    /// `claimer_allow_list` is the allow list account the execution restricts its claimers to.
     async fn claim(
         &self,
         execution_id: &str,
         requester: Pubkey,
         execution_account: Pubkey,
         block_commitment: u64,
+        tip: u64,
+        claimer_allow_list: Option<Pubkey>,
     ) -> Result<Signature>;
+    /// Submits the proof, the submission is rebuilt with a new blockhash until it lands or the
+    /// execution expires at `execution_expiry`. `prover_version` is the version the execution is
+    /// pinned to.
     async fn submit_proof(
         &self,
         execution_id: &str,
@@ -68,41 +72,76 @@ pub trait TransactionSender {
         assumption_digest: &[u8],
         committed_outputs: &[u8],
         additional_accounts: Vec<AccountMeta>,
+        prior_executions: Vec<Pubkey>,
         exit_code_system: u32,
         exit_code_user: u32,
+        execution_expiry: u64,
+        prover_version: ProverVersion,
     ) -> Result<Signature>;
+    /// Submits a batch proof settling every execution of the batch in one transaction, it is
+    /// rebuilt like a single proof submission until it lands or one of the executions expires
+    async fn submit_proof_batch(&self, batch: BatchSubmission) -> Result<Signature>;
+    /// Bids `price` lamports for the claim on an auctioned execution, committing to prove it by
+    /// `completion_slot`
+    async fn bid(
+        &self,
+        execution_id: &str,
+        requester: Pubkey,
+        price: u64,
+        completion_slot: u64,
+        claimer_allow_list: Option<Pubkey>,
+    ) -> Result<Signature>;
+    /// Closes an execution that expired without being settled, the node is paid the crank fee
+    async fn expire(&self, execution_id: &str, requester: Pubkey) -> Result<Signature>;
     async fn get_current_block(&self) -> Result<u64>;
+    /// The current slot, auctions open and close at slots
+    async fn get_current_slot(&self) -> Result<u64>;
     fn get_signature_status(&self, sig: &Signature) -> Option<TransactionStatus>;
     fn clear_signature_status(&self, sig: &Signature);
+    /// Start tracking the status of a signature that was sent before this sender was started
+    fn track_signature(&self, sig: Signature, expiry: u64);
     async fn get_deployment_account(&self, image_id: &str) -> Result<Account>;
+    async fn get_claim_state(
+        &self,
+        requester: Pubkey,
+        execution_id: &str,
+    ) -> Result<Option<ClaimStateV1>>;
+    /// The execution account, None once it was closed
+    async fn get_execution_account(
+        &self,
+        requester: Pubkey,
+        execution_id: &str,
+    ) -> Result<Option<Account>>;
+    /// Any account, None when it does not exist
+    async fn get_account(&self, address: Pubkey) -> Result<Option<Account>>;
 }
 
 pub struct RpcTransactionSender {
     pub rpc_client: Arc<RpcClient>,
-    pub bonsol_program: Pubkey,
-    pub signer: Keypair,
+    pub builder: TransactionBuilder,
+    pub broadcaster: Arc<dyn Broadcaster>,
     pub txn_status_handle: Option<JoinHandle<()>>,
-    pub sigs: Arc<DashMap<Signature, TransactionStatus>>,
+    pub sigs: SignatureTracker,
 }
 
 impl Signer for RpcTransactionSender {
     fn pubkey(&self) -> Pubkey {
-        self.signer.pubkey()
+        self.builder.signer.pubkey()
     }
 
     fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
-        Ok(self.signer.pubkey())
+        Ok(self.builder.signer.pubkey())
     }
 
     fn sign_message(&self, message: &[u8]) -> Signature {
-        self.signer.sign_message(message)
+        self.builder.signer.sign_message(message)
     }
 
     fn try_sign_message(
         &self,
         message: &[u8],
     ) -> std::result::Result<Signature, solana_sdk::signer::SignerError> {
-        self.signer.try_sign_message(message)
+        self.builder.signer.try_sign_message(message)
     }
 
     fn is_interactive(&self) -> bool {
@@ -111,25 +150,71 @@ impl Signer for RpcTransactionSender {
 }
 
 impl RpcTransactionSender {
-    pub fn new(rpc_url: String, bonsol_program: Pubkey, signer: Keypair) -> Self {
+    pub fn new(
+        rpc_url: String,
+        bonsol_program: Pubkey,
+        signer: NodeSigner,
+        fee_config: TransactionFeeConfig,
+    ) -> Self {
+        let rpc_client = Arc::new(RpcClient::new(rpc_url));
+        Self::with_rpc_client(rpc_client, bonsol_program, signer, fee_config)
+    }
+
+    pub fn with_rpc_client(
+        rpc_client: Arc<RpcClient>,
+        bonsol_program: Pubkey,
+        signer: NodeSigner,
+        fee_config: TransactionFeeConfig,
+    ) -> Self {
         Self {
-            rpc_client: Arc::new(RpcClient::new(rpc_url)),
-            signer,
-            bonsol_program,
+            builder: TransactionBuilder {
+                rpc_client: rpc_client.clone(),
+                bonsol_program,
+                signer,
+                fees: Arc::new(FeeStrategy::new(fee_config)),
+            },
+            broadcaster: Arc::new(RpcBroadcaster::new(rpc_client.clone())),
+            rpc_client,
             txn_status_handle: None,
-            sigs: Arc::new(DashMap::new()),
+            sigs: SignatureTracker::default(),
         }
     }
+
+    /// Replaces how transactions are delivered, sending, rebroadcasting and signature tracking
+    /// stay the same
+    pub fn with_broadcaster(mut self, broadcaster: Arc<dyn Broadcaster>) -> Self {
+        self.broadcaster = broadcaster;
+        self
+    }
+
+    /// Broadcasts the transaction and tracks it until it lands
+    async fn send(
+        &self,
+        tx: VersionedTransaction,
+        last_valid: u64,
+        resubmit: Option<Arc<Submission>>,
+    ) -> Result<Signature> {
+        self.broadcaster.broadcast(&tx).await?;
+        Ok(self.sigs.sent(tx, last_valid, resubmit))
+    }
 }
 
 #[async_trait]
 impl TransactionSender for RpcTransactionSender {
+    fn signer(&self) -> NodeSigner {
+        self.builder.signer.clone()
+    }
+
     fn get_signature_status(&self, sig: &Signature) -> Option<TransactionStatus> {
-        self.sigs.get(sig).map(|status| status.value().to_owned())
+        self.sigs.status(sig)
     }
 
     fn clear_signature_status(&self, sig: &Signature) {
-        self.sigs.remove(sig);
+        self.sigs.clear(sig);
+    }
+
+    fn track_signature(&self, sig: Signature, expiry: u64) {
+        self.sigs.track(sig, expiry);
     }
 
     async fn claim(
@@ -138,63 +223,21 @@ impl TransactionSender for RpcTransactionSender {
         requester: Pubkey,
         execution_account: Pubkey,
         block_commitment: u64,
+        tip: u64,
+        claimer_allow_list: Option<Pubkey>,
     ) -> Result<Signature> {
-        let (execution_claim_account, _) = execution_claim_address(execution_account.as_ref());
-        let accounts = vec![
-            AccountMeta::new(execution_account, false),
-            AccountMeta::new_readonly(requester, false),
-            AccountMeta::new(execution_claim_account, false),
-            AccountMeta::new(self.signer.pubkey(), true),
-            AccountMeta::new(self.signer.pubkey(), true),
-            AccountMeta::new_readonly(system_program::id(), false),
-        ];
-        let mut fbb = FlatBufferBuilder::new();
-        let eid = fbb.create_string(execution_id);
-        let stat = ClaimV1::create(
-            &mut fbb,
-            &ClaimV1Args {
+        let (tx, last_valid) = self
+            .builder
+            .claim_transaction(
+                execution_id,
+                requester,
+                execution_account,
                 block_commitment,
-                execution_id: Some(eid),
-            },
-        );
-        fbb.finish(stat, None);
-        let statbytes = fbb.finished_data();
-        let mut fbb2 = FlatBufferBuilder::new();
-        let off = fbb2.create_vector(statbytes);
-        let root = ChannelInstruction::create(
-            &mut fbb2,
-            &ChannelInstructionArgs {
-                ix_type: ChannelInstructionIxType::ClaimV1,
-                claim_v1: Some(off),
-                ..Default::default()
-            },
-        );
-        fbb2.finish(root, None);
-        let ix_data = fbb2.finished_data();
-        let instruction = Instruction::new_with_bytes(self.bonsol_program, ix_data, accounts);
-        let (blockhash_req, last_valid) = self
-            .rpc_client
-            .get_latest_blockhash_with_commitment(self.rpc_client.commitment())
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to get blockhash: {:?}", e))?;
-
-        let msg =
-            v0::Message::try_compile(&self.signer.pubkey(), &[instruction], &[], blockhash_req)?;
-        let tx = VersionedTransaction::try_new(VersionedMessage::V0(msg), &[&self.signer])?;
-        let sig = self
-            .rpc_client
-            .send_transaction_with_config(
-                &tx,
-                RpcSendTransactionConfig {
-                    skip_preflight: true,
-                    ..Default::default()
-                },
+                tip,
+                claimer_allow_list,
             )
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to send transaction: {:?}", e))?;
-        self.sigs
-            .insert(sig, TransactionStatus::Pending { expiry: last_valid });
-        Ok(sig)
+            .await?;
+        self.send(tx, last_valid, None).await
     }
 
     async fn submit_proof(
@@ -208,91 +251,80 @@ impl TransactionSender for RpcTransactionSender {
         assumption_digest: &[u8],
         committed_outputs: &[u8],
         additional_accounts: Vec<AccountMeta>,
+        prior_executions: Vec<Pubkey>,
         exit_code_system: u32,
         exit_code_user: u32,
+        execution_expiry: u64,
+        prover_version: ProverVersion,
     ) -> Result<Signature> {
-        let (execution_request_data_account, _) =
-            execution_address(&requester_account, execution_id.as_bytes());
-        let (id, additional_accounts) = match callback_exec {
-            None => (self.bonsol_program, vec![]),
-            Some(pe) => {
-                let prog = pe.program_id;
-                //todo: add read interface simulation on program to get other accounts
-                (prog, additional_accounts)
-            }
+        let submission = ProofSubmission {
+            execution_id: execution_id.to_string(),
+            requester_account,
+            callback_exec,
+            proof: proof.to_vec(),
+            execution_digest: execution_digest.to_vec(),
+            input_digest: input_digest.to_vec(),
+            assumption_digest: assumption_digest.to_vec(),
+            committed_outputs: committed_outputs.to_vec(),
+            additional_accounts,
+            prior_executions,
+            exit_code_system,
+            exit_code_user,
+            execution_expiry,
+            prover_version,
         };
+        let (tx, last_valid) = self.builder.proof_transaction(&submission).await?;
+        self.send(
+            tx,
+            last_valid,
+            Some(Arc::new(Submission::Proof(submission))),
+        )
+        .await
+    }
 
-        let mut accounts = vec![
-            AccountMeta::new(requester_account, false),
-            AccountMeta::new(execution_request_data_account, false),
-            AccountMeta::new_readonly(id, false),
-            AccountMeta::new(self.signer.pubkey(), true),
-        ];
-        accounts.extend(additional_accounts);
-        let mut fbb = FlatBufferBuilder::new();
-        let proof_vec = fbb.create_vector(proof);
-        let execution_digest = fbb.create_vector(execution_digest);
-        let input_digest = fbb.create_vector(input_digest);
-        let assumption_digest = fbb.create_vector(assumption_digest);
-        let eid = fbb.create_string(execution_id);
-        let out = fbb.create_vector(committed_outputs);
-        let stat = StatusV1::create(
-            &mut fbb,
-            &StatusV1Args {
-                execution_id: Some(eid),                    //0-?? bytes lets say 16
-                status: StatusTypes::Completed,             //1 byte
-                proof: Some(proof_vec),                     //256 bytes
-                execution_digest: Some(execution_digest),   //32 bytes
-                input_digest: Some(input_digest),           //32 bytes
-                assumption_digest: Some(assumption_digest), //32 bytes
-                committed_outputs: Some(out),               //0-?? bytes lets say 32
-                exit_code_system,                           //4 byte
-                exit_code_user,                             //4 byte
-            }, //total ~408 bytes plenty of room for more stuff
-        );
-        fbb.finish(stat, None);
-        let statbytes = fbb.finished_data();
-        let mut fbb2 = FlatBufferBuilder::new();
-        let off = fbb2.create_vector(statbytes);
-        let root = ChannelInstruction::create(
-            &mut fbb2,
-            &ChannelInstructionArgs {
-                ix_type: ChannelInstructionIxType::StatusV1,
-                status_v1: Some(off),
-                ..Default::default()
-            },
-        );
-        fbb2.finish(root, None);
-        let ix_data = fbb2.finished_data();
-        let instruction = Instruction::new_with_bytes(self.bonsol_program, ix_data, accounts);
-        let (blockhash, last_valid) = self
-            .rpc_client
-            .get_latest_blockhash_with_commitment(self.rpc_client.commitment())
+    async fn submit_proof_batch(&self, batch: BatchSubmission) -> Result<Signature> {
+        let (tx, last_valid) = self.builder.batch_transaction(&batch).await?;
+        self.send(tx, last_valid, Some(Arc::new(Submission::Batch(batch))))
             .await
-            .map_err(|e| anyhow::anyhow!("Failed to get blockhash: {:?}", e))?;
-
-        let msg = v0::Message::try_compile(&self.signer.pubkey(), &[instruction], &[], blockhash)?;
-        let tx = VersionedTransaction::try_new(VersionedMessage::V0(msg), &[&self.signer])?;
+    }
 
-        let sig = self
-            .rpc_client
-            .send_and_confirm_transaction_with_spinner_and_config(
-                &tx,
-                CommitmentConfig::confirmed(),
-                RpcSendTransactionConfig {
-                    skip_preflight: true,
-                    ..Default::default()
-                },
+    async fn bid(
+        &self,
+        execution_id: &str,
+        requester: Pubkey,
+        price: u64,
+        completion_slot: u64,
+        claimer_allow_list: Option<Pubkey>,
+    ) -> Result<Signature> {
+        let (tx, last_valid) = self
+            .builder
+            .bid_transaction(
+                execution_id,
+                requester,
+                price,
+                completion_slot,
+                claimer_allow_list,
             )
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to send transaction: {:?}", e))?;
-        self.sigs
-            .insert(sig, TransactionStatus::Pending { expiry: last_valid });
-        Ok(sig)
+            .await?;
+        self.send(tx, last_valid, None).await
+    }
+
+    async fn expire(&self, execution_id: &str, requester: Pubkey) -> Result<Signature> {
+        let claimer = self
+            .get_claim_state(requester, execution_id)
+            .await?
+            .map(|claim| Pubkey::new_from_array(claim.claimer));
+        let (tx, last_valid) = self
+            .builder
+            .expire_transaction(execution_id, requester, claimer)
+            .await?;
+        self.send(tx, last_valid, None).await
     }
 
     fn start(&mut self) {
-        let sigs_ref = self.sigs.clone();
+        let sigs = self.sigs.clone();
+        let builder = self.builder.clone();
+        let broadcaster = self.broadcaster.clone();
         let rpc_client = self.rpc_client.clone();
         self.txn_status_handle = Some(tokio::spawn(async move {
             let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(1));
@@ -301,28 +333,24 @@ impl TransactionSender for RpcTransactionSender {
                 let current_block_height = rpc_client
                     .get_block_height_with_commitment(rpc_client.commitment())
                     .await;
+                let current_slot = rpc_client
+                    .get_slot_with_commitment(rpc_client.commitment())
+                    .await;
 
-                if let Ok(current_block_height) = current_block_height {
-                    sigs_ref.retain(|k, v| {
-                        if let TransactionStatus::Pending { expiry } = v {
-                            if *expiry < current_block_height {
-                                info!("Transaction expired {}", k);
-                                return false;
-                            }
-                        }
-                        true
-                    });
-                    let all_sigs = sigs_ref.iter().map(|x| *x.key()).collect_vec();
-                    let statuses = rpc_client.get_signature_statuses(&all_sigs).await;
-                    if let Ok(statuses) = statuses {
-                        for sig in all_sigs.into_iter().zip(statuses.value.into_iter()) {
-                            if let Some(status) = sig.1 {
-                                sigs_ref.insert(sig.0, TransactionStatus::Confirmed(status));
-                            }
-                        }
-                    }
+                if let (Ok(current_block_height), Ok(current_slot)) =
+                    (current_block_height, current_slot)
+                {
+                    sigs.refresh_statuses(&rpc_client).await;
+                    sigs.rebroadcast(
+                        &builder,
+                        broadcaster.as_ref(),
+                        current_block_height,
+                        current_slot,
+                    )
+                    .await;
+                    sigs.expire_untracked(current_block_height);
                 } else {
-                    error!("Failed to get block height");
+                    error!("Failed to get block height and slot");
                 }
             }
         }));
@@ -335,6 +363,13 @@ impl TransactionSender for RpcTransactionSender {
             .map_err(|e| anyhow::anyhow!("{:?}", e))
     }
 
+    async fn get_current_slot(&self) -> Result<u64> {
+        self.rpc_client
+            .get_slot()
+            .await
+            .map_err(|e| anyhow::anyhow!("{:?}", e))
+    }
+
     async fn get_deployment_account(&self, image_id: &str) -> Result<Account> {
         let (deployment_account, _) = deployment_address(image_id);
         self.rpc_client
@@ -342,4 +377,40 @@ impl TransactionSender for RpcTransactionSender {
             .await
             .map_err(|e| anyhow::anyhow!("Failed to get account: {:?}", e))
     }
+
+    async fn get_claim_state(
+        &self,
+        requester: Pubkey,
+        execution_id: &str,
+    ) -> Result<Option<ClaimStateV1>> {
+        let (execution_account, _) = execution_address(&requester, execution_id.as_bytes());
+        let (claim_account, _) = execution_claim_address(execution_account.as_ref());
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(&claim_account, self.rpc_client.commitment())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get account: {:?}", e))?
+            .value;
+        account
+            .map(|a| ClaimStateV1::load_claim_owned(&a.data))
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("Failed to parse claim account: {:?}", e))
+    }
+
+    async fn get_execution_account(
+        &self,
+        requester: Pubkey,
+        execution_id: &str,
+    ) -> Result<Option<Account>> {
+        let (execution_account, _) = execution_address(&requester, execution_id.as_bytes());
+        self.get_account(execution_account).await
+    }
+
+    async fn get_account(&self, address: Pubkey) -> Result<Option<Account>> {
+        self.rpc_client
+            .get_account_with_commitment(&address, self.rpc_client.commitment())
+            .await
+            .map(|response| response.value)
+            .map_err(|e| anyhow::anyhow!("Failed to get account: {:?}", e))
+    }
 }
@@ -0,0 +1,389 @@
+//! Keeps the transactions the node sent alive until they land.
+//! Signed transactions are rebroadcast at an interval until they are confirmed or their blockhash
+//! expires. Proof submissions are rebuilt with a new blockhash while their execution can still be
+//! proven, a batch while every execution of it can, so a dropped transaction does not throw away
+//! a finished proof.
+use {
+    super::{Submission, TransactionBuilder, TransactionStatus},
+    crate::observe::*,
+    anyhow::Result,
+    async_trait::async_trait,
+    dashmap::DashMap,
+    itertools::Itertools,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_rpc_client_api::config::RpcSendTransactionConfig,
+    solana_sdk::{signature::Signature, transaction::VersionedTransaction},
+    std::{sync::Arc, time::Duration},
+    tracing::{error, warn},
+};
+
+/// Time between two broadcasts of the same transaction
+const REBROADCAST_INTERVAL: Duration = Duration::from_secs(2);
+/// Most signatures `getSignatureStatuses` accepts in a single request
+const MAX_STATUS_QUERY: usize = 256;
+
+/// Delivers signed transactions to the cluster
+#[async_trait]
+pub trait Broadcaster: Send + Sync {
+    async fn broadcast(&self, tx: &VersionedTransaction) -> Result<()>;
+}
+
+pub struct RpcBroadcaster {
+    rpc_client: Arc<RpcClient>,
+}
+
+impl RpcBroadcaster {
+    pub const fn new(rpc_client: Arc<RpcClient>) -> Self {
+        RpcBroadcaster { rpc_client }
+    }
+}
+
+#[async_trait]
+impl Broadcaster for RpcBroadcaster {
+    async fn broadcast(&self, tx: &VersionedTransaction) -> Result<()> {
+        self.rpc_client
+            .send_transaction_with_config(
+                tx,
+                RpcSendTransactionConfig {
+                    skip_preflight: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to send transaction: {:?}", e))?;
+        Ok(())
+    }
+}
+
+/// A transaction sent by this node that has not been confirmed yet
+struct PendingTransaction {
+    tx: VersionedTransaction,
+    last_valid: u64,
+    last_sent: Instant,
+    /// Set for proof submissions, which are rebuilt when their blockhash expires
+    resubmit: Option<Arc<Submission>>,
+}
+
+/// The statuses of the signatures the sender tracks along with the transactions it still
+/// rebroadcasts. A rebuilt transaction replaces the signature it was rebuilt from, the old
+/// signature keeps resolving to the status of the new one.
+#[derive(Clone, Default)]
+pub struct SignatureTracker {
+    statuses: Arc<DashMap<Signature, TransactionStatus>>,
+    pending: Arc<DashMap<Signature, PendingTransaction>>,
+    replacements: Arc<DashMap<Signature, Signature>>,
+}
+
+impl SignatureTracker {
+    /// Tracks a transaction that was just broadcast
+    pub fn sent(
+        &self,
+        tx: VersionedTransaction,
+        last_valid: u64,
+        resubmit: Option<Arc<Submission>>,
+    ) -> Signature {
+        let sig = tx.signatures[0];
+        self.statuses
+            .insert(sig, TransactionStatus::Pending { expiry: last_valid });
+        self.pending.insert(
+            sig,
+            PendingTransaction {
+                tx,
+                last_valid,
+                last_sent: Instant::now(),
+                resubmit,
+            },
+        );
+        sig
+    }
+
+    /// Tracks the status of a signature without rebroadcasting it
+    pub fn track(&self, sig: Signature, expiry: u64) {
+        self.statuses
+            .entry(sig)
+            .or_insert(TransactionStatus::Pending { expiry });
+    }
+
+    /// The signature that replaced `sig`, or `sig` itself when it was not rebuilt
+    fn latest(&self, sig: &Signature) -> Signature {
+        let mut latest = *sig;
+        while let Some(next) = self.replacements.get(&latest) {
+            latest = *next;
+        }
+        latest
+    }
+
+    pub fn status(&self, sig: &Signature) -> Option<TransactionStatus> {
+        self.statuses
+            .get(&self.latest(sig))
+            .map(|status| status.value().to_owned())
+    }
+
+    /// Stops tracking `sig` and every signature that replaced it
+    pub fn clear(&self, sig: &Signature) {
+        let mut current = Some(*sig);
+        while let Some(sig) = current {
+            self.statuses.remove(&sig);
+            self.pending.remove(&sig);
+            current = self.replacements.remove(&sig).map(|(_, next)| next);
+        }
+    }
+
+    /// Records the statuses of the tracked signatures that have landed
+    pub async fn refresh_statuses(&self, rpc_client: &RpcClient) {
+        let all_sigs = self.statuses.iter().map(|x| *x.key()).collect_vec();
+        for chunk in all_sigs.chunks(MAX_STATUS_QUERY) {
+            match rpc_client.get_signature_statuses(chunk).await {
+                Ok(statuses) => {
+                    for (sig, status) in chunk.iter().zip(statuses.value.into_iter()) {
+                        if let Some(status) = status {
+                            self.statuses
+                                .insert(*sig, TransactionStatus::Confirmed(status));
+                            self.pending.remove(sig);
+                        }
+                    }
+                }
+                Err(e) => error!("Failed to get signature statuses: {:?}", e),
+            }
+        }
+    }
+
+    /// Rebroadcasts the pending transactions that are due and rebuilds the proof submissions whose
+    /// blockhash expired before `current_block`, everything else past its blockhash is expired.
+    /// Blockhashes expire at a block height while executions expire at a slot, a submission is
+    /// only rebuilt while its executions have not expired at `current_slot`.
+    pub async fn rebroadcast(
+        &self,
+        builder: &TransactionBuilder,
+        broadcaster: &dyn Broadcaster,
+        current_block: u64,
+        current_slot: u64,
+    ) {
+        let mut resend = Vec::new();
+        let mut rebuild = Vec::new();
+        let mut expired = Vec::new();
+        self.pending.retain(|sig, pending| {
+            if !matches!(
+                self.statuses.get(sig).as_deref(),
+                Some(TransactionStatus::Pending { .. })
+            ) {
+                return false;
+            }
+            if pending.last_valid < current_block {
+                return match &pending.resubmit {
+                    // kept until the rebuilt transaction is sent so a failed rebuild is retried
+                    Some(submission) if submission.expiry() >= current_slot => {
+                        rebuild.push((*sig, submission.clone()));
+                        true
+                    }
+                    _ => {
+                        expired.push(*sig);
+                        false
+                    }
+                };
+            }
+            if pending.last_sent.elapsed() >= REBROADCAST_INTERVAL {
+                pending.last_sent = Instant::now();
+                resend.push(pending.tx.clone());
+            }
+            true
+        });
+
+        for tx in resend {
+            if let Err(e) = broadcaster.broadcast(&tx).await {
+                warn!(
+                    "Failed to rebroadcast transaction {}: {:?}",
+                    tx.signatures[0], e
+                );
+            }
+        }
+        for (sig, submission) in rebuild {
+            match self.resubmit(builder, broadcaster, submission).await {
+                Ok(new_sig) => {
+                    info!("Proof submission {} rebuilt as {}", sig, new_sig);
+                    self.pending.remove(&sig);
+                    self.statuses.remove(&sig);
+                    self.replacements.insert(sig, new_sig);
+                }
+                Err(e) => error!("Failed to rebuild proof submission {}: {:?}", sig, e),
+            }
+        }
+        for sig in expired {
+            self.expire(&sig);
+        }
+    }
+
+    async fn resubmit(
+        &self,
+        builder: &TransactionBuilder,
+        broadcaster: &dyn Broadcaster,
+        submission: Arc<Submission>,
+    ) -> Result<Signature> {
+        let (tx, last_valid) = builder.submission_transaction(&submission).await?;
+        broadcaster.broadcast(&tx).await?;
+        Ok(self.sent(tx, last_valid, Some(submission)))
+    }
+
+    /// Drops the tracked signatures whose blockhash expired before `current_block` and that are
+    /// not rebroadcast
+    pub fn expire_untracked(&self, current_block: u64) {
+        self.statuses.retain(|sig, status| match status {
+            TransactionStatus::Pending { expiry }
+                if *expiry < current_block && !self.pending.contains_key(sig) =>
+            {
+                emit_event!(MetricEvents::TransactionExpired, sig => sig.to_string());
+                false
+            }
+            _ => true,
+        });
+    }
+
+    fn expire(&self, sig: &Signature) {
+        emit_event!(MetricEvents::TransactionExpired, sig => sig.to_string());
+        self.statuses.remove(sig);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            config::ProverNodeConfig,
+            transaction_sender::{FeeStrategy, ProofSubmission},
+        },
+        bonsol_interface::bonsol_schema::ProverVersion,
+        solana_sdk::{pubkey::Pubkey, signature::Keypair},
+        std::sync::Mutex,
+    };
+
+    #[derive(Default)]
+    struct RecordingBroadcaster {
+        sent: Mutex<Vec<Signature>>,
+    }
+
+    #[async_trait]
+    impl Broadcaster for RecordingBroadcaster {
+        async fn broadcast(&self, tx: &VersionedTransaction) -> Result<()> {
+            self.sent.lock().unwrap().push(tx.signatures[0]);
+            Ok(())
+        }
+    }
+
+    fn mock_builder() -> TransactionBuilder {
+        TransactionBuilder {
+            rpc_client: Arc::new(RpcClient::new_mock("succeeds".to_string())),
+            bonsol_program: Pubkey::new_unique(),
+            signer: Arc::new(Keypair::new()),
+            fees: Arc::new(FeeStrategy::new(
+                ProverNodeConfig::default().transaction_fee_config,
+            )),
+        }
+    }
+
+    fn submission(execution_expiry: u64) -> Arc<Submission> {
+        Arc::new(Submission::Proof(ProofSubmission {
+            execution_id: "test".to_string(),
+            requester_account: Pubkey::new_unique(),
+            callback_exec: None,
+            proof: vec![0; 256],
+            execution_digest: vec![0; 32],
+            input_digest: vec![0; 32],
+            assumption_digest: vec![0; 32],
+            committed_outputs: vec![],
+            additional_accounts: vec![],
+            prior_executions: vec![],
+            exit_code_system: 0,
+            exit_code_user: 0,
+            execution_expiry,
+            prover_version: ProverVersion::DEFAULT,
+        }))
+    }
+
+    fn sent_transaction(
+        tracker: &SignatureTracker,
+        resubmit: Option<Arc<Submission>>,
+    ) -> Signature {
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::new_unique()],
+            ..Default::default()
+        };
+        tracker.sent(tx, 10, resubmit)
+    }
+
+    #[tokio::test]
+    async fn rebuilds_expired_proof_submission() {
+        let tracker = SignatureTracker::default();
+        let broadcaster = RecordingBroadcaster::default();
+        let sig = sent_transaction(&tracker, Some(submission(2000)));
+
+        tracker
+            .rebroadcast(&mock_builder(), &broadcaster, 20, 30)
+            .await;
+
+        let sent = broadcaster.sent.lock().unwrap().clone();
+        assert_eq!(sent.len(), 1);
+        assert_ne!(sent[0], sig);
+        // the mock rpc reports 1234 as the last valid block height of the new blockhash
+        assert_eq!(
+            tracker.status(&sig),
+            Some(TransactionStatus::Pending { expiry: 1234 })
+        );
+        tracker.clear(&sig);
+        assert_eq!(tracker.status(&sent[0]), None);
+    }
+
+    #[tokio::test]
+    async fn expires_transactions_that_cannot_be_rebuilt() {
+        let tracker = SignatureTracker::default();
+        let broadcaster = RecordingBroadcaster::default();
+        let claim = sent_transaction(&tracker, None);
+        let proof = sent_transaction(&tracker, Some(submission(15)));
+        let restored = Signature::new_unique();
+        tracker.track(restored, 10);
+
+        tracker
+            .rebroadcast(&mock_builder(), &broadcaster, 20, 30)
+            .await;
+        tracker.expire_untracked(20);
+
+        assert!(broadcaster.sent.lock().unwrap().is_empty());
+        assert_eq!(tracker.status(&claim), None);
+        assert_eq!(tracker.status(&proof), None);
+        assert_eq!(tracker.status(&restored), None);
+    }
+
+    #[tokio::test]
+    async fn rebroadcasts_only_after_interval() {
+        let tracker = SignatureTracker::default();
+        let broadcaster = RecordingBroadcaster::default();
+        let sig = sent_transaction(&tracker, None);
+
+        tracker
+            .rebroadcast(&mock_builder(), &broadcaster, 5, 8)
+            .await;
+        assert!(broadcaster.sent.lock().unwrap().is_empty());
+
+        tracker.pending.get_mut(&sig).unwrap().last_sent -= REBROADCAST_INTERVAL;
+        tracker
+            .rebroadcast(&mock_builder(), &broadcaster, 5, 8)
+            .await;
+        assert_eq!(*broadcaster.sent.lock().unwrap(), vec![sig]);
+    }
+
+    #[tokio::test]
+    async fn expires_submissions_of_executions_past_their_slot() {
+        let tracker = SignatureTracker::default();
+        let broadcaster = RecordingBroadcaster::default();
+        // the block height trails the slot, the execution expired at slot 2000 while the block
+        // height is still below it
+        let sig = sent_transaction(&tracker, Some(submission(2000)));
+
+        tracker
+            .rebroadcast(&mock_builder(), &broadcaster, 1500, 2100)
+            .await;
+
+        assert!(broadcaster.sent.lock().unwrap().is_empty());
+        assert_eq!(tracker.status(&sig), None);
+    }
+}
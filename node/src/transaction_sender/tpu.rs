@@ -0,0 +1,303 @@
+//! Sends transactions straight to the TPU ports of the current and upcoming leaders over QUIC.
+//! Claims are a race between provers, skipping the rpc's forwarding gets them to the leader first.
+//! Everything besides sending, blockhashes, accounts and signature statuses, still goes through
+//! the rpc.
+use {
+    super::{
+        BatchSubmission, Broadcaster, RpcBroadcaster, RpcTransactionSender, TransactionSender,
+        TransactionStatus,
+    },
+    crate::{config::TransactionFeeConfig, signer::NodeSigner, types::ProgramExec},
+    anyhow::{anyhow, Result},
+    async_trait::async_trait,
+    bonsol_interface::{bonsol_schema::ProverVersion, claim_state::ClaimStateV1},
+    solana_client::{nonblocking::tpu_client::TpuClient, tpu_client::TpuClientConfig},
+    solana_quic_client::{QuicConfig, QuicConnectionManager, QuicPool},
+    solana_sdk::{
+        account::Account, instruction::AccountMeta, pubkey::Pubkey, signature::Signature,
+        transaction::VersionedTransaction,
+    },
+    std::sync::Arc,
+    tracing::warn,
+};
+
+/// Delivers serialized transactions to the leaders, implemented by the QUIC `TpuClient`
+#[async_trait]
+pub trait LeaderClient: Send + Sync {
+    async fn send_to_leaders(&self, wire_transaction: Vec<u8>) -> Result<()>;
+}
+
+#[async_trait]
+impl LeaderClient for TpuClient<QuicPool, QuicConnectionManager, QuicConfig> {
+    async fn send_to_leaders(&self, wire_transaction: Vec<u8>) -> Result<()> {
+        self.try_send_wire_transaction(wire_transaction)
+            .await
+            .map_err(|e| anyhow!("Failed to send transaction to the leaders: {:?}", e))
+    }
+}
+
+/// Sends to the leaders and falls back to the rpc when no leader could be reached
+struct TpuBroadcaster {
+    leaders: Box<dyn LeaderClient>,
+    fallback: RpcBroadcaster,
+}
+
+#[async_trait]
+impl Broadcaster for TpuBroadcaster {
+    async fn broadcast(&self, tx: &VersionedTransaction) -> Result<()> {
+        let wire_transaction = bincode::serialize(tx)?;
+        if let Err(e) = self.leaders.send_to_leaders(wire_transaction).await {
+            warn!(
+                "Falling back to rpc for transaction {}: {:?}",
+                tx.signatures[0], e
+            );
+            self.fallback.broadcast(tx).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds, tracks and rebroadcasts transactions like the `RpcTransactionSender`, but delivers
+/// them to the leaders
+pub struct TpuTransactionSender {
+    rpc: RpcTransactionSender,
+}
+
+impl TpuTransactionSender {
+    /// Connects to the leader schedule through `websocket_url`, transactions are sent to the
+    /// leaders of the next `fanout_slots` slots
+    pub async fn new(
+        rpc_url: String,
+        websocket_url: String,
+        fanout_slots: u64,
+        bonsol_program: Pubkey,
+        signer: NodeSigner,
+        fee_config: TransactionFeeConfig,
+    ) -> Result<Self> {
+        let rpc = RpcTransactionSender::new(rpc_url, bonsol_program, signer, fee_config);
+        let tpu_client = TpuClient::new(
+            "bonsol-node",
+            rpc.rpc_client.clone(),
+            &websocket_url,
+            TpuClientConfig { fanout_slots },
+        )
+        .await
+        .map_err(|e| anyhow!("Failed to create tpu client: {:?}", e))?;
+        Ok(Self::with_leader_client(rpc, Box::new(tpu_client)))
+    }
+
+    pub fn with_leader_client(rpc: RpcTransactionSender, leaders: Box<dyn LeaderClient>) -> Self {
+        let broadcaster = TpuBroadcaster {
+            leaders,
+            fallback: RpcBroadcaster::new(rpc.rpc_client.clone()),
+        };
+        Self {
+            rpc: rpc.with_broadcaster(Arc::new(broadcaster)),
+        }
+    }
+}
+
+#[async_trait]
+impl TransactionSender for TpuTransactionSender {
+    fn start(&mut self) {
+        self.rpc.start();
+    }
+
+    fn signer(&self) -> NodeSigner {
+        self.rpc.signer()
+    }
+
+    async fn claim(
+        &self,
+        execution_id: &str,
+        requester: Pubkey,
+        execution_account: Pubkey,
+        block_commitment: u64,
+        tip: u64,
+        claimer_allow_list: Option<Pubkey>,
+    ) -> Result<Signature> {
+        self.rpc
+            .claim(
+                execution_id,
+                requester,
+                execution_account,
+                block_commitment,
+                tip,
+                claimer_allow_list,
+            )
+            .await
+    }
+
+    async fn submit_proof(
+        &self,
+        execution_id: &str,
+        requester_account: Pubkey,
+        callback_exec: Option<ProgramExec>,
+        proof: &[u8],
+        execution_digest: &[u8],
+        input_digest: &[u8],
+        assumption_digest: &[u8],
+        committed_outputs: &[u8],
+        additional_accounts: Vec<AccountMeta>,
+        prior_executions: Vec<Pubkey>,
+        exit_code_system: u32,
+        exit_code_user: u32,
+        execution_expiry: u64,
+        prover_version: ProverVersion,
+    ) -> Result<Signature> {
+        self.rpc
+            .submit_proof(
+                execution_id,
+                requester_account,
+                callback_exec,
+                proof,
+                execution_digest,
+                input_digest,
+                assumption_digest,
+                committed_outputs,
+                additional_accounts,
+                prior_executions,
+                exit_code_system,
+                exit_code_user,
+                execution_expiry,
+                prover_version,
+            )
+            .await
+    }
+
+    async fn submit_proof_batch(&self, batch: BatchSubmission) -> Result<Signature> {
+        self.rpc.submit_proof_batch(batch).await
+    }
+
+    async fn bid(
+        &self,
+        execution_id: &str,
+        requester: Pubkey,
+        price: u64,
+        completion_slot: u64,
+        claimer_allow_list: Option<Pubkey>,
+    ) -> Result<Signature> {
+        self.rpc
+            .bid(
+                execution_id,
+                requester,
+                price,
+                completion_slot,
+                claimer_allow_list,
+            )
+            .await
+    }
+
+    async fn expire(&self, execution_id: &str, requester: Pubkey) -> Result<Signature> {
+        self.rpc.expire(execution_id, requester).await
+    }
+
+    async fn get_current_block(&self) -> Result<u64> {
+        self.rpc.get_current_block().await
+    }
+
+    async fn get_current_slot(&self) -> Result<u64> {
+        self.rpc.get_current_slot().await
+    }
+
+    fn get_signature_status(&self, sig: &Signature) -> Option<TransactionStatus> {
+        self.rpc.get_signature_status(sig)
+    }
+
+    fn clear_signature_status(&self, sig: &Signature) {
+        self.rpc.clear_signature_status(sig)
+    }
+
+    fn track_signature(&self, sig: Signature, expiry: u64) {
+        self.rpc.track_signature(sig, expiry)
+    }
+
+    async fn get_deployment_account(&self, image_id: &str) -> Result<Account> {
+        self.rpc.get_deployment_account(image_id).await
+    }
+
+    async fn get_claim_state(
+        &self,
+        requester: Pubkey,
+        execution_id: &str,
+    ) -> Result<Option<ClaimStateV1>> {
+        self.rpc.get_claim_state(requester, execution_id).await
+    }
+
+    async fn get_execution_account(
+        &self,
+        requester: Pubkey,
+        execution_id: &str,
+    ) -> Result<Option<Account>> {
+        self.rpc
+            .get_execution_account(requester, execution_id)
+            .await
+    }
+
+    async fn get_account(&self, address: Pubkey) -> Result<Option<Account>> {
+        self.rpc.get_account(address).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*, crate::config::ProverNodeConfig,
+        solana_rpc_client::nonblocking::rpc_client::RpcClient, solana_sdk::signature::Keypair,
+        std::sync::Mutex,
+    };
+
+    /// Stands in for the leaders' TPU, recording what it receives
+    #[derive(Default)]
+    struct RecordingLeaders {
+        received: Arc<Mutex<Vec<Vec<u8>>>>,
+    }
+
+    #[async_trait]
+    impl LeaderClient for RecordingLeaders {
+        async fn send_to_leaders(&self, wire_transaction: Vec<u8>) -> Result<()> {
+            self.received.lock().unwrap().push(wire_transaction);
+            Ok(())
+        }
+    }
+
+    fn mock_rpc_sender() -> RpcTransactionSender {
+        RpcTransactionSender::with_rpc_client(
+            Arc::new(RpcClient::new_mock("succeeds".to_string())),
+            Pubkey::new_unique(),
+            Arc::new(Keypair::new()),
+            ProverNodeConfig::default().transaction_fee_config,
+        )
+    }
+
+    #[tokio::test]
+    async fn claim_is_sent_to_leaders_and_tracked() {
+        let leaders = RecordingLeaders::default();
+        let received = leaders.received.clone();
+        let sender = TpuTransactionSender::with_leader_client(mock_rpc_sender(), Box::new(leaders));
+
+        let sig = sender
+            .claim(
+                "test",
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                100,
+                1000,
+                None,
+            )
+            .await
+            .expect("failed to send claim");
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        let tx: VersionedTransaction =
+            bincode::deserialize(&received[0]).expect("invalid wire transaction");
+        assert_eq!(tx.signatures[0], sig);
+        assert!(tx.verify_with_results().iter().all(|ok| *ok));
+        // the mock rpc reports 1234 as the last valid block height
+        assert_eq!(
+            sender.get_signature_status(&sig),
+            Some(TransactionStatus::Pending { expiry: 1234 })
+        );
+    }
+}
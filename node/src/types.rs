@@ -1,4 +1,5 @@
 use {
+    serde::{Deserialize, Serialize},
     solana_sdk::{instruction::CompiledInstruction, message::AccountKeys, pubkey::Pubkey},
     solana_transaction_status::InnerInstruction,
 };
@@ -102,7 +103,7 @@ pub enum CallbackStatus {
     Failure,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProgramExec {
     pub program_id: Pubkey,
     pub instruction_prefix: Vec<u8>,
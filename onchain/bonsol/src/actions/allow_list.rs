@@ -0,0 +1,115 @@
+use bonsol_interface::{
+    allow_list::MAX_ALLOW_LIST_ID_LEN,
+    bonsol_schema::{AllowListV1, ChannelInstruction, ChannelInstructionIxType},
+    util::allow_list_address_seeds,
+};
+
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, system_program};
+
+use crate::{assertions::*, error::ChannelError, utilities::*};
+
+pub struct AllowListAccounts<'a, 'b> {
+    pub authority: &'a AccountInfo<'a>,
+    pub allow_list: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub id: &'b str,
+    pub allow_list_bump: u8,
+}
+
+impl<'a, 'b> AllowListAccounts<'a, 'b> {
+    fn from_instruction(
+        accounts: &'a [AccountInfo<'a>],
+        data: &'b AllowListV1<'b>,
+    ) -> Result<Self, ChannelError> {
+        let id = data
+            .id()
+            .filter(|id| !id.is_empty() && id.len() <= MAX_ALLOW_LIST_ID_LEN)
+            .ok_or(ChannelError::InvalidInstruction)?;
+        let authority = data
+            .authority()
+            .map(|b| b.bytes())
+            .ok_or(ChannelError::InvalidInstruction)?;
+        let authority_account = &accounts[0];
+        check_writable_signer(authority_account, ChannelError::InvalidPayerAccount)?;
+        check_bytes_match(
+            authority_account.key.as_ref(),
+            authority,
+            ChannelError::InvalidPayerAccount,
+        )?;
+        let allow_list = &accounts[1];
+        check_writeable(allow_list, ChannelError::InvalidAllowListAccount)?;
+        let allow_list_bump = check_pda(
+            &allow_list_address_seeds(authority_account.key, id),
+            allow_list.key,
+            ChannelError::InvalidAllowListAccount,
+        )?;
+        let system_program = &accounts[2];
+        check_key_match(
+            system_program,
+            &system_program::ID,
+            ChannelError::InvalidInstruction,
+        )?;
+        Ok(AllowListAccounts {
+            authority: authority_account,
+            allow_list,
+            system_program,
+            id,
+            allow_list_bump,
+        })
+    }
+
+    fn exists(&self) -> bool {
+        !(self.allow_list.data_len() == 0 && self.allow_list.owner == &system_program::ID)
+    }
+}
+
+/// Handles `SetAllowListV1` and `CloseAllowListV1`, the allow list lives at an address derived
+/// from the signing authority so only it can change the list
+pub fn process_allow_list_v1<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    ix: ChannelInstruction<'a>,
+) -> Result<(), ProgramError> {
+    let al = ix
+        .allow_list_v1_nested_flatbuffer()
+        .ok_or(ChannelError::InvalidInstruction)?;
+    let aa = AllowListAccounts::from_instruction(accounts, &al)?;
+    match ix.ix_type() {
+        ChannelInstructionIxType::SetAllowListV1 => {
+            // the allow list is stored as the instruction sent it
+            let bytes = ix.allow_list_v1().unwrap().bytes();
+            if aa.exists() {
+                msg!("update allow list {}", aa.id);
+                check_owner(
+                    aa.allow_list,
+                    &crate::ID,
+                    ChannelError::InvalidAllowListAccount,
+                )?;
+                resave_structure(aa.allow_list, bytes, aa.authority)
+            } else {
+                msg!("create allow list {}", aa.id);
+                let bump = [aa.allow_list_bump];
+                let mut seeds = allow_list_address_seeds(aa.authority.key, aa.id);
+                seeds.push(&bump);
+                save_structure(
+                    aa.allow_list,
+                    &seeds,
+                    bytes,
+                    aa.authority,
+                    aa.system_program,
+                    None,
+                )
+                .map_err(Into::into)
+            }
+        }
+        ChannelInstructionIxType::CloseAllowListV1 => {
+            msg!("close allow list {}", aa.id);
+            check_owner(
+                aa.allow_list,
+                &crate::ID,
+                ChannelError::InvalidAllowListAccount,
+            )?;
+            close_program_account(aa.allow_list, aa.authority)
+        }
+        _ => Err(ChannelError::InvalidInstruction.into()),
+    }
+}
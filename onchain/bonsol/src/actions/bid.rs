@@ -0,0 +1,129 @@
+use bonsol_interface::{
+    bid_state::{BidStateV1, PlacedBid},
+    bonsol_schema::{root_as_execution_request_v1, BidV1, ChannelInstruction},
+    util::{execution_address_seeds, execution_bids_address_seeds},
+};
+
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, msg, program_error::ProgramError, sysvar::Sysvar,
+};
+
+use crate::{
+    allow_list::check_claimer_allowed, assertions::*, auction::*, error::ChannelError,
+    reputation::*,
+};
+
+pub struct BidAccounts<'a, 'b> {
+    pub exec: &'a AccountInfo<'a>,
+    pub requester: &'a AccountInfo<'a>,
+    pub bids: &'a AccountInfo<'a>,
+    pub bidder: &'a AccountInfo<'a>,
+    pub bidder_prover: &'a AccountInfo<'a>,
+    pub execution_id: &'b str,
+    pub bid: PlacedBid,
+}
+
+impl<'a, 'b> BidAccounts<'a, 'b> {
+    fn from_instruction(
+        accounts: &'a [AccountInfo<'a>],
+        data: &'b BidV1<'b>,
+        current_block: u64,
+    ) -> Result<Self, ChannelError> {
+        let execution_id = data
+            .execution_id()
+            .ok_or(ChannelError::InvalidInstruction)?;
+        let ba = BidAccounts {
+            exec: &accounts[0],
+            requester: &accounts[1],
+            bids: &accounts[2],
+            bidder: &accounts[3],
+            bidder_prover: &accounts[4],
+            execution_id,
+            bid: PlacedBid::new(
+                accounts[3].key,
+                data.price(),
+                data.completion_slot(),
+                current_block,
+            ),
+        };
+        if !ba.bidder.is_signer {
+            return Err(ChannelError::InvalidClaimerAccount);
+        }
+        check_writeable(ba.bids, ChannelError::InvalidBidAccount)?;
+        check_owner(
+            ba.exec,
+            &crate::ID,
+            ChannelError::InvalidExecutionAccountOwner,
+        )?;
+        check_pda(
+            &execution_address_seeds(ba.requester.key, execution_id.as_bytes()),
+            ba.exec.key,
+            ChannelError::InvalidExecutionAccount,
+        )?;
+        check_pda(
+            &execution_bids_address_seeds(ba.exec.key.as_ref()),
+            ba.bids.key,
+            ChannelError::InvalidBidAccount,
+        )?;
+        let exec_data = ba
+            .exec
+            .try_borrow_data()
+            .map_err(|_| ChannelError::CannotBorrowData)?;
+        // settled executions only keep their exit code
+        let execution_request = root_as_execution_request_v1(&exec_data)
+            .map_err(|_| ChannelError::InvalidExecutionAccountData)?;
+        if execution_request.execution_id() != Some(execution_id) {
+            return Err(ChannelError::InvalidExecutionId);
+        }
+        if !auctioned(&execution_request) {
+            return Err(ChannelError::InvalidAuction);
+        }
+        // the winner is paid its bid out of the tip
+        if ba.bid.price > execution_request.tip()
+            || ba.bid.completion_slot > execution_request.max_block_height()
+        {
+            return Err(ChannelError::InvalidBid);
+        }
+        let registration = load_prover(ba.bidder_prover, ba.bidder.key)?;
+        if !eligible(
+            registration.as_ref(),
+            execution_request.min_reputation(),
+            execution_request.min_proofs(),
+        ) {
+            return Err(ChannelError::InsufficientReputation);
+        }
+        // the allow list the request references follows the registry account
+        check_claimer_allowed(&execution_request, &accounts[5..], ba.bidder.key)?;
+        Ok(ba)
+    }
+}
+
+/// Places a bid for the claim on an auctioned execution while its auction is open. A prover
+/// bidding again replaces its earlier bid. Only provers that could claim the execution can bid,
+/// and a bid commits to a proof after the auction and before the max block height.
+pub fn process_bid_v1<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    ix: ChannelInstruction<'a>,
+) -> Result<(), ProgramError> {
+    let bd = ix
+        .bid_v1_nested_flatbuffer()
+        .ok_or(ChannelError::InvalidInstruction)?;
+    let current_block = Clock::get()?.slot;
+    let ba = BidAccounts::from_instruction(accounts, &bd, current_block)?;
+    let mut bids = load_bids(ba.bids)?;
+    if current_block > bids.auction_end {
+        return Err(ChannelError::AuctionClosed.into());
+    }
+    if ba.bid.completion_slot <= bids.auction_end {
+        return Err(ChannelError::InvalidBid.into());
+    }
+    bids.place(ba.bid).map_err(|_| ChannelError::InvalidBid)?;
+    BidStateV1::save_bids(&bids, ba.bids);
+    msg!(
+        "{} Bid {} by {}",
+        ba.execution_id,
+        ba.bid.price,
+        ba.bidder.key
+    );
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use bonsol_interface::{
+    bonsol_schema::{root_as_execution_request_v1, CancelV1, ChannelInstruction, ExitCode},
+    claim_state::ClaimStateV1,
+    util::{execution_address_seeds, execution_claim_address_seeds},
+};
+
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, msg, program_error::ProgramError, system_program,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    assertions::*,
+    auction::{auctioned, close_bids},
+    error::ChannelError,
+    reputation::record_missed_commitment,
+    stake::*,
+    utilities::*,
+};
+
+pub struct CancelAccounts<'a, 'b> {
+    pub requester: &'a AccountInfo<'a>,
+    pub exec: &'a AccountInfo<'a>,
+    pub exec_claim: &'a AccountInfo<'a>,
+    pub execution_id: &'b str,
+    pub auctioned: bool,
+}
+
+impl<'a, 'b> CancelAccounts<'a, 'b> {
+    fn from_instruction(
+        accounts: &'a [AccountInfo<'a>],
+        data: &'b CancelV1<'b>,
+    ) -> Result<Self, ChannelError> {
+        let execution_id = data
+            .execution_id()
+            .ok_or(ChannelError::InvalidInstruction)?;
+        let mut ca = CancelAccounts {
+            requester: &accounts[0],
+            exec: &accounts[1],
+            exec_claim: &accounts[2],
+            execution_id,
+            auctioned: false,
+        };
+        check_writable_signer(ca.requester, ChannelError::InvalidRequesterAccount)?;
+        check_writeable(ca.exec, ChannelError::InvalidExecutionAccount)?;
+        check_owner(
+            ca.exec,
+            &crate::ID,
+            ChannelError::InvalidExecutionAccountOwner,
+        )?;
+        check_pda(
+            &execution_address_seeds(ca.requester.key, execution_id.as_bytes()),
+            ca.exec.key,
+            ChannelError::InvalidExecutionAccount,
+        )?;
+        check_writeable(ca.exec_claim, ChannelError::InvalidClaimAccount)?;
+        check_pda(
+            &execution_claim_address_seeds(ca.exec.key.as_ref()),
+            ca.exec_claim.key,
+            ChannelError::InvalidClaimAccount,
+        )?;
+        let exec_data = ca
+            .exec
+            .try_borrow_data()
+            .map_err(|_| ChannelError::CannotBorrowData)?;
+        // settled executions only keep their exit code
+        let execution_request = root_as_execution_request_v1(&exec_data)
+            .map_err(|_| ChannelError::InvalidExecutionAccountData)?;
+        if execution_request.execution_id() != Some(execution_id) {
+            return Err(ChannelError::InvalidExecutionId);
+        }
+        ca.auctioned = auctioned(&execution_request);
+        Ok(ca)
+    }
+}
+
+/// Closes an execution request that no prover is working on, its rent and tip go back to the
+/// requester. A claim whose block commitment has passed no longer protects the execution, the
+/// claimer's stake is forfeited to the requester and the missed commitment is recorded in its
+/// registration. The bids of an auctioned execution are closed to the requester.
+pub fn process_cancel_v1<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    ix: ChannelInstruction<'a>,
+) -> Result<(), ProgramError> {
+    let cl = ix
+        .cancel_v1_nested_flatbuffer()
+        .ok_or(ChannelError::InvalidInstruction)?;
+    let ca = CancelAccounts::from_instruction(accounts, &cl)?;
+    if ca.exec_claim.owner != &system_program::ID {
+        check_owner(ca.exec_claim, &crate::ID, ChannelError::InvalidClaimAccount)?;
+        let claim = {
+            let data = ca.exec_claim.try_borrow_data()?;
+            ClaimStateV1::load_claim_owned(&data).map_err(|_| ChannelError::InvalidClaimAccount)?
+        };
+        if Clock::get()?.slot <= claim.block_commitment {
+            return Err(ChannelError::ActiveClaimExists.into());
+        }
+        // the registry account of the claimer follows the claim account
+        record_missed_commitment(accounts, 3, &claim.claimer)?;
+        slash_stake(ca.exec_claim, ca.requester)?;
+    }
+    if ca.auctioned {
+        close_bids(&accounts[3..], ca.exec.key, ca.requester)?;
+    }
+    msg!("{} Cancelled", ca.execution_id);
+    cleanup_execution_account(ca.exec, ca.requester, ExitCode::Cancelled as u8)
+}
@@ -1,6 +1,7 @@
 use bonsol_interface::{
     bonsol_schema::{root_as_execution_request_v1, ChannelInstruction, ClaimV1},
     claim_state::ClaimStateV1,
+    prover_state::ProverV1,
     util::{execution_address_seeds, execution_claim_address_seeds},
 };
 
@@ -8,7 +9,10 @@ use solana_program::{
     account_info::AccountInfo, msg, program_error::ProgramError, system_program, sysvar::Sysvar,
 };
 
-use crate::{assertions::*, error::ChannelError, utilities::*};
+use crate::{
+    allow_list::check_claimer_allowed, assertions::*, auction::*, error::ChannelError,
+    reputation::*, stake::*, utilities::*,
+};
 
 pub struct ClaimAccounts<'a, 'b> {
     pub exec: &'a AccountInfo<'a>,
@@ -17,11 +21,13 @@ pub struct ClaimAccounts<'a, 'b> {
     pub claimer: &'a AccountInfo<'a>,
     pub payer: &'a AccountInfo<'a>,
     pub system_program: &'a AccountInfo<'a>,
+    pub claimer_prover: &'a AccountInfo<'a>,
+    pub bids: Option<&'a AccountInfo<'a>>,
     pub execution_id: &'b str,
     pub block_commitment: u64,
     pub existing_claim: bool,
     pub stake: u64,
-    pub expired: bool,
+    pub price: u64,
 }
 
 impl<'a, 'b> ClaimAccounts<'a, 'b> {
@@ -38,16 +44,19 @@ impl<'a, 'b> ClaimAccounts<'a, 'b> {
                 claimer: &accounts[3],
                 payer: &accounts[4],
                 system_program: &accounts[5],
+                claimer_prover: &accounts[6],
+                bids: None,
                 execution_id: executionid,
                 block_commitment: data.block_commitment(),
                 existing_claim: false,
                 stake: 0,
-                expired: false,
+                price: 0,
             };
             check_writable_signer(ca.payer, ChannelError::InvalidPayerAccount)?;
             check_writable_signer(ca.claimer, ChannelError::InvalidClaimerAccount)?;
             check_writeable(ca.exec_claim, ChannelError::InvalidClaimAccount)?;
             check_writeable(ca.exec, ChannelError::InvalidExecutionAccount)?;
+            check_writeable(ca.requester, ChannelError::InvalidRequesterAccount)?;
             check_owner(
                 ca.exec,
                 &crate::ID,
@@ -71,15 +80,41 @@ impl<'a, 'b> ClaimAccounts<'a, 'b> {
             if expected_eid != executionid {
                 return Err(ChannelError::InvalidExecutionId);
             }
-            let tip = execution_request.tip();
-            if ca.claimer.lamports() < tip {
-                return Err(ChannelError::InsufficientStake);
-            }
+            // expired executions are closed with ExpireV1
             if execution_request.max_block_height() < current_block {
-                ca.expired = true;
+                return Err(ChannelError::ExecutionExpired);
+            }
+            let registration = load_prover(ca.claimer_prover, ca.claimer.key)?;
+            if !eligible(
+                registration.as_ref(),
+                execution_request.min_reputation(),
+                execution_request.min_proofs(),
+            ) {
+                return Err(ChannelError::InsufficientReputation);
+            }
+            // the allow list the request references follows the registry accounts
+            check_claimer_allowed(&execution_request, &accounts[7..], ca.claimer.key)?;
+            ca.existing_claim =
+                !(ca.exec_claim.data_len() == 0 && ca.exec_claim.owner == &system_program::ID);
+            ca.price = execution_request.tip();
+            // the auction decides the first claim, a lapsed claim is taken over for the tip
+            if auctioned(&execution_request) && !ca.existing_claim {
+                let bids = find_bids_account(&accounts[7..], ca.exec.key)
+                    .ok_or(ChannelError::InvalidBidAccount)?;
+                check_writeable(bids, ChannelError::InvalidBidAccount)?;
+                ca.price = auction_price(
+                    &load_bids(bids)?,
+                    ca.claimer.key,
+                    ca.block_commitment,
+                    current_block,
+                    ca.price,
+                )?;
+                ca.bids = Some(bids);
             }
-            // make this more dynamic
-            ca.stake = tip / 2;
+            if ca.claimer.lamports() < ca.price {
+                return Err(ChannelError::InsufficientStake);
+            }
+            ca.stake = claim_stake(ca.price);
             let mut exec_claim_seeds = execution_claim_address_seeds(ca.exec.key.as_ref());
             let bump = [check_pda(
                 &exec_claim_seeds,
@@ -87,7 +122,7 @@ impl<'a, 'b> ClaimAccounts<'a, 'b> {
                 ChannelError::InvalidClaimAccount,
             )?];
             exec_claim_seeds.push(&bump);
-            if ca.exec_claim.data_len() == 0 && ca.exec_claim.owner == &system_program::ID {
+            if !ca.existing_claim {
                 create_program_account(
                     ca.exec_claim,
                     &exec_claim_seeds,
@@ -98,7 +133,6 @@ impl<'a, 'b> ClaimAccounts<'a, 'b> {
                 )?;
             } else {
                 check_owner(ca.exec_claim, &crate::ID, ChannelError::InvalidClaimAccount)?;
-                ca.existing_claim = true;
             }
             return Ok(ca);
         }
@@ -118,29 +152,47 @@ pub fn process_claim_v1<'a>(
     let cl = cl.unwrap();
     let current_block = solana_program::clock::Clock::get()?.slot;
     let ca = ClaimAccounts::from_instruction(accounts, &cl, current_block)?;
-    if ca.expired {
-        cleanup_execution_account(ca.exec, ca.claimer, ChannelError::ExecutionExpired as u8)?;
-        msg!("Execution expired");
-        return Ok(());
-    }
     if ca.existing_claim {
-        let mut data = ca.exec_claim.try_borrow_mut_data()?;
-        let current_claim =
-            ClaimStateV1::load_claim(*data).map_err(|_| ChannelError::InvalidClaimAccount)?;
-        transfer_owned(ca.exec_claim, ca.claimer, ca.stake)?;
-        if current_block > current_claim.block_commitment {
-            let claim =
-                ClaimStateV1::from_claim_ix(ca.claimer.key, current_block, ca.block_commitment);
-            drop(data);
-            ClaimStateV1::save_claim(&claim, ca.exec_claim);
-            transfer_unowned(ca.claimer, ca.exec_claim, ca.stake)
-        } else {
-            Err(ChannelError::ActiveClaimExists.into())
+        let current_claim = {
+            let data = ca.exec_claim.try_borrow_data()?;
+            ClaimStateV1::load_claim_owned(&data).map_err(|_| ChannelError::InvalidClaimAccount)?
+        };
+        if current_block <= current_claim.block_commitment {
+            return Err(ChannelError::ActiveClaimExists.into());
         }
+        msg!(
+            "{} Claim lapsed at {}",
+            ca.execution_id,
+            current_claim.block_commitment
+        );
+        // the registry account of the lapsed claimer follows the claimer's
+        record_missed_commitment(accounts, 7, &current_claim.claimer)?;
+        // the new stake is escrowed before the lapsed one is paid out, the runtime rejects a
+        // transfer while the requester's balance is changed
+        let lapsed_stake = held_stake(ca.exec_claim);
+        transfer_unowned(ca.claimer, ca.exec_claim, ca.stake)?;
+        split_lapsed_stake(ca.exec_claim, lapsed_stake, ca.requester, ca.claimer)?;
+        let claim = ClaimStateV1::from_claim_ix(
+            ca.claimer.key,
+            current_block,
+            ca.block_commitment,
+            ca.price,
+        );
+        ClaimStateV1::save_claim(&claim, ca.exec_claim);
     } else {
-        let claim = ClaimStateV1::from_claim_ix(ca.claimer.key, current_block, ca.block_commitment);
+        let claim = ClaimStateV1::from_claim_ix(
+            ca.claimer.key,
+            current_block,
+            ca.block_commitment,
+            ca.price,
+        );
         transfer_unowned(ca.claimer, ca.exec_claim, ca.stake)?;
         ClaimStateV1::save_claim(&claim, ca.exec_claim);
-        Ok(())
+        // the auction is decided, its bids account goes back to the requester
+        if let Some(bids) = ca.bids {
+            close_program_account(bids, ca.requester)?;
+        }
     }
+    record(ca.claimer_prover, ca.claimer.key, ProverV1::record_claim)?;
+    Ok(())
 }
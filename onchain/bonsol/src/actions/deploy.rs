@@ -5,7 +5,7 @@ use bonsol_interface::{
     util::{deployment_address_seeds, img_id_hash},
 };
 
-use solana_program::{account_info::AccountInfo, msg, system_program};
+use solana_program::{account_info::AccountInfo, hash::HASH_BYTES, msg, system_program};
 
 pub struct DeployAccounts<'a, 'b> {
     pub deployer: &'a AccountInfo<'a>,
@@ -32,7 +32,7 @@ impl<'a, 'b> DeployAccounts<'a, 'b> {
                 extra_accounts: &accounts[4..],
                 deployment_bump: None,
                 image_id: imageid,
-                image_checksum: &[],
+                image_checksum: check_image_checksum(data.image_checksum().map(|c| c.bytes()))?,
             };
             let owner = data
                 .owner()
@@ -70,6 +70,15 @@ impl<'a, 'b> DeployAccounts<'a, 'b> {
     }
 }
 
+/// Deployments must carry the sha256 of their elf so provers can check a download before loading it
+fn check_image_checksum(checksum: Option<&[u8]>) -> Result<&[u8], ChannelError> {
+    match checksum {
+        None => Err(ChannelError::MissingImageChecksum),
+        Some(checksum) if checksum.len() != HASH_BYTES => Err(ChannelError::InvalidImageChecksum),
+        Some(checksum) => Ok(checksum),
+    }
+}
+
 pub fn process_deploy_v1<'a>(
     accounts: &'a [AccountInfo<'a>],
     ix: ChannelInstruction<'a>,
@@ -96,3 +105,21 @@ pub fn process_deploy_v1<'a>(
         None,
     )
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn requires_a_sha256_image_checksum() {
+        assert_eq!(
+            check_image_checksum(None),
+            Err(ChannelError::MissingImageChecksum)
+        );
+        assert_eq!(
+            check_image_checksum(Some(&[1; 20][..])),
+            Err(ChannelError::InvalidImageChecksum)
+        );
+        assert_eq!(check_image_checksum(Some(&[1; 32][..])), Ok(&[1u8; 32][..]));
+    }
+}
@@ -1,11 +1,17 @@
-use crate::{assertions::*, error::ChannelError, utilities::*};
+use crate::{assertions::*, auction::auctioned, error::ChannelError, utilities::*};
 
 use bonsol_interface::{
-    bonsol_schema::{root_as_deploy_v1, ChannelInstruction, ExecutionRequestV1, InputType},
-    util::execution_address_seeds,
+    bid_state::BidStateV1,
+    bonsol_schema::{
+        parse_prior_execution, root_as_deploy_v1, ChannelInstruction, ExecutionRequestV1, InputType,
+    },
+    util::{execution_address_seeds, execution_bids_address_seeds},
 };
 
-use solana_program::{account_info::AccountInfo, bpf_loader_upgradeable, system_program};
+use solana_program::{
+    account_info::AccountInfo, bpf_loader_upgradeable, clock::Clock, program_error::ProgramError,
+    system_program, sysvar::Sysvar,
+};
 
 pub struct ExecuteAccounts<'a, 'b> {
     pub requester: &'a AccountInfo<'a>,
@@ -17,12 +23,15 @@ pub struct ExecuteAccounts<'a, 'b> {
     pub extra_accounts: &'a [AccountInfo<'a>],
     pub execution_id: &'b str,
     pub exec_bump: Option<u8>,
+    pub bids_bump: Option<u8>,
+    pub auction_end: u64,
 }
 
 impl<'a, 'b> ExecuteAccounts<'a, 'b> {
     fn from_instruction(
         accounts: &'a [AccountInfo<'a>],
         data: &'b ExecutionRequestV1<'b>,
+        current_block: u64,
     ) -> Result<Self, ChannelError> {
         if let Some(executionid) = data.execution_id() {
             let evec = executionid;
@@ -36,6 +45,8 @@ impl<'a, 'b> ExecuteAccounts<'a, 'b> {
                 extra_accounts: &accounts[6..],
                 execution_id: evec,
                 exec_bump: None,
+                bids_bump: None,
+                auction_end: 0,
             };
             check_writable_signer(ea.requester, ChannelError::InvalidRequesterAccount)?;
             check_writable_signer(ea.payer, ChannelError::InvalidPayerAccount)?;
@@ -57,6 +68,9 @@ impl<'a, 'b> ExecuteAccounts<'a, 'b> {
                 .map_err(|_| ChannelError::InvalidDeploymentAccount)?;
             let deploy = root_as_deploy_v1(deploy_data)
                 .map_err(|_| ChannelError::InvalidDeploymentAccount)?;
+            if deploy.deprecated() {
+                return Err(ChannelError::DeploymentDeprecated);
+            }
 
             let inputs = data.input().ok_or(ChannelError::InvalidInputs)?;
             let invalid_input_type_count = inputs
@@ -66,6 +80,26 @@ impl<'a, 'b> ExecuteAccounts<'a, 'b> {
             if invalid_input_type_count > 0 {
                 return Err(ChannelError::InvalidInputType);
             }
+            // the receipts of public proofs are resolved while prior executions stay assumed
+            // until the status checks them against their accounts, a request cannot mix both
+            let mut prior_executions = inputs
+                .iter()
+                .filter(|i| i.input_type() == InputType::PriorExecution)
+                .peekable();
+            if prior_executions.peek().is_some()
+                && inputs
+                    .iter()
+                    .any(|i| i.input_type() == InputType::PublicProof)
+            {
+                return Err(ChannelError::InvalidInputType);
+            }
+            if prior_executions.any(|i| {
+                i.data()
+                    .and_then(|d| parse_prior_execution(d.bytes()))
+                    .is_none()
+            }) {
+                return Err(ChannelError::InvalidInputs);
+            }
             // this should never be less than 1
             let required_input_size = deploy.inputs().map(|x| x.len()).unwrap_or(1);
             if inputs.len() != required_input_size {
@@ -105,6 +139,25 @@ impl<'a, 'b> ExecuteAccounts<'a, 'b> {
                 &system_program::ID,
                 ChannelError::InvalidInstruction,
             )?;
+            if auctioned(data) {
+                // the bids account of an auctioned request is the first extra account
+                let bids = ea
+                    .extra_accounts
+                    .first()
+                    .ok_or(ChannelError::InvalidBidAccount)?;
+                check_writeable(bids, ChannelError::InvalidBidAccount)?;
+                ensure_0(bids, ChannelError::InvalidBidAccount)?;
+                ea.bids_bump = Some(check_pda(
+                    &execution_bids_address_seeds(ea.exec.key.as_ref()),
+                    bids.key,
+                    ChannelError::InvalidBidAccount,
+                )?);
+                ea.auction_end = current_block.saturating_add(data.auction_slots());
+                // the winner needs blocks to claim and prove the execution
+                if ea.auction_end >= data.max_block_height() {
+                    return Err(ChannelError::InvalidAuction);
+                }
+            }
             return Ok(ea);
         }
 
@@ -115,16 +168,40 @@ impl<'a, 'b> ExecuteAccounts<'a, 'b> {
 pub fn process_execute_v1<'a>(
     accounts: &'a [AccountInfo<'a>],
     ix: ChannelInstruction<'a>,
-) -> Result<(), ChannelError> {
+) -> Result<(), ProgramError> {
     let er = ix.execute_v1_nested_flatbuffer();
     if er.is_none() {
-        return Err(ChannelError::InvalidInstruction);
+        return Err(ChannelError::InvalidInstruction.into());
     }
     let er = er.unwrap();
-    let ea = ExecuteAccounts::from_instruction(accounts, &er)?;
+    let current_block = Clock::get()?.slot;
+    let ea = ExecuteAccounts::from_instruction(accounts, &er, current_block)?;
     let b = [ea.exec_bump.unwrap()];
     let mut seeds = execution_address_seeds(ea.requester.key, ea.execution_id.as_bytes());
     seeds.push(&b);
     let bytes = ix.execute_v1().unwrap().bytes();
-    save_structure(ea.exec, &seeds, bytes, ea.payer, ea.system_program, None)
+    // the tip is escrowed in the execution account until it is paid out or refunded
+    save_structure(
+        ea.exec,
+        &seeds,
+        bytes,
+        ea.payer,
+        ea.system_program,
+        Some(er.tip()),
+    )?;
+    if let Some(bids_bump) = ea.bids_bump {
+        let b = [bids_bump];
+        let mut seeds = execution_bids_address_seeds(ea.exec.key.as_ref());
+        seeds.push(&b);
+        create_program_account(
+            &ea.extra_accounts[0],
+            &seeds,
+            std::mem::size_of::<BidStateV1>() as u64,
+            ea.payer,
+            ea.system_program,
+            None,
+        )?;
+        BidStateV1::save_bids(&BidStateV1::new(ea.auction_end), &ea.extra_accounts[0]);
+    }
+    Ok(())
 }
@@ -0,0 +1,134 @@
+use bonsol_interface::{
+    bonsol_schema::{root_as_execution_request_v1, ChannelInstruction, ExitCode, ExpireV1},
+    claim_state::ClaimStateV1,
+    util::{execution_address_seeds, execution_claim_address_seeds, EXPIRE_CRANK_FEE},
+};
+
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, msg, program_error::ProgramError, system_program,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    assertions::*,
+    auction::{auctioned, close_bids},
+    error::ChannelError,
+    reputation::record_missed_commitment,
+    stake::*,
+    utilities::*,
+};
+
+pub struct ExpireAccounts<'a, 'b> {
+    pub cranker: &'a AccountInfo<'a>,
+    pub requester: &'a AccountInfo<'a>,
+    pub exec: &'a AccountInfo<'a>,
+    pub exec_claim: &'a AccountInfo<'a>,
+    pub execution_id: &'b str,
+    pub auctioned: bool,
+    pub tip: u64,
+}
+
+impl<'a, 'b> ExpireAccounts<'a, 'b> {
+    fn from_instruction(
+        accounts: &'a [AccountInfo<'a>],
+        data: &'b ExpireV1<'b>,
+        current_block: u64,
+    ) -> Result<Self, ChannelError> {
+        let execution_id = data
+            .execution_id()
+            .ok_or(ChannelError::InvalidInstruction)?;
+        let mut ea = ExpireAccounts {
+            cranker: &accounts[0],
+            requester: &accounts[1],
+            exec: &accounts[2],
+            exec_claim: &accounts[3],
+            execution_id,
+            auctioned: false,
+            tip: 0,
+        };
+        check_writable_signer(ea.cranker, ChannelError::InvalidPayerAccount)?;
+        check_writeable(ea.requester, ChannelError::InvalidRequesterAccount)?;
+        check_writeable(ea.exec, ChannelError::InvalidExecutionAccount)?;
+        check_owner(
+            ea.exec,
+            &crate::ID,
+            ChannelError::InvalidExecutionAccountOwner,
+        )?;
+        check_pda(
+            &execution_address_seeds(ea.requester.key, execution_id.as_bytes()),
+            ea.exec.key,
+            ChannelError::InvalidExecutionAccount,
+        )?;
+        check_writeable(ea.exec_claim, ChannelError::InvalidClaimAccount)?;
+        check_pda(
+            &execution_claim_address_seeds(ea.exec.key.as_ref()),
+            ea.exec_claim.key,
+            ChannelError::InvalidClaimAccount,
+        )?;
+        let exec_data = ea
+            .exec
+            .try_borrow_data()
+            .map_err(|_| ChannelError::CannotBorrowData)?;
+        // settled executions only keep their exit code
+        let execution_request = root_as_execution_request_v1(&exec_data)
+            .map_err(|_| ChannelError::InvalidExecutionAccountData)?;
+        if execution_request.execution_id() != Some(execution_id) {
+            return Err(ChannelError::InvalidExecutionId);
+        }
+        ea.auctioned = auctioned(&execution_request);
+        if execution_request.max_block_height() >= current_block {
+            return Err(ChannelError::ExecutionNotExpired);
+        }
+        ea.tip = execution_request.tip();
+        Ok(ea)
+    }
+}
+
+/// Closes an execution request that was not settled before its max block height. Anyone can
+/// send it, the sender is paid a crank fee out of the tip. The prover that claimed the execution
+/// did not deliver, its stake is forfeited to the requester along with the rest of the tip and
+/// the rent, and the missed commitment is recorded in its registration. The bids of an auctioned
+/// execution are closed to the requester.
+pub fn process_expire_v1<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    ix: ChannelInstruction<'a>,
+) -> Result<(), ProgramError> {
+    let ex = ix
+        .expire_v1_nested_flatbuffer()
+        .ok_or(ChannelError::InvalidInstruction)?;
+    let current_block = Clock::get()?.slot;
+    let ea = ExpireAccounts::from_instruction(accounts, &ex, current_block)?;
+    if ea.exec_claim.owner != &system_program::ID {
+        check_owner(ea.exec_claim, &crate::ID, ChannelError::InvalidClaimAccount)?;
+        let claim = {
+            let data = ea.exec_claim.try_borrow_data()?;
+            ClaimStateV1::load_claim_owned(&data).map_err(|_| ChannelError::InvalidClaimAccount)?
+        };
+        // the registry account of the claimer follows the claim account
+        record_missed_commitment(accounts, 4, &claim.claimer)?;
+        slash_stake(ea.exec_claim, ea.requester)?;
+    }
+    if ea.auctioned {
+        close_bids(&accounts[4..], ea.exec.key, ea.requester)?;
+    }
+    transfer_owned(ea.exec, ea.cranker, crank_fee(ea.tip))?;
+    msg!("{} Expired", ea.execution_id);
+    cleanup_execution_account(ea.exec, ea.requester, ExitCode::Expired as u8)
+}
+
+/// The crank fee is capped by the tip so the rent of the execution account is never touched
+fn crank_fee(tip: u64) -> u64 {
+    EXPIRE_CRANK_FEE.min(tip)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn crank_fee_never_exceeds_the_tip() {
+        assert_eq!(crank_fee(1_000_000), EXPIRE_CRANK_FEE);
+        assert_eq!(crank_fee(100), 100);
+        assert_eq!(crank_fee(0), 0);
+    }
+}
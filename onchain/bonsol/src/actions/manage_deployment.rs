@@ -0,0 +1,110 @@
+use crate::{assertions::*, error::ChannelError, utilities::*};
+
+use bonsol_interface::{
+    bonsol_schema::{
+        root_as_deploy_v1, ChannelInstruction, ChannelInstructionIxType, DeployV1T,
+        ManageDeploymentV1,
+    },
+    util::{deployment_address_seeds, img_id_hash},
+};
+use flatbuffers::FlatBufferBuilder;
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, system_program};
+
+pub struct ManageDeploymentAccounts<'a, 'b> {
+    pub owner: &'a AccountInfo<'a>,
+    pub payer: &'a AccountInfo<'a>,
+    pub deployment: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub image_id: &'b str,
+}
+
+impl<'a, 'b> ManageDeploymentAccounts<'a, 'b> {
+    fn from_instruction(
+        accounts: &'a [AccountInfo<'a>],
+        data: &'b ManageDeploymentV1<'b>,
+    ) -> Result<(Self, DeployV1T), ChannelError> {
+        let image_id = data.image_id().ok_or(ChannelError::InvalidInstruction)?;
+        let ma = ManageDeploymentAccounts {
+            owner: &accounts[0],
+            payer: &accounts[1],
+            deployment: &accounts[2],
+            system_program: &accounts[3],
+            image_id,
+        };
+        check_writable_signer(ma.owner, ChannelError::InvalidDeployerAccount)?;
+        check_writable_signer(ma.payer, ChannelError::InvalidPayerAccount)?;
+        check_writeable(ma.deployment, ChannelError::InvalidDeploymentAccount)?;
+        check_owner(
+            ma.deployment,
+            &crate::ID,
+            ChannelError::InvalidDeploymentAccount,
+        )?;
+        check_pda(
+            &deployment_address_seeds(&img_id_hash(image_id)),
+            ma.deployment.key,
+            ChannelError::InvalidDeploymentAccountPDA,
+        )?;
+        check_key_match(
+            ma.system_program,
+            &system_program::ID,
+            ChannelError::InvalidInstruction,
+        )?;
+        let deployment = {
+            let deploy_data = &*ma
+                .deployment
+                .try_borrow_data()
+                .map_err(|_| ChannelError::CannotBorrowData)?;
+            let deploy = root_as_deploy_v1(deploy_data)
+                .map_err(|_| ChannelError::InvalidDeploymentAccount)?;
+            let owner = deploy
+                .owner()
+                .map(|b| b.bytes())
+                .ok_or(ChannelError::InvalidDeploymentAccount)?;
+            check_bytes_match(
+                ma.owner.key.as_ref(),
+                owner,
+                ChannelError::InvalidDeployerAccount,
+            )?;
+            deploy.unpack()
+        };
+        Ok((ma, deployment))
+    }
+
+    /// Writes the changed deployment back, the payer covers or gets back the rent difference
+    fn save(&self, deployment: &DeployV1T) -> Result<(), ProgramError> {
+        let mut fbb = FlatBufferBuilder::new();
+        let offset = deployment.pack(&mut fbb);
+        fbb.finish(offset, None);
+        resave_structure(self.deployment, fbb.finished_data(), self.payer)
+    }
+}
+
+/// Handles `UpdateDeploymentUrlV1`, `DeprecateDeploymentV1` and `CloseDeploymentV1`, all checked
+/// against the owner of the deployment
+pub fn process_manage_deployment_v1<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    ix: ChannelInstruction<'a>,
+) -> Result<(), ProgramError> {
+    let md = ix
+        .manage_deployment_v1_nested_flatbuffer()
+        .ok_or(ChannelError::InvalidInstruction)?;
+    let (ma, mut deployment) = ManageDeploymentAccounts::from_instruction(accounts, &md)?;
+    match ix.ix_type() {
+        ChannelInstructionIxType::UpdateDeploymentUrlV1 => {
+            msg!("update deployment url {}", ma.image_id);
+            let url = md.url().ok_or(ChannelError::InvalidInstruction)?;
+            deployment.url = Some(url.to_string());
+            ma.save(&deployment)
+        }
+        ChannelInstructionIxType::DeprecateDeploymentV1 => {
+            msg!("deprecate deployment {}", ma.image_id);
+            deployment.deprecated = true;
+            ma.save(&deployment)
+        }
+        ChannelInstructionIxType::CloseDeploymentV1 => {
+            msg!("close deployment {}", ma.image_id);
+            close_program_account(ma.deployment, ma.owner)
+        }
+        _ => Err(ChannelError::InvalidInstruction.into()),
+    }
+}
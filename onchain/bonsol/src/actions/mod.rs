@@ -1,9 +1,25 @@
+mod allow_list;
+mod bid;
+mod cancel;
 mod claim;
 mod deploy;
 mod execute;
+mod expire;
+mod manage_deployment;
+mod register_prover;
 mod status;
+mod status_batch;
+mod verifier_config;
 
+pub use allow_list::*;
+pub use bid::*;
+pub use cancel::*;
 pub use claim::*;
 pub use deploy::*;
 pub use execute::*;
+pub use expire::*;
+pub use manage_deployment::*;
+pub use register_prover::*;
 pub use status::*;
+pub use status_batch::*;
+pub use verifier_config::*;
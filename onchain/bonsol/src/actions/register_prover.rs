@@ -0,0 +1,92 @@
+use bonsol_interface::{
+    bonsol_schema::{ChannelInstruction, RegisterProverV1},
+    prover_state::ProverV1,
+    prover_version::ProverVersion,
+    util::prover_address_seeds,
+};
+
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, msg, program_error::ProgramError, system_program,
+    sysvar::Sysvar,
+};
+
+use crate::{assertions::*, error::ChannelError, reputation::load_prover, utilities::*};
+
+pub struct RegisterProverAccounts<'a, 'b> {
+    pub operator: &'a AccountInfo<'a>,
+    pub prover_account: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub metadata_uri: &'b str,
+    pub prover_versions: Vec<u16>,
+}
+
+impl<'a, 'b> RegisterProverAccounts<'a, 'b> {
+    fn from_instruction(
+        accounts: &'a [AccountInfo<'a>],
+        data: &'b RegisterProverV1<'b>,
+    ) -> Result<Self, ChannelError> {
+        let ra = RegisterProverAccounts {
+            operator: &accounts[0],
+            prover_account: &accounts[1],
+            system_program: &accounts[2],
+            metadata_uri: data.metadata_uri().unwrap_or_default(),
+            prover_versions: data
+                .prover_versions()
+                .map(|versions| versions.iter().map(|v| v.0).collect())
+                .unwrap_or_default(),
+        };
+        check_writable_signer(ra.operator, ChannelError::InvalidPayerAccount)?;
+        check_writeable(ra.prover_account, ChannelError::InvalidProverAccount)?;
+        check_key_match(
+            ra.system_program,
+            &system_program::ID,
+            ChannelError::InvalidInstruction,
+        )?;
+        if data
+            .prover_versions()
+            .is_some_and(|versions| versions.iter().any(|v| ProverVersion::try_from(v).is_err()))
+        {
+            return Err(ChannelError::InvalidProverRegistration);
+        }
+        Ok(ra)
+    }
+}
+
+/// Registers the signer as a prover, or updates the metadata and supported versions of its
+/// registration. The counters of an existing registration are kept.
+pub fn process_register_prover_v1<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    ix: ChannelInstruction<'a>,
+) -> Result<(), ProgramError> {
+    let rp = ix
+        .register_prover_v1_nested_flatbuffer()
+        .ok_or(ChannelError::InvalidInstruction)?;
+    let ra = RegisterProverAccounts::from_instruction(accounts, &rp)?;
+    let current_block = Clock::get()?.slot;
+    let existing = load_prover(ra.prover_account, ra.operator.key)?;
+    let mut registration =
+        existing.unwrap_or_else(|| ProverV1::new(ra.operator.key, current_block));
+    registration
+        .register(ra.metadata_uri, &ra.prover_versions)
+        .map_err(|_| ChannelError::InvalidProverRegistration)?;
+    if existing.is_none() {
+        let mut seeds = prover_address_seeds(ra.operator.key);
+        let bump = [check_pda(
+            &seeds,
+            ra.prover_account.key,
+            ChannelError::InvalidProverAccount,
+        )?];
+        seeds.push(&bump);
+        create_program_account(
+            ra.prover_account,
+            &seeds,
+            std::mem::size_of::<ProverV1>() as u64,
+            ra.operator,
+            ra.system_program,
+            None,
+        )?;
+        msg!("Prover {} registered", ra.operator.key);
+    }
+    ProverV1::save_prover(&registration, ra.prover_account);
+    Ok(())
+}
@@ -1,19 +1,24 @@
 use crate::{
     assertions::*,
+    auction::auctioned,
     error::ChannelError,
     proof_handling::{
-        output_digest_v1_0_1, output_digest_v1_2_1, prepare_inputs_v1_0_1, prepare_inputs_v1_2_1,
-        verify_risc0_v1_0_1, verify_risc0_v1_2_1,
+        assumptions_digest, claim_inputs, output_digest, receipt_claim_digest, verify_risc0,
     },
+    reputation::record,
+    stake::*,
     utilities::*,
+    verifier_config::{load_verifier, Verifier},
 };
 
 use bonsol_interface::{
     bonsol_schema::{
-        root_as_execution_request_v1, ChannelInstruction, ExecutionRequestV1, ExitCode, StatusV1,
+        parse_prior_execution, root_as_execution_request_v1, ChannelInstruction,
+        ExecutionRequestV1, ExitCode, InputType, StatusV1,
     },
-    prover_version::{ProverVersion, VERSION_V1_0_1, VERSION_V1_2_1},
-    util::execution_address_seeds,
+    claim_state::ClaimStateV1,
+    prover_state::ProverV1,
+    util::{execution_address_seeds, execution_claim_address_seeds, proven_claim_digest},
 };
 
 use solana_program::{
@@ -24,14 +29,21 @@ use solana_program::{
     program::invoke_signed,
     program_error::ProgramError,
     program_memory::sol_memcmp,
+    pubkey::Pubkey,
+    system_program,
     sysvar::Sysvar,
 };
 
-struct StatusAccounts<'a, 'b> {
+pub(crate) struct StatusAccounts<'a, 'b> {
     pub requester: &'a AccountInfo<'a>,
     pub exec: &'a AccountInfo<'a>,
     pub prover: &'a AccountInfo<'a>,
     pub callback_program: &'a AccountInfo<'a>,
+    pub exec_claim: &'a AccountInfo<'a>,
+    pub claimer_prover: &'a AccountInfo<'a>,
+    pub verifier_config: &'a AccountInfo<'a>,
+    /// The accounts of the executions the execution assumes, in the order of its inputs
+    pub prior_executions: &'a [AccountInfo<'a>],
     pub extra_accounts: &'a [AccountInfo<'a>],
     pub exec_bump: Option<u8>,
     pub eid: &'b str,
@@ -45,20 +57,22 @@ impl<'a, 'b> StatusAccounts<'a, 'b> {
         let ea = &accounts[1];
         let prover = &accounts[3];
         let callback_program = &accounts[2];
+        let exec_claim = &accounts[4];
         let eid = data
             .execution_id()
             .ok_or(ChannelError::InvalidExecutionAccount)?;
-        let bmp = Some(check_pda(
-            &execution_address_seeds(accounts[0].key, eid.as_bytes()),
-            ea.key,
-            ChannelError::InvalidExecutionAccount,
-        )?);
+        check_writable_signer(prover, ChannelError::InvalidProverAccount)?;
+        let bmp = Some(check_execution_accounts(&accounts[0], ea, exec_claim, eid)?);
         let stat = StatusAccounts {
             requester: &accounts[0],
             exec: &accounts[1],
             callback_program,
             prover,
-            extra_accounts: &accounts[4..],
+            exec_claim,
+            claimer_prover: &accounts[5],
+            verifier_config: &accounts[6],
+            prior_executions: &[],
+            extra_accounts: &accounts[7..],
             exec_bump: bmp,
             eid,
         };
@@ -66,6 +80,28 @@ impl<'a, 'b> StatusAccounts<'a, 'b> {
     }
 }
 
+/// Checks the execution and claim accounts of the execution `eid`, returns the bump of the
+/// execution account
+pub(crate) fn check_execution_accounts(
+    requester: &AccountInfo,
+    exec: &AccountInfo,
+    exec_claim: &AccountInfo,
+    eid: &str,
+) -> Result<u8, ChannelError> {
+    let bump = check_pda(
+        &execution_address_seeds(requester.key, eid.as_bytes()),
+        exec.key,
+        ChannelError::InvalidExecutionAccount,
+    )?;
+    check_writeable(exec_claim, ChannelError::InvalidClaimAccount)?;
+    check_pda(
+        &execution_claim_address_seeds(exec.key.as_ref()),
+        exec_claim.key,
+        ChannelError::InvalidClaimAccount,
+    )?;
+    Ok(bump)
+}
+
 pub fn process_status_v1<'a>(
     accounts: &'a [AccountInfo<'a>],
     ix: ChannelInstruction,
@@ -75,14 +111,18 @@ pub fn process_status_v1<'a>(
         return Err(ChannelError::InvalidInstruction.into());
     }
     let st = st.unwrap();
-    let sa = StatusAccounts::from_instruction(accounts, &st)?;
+    let mut sa = StatusAccounts::from_instruction(accounts, &st)?;
     let er_ref = sa.exec.try_borrow_data()?;
     let er =
         root_as_execution_request_v1(&er_ref).map_err(|_| ChannelError::InvalidExecutionAccount)?;
-    let pr_v = st.proof().filter(|x| x.len() == 256);
-    if er.max_block_height() < Clock::get()?.slot {
-        return Err(ChannelError::ExecutionExpired.into());
+    // the accounts of the prior executions come before the callback's extra accounts
+    let priors = prior_executions(&er).count();
+    if priors > sa.extra_accounts.len() {
+        return Err(ChannelError::InvalidPriorExecution.into());
     }
+    (sa.prior_executions, sa.extra_accounts) = sa.extra_accounts.split_at(priors);
+    let pr_v = st.proof().filter(|x| x.len() == 256);
+    let claim = check_settleable(&sa, &er)?;
     let execution_digest_v = st.execution_digest().map(|x| x.bytes());
     let input_digest_v = st.input_digest().map(|x| x.bytes());
     let assumption_digest_v = st.assumption_digest().map(|x| x.bytes());
@@ -102,125 +142,260 @@ pub fn process_status_v1<'a>(
             er.input_digest()
                 .map(|x| check_bytes_match(x.bytes(), input_digest, ChannelError::InputsDontMatch));
         }
-        let verified = verify_with_prover(input_digest, co, asud, er, exed, st, proof)?;
-        let tip = er.tip();
+        let verifier = load_verifier(sa.verifier_config, er.prover_version())?;
+        let prior_claims = check_prior_executions(&sa, &er)?;
+        check_bytes_match(
+            &assumptions_digest(&verifier.constants, &prior_claims),
+            asud,
+            ChannelError::AssumptionsDontMatch,
+        )?;
+        let output_digest = output_digest(&verifier.constants, input_digest, co, asud);
+        let (verified, claim_digest) =
+            verify_with_prover(&verifier, er, exed, &output_digest, st, proof)?;
+        drop(er_ref);
         if verified {
-            let callback_program_set =
-                sol_memcmp(sa.callback_program.key.as_ref(), crate::ID.as_ref(), 32) != 0;
-            let ix_prefix_set = er.callback_instruction_prefix().is_some();
-            if callback_program_set && ix_prefix_set {
-                let cbp = er
-                    .callback_program_id()
-                    .map(|b| b.bytes())
-                    .unwrap_or(crate::ID.as_ref());
-                check_bytes_match(
-                    cbp,
-                    sa.callback_program.key.as_ref(),
-                    ChannelError::InvalidCallbackProgram,
-                )?;
+            settle_proven(
+                &sa,
+                claim.as_ref(),
+                &claim_digest,
+                input_digest,
+                committed_outputs_v,
+            )?;
+        } else {
+            msg!("{} Verifying Failed Cleaning up", sa.eid);
+            check_claimer(&sa, claim.as_ref())?;
+            settle_claim(&sa, claim.as_ref(), false)?;
+            cleanup_execution_account(sa.exec, sa.requester, ExitCode::VerifyError as u8)?;
+        }
+    } else {
+        msg!("{} Proving Failed Cleaning up", sa.eid);
+        drop(er_ref);
+        check_claimer(&sa, claim.as_ref())?;
+        settle_claim(&sa, claim.as_ref(), false)?;
+        cleanup_execution_account(sa.exec, sa.requester, ExitCode::ProvingError as u8)?;
+    }
+    Ok(())
+}
+
+/// Checks that the execution can still be settled by the prover, returns the claim on it. Only
+/// the claimer settles an execution while its commitment is active, another prover can take over
+/// with a proof once it lapsed.
+pub(crate) fn check_settleable(
+    sa: &StatusAccounts,
+    er: &ExecutionRequestV1,
+) -> Result<Option<ClaimStateV1>, ProgramError> {
+    let slot = Clock::get()?.slot;
+    if er.max_block_height() < slot {
+        return Err(ChannelError::ExecutionExpired.into());
+    }
+    let claim = current_claim(sa)?;
+    // auctioned executions are only proven by the prover that claimed them
+    if auctioned(er) && claim.is_none() {
+        return Err(ChannelError::ClaimRequired.into());
+    }
+    if let Some(c) = &claim {
+        if slot <= c.block_commitment {
+            check_claimer(sa, Some(c))?;
+        }
+    }
+    Ok(claim)
+}
+
+/// Checks that the prover holds the claim on the execution. A failed proof slashes the claimer,
+/// only the claimer can report one.
+fn check_claimer(sa: &StatusAccounts, claim: Option<&ClaimStateV1>) -> Result<(), ChannelError> {
+    match claim {
+        Some(c) if c.claimer != sa.prover.key.to_bytes() => Err(ChannelError::NotClaimer),
+        _ => Ok(()),
+    }
+}
+
+/// The data of the execution's `PriorExecution` inputs, in order
+pub(crate) fn prior_executions<'b>(
+    er: &ExecutionRequestV1<'b>,
+) -> impl Iterator<Item = &'b [u8]> + 'b {
+    er.input()
+        .into_iter()
+        .flatten()
+        .filter(|i| i.input_type() == InputType::PriorExecution)
+        .map(|i| i.data().map(|d| d.bytes()).unwrap_or_default())
+}
+
+/// Checks that the prior execution accounts are the proven executions the execution's inputs
+/// name, returns the receipt claim digests their proofs verified in the order of the inputs
+fn check_prior_executions(
+    sa: &StatusAccounts,
+    er: &ExecutionRequestV1,
+) -> Result<Vec<[u8; 32]>, ChannelError> {
+    prior_executions(er)
+        .zip(sa.prior_executions)
+        .map(|(input, account)| {
+            let (requester, eid) =
+                parse_prior_execution(input).ok_or(ChannelError::InvalidPriorExecution)?;
+            let requester = Pubkey::new_from_array(requester);
+            check_pda(
+                &execution_address_seeds(&requester, eid.as_bytes()),
+                account.key,
+                ChannelError::InvalidPriorExecution,
+            )?;
+            check_owner(account, &crate::ID, ChannelError::InvalidPriorExecution)?;
+            let data = account
+                .try_borrow_data()
+                .map_err(|_| ChannelError::CannotBorrowData)?;
+            // a prior execution that is pending or did not settle with a proof cannot be assumed
+            proven_claim_digest(&data).ok_or(ChannelError::InvalidPriorExecution)
+        })
+        .collect()
+}
+
+/// Settles a proven execution, its callback is invoked, the prover is paid and the execution
+/// account keeps the digest of the receipt claim that was verified
+pub(crate) fn settle_proven(
+    sa: &StatusAccounts,
+    claim: Option<&ClaimStateV1>,
+    claim_digest: &[u8; 32],
+    input_digest: &[u8],
+    committed_outputs: Option<&[u8]>,
+) -> Result<(), ProgramError> {
+    let er_ref = sa.exec.try_borrow_data()?;
+    let er =
+        root_as_execution_request_v1(&er_ref).map_err(|_| ChannelError::InvalidExecutionAccount)?;
+    // the claimer is paid the price it claimed the execution for, the rest of the tip is
+    // refunded to the requester
+    let payout = claim.map_or(er.tip(), |c| c.price);
+    let callback_program_set =
+        sol_memcmp(sa.callback_program.key.as_ref(), crate::ID.as_ref(), 32) != 0;
+    let ix_prefix_set = er.callback_instruction_prefix().is_some();
+    let callback = if callback_program_set && ix_prefix_set {
+        let cbp = er
+            .callback_program_id()
+            .map(|b| b.bytes())
+            .unwrap_or(crate::ID.as_ref());
+        check_bytes_match(
+            cbp,
+            sa.callback_program.key.as_ref(),
+            ChannelError::InvalidCallbackProgram,
+        )?;
 
-                let b = [sa.exec_bump.unwrap()];
-                let mut seeds = execution_address_seeds(sa.requester.key, sa.eid.as_bytes());
-                seeds.push(&b);
-                let mut ainfos = vec![sa.exec.clone(), sa.callback_program.clone()];
-                ainfos.extend(sa.extra_accounts.iter().cloned());
-                // ER is the signer, it is reuired to save the execution id in the calling program
-                let mut accounts = vec![AccountMeta::new_readonly(*sa.exec.key, true)];
-                if let Some(extra_accounts) = er.callback_extra_accounts() {
-                    if extra_accounts.len() != sa.extra_accounts.len() {
+        let mut ainfos = vec![sa.exec.clone(), sa.callback_program.clone()];
+        ainfos.extend(sa.extra_accounts.iter().cloned());
+        // ER is the signer, it is reuired to save the execution id in the calling program
+        let mut accounts = vec![AccountMeta::new_readonly(*sa.exec.key, true)];
+        if let Some(extra_accounts) = er.callback_extra_accounts() {
+            if extra_accounts.len() != sa.extra_accounts.len() {
+                return Err(ChannelError::InvalidCallbackExtraAccounts.into());
+            }
+            for (i, a) in sa.extra_accounts.iter().enumerate() {
+                let stored_a = extra_accounts.get(i);
+                let key: [u8; 32] = stored_a.pubkey().into();
+                if sol_memcmp(a.key.as_ref(), &key, 32) != 0 {
+                    return Err(ChannelError::InvalidCallbackExtraAccounts.into());
+                }
+                // dont cary feepayer signature through to callback we set all signer to false except the ER
+                if a.is_writable {
+                    if !stored_a.writable() == 0 {
                         return Err(ChannelError::InvalidCallbackExtraAccounts.into());
                     }
-                    for (i, a) in sa.extra_accounts.iter().enumerate() {
-                        let stored_a = extra_accounts.get(i);
-                        let key: [u8; 32] = stored_a.pubkey().into();
-                        if sol_memcmp(a.key.as_ref(), &key, 32) != 0 {
-                            return Err(ChannelError::InvalidCallbackExtraAccounts.into());
-                        }
-                        // dont cary feepayer signature through to callback we set all signer to false except the ER
-                        if a.is_writable {
-                            if !stored_a.writable() == 0 {
-                                return Err(ChannelError::InvalidCallbackExtraAccounts.into());
-                            }
-                            accounts.push(AccountMeta::new(*a.key, false));
-                        } else {
-                            if stored_a.writable() == 1 {
-                                //maybe relax this for devs?
-                                return Err(ChannelError::InvalidCallbackExtraAccounts.into());
-                            }
-                            accounts.push(AccountMeta::new_readonly(*a.key, false));
-                        }
-                    }
-                }
-                let payload = if er.forward_output() && st.committed_outputs().is_some() {
-                    [
-                        er.callback_instruction_prefix().unwrap().bytes(),
-                        input_digest,
-                        st.committed_outputs().unwrap().bytes(),
-                    ]
-                    .concat()
+                    accounts.push(AccountMeta::new(*a.key, false));
                 } else {
-                    er.callback_instruction_prefix().unwrap().bytes().to_vec()
-                };
-                let callback_ix =
-                    Instruction::new_with_bytes(*sa.callback_program.key, &payload, accounts);
-                drop(er_ref);
-                let res = invoke_signed(&callback_ix, &ainfos, &[&seeds]);
-                match res {
-                    Ok(_) => {}
-                    Err(e) => {
-                        msg!("{} Callback Failed: {:?}", sa.eid, e);
+                    if stored_a.writable() == 1 {
+                        //maybe relax this for devs?
+                        return Err(ChannelError::InvalidCallbackExtraAccounts.into());
                     }
+                    accounts.push(AccountMeta::new_readonly(*a.key, false));
                 }
             }
-            // add curve reduction here
-            payout_tip(sa.exec, sa.prover, tip)?;
-            cleanup_execution_account(sa.exec, sa.requester, ExitCode::Success as u8)?;
-        } else {
-            msg!("{} Verifying Failed Cleaning up", sa.eid);
-            cleanup_execution_account(sa.exec, sa.requester, ExitCode::VerifyError as u8)?;
         }
+        let payload = match committed_outputs {
+            Some(co) if er.forward_output() => [
+                er.callback_instruction_prefix().unwrap().bytes(),
+                input_digest,
+                co,
+            ]
+            .concat(),
+            _ => er.callback_instruction_prefix().unwrap().bytes().to_vec(),
+        };
+        Some((
+            Instruction::new_with_bytes(*sa.callback_program.key, &payload, accounts),
+            ainfos,
+        ))
     } else {
-        msg!("{} Proving Failed Cleaning up", sa.eid);
-        cleanup_execution_account(sa.exec, sa.requester, ExitCode::ProvingError as u8)?;
+        None
+    };
+    // the callback reads the execution account and it is closed below
+    drop(er_ref);
+    if let Some((callback_ix, ainfos)) = callback {
+        let b = [sa.exec_bump.unwrap()];
+        let mut seeds = execution_address_seeds(sa.requester.key, sa.eid.as_bytes());
+        seeds.push(&b);
+        let res = invoke_signed(&callback_ix, &ainfos, &[&seeds]);
+        match res {
+            Ok(_) => {}
+            Err(e) => {
+                msg!("{} Callback Failed: {:?}", sa.eid, e);
+            }
+        }
     }
-    Ok(())
+    // add curve reduction here
+    payout_tip(sa.exec, sa.prover, payout)?;
+    settle_claim(sa, claim, true)?;
+    record_proven_execution(sa.exec, sa.requester, claim_digest)
+}
+
+/// The claim on the execution, None when no prover claimed it
+fn current_claim(sa: &StatusAccounts) -> Result<Option<ClaimStateV1>, ProgramError> {
+    if sa.exec_claim.owner == &system_program::ID {
+        return Ok(None);
+    }
+    check_owner(sa.exec_claim, &crate::ID, ChannelError::InvalidClaimAccount)?;
+    let data = sa.exec_claim.try_borrow_data()?;
+    let claim =
+        ClaimStateV1::load_claim_owned(&data).map_err(|_| ChannelError::InvalidClaimAccount)?;
+    Ok(Some(claim))
+}
+
+/// Returns or slashes the stake of the prover holding the claim, records the outcome in its
+/// registration and closes the claim account
+fn settle_claim(
+    sa: &StatusAccounts,
+    claim: Option<&ClaimStateV1>,
+    verified: bool,
+) -> Result<(), ProgramError> {
+    let Some(claim) = claim else {
+        return Ok(());
+    };
+    let settlement = status_settlement(claim, sa.prover.key, verified);
+    let outcome: fn(&mut ProverV1) = match settlement {
+        Settlement::Return => ProverV1::record_proof,
+        // another prover delivered the proof
+        Settlement::Slash if verified => ProverV1::record_missed_commitment,
+        Settlement::Slash => ProverV1::record_slash,
+    };
+    record(
+        sa.claimer_prover,
+        &Pubkey::new_from_array(claim.claimer),
+        outcome,
+    )?;
+    settle_stake(sa.exec_claim, settlement, sa.prover, sa.requester)
 }
 
 fn verify_with_prover(
-    input_digest: &[u8],
-    co: &[u8],
-    asud: &[u8],
+    verifier: &Verifier,
     er: ExecutionRequestV1,
     exed: &[u8],
+    output_digest: &[u8],
     st: StatusV1,
     proof: &[u8; 256],
-) -> Result<bool, ProgramError> {
-    let prover_version =
-        ProverVersion::try_from(er.prover_version()).unwrap_or(ProverVersion::default());
-    let verified = match prover_version {
-        VERSION_V1_0_1 => {
-            let output_digest = output_digest_v1_0_1(input_digest, co, asud);
-            let proof_inputs = prepare_inputs_v1_0_1(
-                er.image_id().unwrap(),
-                exed,
-                output_digest.as_ref(),
-                st.exit_code_system(),
-                st.exit_code_user(),
-            )?;
-            verify_risc0_v1_0_1(proof, &proof_inputs)?
-        }
-        VERSION_V1_2_1 => {
-            let output_digest = output_digest_v1_2_1(input_digest, co, asud);
-            let proof_inputs = prepare_inputs_v1_2_1(
-                er.image_id().unwrap(),
-                exed,
-                output_digest.as_ref(),
-                st.exit_code_system(),
-                st.exit_code_user(),
-            )?;
-            verify_risc0_v1_2_1(proof, &proof_inputs)?
-        }
-        _ => false,
-    };
-    Ok(verified)
+) -> Result<(bool, [u8; 32]), ProgramError> {
+    let claim_digest = receipt_claim_digest(
+        &verifier.constants,
+        er.image_id().unwrap(),
+        exed,
+        output_digest,
+        st.exit_code_system(),
+        st.exit_code_user(),
+    )?;
+    let proof_inputs = claim_inputs(&verifier.constants, claim_digest)?;
+    let verified = verify_risc0(proof, &proof_inputs, &verifier.verifying_key())?;
+    Ok((verified, claim_digest))
 }
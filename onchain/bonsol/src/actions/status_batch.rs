@@ -0,0 +1,224 @@
+use crate::{
+    assertions::*,
+    error::ChannelError,
+    proof_handling::{claim_inputs, output_digest, receipt_claim_digest, verify_risc0},
+    verifier_config::load_verifier,
+};
+
+use super::status::{
+    check_execution_accounts, check_settleable, prior_executions, settle_proven, StatusAccounts,
+};
+
+use bonsol_interface::{
+    bonsol_schema::{root_as_execution_request_v1, ChannelInstruction, ProverVersion, StatusV1},
+    claim_state::ClaimStateV1,
+    verifier_config::verifier_version,
+};
+
+use solana_program::{
+    account_info::AccountInfo, msg, program_error::ProgramError, program_memory::sol_memcmp,
+};
+
+/// Accounts every execution of a batch passes ahead of its callback's extra accounts: requester,
+/// execution, callback program, claim and the registry of the claimer
+const EXECUTION_ACCOUNTS: usize = 5;
+
+/// A batched execution that passed its checks, it is settled once the batch proof is verified
+struct BatchedExecution<'a, 'b> {
+    accounts: StatusAccounts<'a, 'b>,
+    claim: Option<ClaimStateV1>,
+    claim_digest: [u8; 32],
+    input_digest: &'b [u8],
+    committed_outputs: &'b [u8],
+}
+
+/// The accounts of the batched execution `status` starting at `offset`, returns them with the
+/// offset of the next execution and the prover version the execution is pinned to
+fn batched_accounts<'a, 'b>(
+    accounts: &'a [AccountInfo<'a>],
+    offset: usize,
+    status: &StatusV1<'b>,
+) -> Result<(StatusAccounts<'a, 'b>, usize, ProverVersion), ProgramError> {
+    let execution = accounts
+        .get(offset..offset + EXECUTION_ACCOUNTS)
+        .ok_or(ChannelError::InvalidBatch)?;
+    let eid = status
+        .execution_id()
+        .ok_or(ChannelError::InvalidExecutionAccount)?;
+    let exec_bump = check_execution_accounts(&execution[0], &execution[1], &execution[3], eid)?;
+    let (extra, prover_version) = {
+        let er_ref = execution[1].try_borrow_data()?;
+        let er = root_as_execution_request_v1(&er_ref)
+            .map_err(|_| ChannelError::InvalidExecutionAccount)?;
+        // the batch image resolves every assumption, executions assuming prior executions are
+        // settled one by one
+        if prior_executions(&er).next().is_some() {
+            return Err(ChannelError::InvalidBatch.into());
+        }
+        // the callback's extra accounts are only passed when the callback is invoked
+        let callback_program_set =
+            sol_memcmp(execution[2].key.as_ref(), crate::ID.as_ref(), 32) != 0;
+        let extra = match er.callback_extra_accounts() {
+            Some(extra_accounts) if callback_program_set => extra_accounts.len(),
+            _ => 0,
+        };
+        (extra, er.prover_version())
+    };
+    let next = offset + EXECUTION_ACCOUNTS + extra;
+    let extra_accounts = accounts
+        .get(offset + EXECUTION_ACCOUNTS..next)
+        .ok_or(ChannelError::InvalidBatch)?;
+    let sa = StatusAccounts {
+        requester: &execution[0],
+        exec: &execution[1],
+        prover: &accounts[0],
+        callback_program: &execution[2],
+        exec_claim: &execution[3],
+        claimer_prover: &execution[4],
+        verifier_config: &accounts[1],
+        prior_executions: &[],
+        extra_accounts,
+        exec_bump: Some(exec_bump),
+        eid,
+    };
+    Ok((sa, next, prover_version))
+}
+
+/// Handles `StatusBatchV1`, one proof of the batch image settles every execution of the batch.
+/// The batch image verifies the receipt claim of each execution as an assumption and commits
+/// their digests, so the program checks the executions the batch claims by recomputing that
+/// journal. A batch is settled as a whole, it fails when its proof does not verify.
+pub fn process_status_batch_v1<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    ix: ChannelInstruction<'a>,
+) -> Result<(), ProgramError> {
+    let batch = ix
+        .status_batch_v1_nested_flatbuffer()
+        .ok_or(ChannelError::InvalidInstruction)?;
+    let proof: &[u8; 256] = batch
+        .proof()
+        .and_then(|p| p.bytes().try_into().ok())
+        .ok_or(ChannelError::InvalidInstruction)?;
+    let batch_execution_digest = batch
+        .execution_digest()
+        .map(|d| d.bytes())
+        .ok_or(ChannelError::InvalidInstruction)?;
+    let statuses = batch
+        .statuses()
+        .filter(|s| !s.is_empty())
+        .ok_or(ChannelError::InvalidBatch)?;
+    let prover = accounts.first().ok_or(ChannelError::InvalidBatch)?;
+    check_writable_signer(prover, ChannelError::InvalidBatch)?;
+    let verifier_config = accounts.get(1).ok_or(ChannelError::InvalidBatch)?;
+
+    let mut batch_version = None;
+    let mut verifier = None;
+    let mut executions: Vec<BatchedExecution> = Vec::with_capacity(statuses.len());
+    let mut journal = Vec::with_capacity(statuses.len() * 32);
+    let mut offset = 2;
+    for status in statuses.iter() {
+        let (sa, next, prover_version) = batched_accounts(accounts, offset, &status)?;
+        offset = next;
+        if executions
+            .iter()
+            .any(|e| e.accounts.exec.key == sa.exec.key)
+        {
+            return Err(ChannelError::InvalidBatch.into());
+        }
+        match batch_version {
+            None => {
+                let loaded = load_verifier(verifier_config, prover_version)?;
+                if loaded.batch_image_id.is_none() {
+                    return Err(ChannelError::BatchUnavailable.into());
+                }
+                batch_version = Some(verifier_version(prover_version));
+                verifier = Some(loaded);
+            }
+            // one proof is verified with one verifier, the batch cannot mix prover versions
+            Some(version) if version != verifier_version(prover_version) => {
+                return Err(ChannelError::InvalidBatch.into());
+            }
+            Some(_) => {}
+        }
+        let constants = verifier
+            .as_ref()
+            .map(|v| &v.constants)
+            .ok_or(ChannelError::InvalidBatch)?;
+        let (Some(exed), Some(input_digest), Some(asud), Some(co)) = (
+            status.execution_digest().map(|x| x.bytes()),
+            status.input_digest().map(|x| x.bytes()),
+            status.assumption_digest().map(|x| x.bytes()),
+            status.committed_outputs().map(|x| x.bytes()),
+        ) else {
+            return Err(ChannelError::InvalidBatch.into());
+        };
+        check_bytes_match(asud, &[0; 32], ChannelError::AssumptionsDontMatch)?;
+        let (claim, claim_digest) = {
+            let er_ref = sa.exec.try_borrow_data()?;
+            let er = root_as_execution_request_v1(&er_ref)
+                .map_err(|_| ChannelError::InvalidExecutionAccount)?;
+            let claim = check_settleable(&sa, &er)?;
+            if er.verify_input_hash() {
+                if let Some(expected) = er.input_digest() {
+                    check_bytes_match(
+                        expected.bytes(),
+                        input_digest,
+                        ChannelError::InputsDontMatch,
+                    )?;
+                }
+            }
+            let output_digest = output_digest(constants, input_digest, co, asud);
+            let claim_digest = receipt_claim_digest(
+                constants,
+                er.image_id().ok_or(ChannelError::InvalidExecutionAccount)?,
+                exed,
+                &output_digest,
+                status.exit_code_system(),
+                status.exit_code_user(),
+            )?;
+            journal.extend_from_slice(&claim_digest);
+            (claim, claim_digest)
+        };
+        executions.push(BatchedExecution {
+            accounts: sa,
+            claim,
+            claim_digest,
+            input_digest,
+            committed_outputs: co,
+        });
+    }
+    if offset != accounts.len() {
+        return Err(ChannelError::InvalidBatch.into());
+    }
+
+    let verifier = verifier.ok_or(ChannelError::InvalidBatch)?;
+    let batch_image_id = verifier
+        .batch_image_id
+        .as_deref()
+        .ok_or(ChannelError::BatchUnavailable)?;
+    // the batch image resolves every assumption, its journal is the claim digests in order
+    let batch_output_digest = output_digest(&verifier.constants, &[], &journal, &[0; 32]);
+    let batch_claim_digest = receipt_claim_digest(
+        &verifier.constants,
+        batch_image_id,
+        batch_execution_digest,
+        &batch_output_digest,
+        0,
+        0,
+    )?;
+    let inputs = claim_inputs(&verifier.constants, batch_claim_digest)?;
+    if !verify_risc0(proof, &inputs, &verifier.verifying_key())? {
+        return Err(ChannelError::ProofVerificationFailed.into());
+    }
+    msg!("batch of {} verified", executions.len());
+    for execution in executions.iter() {
+        settle_proven(
+            &execution.accounts,
+            execution.claim.as_ref(),
+            &execution.claim_digest,
+            execution.input_digest,
+            Some(execution.committed_outputs),
+        )?;
+    }
+    Ok(())
+}
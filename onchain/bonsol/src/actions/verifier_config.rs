@@ -0,0 +1,162 @@
+use bonsol_interface::{
+    bonsol_schema::{ChannelInstruction, VerifierConfigV1},
+    util::verifier_config_address_seeds,
+    verifier_config::verifier_version,
+};
+
+use solana_program::{
+    account_info::AccountInfo,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::{
+    assertions::*,
+    error::ChannelError,
+    utilities::*,
+    verifier_config::{check_verifier_config_address, Verifier},
+};
+
+pub struct VerifierConfigAccounts<'a> {
+    pub authority: &'a AccountInfo<'a>,
+    pub verifier_config: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub verifier_config_bump: u8,
+}
+
+impl<'a> VerifierConfigAccounts<'a> {
+    fn from_instruction(
+        accounts: &'a [AccountInfo<'a>],
+        data: &VerifierConfigV1,
+    ) -> Result<Self, ChannelError> {
+        let authority = &accounts[0];
+        check_writable_signer(authority, ChannelError::InvalidVerifierAuthority)?;
+        let program_data = &accounts[2];
+        check_key_match(
+            program_data,
+            &bpf_loader_upgradeable::get_program_data_address(&crate::ID),
+            ChannelError::InvalidVerifierAuthority,
+        )?;
+        check_owner(
+            program_data,
+            &bpf_loader_upgradeable::ID,
+            ChannelError::InvalidVerifierAuthority,
+        )?;
+        let upgrade_authority = {
+            let data = program_data
+                .try_borrow_data()
+                .map_err(|_| ChannelError::CannotBorrowData)?;
+            upgrade_authority(&data)
+        };
+        if upgrade_authority != Some(*authority.key) {
+            return Err(ChannelError::InvalidVerifierAuthority);
+        }
+        let verifier_config = &accounts[1];
+        check_writeable(verifier_config, ChannelError::InvalidVerifierConfigAccount)?;
+        let verifier_config_bump =
+            check_verifier_config_address(verifier_config, data.prover_version())?;
+        let system_program = &accounts[3];
+        check_key_match(
+            system_program,
+            &system_program::ID,
+            ChannelError::InvalidInstruction,
+        )?;
+        Ok(VerifierConfigAccounts {
+            authority,
+            verifier_config,
+            system_program,
+            verifier_config_bump,
+        })
+    }
+
+    fn exists(&self) -> bool {
+        !(self.verifier_config.data_len() == 0 && self.verifier_config.owner == &system_program::ID)
+    }
+}
+
+/// The upgrade authority in the data of the program's `ProgramData` account, None once the
+/// program was made immutable
+fn upgrade_authority(data: &[u8]) -> Option<Pubkey> {
+    // bincode of UpgradeableLoaderState::ProgramData { slot, upgrade_authority_address }
+    let metadata = data.get(..UpgradeableLoaderState::size_of_programdata_metadata())?;
+    let (variant, rest) = metadata.split_at(4);
+    let (_slot, authority) = rest.split_at(8);
+    if variant != 3u32.to_le_bytes() || authority[0] != 1 {
+        return None;
+    }
+    Pubkey::try_from(&authority[1..]).ok()
+}
+
+/// Handles `SetVerifierConfigV1`, only the upgrade authority of the program can add, replace or
+/// disable the verifier of a prover version
+pub fn process_set_verifier_config_v1<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    ix: ChannelInstruction<'a>,
+) -> Result<(), ProgramError> {
+    let config = ix
+        .verifier_config_v1_nested_flatbuffer()
+        .ok_or(ChannelError::InvalidInstruction)?;
+    let va = VerifierConfigAccounts::from_instruction(accounts, &config)?;
+    // a config the status instruction cannot build a verifier from is never stored
+    Verifier::try_from(&config)?;
+    let version = verifier_version(config.prover_version());
+    msg!("set verifier config {}", version.0);
+    // the verifier config is stored as the instruction sent it
+    let bytes = ix.verifier_config_v1().unwrap().bytes();
+    if va.exists() {
+        check_owner(
+            va.verifier_config,
+            &crate::ID,
+            ChannelError::InvalidVerifierConfigAccount,
+        )?;
+        resave_structure(va.verifier_config, bytes, va.authority)
+    } else {
+        let version = version.0.to_le_bytes();
+        let bump = [va.verifier_config_bump];
+        let mut seeds = verifier_config_address_seeds(&version);
+        seeds.push(&bump);
+        save_structure(
+            va.verifier_config,
+            &seeds,
+            bytes,
+            va.authority,
+            va.system_program,
+            None,
+        )
+        .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn program_data(variant: u32, authority: Option<&Pubkey>) -> Vec<u8> {
+        let mut data = [variant.to_le_bytes().as_ref(), &42u64.to_le_bytes()].concat();
+        match authority {
+            Some(authority) => {
+                data.push(1);
+                data.extend_from_slice(authority.as_ref());
+            }
+            None => data.extend_from_slice(&[0; 33]),
+        }
+        // the program elf follows the metadata
+        data.extend_from_slice(&[7; 16]);
+        data
+    }
+
+    #[test]
+    fn reads_the_upgrade_authority_of_the_program_data() {
+        let authority = Pubkey::new_unique();
+        assert_eq!(
+            upgrade_authority(&program_data(3, Some(&authority))),
+            Some(authority)
+        );
+        assert_eq!(upgrade_authority(&program_data(3, None)), None);
+        assert_eq!(upgrade_authority(&program_data(2, Some(&authority))), None);
+        assert_eq!(upgrade_authority(&[3, 0, 0, 0]), None);
+    }
+}
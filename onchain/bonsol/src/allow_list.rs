@@ -0,0 +1,68 @@
+//! Claimer restrictions of execution requests.
+//! A request can list the provers allowed to claim it in `allowed_claimers`, and reference an
+//! `AllowListV1` account kept at `allow_list_address(authority, id)` with `claimer_allow_list`.
+//! The provers in either can claim it. A claim on a request referencing an allow list passes that
+//! account after the claim accounts, once the allow list is closed only the provers the request
+//! lists itself can claim it.
+use bonsol_interface::{
+    allow_list::{claimer_allowed, restricts_claimers},
+    bonsol_schema::{root_as_allow_list_v1, AllowListV1, ExecutionRequestV1},
+    util::allow_list_address_seeds,
+};
+
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey, system_program};
+
+use crate::{assertions::*, error::ChannelError};
+
+/// Checks that `allow_list` is stored at the address of its authority and id, returns the bump
+pub fn check_allow_list_address(
+    allow_list: &AllowListV1,
+    address: &Pubkey,
+) -> Result<u8, ChannelError> {
+    let authority = allow_list
+        .authority()
+        .and_then(|a| Pubkey::try_from(a.bytes()).ok())
+        .ok_or(ChannelError::InvalidAllowListAccount)?;
+    let id = allow_list
+        .id()
+        .ok_or(ChannelError::InvalidAllowListAccount)?;
+    check_pda(
+        &allow_list_address_seeds(&authority, id),
+        address,
+        ChannelError::InvalidAllowListAccount,
+    )
+}
+
+/// Rejects `claimer` unless `execution_request` allows it, the allow list the request references
+/// is looked up by its address in `accounts`
+pub fn check_claimer_allowed(
+    execution_request: &ExecutionRequestV1,
+    accounts: &[AccountInfo],
+    claimer: &Pubkey,
+) -> Result<(), ChannelError> {
+    if !restricts_claimers(execution_request) {
+        return Ok(());
+    }
+    let allow_list_account = execution_request.claimer_allow_list().and_then(|address| {
+        accounts
+            .iter()
+            .find(|account| account.key.as_ref() == address.bytes())
+    });
+    let allowed = match allow_list_account {
+        Some(account) if !(account.data_len() == 0 && account.owner == &system_program::ID) => {
+            check_owner(account, &crate::ID, ChannelError::InvalidAllowListAccount)?;
+            let data = account
+                .try_borrow_data()
+                .map_err(|_| ChannelError::CannotBorrowData)?;
+            let allow_list =
+                root_as_allow_list_v1(&data).map_err(|_| ChannelError::InvalidAllowListAccount)?;
+            check_allow_list_address(&allow_list, account.key)?;
+            claimer_allowed(execution_request, Some(&allow_list), &claimer.to_bytes())
+        }
+        _ => claimer_allowed(execution_request, None, &claimer.to_bytes()),
+    };
+    if !allowed {
+        return Err(ChannelError::ClaimerNotAllowed);
+    }
+    Ok(())
+}
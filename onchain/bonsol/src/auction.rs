@@ -0,0 +1,117 @@
+//! Auctioned claims of execution requests.
+//! A request with `auction_slots` opens a `BidStateV1` account at
+//! `execution_bids_address(execution)` when it is submitted. Until the auction ends provers bid
+//! the price they prove the execution for with `BidV1`, and the block they commit to proving it
+//! by. Once it has ended the bids take turns claiming the execution, the best one first, each for
+//! `AUCTION_CLAIM_WINDOW` blocks. A winner that claims it is paid its bid instead of the tip and
+//! the rest of the tip is refunded to the requester. After every bid had its turn the execution
+//! can be claimed by anyone for the tip, as can a claim that lapsed.
+//!
+//! The bids account is closed to the requester when the execution is claimed, cancelled or
+//! expired.
+use bonsol_interface::{
+    bid_state::BidStateV1, bonsol_schema::ExecutionRequestV1, util::execution_bids_address_seeds,
+};
+
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey, system_program};
+
+use crate::{assertions::*, error::ChannelError, utilities::*};
+
+/// Whether `execution_request` is claimed through an auction
+pub fn auctioned(execution_request: &ExecutionRequestV1) -> bool {
+    execution_request.auction_slots() > 0
+}
+
+/// The bids account of the execution at `exec`, looked up by its address in `accounts`
+pub fn find_bids_account<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    exec: &Pubkey,
+) -> Option<&'a AccountInfo<'a>> {
+    let (address, _) =
+        Pubkey::find_program_address(&execution_bids_address_seeds(exec.as_ref()), &crate::ID);
+    accounts.iter().find(|account| account.key == &address)
+}
+
+/// The bids held by `bids_account`
+pub fn load_bids(bids_account: &AccountInfo) -> Result<BidStateV1, ChannelError> {
+    check_owner(bids_account, &crate::ID, ChannelError::InvalidBidAccount)?;
+    let data = bids_account
+        .try_borrow_data()
+        .map_err(|_| ChannelError::CannotBorrowData)?;
+    BidStateV1::load_bids_owned(&data).map_err(|_| ChannelError::InvalidBidAccount)
+}
+
+/// The price `claimer` claims an auctioned execution for at `current_block`, its winning bid
+/// while it has its turn and the tip once every bid had one
+pub fn auction_price(
+    bids: &BidStateV1,
+    claimer: &Pubkey,
+    block_commitment: u64,
+    current_block: u64,
+    tip: u64,
+) -> Result<u64, ChannelError> {
+    if current_block <= bids.auction_end {
+        return Err(ChannelError::AuctionOpen);
+    }
+    match bids.winner_at(current_block) {
+        Some(bid) if bid.bidder != claimer.to_bytes() => Err(ChannelError::NotAuctionWinner),
+        Some(bid) if block_commitment > bid.completion_slot => Err(ChannelError::InvalidBid),
+        Some(bid) => Ok(bid.price),
+        None => Ok(tip),
+    }
+}
+
+/// Closes the bids account of an auctioned execution to the requester, it is found by its
+/// address in `accounts` and is already closed once the execution was claimed
+pub fn close_bids<'a>(
+    accounts: &'a [AccountInfo<'a>],
+    exec: &Pubkey,
+    requester: &AccountInfo,
+) -> Result<(), ChannelError> {
+    let bids_account = find_bids_account(accounts, exec).ok_or(ChannelError::InvalidBidAccount)?;
+    if bids_account.data_len() == 0 && bids_account.owner == &system_program::ID {
+        return Ok(());
+    }
+    check_owner(bids_account, &crate::ID, ChannelError::InvalidBidAccount)?;
+    check_writeable(bids_account, ChannelError::InvalidBidAccount)?;
+    close_program_account(bids_account, requester).map_err(|_| ChannelError::InvalidBidAccount)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bonsol_interface::bid_state::{PlacedBid, AUCTION_CLAIM_WINDOW};
+
+    #[test]
+    fn winners_claim_for_their_bid_in_turn() {
+        let (winner, runner_up) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut bids = BidStateV1::new(100);
+        bids.place(PlacedBid::new(&winner, 300, 150, 50)).unwrap();
+        bids.place(PlacedBid::new(&runner_up, 400, 150, 60))
+            .unwrap();
+
+        assert_eq!(
+            auction_price(&bids, &winner, 150, 100, 1_000),
+            Err(ChannelError::AuctionOpen)
+        );
+        assert_eq!(auction_price(&bids, &winner, 150, 101, 1_000), Ok(300));
+        assert_eq!(
+            auction_price(&bids, &winner, 151, 101, 1_000),
+            Err(ChannelError::InvalidBid)
+        );
+        assert_eq!(
+            auction_price(&bids, &runner_up, 150, 101, 1_000),
+            Err(ChannelError::NotAuctionWinner)
+        );
+        let second_turn = 101 + AUCTION_CLAIM_WINDOW;
+        assert_eq!(
+            auction_price(&bids, &runner_up, 150, second_turn, 1_000),
+            Ok(400)
+        );
+        let open = 101 + 2 * AUCTION_CLAIM_WINDOW;
+        assert_eq!(
+            auction_price(&bids, &Pubkey::new_unique(), 500, open, 1_000),
+            Ok(1_000)
+        );
+    }
+}
@@ -79,6 +79,52 @@ pub enum ChannelError {
     InvalidExecutionAccountOwner,
     #[error("Unexpected Proof System")]
     UnexpectedProofSystem,
+    #[error("Deployment Deprecated")]
+    DeploymentDeprecated,
+    #[error("Execution Not Expired")]
+    ExecutionNotExpired,
+    #[error("Invalid Prover Account")]
+    InvalidProverAccount,
+    #[error("Invalid Prover Registration")]
+    InvalidProverRegistration,
+    #[error("Insufficient Reputation")]
+    InsufficientReputation,
+    #[error("Claimer Not Allowed")]
+    ClaimerNotAllowed,
+    #[error("Invalid Allow List Account")]
+    InvalidAllowListAccount,
+    #[error("Invalid Auction")]
+    InvalidAuction,
+    #[error("Invalid Bid Account")]
+    InvalidBidAccount,
+    #[error("Invalid Bid")]
+    InvalidBid,
+    #[error("Auction Open")]
+    AuctionOpen,
+    #[error("Auction Closed")]
+    AuctionClosed,
+    #[error("Not Auction Winner")]
+    NotAuctionWinner,
+    #[error("Claim Required")]
+    ClaimRequired,
+    #[error("Invalid Verifier Config Account")]
+    InvalidVerifierConfigAccount,
+    #[error("Invalid Verifier Config")]
+    InvalidVerifierConfig,
+    #[error("Invalid Verifier Authority")]
+    InvalidVerifierAuthority,
+    #[error("Verifier Disabled")]
+    VerifierDisabled,
+    #[error("Invalid Batch")]
+    InvalidBatch,
+    #[error("Batch Unavailable")]
+    BatchUnavailable,
+    #[error("Invalid Prior Execution")]
+    InvalidPriorExecution,
+    #[error("Assumptions Dont Match")]
+    AssumptionsDontMatch,
+    #[error("Not Claimer")]
+    NotClaimer,
 }
 
 impl From<ChannelError> for ProgramError {
@@ -2,11 +2,16 @@
 #![cfg_attr(not(test), forbid(unsafe_code))]
 
 pub mod actions;
+pub mod allow_list;
+pub mod auction;
 pub mod error;
 pub mod program;
 pub mod proof_handling;
 pub mod prover;
+pub mod reputation;
+pub mod stake;
 pub mod utilities;
+pub mod verifier_config;
 
 mod assertions;
 mod verifying_key;
@@ -22,6 +22,32 @@ pub fn program<'a>(
         ChannelInstructionIxType::StatusV1 => {
             process_status_v1(accounts, ix)?;
         }
+        ChannelInstructionIxType::StatusBatchV1 => {
+            process_status_batch_v1(accounts, ix)?;
+        }
+        ChannelInstructionIxType::UpdateDeploymentUrlV1
+        | ChannelInstructionIxType::DeprecateDeploymentV1
+        | ChannelInstructionIxType::CloseDeploymentV1 => {
+            process_manage_deployment_v1(accounts, ix)?;
+        }
+        ChannelInstructionIxType::CancelV1 => {
+            process_cancel_v1(accounts, ix)?;
+        }
+        ChannelInstructionIxType::ExpireV1 => {
+            process_expire_v1(accounts, ix)?;
+        }
+        ChannelInstructionIxType::RegisterProverV1 => {
+            process_register_prover_v1(accounts, ix)?;
+        }
+        ChannelInstructionIxType::SetAllowListV1 | ChannelInstructionIxType::CloseAllowListV1 => {
+            process_allow_list_v1(accounts, ix)?;
+        }
+        ChannelInstructionIxType::BidV1 => {
+            process_bid_v1(accounts, ix)?;
+        }
+        ChannelInstructionIxType::SetVerifierConfigV1 => {
+            process_set_verifier_config_v1(accounts, ix)?;
+        }
         _ => return Err(ChannelError::InvalidInstruction.into()),
     };
     Ok(())
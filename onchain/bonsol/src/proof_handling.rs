@@ -4,34 +4,25 @@ use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate
 use groth16_solana::groth16::{Groth16Verifier, Groth16Verifyingkey};
 use solana_program::hash::hashv;
 
-use crate::{
-    error::ChannelError,
-    prover::{PROVER_CONSTANTS_V1_0_1, PROVER_CONSTANTS_V1_2_1},
-    verifying_key::VERIFYINGKEY,
-};
+use bonsol_interface::verifier_config::RISC0_PUBLIC_INPUTS;
 
-type G1 = ark_bn254::g1::G1Affine;
+use crate::{error::ChannelError, prover::ProverConstants};
 
-pub fn verify_risc0_v1_0_1(proof: &[u8], inputs: &[u8]) -> Result<bool, ChannelError> {
-    let ins: [[u8; 32]; 5] = [
-        sized_range::<32>(&inputs[0..32])?,
-        sized_range::<32>(&inputs[32..64])?,
-        sized_range::<32>(&inputs[64..96])?,
-        sized_range::<32>(&inputs[96..128])?,
-        sized_range::<32>(&inputs[128..160])?,
-    ];
-    verify_proof::<5>(proof, ins, &VERIFYINGKEY)
-}
+type G1 = ark_bn254::g1::G1Affine;
 
-pub fn verify_risc0_v1_2_1(proof: &[u8], inputs: &[u8]) -> Result<bool, ChannelError> {
-    let ins: [[u8; 32]; 5] = [
+pub fn verify_risc0(
+    proof: &[u8],
+    inputs: &[u8],
+    vkey: &Groth16Verifyingkey,
+) -> Result<bool, ChannelError> {
+    let ins: [[u8; 32]; RISC0_PUBLIC_INPUTS] = [
         sized_range::<32>(&inputs[0..32])?,
         sized_range::<32>(&inputs[32..64])?,
         sized_range::<32>(&inputs[64..96])?,
         sized_range::<32>(&inputs[96..128])?,
         sized_range::<32>(&inputs[128..160])?,
     ];
-    verify_proof::<5>(proof, ins, &VERIFYINGKEY)
+    verify_proof::<RISC0_PUBLIC_INPUTS>(proof, ins, vkey)
 }
 
 fn verify_proof<const NI: usize>(
@@ -66,7 +57,8 @@ fn verify_proof<const NI: usize>(
         .map_err(|_| ChannelError::ProofVerificationFailed)
 }
 
-pub fn output_digest_v1_0_1(
+pub fn output_digest(
+    constants: &ProverConstants,
     input_digest: &[u8],
     committed_outputs: &[u8],
     assumption_digest: &[u8],
@@ -74,7 +66,7 @@ pub fn output_digest_v1_0_1(
     let jbytes = [input_digest, committed_outputs].concat(); // bad copy here
     let journal = hashv(&[jbytes.as_slice()]);
     hashv(&[
-        PROVER_CONSTANTS_V1_0_1.output_hash.as_ref(),
+        constants.output_hash.as_ref(),
         journal.as_ref(),
         assumption_digest,
         &2u16.to_le_bytes(),
@@ -82,66 +74,38 @@ pub fn output_digest_v1_0_1(
     .to_bytes()
 }
 
-pub fn prepare_inputs_v1_0_1(
+pub fn prepare_inputs(
+    constants: &ProverConstants,
     image_id: &str,
     execution_digest: &[u8],
     output_digest: &[u8],
     system_exit_code: u32,
     user_exit_code: u32,
 ) -> Result<Vec<u8>, ChannelError> {
-    let imgbytes = hex::decode(image_id).map_err(|_| ChannelError::InvalidFieldElement)?;
-    let mut digest = hashv(&[
-        PROVER_CONSTANTS_V1_0_1.receipt_claim_hash.as_ref(),
-        &[0u8; 32],
-        &imgbytes,
+    let digest = receipt_claim_digest(
+        constants,
+        image_id,
         execution_digest,
         output_digest,
-        &system_exit_code.to_le_bytes(),
-        &user_exit_code.to_le_bytes(),
-        &4u16.to_le_bytes(),
-    ])
-    .to_bytes();
-    let (c0, c1) = split_digest_reversed(&mut PROVER_CONSTANTS_V1_0_1.control_root.clone())
-        .map_err(|_| ChannelError::InvalidFieldElement)?;
-    let (half1_bytes, half2_bytes) =
-        split_digest_reversed(&mut digest).map_err(|_| ChannelError::InvalidFieldElement)?;
-    let inputs = [
-        c0,
-        c1,
-        half1_bytes.try_into().unwrap(),
-        half2_bytes.try_into().unwrap(),
-        PROVER_CONSTANTS_V1_0_1.bn254_control_id_bytes,
-    ]
-    .concat();
-    Ok(inputs)
+        system_exit_code,
+        user_exit_code,
+    )?;
+    claim_inputs(constants, digest)
 }
 
-pub fn output_digest_v1_2_1(
-    input_digest: &[u8],
-    committed_outputs: &[u8],
-    assumption_digest: &[u8],
-) -> [u8; 32] {
-    let jbytes = [input_digest, committed_outputs].concat(); // bad copy here
-    let journal = hashv(&[jbytes.as_slice()]);
-    hashv(&[
-        PROVER_CONSTANTS_V1_2_1.output_hash.as_ref(),
-        journal.as_ref(),
-        assumption_digest,
-        &2u16.to_le_bytes(),
-    ])
-    .to_bytes()
-}
-
-pub fn prepare_inputs_v1_2_1(
+/// Digest of the receipt claim of an execution of `image_id`, a batch proof commits to the
+/// claims it aggregates by these digests
+pub fn receipt_claim_digest(
+    constants: &ProverConstants,
     image_id: &str,
     execution_digest: &[u8],
     output_digest: &[u8],
     system_exit_code: u32,
     user_exit_code: u32,
-) -> Result<Vec<u8>, ChannelError> {
+) -> Result<[u8; 32], ChannelError> {
     let imgbytes = hex::decode(image_id).map_err(|_| ChannelError::InvalidFieldElement)?;
-    let mut digest = hashv(&[
-        PROVER_CONSTANTS_V1_2_1.receipt_claim_hash.as_ref(),
+    let digest = hashv(&[
+        constants.receipt_claim_hash.as_ref(),
         &[0u8; 32],
         &imgbytes,
         execution_digest,
@@ -151,7 +115,37 @@ pub fn prepare_inputs_v1_2_1(
         &4u16.to_le_bytes(),
     ])
     .to_bytes();
-    let (c0, c1) = split_digest_reversed(&mut PROVER_CONSTANTS_V1_2_1.control_root.clone())
+    Ok(digest)
+}
+
+/// Digest of the assumptions a guest leaves when it verifies the receipt claims `claim_digests`
+/// in order. The guest adds every assumption to the front of its list, the digest of the empty
+/// list is zero.
+pub fn assumptions_digest(constants: &ProverConstants, claim_digests: &[[u8; 32]]) -> [u8; 32] {
+    claim_digests.iter().fold([0; 32], |list, claim| {
+        // assumptions are resolved with a receipt of any control root
+        let assumption = hashv(&[
+            constants.assumption_hash.as_ref(),
+            claim,
+            &[0u8; 32],
+            &2u16.to_le_bytes(),
+        ]);
+        hashv(&[
+            constants.assumptions_hash.as_ref(),
+            assumption.as_ref(),
+            &list,
+            &2u16.to_le_bytes(),
+        ])
+        .to_bytes()
+    })
+}
+
+/// The public inputs of a groth16 proof of the receipt claim with `digest`
+pub fn claim_inputs(
+    constants: &ProverConstants,
+    mut digest: [u8; 32],
+) -> Result<Vec<u8>, ChannelError> {
+    let (c0, c1) = split_digest_reversed(&mut constants.control_root.clone())
         .map_err(|_| ChannelError::InvalidFieldElement)?;
     let (half1_bytes, half2_bytes) =
         split_digest_reversed(&mut digest).map_err(|_| ChannelError::InvalidFieldElement)?;
@@ -160,7 +154,7 @@ pub fn prepare_inputs_v1_2_1(
         c1,
         half1_bytes.try_into().unwrap(),
         half2_bytes.try_into().unwrap(),
-        PROVER_CONSTANTS_V1_2_1.bn254_control_id_bytes,
+        constants.bn254_control_id_bytes,
     ]
     .concat();
     Ok(inputs)
@@ -303,6 +297,20 @@ mod tests {
         assert!(split_digest_reversed_256(&mut d2).is_err());
     }
 
+    #[test]
+    fn test_assumptions_digest() {
+        let constants = ProverConstants::default();
+        assert_eq!(assumptions_digest(&constants, &[]), [0; 32]);
+        assert_eq!(
+            assumptions_digest(&constants, &[[1; 32]]),
+            hex_literal::hex!("ffd324db33fce565230d0e2ac51e177ab0a717234c1e3689d5d8aa5ef5bc2c29")
+        );
+        assert_eq!(
+            assumptions_digest(&constants, &[[1; 32], [2; 32]]),
+            hex_literal::hex!("15467028d422d8e6e72cb362d644ea509ab4f5828672d533d086a3006ab23f3d")
+        );
+    }
+
     #[test]
     fn test_sized_range() {
         let slice = [1u8; 32];
@@ -6,6 +6,8 @@ pub struct ProverConstants {
     pub bn254_control_id_bytes: [u8; 32],
     pub output_hash: [u8; 32],
     pub receipt_claim_hash: [u8; 32],
+    pub assumption_hash: [u8; 32],
+    pub assumptions_hash: [u8; 32],
 }
 
 pub const PROVER_CONSTANTS_V1_0_1: ProverConstants = ProverConstants {
@@ -15,6 +17,8 @@ pub const PROVER_CONSTANTS_V1_0_1: ProverConstants = ProverConstants {
     ),
     output_hash: hex!("77eafeb366a78b47747de0d7bb176284085ff5564887009a5be63da32d3559d4"),
     receipt_claim_hash: hex!("cb1fefcd1f2d9a64975cbbbf6e161e2914434b0cbb9960b84df5d717e86b48af"),
+    assumption_hash: hex!("9fb524f65d5de53ce0b5dfeb62fd586678676f67a22f58b071c48a46505a2ee8"),
+    assumptions_hash: hex!("8e378d4256f07898df0bb8912f5da80f8e78448c2a7b321f9232e21124186839"),
 };
 
 pub const PROVER_CONSTANTS_V1_2_1: ProverConstants = ProverConstants {
@@ -24,6 +28,8 @@ pub const PROVER_CONSTANTS_V1_2_1: ProverConstants = ProverConstants {
     ),
     output_hash: hex!("77eafeb366a78b47747de0d7bb176284085ff5564887009a5be63da32d3559d4"),
     receipt_claim_hash: hex!("cb1fefcd1f2d9a64975cbbbf6e161e2914434b0cbb9960b84df5d717e86b48af"),
+    assumption_hash: hex!("9fb524f65d5de53ce0b5dfeb62fd586678676f67a22f58b071c48a46505a2ee8"),
+    assumptions_hash: hex!("8e378d4256f07898df0bb8912f5da80f8e78448c2a7b321f9232e21124186839"),
 };
 
 impl Default for ProverConstants {
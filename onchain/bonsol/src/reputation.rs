@@ -0,0 +1,97 @@
+//! Track record of registered provers.
+//! A prover registers a `ProverV1` account at `prover_address(operator)` with `RegisterProverV1`.
+//! Every instruction that settles a claim takes the registry account of the claimer and updates
+//! its counters, the account of a prover that never registered is left empty and nothing is
+//! recorded. Requesters can require a minimum reputation from the provers claiming their
+//! executions, those claims need a registered claimer.
+use bonsol_interface::{prover_state::ProverV1, util::prover_address_seeds};
+
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey, system_program};
+
+use crate::{assertions::*, error::ChannelError};
+
+/// The registration held by `prover_account`, None when `prover` never registered
+pub fn load_prover(
+    prover_account: &AccountInfo,
+    prover: &Pubkey,
+) -> Result<Option<ProverV1>, ChannelError> {
+    check_pda(
+        &prover_address_seeds(prover),
+        prover_account.key,
+        ChannelError::InvalidProverAccount,
+    )?;
+    if prover_account.data_len() == 0 && prover_account.owner == &system_program::ID {
+        return Ok(None);
+    }
+    check_owner(
+        prover_account,
+        &crate::ID,
+        ChannelError::InvalidProverAccount,
+    )?;
+    let data = prover_account
+        .try_borrow_data()
+        .map_err(|_| ChannelError::CannotBorrowData)?;
+    ProverV1::load_prover_owned(&data)
+        .map(Some)
+        .map_err(|_| ChannelError::InvalidProverAccount)
+}
+
+/// Applies `update` to the registration of `prover`, provers that never registered have no
+/// record to update
+pub fn record(
+    prover_account: &AccountInfo,
+    prover: &Pubkey,
+    update: impl FnOnce(&mut ProverV1),
+) -> Result<(), ChannelError> {
+    if let Some(mut registration) = load_prover(prover_account, prover)? {
+        check_writeable(prover_account, ChannelError::InvalidProverAccount)?;
+        update(&mut registration);
+        ProverV1::save_prover(&registration, prover_account);
+    }
+    Ok(())
+}
+
+/// Records the missed commitment of the prover holding a lapsed claim, its registry account is
+/// the instruction account at `index`
+pub fn record_missed_commitment(
+    accounts: &[AccountInfo],
+    index: usize,
+    claimer: &[u8; 32],
+) -> Result<(), ChannelError> {
+    let prover_account = accounts
+        .get(index)
+        .ok_or(ChannelError::InvalidProverAccount)?;
+    record(
+        prover_account,
+        &Pubkey::new_from_array(*claimer),
+        ProverV1::record_missed_commitment,
+    )
+}
+
+/// Whether `registration` may claim an execution requiring `min_reputation` and `min_proofs`,
+/// executions without requirements accept any prover
+pub fn eligible(registration: Option<&ProverV1>, min_reputation: u16, min_proofs: u64) -> bool {
+    if min_reputation == 0 && min_proofs == 0 {
+        return true;
+    }
+    registration.is_some_and(|r| r.meets(min_reputation, min_proofs))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn requirements_need_a_registered_prover() {
+        let mut registration = ProverV1::new(&Pubkey::new_unique(), 0);
+        assert!(eligible(None, 0, 0));
+        assert!(!eligible(None, 1, 0));
+        assert!(!eligible(None, 0, 1));
+        assert!(!eligible(Some(&registration), 0, 1));
+        registration.record_proof();
+        assert!(eligible(Some(&registration), 10_000, 1));
+        registration.record_slash();
+        assert!(!eligible(Some(&registration), 10_000, 1));
+        assert!(eligible(Some(&registration), 5_000, 1));
+    }
+}
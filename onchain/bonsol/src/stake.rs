@@ -0,0 +1,135 @@
+//! Lifecycle of the stake a prover escrows in the claim account when it claims an execution.
+//! * a verified proof from the claimer returns the stake to it with the tip
+//! * a claimer that does not deliver is slashed, its stake goes to the requester
+//! * a commitment that lapses before anyone settles it is split between the requester and the
+//!   prover that claims the execution next
+//!
+//! The claim account is closed once the execution is settled, its rent goes with the stake.
+use bonsol_interface::claim_state::ClaimStateV1;
+
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, rent::Rent,
+};
+
+use crate::utilities::*;
+
+/// Share of the tip a prover escrows when it claims an execution
+pub const fn claim_stake(tip: u64) -> u64 {
+    tip / 2
+}
+
+/// Lamports the claim account holds on top of its rent
+pub fn held_stake(exec_claim: &AccountInfo) -> u64 {
+    exec_claim
+        .lamports()
+        .saturating_sub(Rent::default().minimum_balance(exec_claim.data_len()))
+}
+
+/// How a proof submission settles the claim on its execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Settlement {
+    /// The claimer delivered a verified proof
+    Return,
+    /// The proof failed or was delivered by another prover
+    Slash,
+}
+
+/// The settlement of `claim` when `prover` submits a proof that `verified` or not
+pub fn status_settlement(claim: &ClaimStateV1, prover: &Pubkey, verified: bool) -> Settlement {
+    if verified && claim.claimer == prover.to_bytes() {
+        Settlement::Return
+    } else {
+        Settlement::Slash
+    }
+}
+
+/// The shares of a lapsed stake for the requester and the next claimer, the requester gets the
+/// odd lamport
+pub const fn split_stake(stake: u64) -> (u64, u64) {
+    let next_claimer = stake / 2;
+    (stake - next_claimer, next_claimer)
+}
+
+/// Settles the claim on a proven execution and closes the claim account, `prover` is paid when it
+/// holds the claim and the requester otherwise
+pub fn settle_stake(
+    exec_claim: &AccountInfo,
+    settlement: Settlement,
+    prover: &AccountInfo,
+    requester: &AccountInfo,
+) -> Result<(), ProgramError> {
+    match settlement {
+        Settlement::Return => close_program_account(exec_claim, prover),
+        Settlement::Slash => slash_stake(exec_claim, requester),
+    }
+}
+
+/// The claimer did not deliver, its stake goes to the requester and the claim account is closed
+pub fn slash_stake(exec_claim: &AccountInfo, requester: &AccountInfo) -> Result<(), ProgramError> {
+    close_program_account(exec_claim, requester)
+}
+
+/// Splits the `lapsed_stake` of a claim between the requester and the prover taking the claim
+/// over, the claim account stays open for the new claim
+pub fn split_lapsed_stake(
+    exec_claim: &AccountInfo,
+    lapsed_stake: u64,
+    requester: &AccountInfo,
+    next_claimer: &AccountInfo,
+) -> Result<(), ProgramError> {
+    let (to_requester, to_next_claimer) = split_stake(lapsed_stake);
+    transfer_owned(exec_claim, requester, to_requester)?;
+    transfer_owned(exec_claim, next_claimer, to_next_claimer)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn account<'a>(key: &'a Pubkey, lamports: &'a mut u64, data: &'a mut [u8]) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, true, lamports, data, &crate::ID, false, 0)
+    }
+
+    #[test]
+    fn only_the_claimer_gets_its_stake_back() {
+        let claimer = Pubkey::new_unique();
+        let claim = ClaimStateV1::from_claim_ix(&claimer, 10, 100, 1_000);
+        assert_eq!(
+            status_settlement(&claim, &claimer, true),
+            Settlement::Return
+        );
+        assert_eq!(
+            status_settlement(&claim, &claimer, false),
+            Settlement::Slash
+        );
+        assert_eq!(
+            status_settlement(&claim, &Pubkey::new_unique(), true),
+            Settlement::Slash
+        );
+    }
+
+    #[test]
+    fn lapsed_stake_is_split_with_the_next_claimer() {
+        assert_eq!(split_stake(1_000), (500, 500));
+        assert_eq!(split_stake(1_001), (501, 500));
+        assert_eq!(split_stake(0), (0, 0));
+
+        let rent = Rent::default().minimum_balance(std::mem::size_of::<ClaimStateV1>());
+        let (claim_key, requester_key, next_key) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut claim_data = [0; std::mem::size_of::<ClaimStateV1>()];
+        let (mut claim_lamports, mut requester_lamports, mut next_lamports) = (rent + 1_001, 0, 0);
+        let exec_claim = account(&claim_key, &mut claim_lamports, &mut claim_data);
+        let requester = account(&requester_key, &mut requester_lamports, &mut []);
+        let next_claimer = account(&next_key, &mut next_lamports, &mut []);
+        assert_eq!(held_stake(&exec_claim), 1_001);
+
+        split_lapsed_stake(&exec_claim, 1_001, &requester, &next_claimer).unwrap();
+        assert_eq!(exec_claim.lamports(), rent);
+        assert_eq!(requester.lamports(), 501);
+        assert_eq!(next_claimer.lamports(), 500);
+    }
+}
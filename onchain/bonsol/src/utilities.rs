@@ -4,9 +4,11 @@ use solana_program::{
     program_error::ProgramError,
     program_memory::{sol_memcpy, sol_memset},
     rent::Rent,
-    system_instruction,
+    system_instruction, system_program,
 };
 
+use bonsol_interface::{bonsol_schema::ExitCode, util::PROVEN_EXECUTION_LEN};
+
 use crate::error::ChannelError;
 pub fn cleanup_execution_account(
     exec: &AccountInfo,
@@ -18,9 +20,22 @@ pub fn cleanup_execution_account(
     refund(exec, requester)
 }
 
+/// Cleans up a proven execution, its account keeps the digest of the receipt claim that was
+/// verified after the exit code so that later executions can assume it
+pub fn record_proven_execution(
+    exec: &AccountInfo,
+    requester: &AccountInfo,
+    claim_digest: &[u8; 32],
+) -> Result<(), ProgramError> {
+    exec.realloc(PROVEN_EXECUTION_LEN, false)?;
+    let record = [&[ExitCode::Success as u8], claim_digest.as_ref()].concat();
+    sol_memcpy(&mut exec.data.borrow_mut(), &record, PROVEN_EXECUTION_LEN);
+    refund(exec, requester)
+}
+
 pub fn refund(exec: &AccountInfo, requester: &AccountInfo) -> Result<(), ProgramError> {
     //leave min lamports in the account so that account reuse is not possible
-    let lamports = Rent::default().minimum_balance(1);
+    let lamports = Rent::default().minimum_balance(exec.data_len());
     let refund = exec.lamports();
     **exec.try_borrow_mut_lamports()? = lamports;
     **requester.try_borrow_mut_lamports()? += refund - lamports;
@@ -66,6 +81,38 @@ pub fn save_structure<'a>(
     Ok(())
 }
 
+/// Replaces the data of a program account, `payer` covers the extra rent or gets back the rent
+/// that is no longer needed
+pub fn resave_structure<'a>(
+    account: &'a AccountInfo<'a>,
+    bytes: &[u8],
+    payer: &'a AccountInfo<'a>,
+) -> Result<(), ProgramError> {
+    let rent = Rent::default().minimum_balance(bytes.len());
+    let lamports = account.lamports();
+    if rent > lamports {
+        transfer_unowned(payer, account, rent - lamports)?;
+    }
+    account.realloc(bytes.len(), false)?;
+    sol_memcpy(&mut account.data.borrow_mut(), bytes, bytes.len());
+    if lamports > rent {
+        transfer_owned(account, payer, lamports - rent)?;
+    }
+    Ok(())
+}
+
+/// Empties a program account and hands it back to the system program, its lamports go to
+/// `destination`
+pub fn close_program_account(
+    account: &AccountInfo,
+    destination: &AccountInfo,
+) -> Result<(), ProgramError> {
+    transfer_owned(account, destination, account.lamports())?;
+    account.realloc(0, false)?;
+    account.assign(&system_program::ID);
+    Ok(())
+}
+
 pub fn create_program_account<'a>(
     account: &'a AccountInfo<'a>,
     seeds: &[&[u8]],
@@ -0,0 +1,136 @@
+//! Verifiers of prover versions kept on chain.
+//! `SetVerifierConfigV1` stores the groth16 verifying key, control root and bn254 control id of a
+//! prover version at `verifier_config_address(prover_version)`, `StatusV1` passes that account and
+//! the proof is verified with the config it holds. The verifiers compiled into the program check
+//! the proofs of the versions no config was set for, a disabled config rejects the proofs of its
+//! version. A config can name the batch image whose proofs settle several executions in one
+//! `StatusBatchV1`.
+use bonsol_interface::{
+    bonsol_schema::{root_as_verifier_config_v1, ProverVersion, VerifierConfigV1},
+    util::verifier_config_address_seeds,
+    verifier_config::{valid_verifier_config, verifier_version, G1_LEN},
+};
+use groth16_solana::groth16::Groth16Verifyingkey;
+use solana_program::{account_info::AccountInfo, system_program};
+
+use crate::{
+    assertions::*,
+    error::ChannelError,
+    prover::{ProverConstants, PROVER_CONSTANTS_V1_0_1, PROVER_CONSTANTS_V1_2_1},
+    verifying_key::VERIFYINGKEY,
+};
+
+/// The constants and verifying key the proofs of a prover version are checked with
+pub struct Verifier {
+    pub constants: ProverConstants,
+    /// The image aggregating batched proofs, only a config sets one
+    pub batch_image_id: Option<String>,
+    vk_alpha_g1: [u8; 64],
+    vk_beta_g2: [u8; 128],
+    vk_gamma_g2: [u8; 128],
+    vk_delta_g2: [u8; 128],
+    vk_ic: Vec<[u8; 64]>,
+}
+
+impl Verifier {
+    /// The verifier compiled into the program, unknown versions are verified as the latest
+    pub fn compiled(prover_version: ProverVersion) -> Self {
+        let constants = match prover_version {
+            ProverVersion::V1_0_1 => PROVER_CONSTANTS_V1_0_1,
+            _ => PROVER_CONSTANTS_V1_2_1,
+        };
+        Verifier {
+            constants,
+            batch_image_id: None,
+            vk_alpha_g1: VERIFYINGKEY.vk_alpha_g1,
+            vk_beta_g2: VERIFYINGKEY.vk_beta_g2,
+            vk_gamma_g2: VERIFYINGKEY.vk_gamme_g2,
+            vk_delta_g2: VERIFYINGKEY.vk_delta_g2,
+            vk_ic: VERIFYINGKEY.vk_ic.to_vec(),
+        }
+    }
+
+    pub fn verifying_key(&self) -> Groth16Verifyingkey {
+        Groth16Verifyingkey {
+            nr_pubinputs: self.vk_ic.len(),
+            vk_alpha_g1: self.vk_alpha_g1,
+            vk_beta_g2: self.vk_beta_g2,
+            vk_gamme_g2: self.vk_gamma_g2,
+            vk_delta_g2: self.vk_delta_g2,
+            vk_ic: &self.vk_ic,
+        }
+    }
+}
+
+impl TryFrom<&VerifierConfigV1<'_>> for Verifier {
+    type Error = ChannelError;
+
+    fn try_from(config: &VerifierConfigV1) -> Result<Self, Self::Error> {
+        if !valid_verifier_config(config) {
+            return Err(ChannelError::InvalidVerifierConfig);
+        }
+        fn bytes<const N: usize>(b: Option<flatbuffers::Vector<u8>>) -> [u8; N] {
+            // the lengths are checked by valid_verifier_config
+            b.unwrap().bytes().try_into().unwrap()
+        }
+        Ok(Verifier {
+            constants: ProverConstants {
+                control_root: bytes(config.control_root()),
+                bn254_control_id_bytes: bytes(config.bn254_control_id()),
+                // the digests of the risc0 type tags are the same for every version
+                ..PROVER_CONSTANTS_V1_2_1
+            },
+            batch_image_id: config.batch_image_id().map(str::to_string),
+            vk_alpha_g1: bytes(config.vk_alpha_g1()),
+            vk_beta_g2: bytes(config.vk_beta_g2()),
+            vk_gamma_g2: bytes(config.vk_gamma_g2()),
+            vk_delta_g2: bytes(config.vk_delta_g2()),
+            vk_ic: config
+                .vk_ic()
+                .unwrap()
+                .bytes()
+                .chunks_exact(G1_LEN)
+                .map(|point| point.try_into().unwrap())
+                .collect(),
+        })
+    }
+}
+
+/// Checks that `account` is the verifier config address of `prover_version`, returns the bump
+pub fn check_verifier_config_address(
+    account: &AccountInfo,
+    prover_version: ProverVersion,
+) -> Result<u8, ChannelError> {
+    let version = verifier_version(prover_version).0.to_le_bytes();
+    check_pda(
+        &verifier_config_address_seeds(&version),
+        account.key,
+        ChannelError::InvalidVerifierConfigAccount,
+    )
+}
+
+/// The verifier of `prover_version`, from its config in `account` or compiled into the program
+/// when the account was never set. Fails when the config disables the version.
+pub fn load_verifier(
+    account: &AccountInfo,
+    prover_version: ProverVersion,
+) -> Result<Verifier, ChannelError> {
+    check_verifier_config_address(account, prover_version)?;
+    if account.data_len() == 0 && account.owner == &system_program::ID {
+        return Ok(Verifier::compiled(prover_version));
+    }
+    check_owner(
+        account,
+        &crate::ID,
+        ChannelError::InvalidVerifierConfigAccount,
+    )?;
+    let data = account
+        .try_borrow_data()
+        .map_err(|_| ChannelError::CannotBorrowData)?;
+    let config =
+        root_as_verifier_config_v1(&data).map_err(|_| ChannelError::InvalidVerifierConfig)?;
+    if !config.enabled() {
+        return Err(ChannelError::VerifierDisabled);
+    }
+    Verifier::try_from(&config)
+}
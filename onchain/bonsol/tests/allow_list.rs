@@ -0,0 +1,88 @@
+//! Program tests for executions only some provers can claim.
+mod common;
+
+use bonsol::error::ChannelError;
+use bonsol_interface::{instructions, util::allow_list_address};
+use common::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+const ALLOW_LIST_ID: &str = "trusted";
+
+#[tokio::test]
+async fn only_listed_claimers_can_claim() {
+    let (listed, other) = (Keypair::new(), Keypair::new());
+    let execution = Execution::with_claimers(vec![listed.pubkey()], None);
+    let mut context = start(&execution, &[&listed, &other], None).await;
+
+    let payer = context.payer.pubkey();
+    let ix = execution.claim(&other.pubkey(), &payer, 100, None);
+    let result = send(&mut context, ix, &[&other]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::ClaimerNotAllowed)));
+
+    let ix = execution.claim(&listed.pubkey(), &payer, 100, None);
+    send(&mut context, ix, &[&listed]).await.unwrap();
+}
+
+#[tokio::test]
+async fn allow_list_admits_its_claimers_until_closed() {
+    let (authority, listed, other) = (Keypair::new(), Keypair::new(), Keypair::new());
+    let (allow_list, _) = allow_list_address(&authority.pubkey(), ALLOW_LIST_ID);
+    let execution = Execution::with_claimers(vec![], Some(allow_list));
+    let mut context = start(&execution, &[&authority, &listed, &other], None).await;
+    let payer = context.payer.pubkey();
+
+    let ix = instructions::set_allow_list_v1(&authority.pubkey(), ALLOW_LIST_ID, &[other.pubkey()])
+        .unwrap();
+    send(&mut context, ix, &[&authority]).await.unwrap();
+    // replacing the list drops the claimers it no longer has
+    let ix =
+        instructions::set_allow_list_v1(&authority.pubkey(), ALLOW_LIST_ID, &[listed.pubkey()])
+            .unwrap();
+    send(&mut context, ix, &[&authority]).await.unwrap();
+    let ix = execution.claim(&other.pubkey(), &payer, 100, None);
+    let result = send(&mut context, ix, &[&other]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::ClaimerNotAllowed)));
+
+    let ix = instructions::close_allow_list_v1(&authority.pubkey(), ALLOW_LIST_ID).unwrap();
+    send(&mut context, ix, &[&authority]).await.unwrap();
+    assert_eq!(
+        lamports(&mut context, &authority.pubkey()).await,
+        STARTING_BALANCE
+    );
+    let ix = execution.claim(&listed.pubkey(), &payer, 100, None);
+    let result = send(&mut context, ix, &[&listed]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::ClaimerNotAllowed)));
+}
+
+#[tokio::test]
+async fn listed_claimer_claims_through_the_allow_list() {
+    let (authority, listed) = (Keypair::new(), Keypair::new());
+    let (allow_list, _) = allow_list_address(&authority.pubkey(), ALLOW_LIST_ID);
+    let execution = Execution::with_claimers(vec![], Some(allow_list));
+    let mut context = start(&execution, &[&authority, &listed], None).await;
+
+    let ix =
+        instructions::set_allow_list_v1(&authority.pubkey(), ALLOW_LIST_ID, &[listed.pubkey()])
+            .unwrap();
+    send(&mut context, ix, &[&authority]).await.unwrap();
+    let payer = context.payer.pubkey();
+    let ix = execution.claim(&listed.pubkey(), &payer, 100, None);
+    send(&mut context, ix, &[&listed]).await.unwrap();
+}
+
+#[tokio::test]
+async fn only_the_authority_sets_its_allow_list() {
+    let (authority, intruder) = (Keypair::new(), Keypair::new());
+    let execution = Execution::new();
+    let mut context = start(&execution, &[&authority, &intruder], None).await;
+
+    let mut ix =
+        instructions::set_allow_list_v1(&authority.pubkey(), ALLOW_LIST_ID, &[intruder.pubkey()])
+            .unwrap();
+    ix.accounts[0].pubkey = intruder.pubkey();
+    let result = send(&mut context, ix, &[&intruder]).await;
+    assert_eq!(
+        result,
+        Err(channel_error(ChannelError::InvalidPayerAccount))
+    );
+}
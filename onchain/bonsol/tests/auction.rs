@@ -0,0 +1,154 @@
+//! Program tests for executions whose claim is auctioned to the lowest bid.
+mod common;
+
+use bonsol::error::ChannelError;
+use bonsol_interface::{
+    bonsol_schema::ExitCode, claim_state::ClaimStateV1, instructions, util::EXPIRE_CRANK_FEE,
+};
+use common::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+const AUCTION_END: u64 = 50;
+
+#[tokio::test]
+async fn lowest_bid_claims_for_its_price() {
+    let execution = Execution::with_auction(AUCTION_END);
+    let (winner, outbid) = (Keypair::new(), Keypair::new());
+    let mut context = start(&execution, &[&winner, &outbid], None).await;
+    let requester = execution.requester.pubkey();
+
+    for (bidder, price) in [(&outbid, TIP / 2), (&winner, TIP / 4)] {
+        let ix = instructions::bid_v1(&bidder.pubkey(), &requester, EXECUTION_ID, price, 200, None)
+            .unwrap();
+        send(&mut context, ix, &[bidder]).await.unwrap();
+    }
+    context.warp_to_slot(AUCTION_END + 1).unwrap();
+
+    let payer = context.payer.pubkey();
+    let ix = execution.claim(&outbid.pubkey(), &payer, 200, None);
+    let result = send(&mut context, ix, &[&outbid]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::NotAuctionWinner)));
+    let ix = execution.claim(&winner.pubkey(), &payer, 300, None);
+    let result = send(&mut context, ix, &[&winner]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::InvalidBid)));
+
+    let ix = execution.claim(&winner.pubkey(), &payer, 200, None);
+    send(&mut context, ix, &[&winner]).await.unwrap();
+    let claim = context
+        .banks_client
+        .get_account(execution.exec_claim)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(claim.lamports, claim_rent() + TIP / 8);
+    let claim = ClaimStateV1::load_claim_owned(&claim.data).unwrap();
+    assert_eq!(claim.price, TIP / 4);
+    assert_eq!(lamports(&mut context, &execution.bids).await, 0);
+    assert_eq!(
+        lamports(&mut context, &requester).await,
+        STARTING_BALANCE + bids_rent()
+    );
+}
+
+#[tokio::test]
+async fn bids_are_only_taken_while_the_auction_is_open() {
+    let execution = Execution::with_auction(AUCTION_END);
+    let bidder = Keypair::new();
+    let mut context = start(&execution, &[&bidder], None).await;
+    let requester = execution.requester.pubkey();
+
+    let ix = instructions::bid_v1(
+        &bidder.pubkey(),
+        &requester,
+        EXECUTION_ID,
+        TIP + 1,
+        200,
+        None,
+    )
+    .unwrap();
+    let result = send(&mut context, ix, &[&bidder]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::InvalidBid)));
+    // the proof cannot be committed to before the auction ends
+    let ix = instructions::bid_v1(
+        &bidder.pubkey(),
+        &requester,
+        EXECUTION_ID,
+        TIP,
+        AUCTION_END,
+        None,
+    )
+    .unwrap();
+    let result = send(&mut context, ix, &[&bidder]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::InvalidBid)));
+
+    let payer = context.payer.pubkey();
+    let ix = execution.claim(&bidder.pubkey(), &payer, 200, None);
+    let result = send(&mut context, ix, &[&bidder]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::AuctionOpen)));
+
+    context.warp_to_slot(AUCTION_END + 1).unwrap();
+    let ix =
+        instructions::bid_v1(&bidder.pubkey(), &requester, EXECUTION_ID, TIP, 200, None).unwrap();
+    let result = send(&mut context, ix, &[&bidder]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::AuctionClosed)));
+}
+
+#[tokio::test]
+async fn auction_without_bids_is_claimed_for_the_tip() {
+    let execution = Execution::with_auction(AUCTION_END);
+    let claimer = Keypair::new();
+    let mut context = start(&execution, &[&claimer], None).await;
+    context.warp_to_slot(AUCTION_END + 1).unwrap();
+
+    let payer = context.payer.pubkey();
+    let ix = execution.claim(&claimer.pubkey(), &payer, 200, None);
+    send(&mut context, ix, &[&claimer]).await.unwrap();
+    assert_eq!(
+        lamports(&mut context, &execution.exec_claim).await,
+        claim_rent() + TIP / 2
+    );
+}
+
+#[tokio::test]
+async fn unclaimed_auction_cannot_be_proven() {
+    let execution = Execution::with_auction(AUCTION_END);
+    let prover = Keypair::new();
+    let mut context = start(&execution, &[&prover], None).await;
+
+    let ix = execution.proving_failed(&prover.pubkey());
+    let result = send(&mut context, ix, &[&prover]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::ClaimRequired)));
+}
+
+#[tokio::test]
+async fn cancel_and_expire_close_the_bids() {
+    let execution = Execution::with_auction(AUCTION_END);
+    let mut context = start(&execution, &[], None).await;
+    let ix = instructions::cancel_v1(&execution.requester.pubkey(), EXECUTION_ID, None).unwrap();
+    send(&mut context, ix, &[&execution.requester])
+        .await
+        .unwrap();
+    assert_settled(&mut context, &execution, ExitCode::Cancelled).await;
+    assert_eq!(
+        lamports(&mut context, &execution.requester.pubkey()).await,
+        STARTING_BALANCE + execution.refund() + bids_rent()
+    );
+
+    let execution = Execution::with_auction(AUCTION_END);
+    let cranker = Keypair::new();
+    let mut context = start(&execution, &[&cranker], None).await;
+    context.warp_to_slot(MAX_BLOCK_HEIGHT + 1).unwrap();
+    let ix = instructions::expire_v1(
+        &cranker.pubkey(),
+        &execution.requester.pubkey(),
+        EXECUTION_ID,
+        None,
+    )
+    .unwrap();
+    send(&mut context, ix, &[&cranker]).await.unwrap();
+    assert_settled(&mut context, &execution, ExitCode::Expired).await;
+    assert_eq!(
+        lamports(&mut context, &execution.requester.pubkey()).await,
+        STARTING_BALANCE + execution.refund() - EXPIRE_CRANK_FEE + bids_rent()
+    );
+}
@@ -0,0 +1,526 @@
+//! Harness for the program tests, it runs the program on a pending execution request.
+#![allow(dead_code)]
+use bonsol::error::ChannelError;
+use bonsol_interface::{
+    bid_state::BidStateV1,
+    bonsol_schema::{
+        parse_ix_data, prior_execution_input, ChannelInstruction, ChannelInstructionArgs,
+        ChannelInstructionIxType, ClaimV1, ClaimV1Args, ExitCode, ProgramInputType, ProverVersion,
+        StatusBatchV1, StatusBatchV1Args, StatusTypes, StatusV1, StatusV1Args,
+    },
+    claim_state::ClaimStateV1,
+    instructions::{self, ExecutionConfig, InputRef},
+    prover_state::ProverV1,
+    util::{
+        deployment_address, execution_address, execution_bids_address, execution_claim_address,
+        prior_execution_address, prover_address, verifier_config_address,
+    },
+    verifier_config::VerifierConfig,
+};
+use flatbuffers::FlatBufferBuilder;
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+pub const EXECUTION_ID: &str = "stake";
+pub const IMAGE_ID: &str = "0a0b0c0d0e0f0a0b0c0d0e0f0a0b0c0d0e0f0a0b0c0d0e0f0a0b0c0d0e0f0a0b";
+pub const TIP: u64 = 1_000_000;
+pub const MAX_BLOCK_HEIGHT: u64 = 1_000;
+pub const STARTING_BALANCE: u64 = 1_000_000_000;
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // the program ties the accounts and the instruction data to one lifetime
+    let accounts = Box::leak(accounts.to_vec().into_boxed_slice());
+    let instruction_data = Box::leak(instruction_data.to_vec().into_boxed_slice());
+    bonsol::program::program(program_id, accounts, instruction_data)
+}
+
+pub struct Execution {
+    pub requester: Keypair,
+    pub exec: Pubkey,
+    pub exec_claim: Pubkey,
+    pub bids: Pubkey,
+    pub min_reputation: u16,
+    pub min_proofs: u64,
+    pub allowed_claimers: Vec<Pubkey>,
+    pub claimer_allow_list: Option<Pubkey>,
+    pub auction_slots: u64,
+    /// Data of the `PriorExecution` inputs the execution takes after its public input
+    pub prior_executions: Vec<Vec<u8>>,
+}
+
+impl Execution {
+    pub fn new() -> Self {
+        Self::with_requirements(0, 0)
+    }
+
+    /// An execution only provers with `min_reputation` and `min_proofs` can claim
+    pub fn with_requirements(min_reputation: u16, min_proofs: u64) -> Self {
+        let requester = Keypair::new();
+        let (exec, _) = execution_address(&requester.pubkey(), EXECUTION_ID.as_bytes());
+        let (exec_claim, _) = execution_claim_address(exec.as_ref());
+        let (bids, _) = execution_bids_address(exec.as_ref());
+        Execution {
+            requester,
+            exec,
+            exec_claim,
+            bids,
+            min_reputation,
+            min_proofs,
+            allowed_claimers: Vec::new(),
+            claimer_allow_list: None,
+            auction_slots: 0,
+            prior_executions: Vec::new(),
+        }
+    }
+
+    /// An execution auctioned until block `auction_slots`, it was requested at block 0
+    pub fn with_auction(auction_slots: u64) -> Self {
+        Execution {
+            auction_slots,
+            ..Self::new()
+        }
+    }
+
+    /// An execution only `allowed_claimers` and the provers in `claimer_allow_list` can claim
+    pub fn with_claimers(
+        allowed_claimers: Vec<Pubkey>,
+        claimer_allow_list: Option<Pubkey>,
+    ) -> Self {
+        Execution {
+            allowed_claimers,
+            claimer_allow_list,
+            ..Self::new()
+        }
+    }
+
+    /// An execution assuming the executions `priors`
+    pub fn with_prior_executions(priors: &[&Execution]) -> Self {
+        Execution {
+            prior_executions: priors
+                .iter()
+                .map(|prior| {
+                    prior_execution_input(&prior.requester.pubkey().to_bytes(), EXECUTION_ID)
+                })
+                .collect(),
+            ..Self::new()
+        }
+    }
+
+    /// A claim by `claimer`, `lapsed` is the prover holding the lapsed claim it takes over. The
+    /// allow list the execution references and the bids of an auctioned execution are passed
+    /// last.
+    pub fn claim(
+        &self,
+        claimer: &Pubkey,
+        payer: &Pubkey,
+        block_commitment: u64,
+        lapsed: Option<&Pubkey>,
+    ) -> Instruction {
+        let mut fbb = FlatBufferBuilder::new();
+        let execution_id = fbb.create_string(EXECUTION_ID);
+        let claim = ClaimV1::create(
+            &mut fbb,
+            &ClaimV1Args {
+                execution_id: Some(execution_id),
+                block_commitment,
+            },
+        );
+        fbb.finish(claim, None);
+        let mut accounts = vec![
+            AccountMeta::new(self.exec, false),
+            AccountMeta::new(self.requester.pubkey(), false),
+            AccountMeta::new(self.exec_claim, false),
+            AccountMeta::new(*claimer, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(prover_address(claimer).0, false),
+        ];
+        if let Some(lapsed) = lapsed {
+            accounts.push(AccountMeta::new(prover_address(lapsed).0, false));
+        }
+        if let Some(allow_list) = self.claimer_allow_list {
+            accounts.push(AccountMeta::new_readonly(allow_list, false));
+        }
+        if self.auction_slots > 0 {
+            accounts.push(AccountMeta::new(self.bids, false));
+        }
+        channel_instruction(
+            ChannelInstructionIxType::ClaimV1,
+            fbb.finished_data(),
+            accounts,
+        )
+    }
+
+    /// A status without a proof, the prover could not prove the execution
+    pub fn proving_failed(&self, prover: &Pubkey) -> Instruction {
+        let verifier_config = verifier_config_address(ProverVersion::DEFAULT).0;
+        self.status(prover, false, &verifier_config)
+    }
+
+    /// A status with a proof that does not verify, passing `verifier_config` as the verifier
+    /// config account
+    pub fn proven(&self, prover: &Pubkey, verifier_config: &Pubkey) -> Instruction {
+        self.status(prover, true, verifier_config)
+    }
+
+    fn status(&self, prover: &Pubkey, with_proof: bool, verifier_config: &Pubkey) -> Instruction {
+        let mut fbb = FlatBufferBuilder::new();
+        let execution_id = fbb.create_string(EXECUTION_ID);
+        let args = if with_proof {
+            StatusV1Args {
+                execution_id: Some(execution_id),
+                status: StatusTypes::Completed,
+                proof: Some(fbb.create_vector(&[1u8; 256])),
+                execution_digest: Some(fbb.create_vector(&[2u8; 32])),
+                input_digest: Some(fbb.create_vector(&[3u8; 32])),
+                assumption_digest: Some(fbb.create_vector(&[0u8; 32])),
+                committed_outputs: Some(fbb.create_vector(&[5u8; 32])),
+                ..Default::default()
+            }
+        } else {
+            StatusV1Args {
+                execution_id: Some(execution_id),
+                status: StatusTypes::Failed,
+                ..Default::default()
+            }
+        };
+        let status = StatusV1::create(&mut fbb, &args);
+        fbb.finish(status, None);
+        let mut accounts = vec![
+            AccountMeta::new(self.requester.pubkey(), false),
+            AccountMeta::new(self.exec, false),
+            AccountMeta::new_readonly(bonsol::ID, false),
+            AccountMeta::new(*prover, true),
+            AccountMeta::new(self.exec_claim, false),
+            AccountMeta::new(prover_address(prover).0, false),
+            AccountMeta::new_readonly(*verifier_config, false),
+        ];
+        accounts.extend(self.prior_executions.iter().map(|prior| {
+            AccountMeta::new_readonly(prior_execution_address(prior).unwrap(), false)
+        }));
+        channel_instruction(
+            ChannelInstructionIxType::StatusV1,
+            fbb.finished_data(),
+            accounts,
+        )
+    }
+
+    pub fn request(&self) -> Vec<u8> {
+        let ix = instructions::execute_v1(
+            &self.requester.pubkey(),
+            &self.requester.pubkey(),
+            IMAGE_ID,
+            EXECUTION_ID,
+            [InputRef::public(b"input")]
+                .into_iter()
+                .chain(
+                    self.prior_executions
+                        .iter()
+                        .map(|p| InputRef::prior_execution(p)),
+                )
+                .collect(),
+            TIP,
+            MAX_BLOCK_HEIGHT,
+            ExecutionConfig {
+                verify_input_hash: false,
+                input_hash: None,
+                forward_output: false,
+                min_reputation: self.min_reputation,
+                min_proofs: self.min_proofs,
+                allowed_claimers: self.allowed_claimers.clone(),
+                claimer_allow_list: self.claimer_allow_list,
+                auction_slots: self.auction_slots,
+            },
+            None,
+            None,
+        )
+        .unwrap();
+        let ix = parse_ix_data(&ix.data).unwrap();
+        ix.execute_v1().unwrap().bytes().to_vec()
+    }
+
+    /// The refund of the settled execution, the rent and the tip less what the execution account
+    /// keeps
+    pub fn refund(&self) -> u64 {
+        Rent::default().minimum_balance(self.request().len()) + TIP
+            - Rent::default().minimum_balance(1)
+    }
+}
+
+/// A batch settling `executions` with a proof that does not verify
+pub fn batch(executions: &[&Execution], prover: &Pubkey, verifier_config: &Pubkey) -> Instruction {
+    let mut fbb = FlatBufferBuilder::new();
+    let statuses = executions
+        .iter()
+        .map(|_| {
+            let execution_id = fbb.create_string(EXECUTION_ID);
+            let args = StatusV1Args {
+                execution_id: Some(execution_id),
+                status: StatusTypes::Completed,
+                execution_digest: Some(fbb.create_vector(&[2u8; 32])),
+                input_digest: Some(fbb.create_vector(&[3u8; 32])),
+                assumption_digest: Some(fbb.create_vector(&[0u8; 32])),
+                committed_outputs: Some(fbb.create_vector(&[5u8; 32])),
+                ..Default::default()
+            };
+            StatusV1::create(&mut fbb, &args)
+        })
+        .collect::<Vec<_>>();
+    let args = StatusBatchV1Args {
+        proof: Some(fbb.create_vector(&[1u8; 256])),
+        execution_digest: Some(fbb.create_vector(&[6u8; 32])),
+        statuses: Some(fbb.create_vector(&statuses)),
+    };
+    let batch = StatusBatchV1::create(&mut fbb, &args);
+    fbb.finish(batch, None);
+    let mut accounts = vec![
+        AccountMeta::new(*prover, true),
+        AccountMeta::new_readonly(*verifier_config, false),
+    ];
+    for execution in executions {
+        accounts.extend([
+            AccountMeta::new(execution.requester.pubkey(), false),
+            AccountMeta::new(execution.exec, false),
+            AccountMeta::new_readonly(bonsol::ID, false),
+            AccountMeta::new(execution.exec_claim, false),
+            AccountMeta::new(prover_address(prover).0, false),
+        ]);
+    }
+    channel_instruction(
+        ChannelInstructionIxType::StatusBatchV1,
+        fbb.finished_data(),
+        accounts,
+    )
+}
+
+fn channel_instruction(
+    ix_type: ChannelInstructionIxType,
+    data: &[u8],
+    accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut fbb = FlatBufferBuilder::new();
+    let data = fbb.create_vector(data);
+    let (claim_v1, status_v1, status_batch_v1) = match ix_type {
+        ChannelInstructionIxType::ClaimV1 => (Some(data), None, None),
+        ChannelInstructionIxType::StatusBatchV1 => (None, None, Some(data)),
+        _ => (None, Some(data), None),
+    };
+    let ix = ChannelInstruction::create(
+        &mut fbb,
+        &ChannelInstructionArgs {
+            ix_type,
+            claim_v1,
+            status_v1,
+            status_batch_v1,
+            ..Default::default()
+        },
+    );
+    fbb.finish(ix, None);
+    Instruction::new_with_bytes(bonsol::ID, fbb.finished_data(), accounts)
+}
+
+fn claim_state(claimer: &Pubkey, claimed_at: u64, block_commitment: u64) -> Vec<u8> {
+    [
+        claimer.as_ref(),
+        &claimed_at.to_le_bytes(),
+        &block_commitment.to_le_bytes(),
+        &TIP.to_le_bytes(),
+    ]
+    .concat()
+}
+
+fn system_account(lamports: u64) -> Account {
+    Account::new(lamports, 0, &system_program::ID)
+}
+
+fn program_account(data: Vec<u8>, extra_lamports: u64) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()) + extra_lamports,
+        data,
+        owner: bonsol::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub fn claim_rent() -> u64 {
+    Rent::default().minimum_balance(std::mem::size_of::<ClaimStateV1>())
+}
+
+pub fn bids_rent() -> u64 {
+    Rent::default().minimum_balance(std::mem::size_of::<BidStateV1>())
+}
+
+/// The program with a pending execution, optionally claimed by `claim` until a block. The claim
+/// is for the tip.
+pub fn program(
+    execution: &Execution,
+    provers: &[&Keypair],
+    claim: Option<(Pubkey, u64, u64)>,
+) -> ProgramTest {
+    let mut program_test = ProgramTest::new("bonsol", bonsol::ID, processor!(process_instruction));
+    add_execution(&mut program_test, execution);
+    for prover in provers {
+        program_test.add_account(prover.pubkey(), system_account(STARTING_BALANCE));
+    }
+    if execution.auction_slots > 0 {
+        let bids = BidStateV1::new(execution.auction_slots);
+        program_test.add_account(
+            execution.bids,
+            program_account(bytemuck::bytes_of(&bids).to_vec(), 0),
+        );
+    }
+    if let Some((claimer, block_commitment, stake)) = claim {
+        program_test.add_account(
+            execution.exec_claim,
+            program_account(claim_state(&claimer, 1, block_commitment), stake),
+        );
+    }
+    program_test
+}
+
+/// Adds another pending execution to the program
+pub fn add_execution(program_test: &mut ProgramTest, execution: &Execution) {
+    program_test.add_account(
+        execution.requester.pubkey(),
+        system_account(STARTING_BALANCE),
+    );
+    program_test.add_account(execution.exec, program_account(execution.request(), TIP));
+}
+
+/// Adds `execution` settled by a proof of the receipt claim with `claim_digest`
+pub fn add_proven_execution(
+    program_test: &mut ProgramTest,
+    execution: &Execution,
+    claim_digest: [u8; 32],
+) {
+    let data = [&[ExitCode::Success as u8], claim_digest.as_ref()].concat();
+    program_test.add_account(execution.exec, program_account(data, 0));
+}
+
+/// Stores `config` as the verifier config of its prover version
+pub fn add_verifier_config(program_test: &mut ProgramTest, config: &VerifierConfig) {
+    let ix = instructions::set_verifier_config_v1(&Pubkey::new_unique(), config).unwrap();
+    let ix = parse_ix_data(&ix.data).unwrap();
+    program_test.add_account(
+        verifier_config_address(config.prover_version).0,
+        program_account(ix.verifier_config_v1().unwrap().bytes().to_vec(), 0),
+    );
+}
+
+/// Deploys the image the executions request, it takes one public input
+pub fn add_deployment(program_test: &mut ProgramTest) {
+    let ix = instructions::deploy_v1(
+        &Pubkey::new_unique(),
+        IMAGE_ID,
+        1,
+        &[0; 32],
+        "stake",
+        "https://example.com/stake",
+        vec![ProgramInputType::Public],
+    )
+    .unwrap();
+    let ix = parse_ix_data(&ix.data).unwrap();
+    program_test.add_account(
+        deployment_address(IMAGE_ID).0,
+        program_account(ix.deploy_v1().unwrap().bytes().to_vec(), 0),
+    );
+}
+
+/// Starts the program with a pending execution, optionally claimed by `claim` until a block
+pub async fn start(
+    execution: &Execution,
+    provers: &[&Keypair],
+    claim: Option<(Pubkey, u64, u64)>,
+) -> ProgramTestContext {
+    program(execution, provers, claim)
+        .start_with_context()
+        .await
+}
+
+/// Adds the registration of a prover to the program
+pub fn add_registration(program_test: &mut ProgramTest, registration: &ProverV1) {
+    let operator = Pubkey::new_from_array(registration.operator);
+    program_test.add_account(
+        prover_address(&operator).0,
+        program_account(bytemuck::bytes_of(registration).to_vec(), 0),
+    );
+}
+
+pub async fn registration(context: &mut ProgramTestContext, operator: &Pubkey) -> Option<ProverV1> {
+    context
+        .banks_client
+        .get_account(prover_address(operator).0)
+        .await
+        .unwrap()
+        .map(|a| ProverV1::load_prover_owned(&a.data).unwrap())
+}
+
+pub async fn send(
+    context: &mut ProgramTestContext,
+    ix: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), TransactionError> {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .map_err(|e| e.unwrap())
+}
+
+pub async fn lamports(context: &mut ProgramTestContext, key: &Pubkey) -> u64 {
+    context
+        .banks_client
+        .get_account(*key)
+        .await
+        .unwrap()
+        .map(|a| a.lamports)
+        .unwrap_or_default()
+}
+
+pub async fn assert_settled(
+    context: &mut ProgramTestContext,
+    execution: &Execution,
+    code: ExitCode,
+) {
+    let exec = context
+        .banks_client
+        .get_account(execution.exec)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(exec.data, vec![code as u8]);
+    let claim = context
+        .banks_client
+        .get_account(execution.exec_claim)
+        .await
+        .unwrap();
+    assert!(claim.is_none(), "the claim account is closed");
+}
+
+pub fn channel_error(e: ChannelError) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(e as u32))
+}
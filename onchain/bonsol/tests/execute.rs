@@ -0,0 +1,52 @@
+//! Program tests for new execution requests.
+mod common;
+
+use bonsol_interface::{
+    instructions::{self, ExecutionConfig, InputRef},
+    util::execution_address,
+};
+use common::*;
+use solana_sdk::{
+    rent::Rent,
+    signature::{Keypair, Signer},
+};
+
+#[tokio::test]
+async fn execute_escrows_the_tip() {
+    let requester = Keypair::new();
+    let mut program_test = program(&Execution::new(), &[&requester], None);
+    add_deployment(&mut program_test);
+    let mut context = program_test.start_with_context().await;
+
+    let ix = instructions::execute_v1(
+        &requester.pubkey(),
+        &requester.pubkey(),
+        IMAGE_ID,
+        EXECUTION_ID,
+        vec![InputRef::public(b"input")],
+        TIP,
+        MAX_BLOCK_HEIGHT,
+        ExecutionConfig {
+            verify_input_hash: false,
+            ..Default::default()
+        },
+        None,
+        None,
+    )
+    .unwrap();
+    send(&mut context, ix, &[&requester]).await.unwrap();
+
+    let (exec, _) = execution_address(&requester.pubkey(), EXECUTION_ID.as_bytes());
+    let account = context
+        .banks_client
+        .get_account(exec)
+        .await
+        .unwrap()
+        .unwrap();
+    let rent = Rent::default().minimum_balance(account.data.len());
+    assert_eq!(account.lamports, rent + TIP);
+    assert_eq!(
+        lamports(&mut context, &requester.pubkey()).await,
+        STARTING_BALANCE - rent - TIP
+    );
+}
@@ -0,0 +1,82 @@
+//! Program tests for executions assuming the proven outputs of prior executions.
+mod common;
+
+use bonsol::error::ChannelError;
+use bonsol_interface::{bonsol_schema::ProverVersion, util::verifier_config_address};
+use common::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn pending_prior_executions_cannot_be_assumed() {
+    let prover = Keypair::new();
+    let prior = Execution::new();
+    let execution = Execution::with_prior_executions(&[&prior]);
+    let mut program_test = program(&execution, &[&prover], None);
+    add_execution(&mut program_test, &prior);
+    let mut context = program_test.start_with_context().await;
+
+    let verifier_config = verifier_config_address(ProverVersion::DEFAULT).0;
+    let ix = execution.proven(&prover.pubkey(), &verifier_config);
+    let result = send(&mut context, ix, &[&prover]).await;
+    assert_eq!(
+        result,
+        Err(channel_error(ChannelError::InvalidPriorExecution))
+    );
+}
+
+#[tokio::test]
+async fn statuses_pass_the_prior_execution_accounts() {
+    let prover = Keypair::new();
+    let prior = Execution::new();
+    let execution = Execution::with_prior_executions(&[&prior]);
+    let mut program_test = program(&execution, &[&prover], None);
+    add_proven_execution(&mut program_test, &prior, [8; 32]);
+    let mut context = program_test.start_with_context().await;
+
+    let verifier_config = verifier_config_address(ProverVersion::DEFAULT).0;
+    let mut ix = execution.proven(&prover.pubkey(), &verifier_config);
+    ix.accounts.pop();
+    let result = send(&mut context, ix, &[&prover]).await;
+    assert_eq!(
+        result,
+        Err(channel_error(ChannelError::InvalidPriorExecution))
+    );
+}
+
+#[tokio::test]
+async fn proofs_assume_the_claims_of_the_prior_executions() {
+    let prover = Keypair::new();
+    let prior = Execution::new();
+    let execution = Execution::with_prior_executions(&[&prior]);
+    let mut program_test = program(&execution, &[&prover], None);
+    add_proven_execution(&mut program_test, &prior, [8; 32]);
+    let mut context = program_test.start_with_context().await;
+
+    // the status claims no assumptions while the execution assumes the prior one
+    let verifier_config = verifier_config_address(ProverVersion::DEFAULT).0;
+    let ix = execution.proven(&prover.pubkey(), &verifier_config);
+    let result = send(&mut context, ix, &[&prover]).await;
+    assert_eq!(
+        result,
+        Err(channel_error(ChannelError::AssumptionsDontMatch))
+    );
+    assert_eq!(
+        lamports(&mut context, &prover.pubkey()).await,
+        STARTING_BALANCE
+    );
+}
+
+#[tokio::test]
+async fn executions_assuming_prior_executions_are_not_batched() {
+    let prover = Keypair::new();
+    let prior = Execution::new();
+    let execution = Execution::with_prior_executions(&[&prior]);
+    let mut program_test = program(&execution, &[&prover], None);
+    add_proven_execution(&mut program_test, &prior, [8; 32]);
+    let mut context = program_test.start_with_context().await;
+
+    let verifier_config = verifier_config_address(ProverVersion::DEFAULT).0;
+    let ix = batch(&[&execution], &prover.pubkey(), &verifier_config);
+    let result = send(&mut context, ix, &[&prover]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::InvalidBatch)));
+}
@@ -0,0 +1,178 @@
+//! Program tests for the registry of provers and the counters of their claims.
+mod common;
+
+use bonsol::error::ChannelError;
+use bonsol_interface::{
+    bonsol_schema::{ExitCode, ProverVersion},
+    instructions,
+    prover_state::ProverV1,
+};
+use common::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// A registration with `proofs` proofs and `slashes` slashes
+fn track_record(operator: &Keypair, proofs: u64, slashes: u64) -> ProverV1 {
+    let mut registration = ProverV1::new(&operator.pubkey(), 1);
+    registration.claims = proofs + slashes;
+    registration.proofs = proofs;
+    registration.slashes = slashes;
+    registration
+}
+
+#[tokio::test]
+async fn registration_keeps_the_claim_history() {
+    let execution = Execution::new();
+    let prover = Keypair::new();
+    let mut context = start(&execution, &[&prover], None).await;
+
+    let ix = instructions::register_prover_v1(
+        &prover.pubkey(),
+        "https://prover.example",
+        &[ProverVersion::V1_2_1],
+    )
+    .unwrap();
+    send(&mut context, ix, &[&prover]).await.unwrap();
+    let payer = context.payer.pubkey();
+    let ix = execution.claim(&prover.pubkey(), &payer, 100, None);
+    send(&mut context, ix, &[&prover]).await.unwrap();
+    let ix = instructions::register_prover_v1(
+        &prover.pubkey(),
+        "https://moved.example",
+        &[ProverVersion::V1_0_1, ProverVersion::V1_2_1],
+    )
+    .unwrap();
+    send(&mut context, ix, &[&prover]).await.unwrap();
+
+    let registration = registration(&mut context, &prover.pubkey()).await.unwrap();
+    assert_eq!(registration.operator, prover.pubkey().to_bytes());
+    assert_eq!(registration.metadata_uri(), "https://moved.example");
+    assert_eq!(
+        registration.prover_versions(),
+        &[ProverVersion::V1_0_1.0, ProverVersion::V1_2_1.0]
+    );
+    assert_eq!(registration.claims, 1);
+}
+
+#[tokio::test]
+async fn registration_rejects_unknown_prover_versions() {
+    let execution = Execution::new();
+    let prover = Keypair::new();
+    let mut context = start(&execution, &[&prover], None).await;
+
+    let ix =
+        instructions::register_prover_v1(&prover.pubkey(), "", &[ProverVersion(u16::MAX)]).unwrap();
+    let result = send(&mut context, ix, &[&prover]).await;
+    assert_eq!(
+        result,
+        Err(channel_error(ChannelError::InvalidProverRegistration))
+    );
+}
+
+#[tokio::test]
+async fn claim_requires_the_minimum_reputation() {
+    let execution = Execution::with_requirements(9_000, 5);
+    let (unregistered, unreliable, reliable) = (Keypair::new(), Keypair::new(), Keypair::new());
+    let mut program_test = program(&execution, &[&unregistered, &unreliable, &reliable], None);
+    add_registration(&mut program_test, &track_record(&unreliable, 8, 2));
+    add_registration(&mut program_test, &track_record(&reliable, 9, 1));
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.pubkey();
+
+    for prover in [&unregistered, &unreliable] {
+        let ix = execution.claim(&prover.pubkey(), &payer, 100, None);
+        let result = send(&mut context, ix, &[prover]).await;
+        assert_eq!(
+            result,
+            Err(channel_error(ChannelError::InsufficientReputation))
+        );
+    }
+    let ix = execution.claim(&reliable.pubkey(), &payer, 100, None);
+    send(&mut context, ix, &[&reliable]).await.unwrap();
+
+    let registration = registration(&mut context, &reliable.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(registration.claims, 11);
+}
+
+#[tokio::test]
+async fn taking_over_a_lapsed_claim_records_the_missed_commitment() {
+    let execution = Execution::new();
+    let (holder, claimer) = (Keypair::new(), Keypair::new());
+    let mut program_test = program(
+        &execution,
+        &[&claimer],
+        Some((holder.pubkey(), 10, TIP / 2)),
+    );
+    add_registration(&mut program_test, &track_record(&holder, 3, 0));
+    let mut context = program_test.start_with_context().await;
+    context.warp_to_slot(20).unwrap();
+
+    let payer = context.payer.pubkey();
+    let ix = execution.claim(&claimer.pubkey(), &payer, 100, None);
+    let result = send(&mut context, ix, &[&claimer]).await;
+    assert_eq!(
+        result,
+        Err(channel_error(ChannelError::InvalidProverAccount))
+    );
+    let ix = execution.claim(&claimer.pubkey(), &payer, 100, Some(&holder.pubkey()));
+    send(&mut context, ix, &[&claimer]).await.unwrap();
+
+    let holder = registration(&mut context, &holder.pubkey()).await.unwrap();
+    assert_eq!(holder.missed_commitments, 1);
+    assert_eq!(holder.slashes, 1);
+    assert_eq!(holder.reputation(), 7_500);
+    // the new claimer never registered
+    assert!(registration(&mut context, &claimer.pubkey())
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn failed_proof_records_a_slash() {
+    let execution = Execution::new();
+    let prover = Keypair::new();
+    let mut program_test = program(
+        &execution,
+        &[&prover],
+        Some((prover.pubkey(), 500, TIP / 2)),
+    );
+    add_registration(&mut program_test, &track_record(&prover, 1, 0));
+    let mut context = program_test.start_with_context().await;
+
+    let ix = execution.proving_failed(&prover.pubkey());
+    send(&mut context, ix, &[&prover]).await.unwrap();
+
+    assert_settled(&mut context, &execution, ExitCode::ProvingError).await;
+    let registration = registration(&mut context, &prover.pubkey()).await.unwrap();
+    assert_eq!(registration.slashes, 1);
+    assert_eq!(registration.missed_commitments, 0);
+    assert_eq!(registration.proofs, 1);
+}
+
+#[tokio::test]
+async fn expiry_records_the_missed_commitment() {
+    let execution = Execution::new();
+    let (holder, cranker) = (Keypair::new(), Keypair::new());
+    let mut program_test = program(
+        &execution,
+        &[&cranker],
+        Some((holder.pubkey(), 500, TIP / 2)),
+    );
+    add_registration(&mut program_test, &track_record(&holder, 0, 0));
+    let mut context = program_test.start_with_context().await;
+    context.warp_to_slot(MAX_BLOCK_HEIGHT + 1).unwrap();
+
+    let ix = instructions::expire_v1(
+        &cranker.pubkey(),
+        &execution.requester.pubkey(),
+        EXECUTION_ID,
+        Some(&holder.pubkey()),
+    )
+    .unwrap();
+    send(&mut context, ix, &[&cranker]).await.unwrap();
+
+    let holder = registration(&mut context, &holder.pubkey()).await.unwrap();
+    assert_eq!(holder.missed_commitments, 1);
+    assert_eq!(holder.slashes, 1);
+}
@@ -0,0 +1,251 @@
+//! Program tests for the lifecycle of the stake a prover escrows when it claims an execution.
+//! Verified proofs need a real groth16 proof, the settlement they lead to is covered by the unit
+//! tests of `bonsol::stake`.
+mod common;
+
+use bonsol::error::ChannelError;
+use bonsol_interface::{
+    bonsol_schema::{ExitCode, ProverVersion},
+    claim_state::ClaimStateV1,
+    instructions,
+    util::{verifier_config_address, EXPIRE_CRANK_FEE},
+};
+use common::*;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn claim_escrows_the_stake() {
+    let execution = Execution::new();
+    let claimer = Keypair::new();
+    let mut context = start(&execution, &[&claimer], None).await;
+
+    let payer = context.payer.pubkey();
+    let ix = execution.claim(&claimer.pubkey(), &payer, 100, None);
+    send(&mut context, ix, &[&claimer]).await.unwrap();
+
+    assert_eq!(
+        lamports(&mut context, &execution.exec_claim).await,
+        claim_rent() + TIP / 2
+    );
+    assert_eq!(
+        lamports(&mut context, &claimer.pubkey()).await,
+        STARTING_BALANCE - TIP / 2
+    );
+}
+
+#[tokio::test]
+async fn active_claim_cannot_be_taken_over() {
+    let execution = Execution::new();
+    let (holder, claimer) = (Keypair::new(), Keypair::new());
+    let claim = (holder.pubkey(), 500, TIP / 2);
+    let mut context = start(&execution, &[&claimer], Some(claim)).await;
+
+    let payer = context.payer.pubkey();
+    let ix = execution.claim(&claimer.pubkey(), &payer, 600, Some(&holder.pubkey()));
+    let result = send(&mut context, ix, &[&claimer]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::ActiveClaimExists)));
+}
+
+#[tokio::test]
+async fn lapsed_stake_is_split_with_the_next_claimer() {
+    let execution = Execution::new();
+    let (holder, claimer) = (Keypair::new(), Keypair::new());
+    let lapsed_stake = 300_001;
+    let claim = (holder.pubkey(), 10, lapsed_stake);
+    let mut context = start(&execution, &[&claimer], Some(claim)).await;
+    context.warp_to_slot(20).unwrap();
+
+    let payer = context.payer.pubkey();
+    let ix = execution.claim(&claimer.pubkey(), &payer, 100, Some(&holder.pubkey()));
+    send(&mut context, ix, &[&claimer]).await.unwrap();
+
+    assert_eq!(
+        lamports(&mut context, &execution.requester.pubkey()).await,
+        STARTING_BALANCE + 150_001
+    );
+    assert_eq!(
+        lamports(&mut context, &claimer.pubkey()).await,
+        STARTING_BALANCE + 150_000 - TIP / 2
+    );
+    let claim = context
+        .banks_client
+        .get_account(execution.exec_claim)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(claim.lamports, claim_rent() + TIP / 2);
+    let claim = ClaimStateV1::load_claim_owned(&claim.data).unwrap();
+    assert_eq!(claim.claimer, claimer.pubkey().to_bytes());
+    assert_eq!(claim.block_commitment, 100);
+}
+
+#[tokio::test]
+async fn claiming_an_expired_execution_is_rejected() {
+    let execution = Execution::new();
+    let claimer = Keypair::new();
+    let mut context = start(&execution, &[&claimer], None).await;
+    context.warp_to_slot(MAX_BLOCK_HEIGHT + 1).unwrap();
+
+    let payer = context.payer.pubkey();
+    let ix = execution.claim(&claimer.pubkey(), &payer, MAX_BLOCK_HEIGHT + 10, None);
+    let result = send(&mut context, ix, &[&claimer]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::ExecutionExpired)));
+}
+
+#[tokio::test]
+async fn failed_proof_slashes_the_claimer() {
+    let execution = Execution::new();
+    let prover = Keypair::new();
+    let claim = (prover.pubkey(), 500, TIP / 2);
+    let mut context = start(&execution, &[&prover], Some(claim)).await;
+
+    let ix = execution.proving_failed(&prover.pubkey());
+    send(&mut context, ix, &[&prover]).await.unwrap();
+
+    assert_settled(&mut context, &execution, ExitCode::ProvingError).await;
+    assert_eq!(
+        lamports(&mut context, &execution.requester.pubkey()).await,
+        STARTING_BALANCE + execution.refund() + claim_rent() + TIP / 2
+    );
+    assert_eq!(
+        lamports(&mut context, &prover.pubkey()).await,
+        STARTING_BALANCE
+    );
+}
+
+#[tokio::test]
+async fn unsigned_status_is_rejected() {
+    let execution = Execution::new();
+    let prover = Keypair::new();
+    let claim = (prover.pubkey(), 500, TIP / 2);
+    let mut context = start(&execution, &[&prover], Some(claim)).await;
+
+    let mut ix = execution.proving_failed(&prover.pubkey());
+    ix.accounts[3].is_signer = false;
+    let result = send(&mut context, ix, &[]).await;
+    assert_eq!(
+        result,
+        Err(channel_error(ChannelError::InvalidProverAccount))
+    );
+    assert_eq!(
+        lamports(&mut context, &execution.exec_claim).await,
+        claim_rent() + TIP / 2
+    );
+}
+
+#[tokio::test]
+async fn only_the_claimer_settles_its_claim() {
+    let execution = Execution::new();
+    let (holder, intruder) = (Keypair::new(), Keypair::new());
+    let claim = (holder.pubkey(), 10, TIP / 2);
+    let mut context = start(&execution, &[&intruder], Some(claim)).await;
+
+    // a proof from another prover while the commitment is active
+    let verifier_config = verifier_config_address(ProverVersion::DEFAULT).0;
+    let ix = execution.proven(&intruder.pubkey(), &verifier_config);
+    let result = send(&mut context, ix, &[&intruder]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::NotClaimer)));
+
+    // failures are only reported by the claimer, even once its commitment lapsed
+    context.warp_to_slot(20).unwrap();
+    let ix = execution.proving_failed(&intruder.pubkey());
+    let result = send(&mut context, ix, &[&intruder]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::NotClaimer)));
+    let ix = execution.proving_failed(&execution.requester.pubkey());
+    let result = send(&mut context, ix, &[&execution.requester]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::NotClaimer)));
+    assert_eq!(
+        lamports(&mut context, &execution.exec_claim).await,
+        claim_rent() + TIP / 2
+    );
+}
+
+#[tokio::test]
+async fn cancel_slashes_a_lapsed_claim() {
+    let execution = Execution::new();
+    let holder = Keypair::new();
+    let claim = (holder.pubkey(), 10, TIP / 2);
+    let mut context = start(&execution, &[], Some(claim)).await;
+    context.warp_to_slot(20).unwrap();
+
+    let ix = instructions::cancel_v1(
+        &execution.requester.pubkey(),
+        EXECUTION_ID,
+        Some(&holder.pubkey()),
+    )
+    .unwrap();
+    send(&mut context, ix, &[&execution.requester])
+        .await
+        .unwrap();
+
+    assert_settled(&mut context, &execution, ExitCode::Cancelled).await;
+    assert_eq!(
+        lamports(&mut context, &execution.requester.pubkey()).await,
+        STARTING_BALANCE + execution.refund() + claim_rent() + TIP / 2
+    );
+}
+
+#[tokio::test]
+async fn cancel_is_refused_while_the_claim_is_active() {
+    let execution = Execution::new();
+    let holder = Keypair::new();
+    let claim = (holder.pubkey(), 500, TIP / 2);
+    let mut context = start(&execution, &[], Some(claim)).await;
+
+    let ix = instructions::cancel_v1(
+        &execution.requester.pubkey(),
+        EXECUTION_ID,
+        Some(&holder.pubkey()),
+    )
+    .unwrap();
+    let result = send(&mut context, ix, &[&execution.requester]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::ActiveClaimExists)));
+}
+
+#[tokio::test]
+async fn expire_slashes_the_claim_and_pays_the_crank_fee() {
+    let execution = Execution::new();
+    let (holder, cranker) = (Keypair::new(), Keypair::new());
+    let claim = (holder.pubkey(), 500, TIP / 2);
+    let mut context = start(&execution, &[&cranker], Some(claim)).await;
+    context.warp_to_slot(MAX_BLOCK_HEIGHT + 1).unwrap();
+
+    let ix = instructions::expire_v1(
+        &cranker.pubkey(),
+        &execution.requester.pubkey(),
+        EXECUTION_ID,
+        Some(&holder.pubkey()),
+    )
+    .unwrap();
+    send(&mut context, ix, &[&cranker]).await.unwrap();
+
+    assert_settled(&mut context, &execution, ExitCode::Expired).await;
+    assert_eq!(
+        lamports(&mut context, &cranker.pubkey()).await,
+        STARTING_BALANCE + EXPIRE_CRANK_FEE
+    );
+    assert_eq!(
+        lamports(&mut context, &execution.requester.pubkey()).await,
+        STARTING_BALANCE + execution.refund() - EXPIRE_CRANK_FEE + claim_rent() + TIP / 2
+    );
+}
+
+#[tokio::test]
+async fn expire_is_refused_before_the_max_block_height() {
+    let execution = Execution::new();
+    let cranker = Keypair::new();
+    let mut context = start(&execution, &[&cranker], None).await;
+
+    let ix = instructions::expire_v1(
+        &cranker.pubkey(),
+        &execution.requester.pubkey(),
+        EXECUTION_ID,
+        None,
+    )
+    .unwrap();
+    let result = send(&mut context, ix, &[&cranker]).await;
+    assert_eq!(
+        result,
+        Err(channel_error(ChannelError::ExecutionNotExpired))
+    );
+}
@@ -0,0 +1,86 @@
+//! Program tests for settling several executions with one batch proof.
+mod common;
+
+use bonsol::error::ChannelError;
+use bonsol_interface::{
+    bonsol_schema::ProverVersion,
+    util::verifier_config_address,
+    verifier_config::{VerifierConfig, RISC0_PUBLIC_INPUTS},
+};
+use common::*;
+use solana_sdk::{
+    rent::Rent,
+    signature::{Keypair, Signer},
+};
+
+fn batching_config() -> VerifierConfig {
+    VerifierConfig {
+        prover_version: ProverVersion::V1_2_1,
+        enabled: true,
+        control_root: [1; 32],
+        bn254_control_id: [2; 32],
+        vk_alpha_g1: [3; 64],
+        vk_beta_g2: [4; 128],
+        vk_gamma_g2: [5; 128],
+        vk_delta_g2: [6; 128],
+        vk_ic: vec![[7; 64]; RISC0_PUBLIC_INPUTS + 1],
+        batch_image_id: Some(IMAGE_ID.to_string()),
+    }
+}
+
+#[tokio::test]
+async fn batches_need_a_batch_image() {
+    let prover = Keypair::new();
+    let execution = Execution::new();
+    let mut context = start(&execution, &[&prover], None).await;
+
+    // the verifier compiled into the program has no batch image
+    let verifier_config = verifier_config_address(ProverVersion::DEFAULT).0;
+    let ix = batch(&[&execution], &prover.pubkey(), &verifier_config);
+    let result = send(&mut context, ix, &[&prover]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::BatchUnavailable)));
+}
+
+#[tokio::test]
+async fn batches_settle_an_execution_once() {
+    let prover = Keypair::new();
+    let execution = Execution::new();
+    let mut program_test = program(&execution, &[&prover], None);
+    add_verifier_config(&mut program_test, &batching_config());
+    let mut context = program_test.start_with_context().await;
+
+    let verifier_config = verifier_config_address(ProverVersion::DEFAULT).0;
+    let ix = batch(
+        &[&execution, &execution],
+        &prover.pubkey(),
+        &verifier_config,
+    );
+    let result = send(&mut context, ix, &[&prover]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::InvalidBatch)));
+}
+
+#[tokio::test]
+async fn unverified_batches_settle_nothing() {
+    let prover = Keypair::new();
+    let (first, second) = (Execution::new(), Execution::new());
+    let mut program_test = program(&first, &[&prover], None);
+    add_execution(&mut program_test, &second);
+    add_verifier_config(&mut program_test, &batching_config());
+    let mut context = program_test.start_with_context().await;
+
+    let verifier_config = verifier_config_address(ProverVersion::DEFAULT).0;
+    let ix = batch(&[&first, &second], &prover.pubkey(), &verifier_config);
+    let result = send(&mut context, ix, &[&prover]).await;
+    assert!(result.is_err());
+    // both executions are still pending with their tips
+    for execution in [&first, &second] {
+        assert_eq!(
+            lamports(&mut context, &execution.exec).await,
+            Rent::default().minimum_balance(execution.request().len()) + TIP
+        );
+    }
+    assert_eq!(
+        lamports(&mut context, &prover.pubkey()).await,
+        STARTING_BALANCE
+    );
+}
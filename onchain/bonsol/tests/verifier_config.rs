@@ -0,0 +1,121 @@
+//! Program tests for the verifier configs the upgrade authority keeps per prover version.
+mod common;
+
+use bonsol::error::ChannelError;
+use bonsol_interface::{
+    bonsol_schema::{root_as_verifier_config_v1, ProverVersion},
+    instructions,
+    util::{program_data_address, verifier_config_address},
+    verifier_config::{VerifierConfig, RISC0_PUBLIC_INPUTS},
+};
+use common::*;
+use solana_program_test::ProgramTest;
+use solana_sdk::{
+    account::Account,
+    bpf_loader_upgradeable,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+fn verifier_config(prover_version: ProverVersion, enabled: bool) -> VerifierConfig {
+    VerifierConfig {
+        prover_version,
+        enabled,
+        control_root: [1; 32],
+        bn254_control_id: [2; 32],
+        vk_alpha_g1: [3; 64],
+        vk_beta_g2: [4; 128],
+        vk_gamma_g2: [5; 128],
+        vk_delta_g2: [6; 128],
+        vk_ic: vec![[7; 64]; RISC0_PUBLIC_INPUTS + 1],
+        batch_image_id: None,
+    }
+}
+
+/// Makes `authority` the upgrade authority of the program
+fn add_upgrade_authority(program_test: &mut ProgramTest, authority: &Pubkey) {
+    let data = [
+        3u32.to_le_bytes().as_ref(),
+        &0u64.to_le_bytes(),
+        &[1],
+        authority.as_ref(),
+    ]
+    .concat();
+    program_test.add_account(
+        program_data_address(),
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: bpf_loader_upgradeable::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+#[tokio::test]
+async fn only_the_upgrade_authority_sets_verifier_configs() {
+    let (authority, intruder) = (Keypair::new(), Keypair::new());
+    let execution = Execution::new();
+    let mut program_test = program(&execution, &[&authority, &intruder], None);
+    add_upgrade_authority(&mut program_test, &authority.pubkey());
+    let mut context = program_test.start_with_context().await;
+
+    let config = verifier_config(ProverVersion::V1_0_1, true);
+    let ix = instructions::set_verifier_config_v1(&intruder.pubkey(), &config).unwrap();
+    let result = send(&mut context, ix, &[&intruder]).await;
+    assert_eq!(
+        result,
+        Err(channel_error(ChannelError::InvalidVerifierAuthority))
+    );
+
+    let ix = instructions::set_verifier_config_v1(&authority.pubkey(), &config).unwrap();
+    send(&mut context, ix, &[&authority]).await.unwrap();
+    let account = context
+        .banks_client
+        .get_account(verifier_config_address(ProverVersion::V1_0_1).0)
+        .await
+        .unwrap()
+        .unwrap();
+    let stored = root_as_verifier_config_v1(&account.data).unwrap();
+    assert_eq!(stored.prover_version(), ProverVersion::V1_0_1);
+    assert_eq!(stored.control_root().unwrap().bytes(), &[1; 32]);
+}
+
+#[tokio::test]
+async fn disabled_versions_reject_proofs() {
+    let (authority, prover) = (Keypair::new(), Keypair::new());
+    let execution = Execution::new();
+    let mut program_test = program(&execution, &[&authority, &prover], None);
+    add_upgrade_authority(&mut program_test, &authority.pubkey());
+    let mut context = program_test.start_with_context().await;
+
+    // requests on the default version are verified by the latest one
+    let config = verifier_config(ProverVersion::V1_2_1, false);
+    let ix = instructions::set_verifier_config_v1(&authority.pubkey(), &config).unwrap();
+    send(&mut context, ix, &[&authority]).await.unwrap();
+
+    let verifier_config = verifier_config_address(ProverVersion::DEFAULT).0;
+    let ix = execution.proven(&prover.pubkey(), &verifier_config);
+    let result = send(&mut context, ix, &[&prover]).await;
+    assert_eq!(result, Err(channel_error(ChannelError::VerifierDisabled)));
+    assert_eq!(
+        lamports(&mut context, &prover.pubkey()).await,
+        STARTING_BALANCE
+    );
+}
+
+#[tokio::test]
+async fn status_passes_the_verifier_config_of_the_execution_version() {
+    let prover = Keypair::new();
+    let execution = Execution::new();
+    let mut context = start(&execution, &[&prover], None).await;
+
+    let verifier_config = verifier_config_address(ProverVersion::V1_0_1).0;
+    let ix = execution.proven(&prover.pubkey(), &verifier_config);
+    let result = send(&mut context, ix, &[&prover]).await;
+    assert_eq!(
+        result,
+        Err(channel_error(ChannelError::InvalidVerifierConfigAccount))
+    );
+}
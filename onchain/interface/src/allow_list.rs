@@ -0,0 +1,100 @@
+use bonsol_schema::{AllowListV1, Claimer, ExecutionRequestV1};
+use flatbuffers::Vector;
+
+/// Longest id an allow list can have, the id is one of the seeds of its address
+pub const MAX_ALLOW_LIST_ID_LEN: usize = 32;
+
+/// Whether only some provers can claim the execution, through `allowed_claimers` or
+/// `claimer_allow_list`
+pub fn restricts_claimers(execution_request: &ExecutionRequestV1) -> bool {
+    execution_request
+        .allowed_claimers()
+        .is_some_and(|claimers| !claimers.is_empty())
+        || execution_request.claimer_allow_list().is_some()
+}
+
+/// Whether `claimer` can claim the execution. The provers listed in the request and in the allow
+/// list it references are allowed, `allow_list` is None when that account does not exist.
+pub fn claimer_allowed(
+    execution_request: &ExecutionRequestV1,
+    allow_list: Option<&AllowListV1>,
+    claimer: &[u8; 32],
+) -> bool {
+    !restricts_claimers(execution_request)
+        || listed(execution_request.allowed_claimers(), claimer)
+        || allow_list.is_some_and(|list| listed(list.claimers(), claimer))
+}
+
+fn listed(claimers: Option<Vector<Claimer>>, claimer: &[u8; 32]) -> bool {
+    claimers.is_some_and(|claimers| {
+        claimers.iter().any(|c| {
+            let key: [u8; 32] = c.pubkey().into();
+            &key == claimer
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::instructions::{execute_v1, set_allow_list_v1, ExecutionConfig, InputRef};
+    use bonsol_schema::parse_ix_data;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn request(config: ExecutionConfig) -> Vec<u8> {
+        let requester = Pubkey::new_unique();
+        let ix = execute_v1(
+            &requester,
+            &requester,
+            "image",
+            "execution",
+            vec![InputRef::public(b"input")],
+            1,
+            100,
+            config,
+            None,
+            None,
+        )
+        .unwrap();
+        let ix = parse_ix_data(&ix.data).unwrap();
+        ix.execute_v1().unwrap().bytes().to_vec()
+    }
+
+    #[test]
+    fn unrestricted_requests_accept_any_claimer() {
+        let data = request(ExecutionConfig {
+            verify_input_hash: false,
+            ..Default::default()
+        });
+        let er = bonsol_schema::root_as_execution_request_v1(&data).unwrap();
+        assert!(!restricts_claimers(&er));
+        assert!(claimer_allowed(&er, None, &[1; 32]));
+    }
+
+    #[test]
+    fn claimers_are_listed_in_the_request_or_the_allow_list() {
+        let (inline, listed, other) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let authority = Pubkey::new_unique();
+        let ix = set_allow_list_v1(&authority, "trusted", &[listed]).unwrap();
+        let ix = parse_ix_data(&ix.data).unwrap();
+        let allow_list = ix.allow_list_v1_nested_flatbuffer().unwrap();
+        let data = request(ExecutionConfig {
+            verify_input_hash: false,
+            allowed_claimers: vec![inline],
+            claimer_allow_list: Some(crate::util::allow_list_address(&authority, "trusted").0),
+            ..Default::default()
+        });
+        let er = bonsol_schema::root_as_execution_request_v1(&data).unwrap();
+        assert!(restricts_claimers(&er));
+        assert!(claimer_allowed(&er, Some(&allow_list), &inline.to_bytes()));
+        assert!(claimer_allowed(&er, Some(&allow_list), &listed.to_bytes()));
+        assert!(!claimer_allowed(&er, Some(&allow_list), &other.to_bytes()));
+        // a closed allow list only leaves the provers listed in the request
+        assert!(claimer_allowed(&er, None, &inline.to_bytes()));
+        assert!(!claimer_allowed(&er, None, &listed.to_bytes()));
+    }
+}
@@ -0,0 +1,210 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::error::ClientError;
+
+#[cfg(feature = "on-chain")]
+use {
+    solana_program::account_info::AccountInfo, solana_program::program_memory::sol_memcpy,
+    solana_program::pubkey::Pubkey,
+};
+
+#[cfg(not(feature = "on-chain"))]
+use solana_sdk::pubkey::Pubkey;
+
+/// Most bids an auction holds, a bid above all of them is rejected once it is full
+pub const MAX_BIDS: usize = 16;
+/// Slots each bid has to claim the execution in turn once the auction has ended
+pub const AUCTION_CLAIM_WINDOW: u64 = 20;
+
+#[repr(C)]
+#[derive(Pod, Copy, Clone, Zeroable, Debug, PartialEq, Eq)]
+pub struct PlacedBid {
+    pub bidder: [u8; 32],
+    /// Lamports of the tip the bidder is paid when it proves the execution
+    pub price: u64,
+    /// Block the bidder commits to submitting the proof by
+    pub completion_slot: u64,
+    pub placed_at: u64,
+}
+
+/// Bids for the claim on an auctioned execution, kept at `execution_bids_address(execution)`.
+/// The bids are ordered by price, then completion slot, then age, the best bid first.
+#[repr(C)]
+#[derive(Pod, Copy, Clone, Zeroable)]
+pub struct BidStateV1 {
+    /// Last block bids are accepted at
+    pub auction_end: u64,
+    pub bid_count: u64,
+    pub bids: [PlacedBid; MAX_BIDS],
+}
+
+pub struct BidStateHolder {
+    data: Vec<u8>,
+}
+
+impl BidStateHolder {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self { data }
+    }
+
+    pub fn bids(&self) -> Result<BidStateV1, ClientError> {
+        BidStateV1::load_bids_owned(&self.data)
+    }
+}
+
+impl PlacedBid {
+    pub fn new(bidder: &Pubkey, price: u64, completion_slot: u64, slot: u64) -> Self {
+        PlacedBid {
+            bidder: bidder.to_bytes(),
+            price,
+            completion_slot,
+            placed_at: slot,
+        }
+    }
+
+    fn rank(&self) -> (u64, u64, u64) {
+        (self.price, self.completion_slot, self.placed_at)
+    }
+}
+
+impl BidStateV1 {
+    pub fn load_bids_owned(data: &[u8]) -> Result<Self, ClientError> {
+        bytemuck::try_pod_read_unaligned::<BidStateV1>(data)
+            .map_err(|_| ClientError::InvalidBidAccount)
+    }
+
+    pub fn new(auction_end: u64) -> Self {
+        BidStateV1 {
+            auction_end,
+            ..Zeroable::zeroed()
+        }
+    }
+
+    pub fn bids(&self) -> &[PlacedBid] {
+        let count = (self.bid_count as usize).min(MAX_BIDS);
+        &self.bids[..count]
+    }
+
+    /// Places `bid`, replacing any earlier bid of the same bidder. When the auction is full the
+    /// worst bid makes room, a bid that would be the worst is rejected.
+    pub fn place(&mut self, bid: PlacedBid) -> Result<(), ClientError> {
+        let mut bids: Vec<PlacedBid> = self
+            .bids()
+            .iter()
+            .filter(|b| b.bidder != bid.bidder)
+            .copied()
+            .collect();
+        let position = bids.partition_point(|b| b.rank() <= bid.rank());
+        if position == MAX_BIDS {
+            return Err(ClientError::InvalidBid);
+        }
+        bids.insert(position, bid);
+        bids.truncate(MAX_BIDS);
+        self.bids = [Zeroable::zeroed(); MAX_BIDS];
+        self.bids[..bids.len()].copy_from_slice(&bids);
+        self.bid_count = bids.len() as u64;
+        Ok(())
+    }
+
+    /// The bid whose turn it is to claim the execution at `slot`. None while the auction is open
+    /// and once every bid had its turn, the execution can then be claimed by anyone for the full
+    /// tip.
+    pub fn winner_at(&self, slot: u64) -> Option<&PlacedBid> {
+        if slot <= self.auction_end {
+            return None;
+        }
+        let turn = (slot - self.auction_end - 1) / AUCTION_CLAIM_WINDOW;
+        self.bids().get(turn as usize)
+    }
+
+    /// The first and last block `bidder` can claim the execution at, None when it has no bid
+    pub fn claim_window(&self, bidder: &Pubkey) -> Option<(u64, u64)> {
+        let turn = self
+            .bids()
+            .iter()
+            .position(|b| b.bidder == bidder.to_bytes())? as u64;
+        let start = self.auction_end + 1 + turn * AUCTION_CLAIM_WINDOW;
+        Some((start, start + AUCTION_CLAIM_WINDOW - 1))
+    }
+
+    #[cfg(feature = "on-chain")]
+    pub fn save_bids(bids: &Self, ba: &AccountInfo) {
+        let bid_data = bytemuck::bytes_of(bids);
+        sol_memcpy(&mut ba.data.borrow_mut(), bid_data, bid_data.len());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bids_are_ranked_by_price_then_completion() {
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut auction = BidStateV1::new(100);
+        auction.place(PlacedBid::new(&a, 500, 200, 10)).unwrap();
+        auction.place(PlacedBid::new(&b, 400, 300, 11)).unwrap();
+        auction.place(PlacedBid::new(&c, 400, 250, 12)).unwrap();
+        let order: Vec<[u8; 32]> = auction.bids().iter().map(|b| b.bidder).collect();
+        assert_eq!(order, vec![c.to_bytes(), b.to_bytes(), a.to_bytes()]);
+
+        // a new bid replaces the bidder's earlier one
+        auction.place(PlacedBid::new(&a, 300, 200, 13)).unwrap();
+        assert_eq!(auction.bids().len(), 3);
+        assert_eq!(auction.bids()[0].bidder, a.to_bytes());
+    }
+
+    #[test]
+    fn full_auctions_only_take_better_bids() {
+        let mut auction = BidStateV1::new(100);
+        for price in 1..=MAX_BIDS as u64 {
+            auction
+                .place(PlacedBid::new(&Pubkey::new_unique(), price * 10, 200, 1))
+                .unwrap();
+        }
+        let worse = PlacedBid::new(&Pubkey::new_unique(), MAX_BIDS as u64 * 10, 200, 2);
+        assert!(auction.place(worse).is_err());
+        let better = PlacedBid::new(&Pubkey::new_unique(), 5, 200, 2);
+        auction.place(better).unwrap();
+        assert_eq!(auction.bids().len(), MAX_BIDS);
+        assert_eq!(auction.bids()[0], better);
+        assert_eq!(
+            auction.bids()[MAX_BIDS - 1].price,
+            (MAX_BIDS as u64 - 1) * 10
+        );
+    }
+
+    #[test]
+    fn bids_claim_in_turn_after_the_auction() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut auction = BidStateV1::new(100);
+        auction.place(PlacedBid::new(&a, 10, 500, 1)).unwrap();
+        auction.place(PlacedBid::new(&b, 20, 500, 1)).unwrap();
+        assert_eq!(auction.winner_at(100), None);
+        assert_eq!(auction.winner_at(101).unwrap().bidder, a.to_bytes());
+        assert_eq!(
+            auction
+                .winner_at(100 + AUCTION_CLAIM_WINDOW)
+                .unwrap()
+                .bidder,
+            a.to_bytes()
+        );
+        assert_eq!(
+            auction
+                .winner_at(101 + AUCTION_CLAIM_WINDOW)
+                .unwrap()
+                .bidder,
+            b.to_bytes()
+        );
+        assert_eq!(auction.winner_at(101 + 2 * AUCTION_CLAIM_WINDOW), None);
+        assert_eq!(
+            auction.claim_window(&b),
+            Some((101 + AUCTION_CLAIM_WINDOW, 100 + 2 * AUCTION_CLAIM_WINDOW))
+        );
+        assert_eq!(auction.claim_window(&Pubkey::new_unique()), None);
+    }
+}
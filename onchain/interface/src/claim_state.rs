@@ -17,6 +17,9 @@ pub struct ClaimStateV1 {
     pub claimer: [u8; 32],
     pub claimed_at: u64,
     pub block_commitment: u64,
+    /// Lamports the claimer is paid for a verified proof, the winning bid of an auctioned
+    /// execution and the tip otherwise
+    pub price: u64,
 }
 
 pub struct ClaimStateHolder {
@@ -46,11 +49,12 @@ impl ClaimStateV1 {
             .map_err(|_| ClientError::InvalidClaimAccount)
     }
 
-    pub fn from_claim_ix(claimer: &Pubkey, slot: u64, block_commitment: u64) -> Self {
+    pub fn from_claim_ix(claimer: &Pubkey, slot: u64, block_commitment: u64, price: u64) -> Self {
         ClaimStateV1 {
             claimer: claimer.to_bytes(),
             claimed_at: slot,
             block_commitment,
+            price,
         }
     }
 
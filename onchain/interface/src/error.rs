@@ -24,6 +24,14 @@ pub enum ClientError {
     InvalidCallbackImageId,
     #[error("Execution Request Reused")]
     ExecutionRequestReused,
+    #[error("InvalidProverAccount")]
+    InvalidProverAccount,
+    #[error("InvalidProverRegistration")]
+    InvalidProverRegistration,
+    #[error("InvalidBidAccount")]
+    InvalidBidAccount,
+    #[error("InvalidBid")]
+    InvalidBid,
 }
 
 impl From<ClientError> for ProgramError {
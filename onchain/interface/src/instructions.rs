@@ -1,12 +1,19 @@
 use bonsol_schema::{
-    Account, ChannelInstruction, ChannelInstructionArgs, ChannelInstructionIxType, DeployV1,
-    DeployV1Args, ExecutionRequestV1, ExecutionRequestV1Args, InputBuilder, InputType,
-    ProgramInputType, ProverVersion,
+    root_as_verifier_config_v1, Account, AllowListV1, AllowListV1Args, BidV1, BidV1Args, CancelV1,
+    CancelV1Args, ChannelInstruction, ChannelInstructionArgs, ChannelInstructionIxType, Claimer,
+    DeployV1, DeployV1Args, ExecutionRequestV1, ExecutionRequestV1Args, ExpireV1, ExpireV1Args,
+    InputBuilder, InputType, ManageDeploymentV1, ManageDeploymentV1Args, ProgramInputType,
+    ProverVersion, RegisterProverV1, RegisterProverV1Args, VerifierConfigV1, VerifierConfigV1Args,
 };
 use flatbuffers::{FlatBufferBuilder, WIPOffset};
 
+use crate::allow_list::MAX_ALLOW_LIST_ID_LEN;
 use crate::error::ClientError;
-use crate::util::{deployment_address, execution_address};
+use crate::util::{
+    allow_list_address, deployment_address, execution_address, execution_bids_address,
+    execution_claim_address, program_data_address, prover_address, verifier_config_address,
+};
+use crate::verifier_config::{valid_verifier_config, VerifierConfig};
 
 #[cfg(feature = "on-chain")]
 use {
@@ -24,6 +31,7 @@ pub fn deploy_v1(
     signer: &Pubkey,
     image_id: &str,
     image_size: u64,
+    image_checksum: &[u8],
     program_name: &str,
     url: &str,
     inputs: Vec<ProgramInputType>,
@@ -41,6 +49,7 @@ pub fn deploy_v1(
     let name = fbb.create_string(program_name);
     let owner = fbb.create_vector(signer.as_ref());
     let fb_inputs = fbb.create_vector(inputs.as_slice());
+    let checksum = fbb.create_vector(image_checksum);
     let fbb_deploy = DeployV1::create(
         &mut fbb,
         &DeployV1Args {
@@ -50,6 +59,8 @@ pub fn deploy_v1(
             url: Some(url),
             size_: image_size,
             inputs: Some(fb_inputs),
+            image_checksum: Some(checksum),
+            deprecated: false,
         },
     );
     fbb.finish(fbb_deploy, None);
@@ -69,6 +80,80 @@ pub fn deploy_v1(
     Ok(Instruction::new_with_bytes(crate::ID, ix_data, accounts))
 }
 
+/// Points an existing deployment at a new url, only the owner of the deployment can sign it
+pub fn update_deployment_url_v1(
+    owner: &Pubkey,
+    image_id: &str,
+    url: &str,
+) -> Result<Instruction, ClientError> {
+    manage_deployment_v1(
+        ChannelInstructionIxType::UpdateDeploymentUrlV1,
+        owner,
+        image_id,
+        Some(url),
+    )
+}
+
+/// Marks a deployment deprecated, it accepts no new executions afterwards
+pub fn deprecate_deployment_v1(owner: &Pubkey, image_id: &str) -> Result<Instruction, ClientError> {
+    manage_deployment_v1(
+        ChannelInstructionIxType::DeprecateDeploymentV1,
+        owner,
+        image_id,
+        None,
+    )
+}
+
+/// Closes a deployment and returns its rent to the owner
+pub fn close_deployment_v1(owner: &Pubkey, image_id: &str) -> Result<Instruction, ClientError> {
+    manage_deployment_v1(
+        ChannelInstructionIxType::CloseDeploymentV1,
+        owner,
+        image_id,
+        None,
+    )
+}
+
+fn manage_deployment_v1(
+    ix_type: ChannelInstructionIxType,
+    owner: &Pubkey,
+    image_id: &str,
+    url: Option<&str>,
+) -> Result<Instruction, ClientError> {
+    let (deployment_account, _) = deployment_address(image_id);
+    let accounts = vec![
+        AccountMeta::new(owner.to_owned(), true),
+        AccountMeta::new(owner.to_owned(), true),
+        AccountMeta::new(deployment_account, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    let mut fbb = FlatBufferBuilder::new();
+    let url = url.map(|url| fbb.create_string(url));
+    let image_id = fbb.create_string(image_id);
+    let fbb_manage = ManageDeploymentV1::create(
+        &mut fbb,
+        &ManageDeploymentV1Args {
+            image_id: Some(image_id),
+            url,
+        },
+    );
+    fbb.finish(fbb_manage, None);
+    let ix_data = fbb.finished_data();
+    let mut fbb = FlatBufferBuilder::new();
+    let ix = fbb.create_vector(ix_data);
+    let fbb_ix = ChannelInstruction::create(
+        &mut fbb,
+        &ChannelInstructionArgs {
+            ix_type,
+            manage_deployment_v1: Some(ix),
+            ..Default::default()
+        },
+    );
+    fbb.finish(fbb_ix, None);
+    let ix_data = fbb.finished_data();
+    Ok(Instruction::new_with_bytes(crate::ID, ix_data, accounts))
+}
+
 // todo hold attributes for scheme and versions selection
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -77,6 +162,23 @@ pub struct ExecutionConfig<'a> {
     pub verify_input_hash: bool,
     pub input_hash: Option<&'a [u8]>,
     pub forward_output: bool,
+    /// Reputation in basis points the claimer must have, see `ProverV1::reputation`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_reputation: u16,
+    /// Verified proofs the claimer must have delivered
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_proofs: u64,
+    /// Provers that can claim the execution, along with the ones in `claimer_allow_list`. Anyone
+    /// can claim it when neither is set.
+    #[cfg_attr(feature = "serde", serde(default, with = "serde_helpers::pubkeys"))]
+    pub allowed_claimers: Vec<Pubkey>,
+    /// Allow list account, see `set_allow_list_v1`, whose provers can claim the execution
+    #[cfg_attr(feature = "serde", serde(default, with = "serde_helpers::optpubkey"))]
+    pub claimer_allow_list: Option<Pubkey>,
+    /// Slots provers bid for the claim after the request, the lowest bid wins and is paid
+    /// instead of the tip. Zero lets the first prover claim it for the tip.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub auction_slots: u64,
 }
 
 #[cfg(feature = "serde")]
@@ -103,6 +205,30 @@ pub mod serde_helpers {
         }
     }
 
+    pub mod pubkeys {
+        use std::str::FromStr;
+
+        use serde::{self, Deserialize, Deserializer, Serializer};
+        use solana_sdk::pubkey::Pubkey;
+
+        pub fn serialize<S>(value: &[Pubkey], serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_seq(value.iter().map(|v| v.to_string()))
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Pubkey>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Vec::<String>::deserialize(deserializer)?
+                .iter()
+                .map(|s| Pubkey::from_str(s).map_err(serde::de::Error::custom))
+                .collect()
+        }
+    }
+
     pub mod optpubkey {
         use std::str::FromStr;
 
@@ -146,6 +272,11 @@ impl Default for ExecutionConfig<'_> {
             verify_input_hash: true,
             input_hash: None,
             forward_output: false,
+            min_reputation: 0,
+            min_proofs: 0,
+            allowed_claimers: Vec::new(),
+            claimer_allow_list: None,
+            auction_slots: 0,
         }
     }
 }
@@ -200,6 +331,13 @@ impl<'a> InputRef<'a> {
             data,
         }
     }
+    /// `data` is the requester followed by the execution id, see `prior_execution_input`
+    pub fn prior_execution(data: &'a [u8]) -> Self {
+        Self {
+            input_type: InputType::PriorExecution,
+            data,
+        }
+    }
 }
 
 /// Executes a bonsol program.
@@ -273,7 +411,7 @@ pub fn execute_v1_with_accounts<'a>(
         } else {
             (None, None, None)
         };
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(*requester, true),
         AccountMeta::new(*payer, true),
         AccountMeta::new(*execution_account, false),
@@ -281,6 +419,10 @@ pub fn execute_v1_with_accounts<'a>(
         AccountMeta::new_readonly(callback_pubkey.unwrap_or(crate::ID), false),
         AccountMeta::new_readonly(system_program::id(), false),
     ];
+    if config.auction_slots > 0 {
+        let (bids_account, _) = execution_bids_address(execution_account.as_ref());
+        accounts.push(AccountMeta::new(bids_account, false));
+    }
     let inputlen = inputs.len();
     let mut inputs_vec = Vec::with_capacity(inputlen);
     for input in inputs {
@@ -296,6 +438,17 @@ pub fn execute_v1_with_accounts<'a>(
     let execution_id = fbb.create_string(execution_id);
 
     let input_digest = config.input_hash.map(|ih| fbb.create_vector(ih));
+    let allowed_claimers = (!config.allowed_claimers.is_empty()).then(|| {
+        let claimers: Vec<Claimer> = config
+            .allowed_claimers
+            .iter()
+            .map(|c| Claimer::new(&c.to_bytes()))
+            .collect();
+        fbb.create_vector(&claimers)
+    });
+    let claimer_allow_list = config
+        .claimer_allow_list
+        .map(|list| fbb.create_vector(list.as_ref()));
 
     // typically cli will pass None for the optional prover_version indicating bonsol should handle
     // the default case here
@@ -315,6 +468,11 @@ pub fn execute_v1_with_accounts<'a>(
             input_digest,
             callback_extra_accounts: extra_accounts,
             prover_version,
+            min_reputation: config.min_reputation,
+            min_proofs: config.min_proofs,
+            allowed_claimers,
+            claimer_allow_list,
+            auction_slots: config.auction_slots,
         },
     );
     fbb.finish(fbb_execute, None);
@@ -333,3 +491,328 @@ pub fn execute_v1_with_accounts<'a>(
     let ix_data = fbb.finished_data();
     Ok(Instruction::new_with_bytes(crate::ID, ix_data, accounts))
 }
+
+/// Withdraws an execution request that no prover holds an active claim on, the rent and tip go
+/// back to the requester. `claimer` is the prover holding the lapsed claim on it, if any. The bids
+/// of an auctioned execution are closed with it.
+pub fn cancel_v1(
+    requester: &Pubkey,
+    execution_id: &str,
+    claimer: Option<&Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let (execution_account, _) = execution_address(requester, execution_id.as_bytes());
+    let (claim_account, _) = execution_claim_address(execution_account.as_ref());
+    let mut accounts = vec![
+        AccountMeta::new(*requester, true),
+        AccountMeta::new(execution_account, false),
+        AccountMeta::new(claim_account, false),
+    ];
+    accounts.extend(claimer_prover_account(claimer));
+    accounts.push(AccountMeta::new(
+        execution_bids_address(execution_account.as_ref()).0,
+        false,
+    ));
+    let mut fbb = FlatBufferBuilder::new();
+    let execution_id = fbb.create_string(execution_id);
+    let fbb_cancel = CancelV1::create(
+        &mut fbb,
+        &CancelV1Args {
+            execution_id: Some(execution_id),
+        },
+    );
+    fbb.finish(fbb_cancel, None);
+    let ix_data = fbb.finished_data();
+    let mut fbb = FlatBufferBuilder::new();
+    let ix = fbb.create_vector(ix_data);
+    let fbb_ix = ChannelInstruction::create(
+        &mut fbb,
+        &ChannelInstructionArgs {
+            ix_type: ChannelInstructionIxType::CancelV1,
+            cancel_v1: Some(ix),
+            ..Default::default()
+        },
+    );
+    fbb.finish(fbb_ix, None);
+    let ix_data = fbb.finished_data();
+    Ok(Instruction::new_with_bytes(crate::ID, ix_data, accounts))
+}
+
+/// Closes an execution request that was not settled before its max block height, anyone can send
+/// it. The requester gets back the rent and tip less the crank fee paid to `cranker`, and the stake
+/// of `claimer`, the prover that claimed it if any. The bids of an auctioned execution are closed
+/// with it.
+pub fn expire_v1(
+    cranker: &Pubkey,
+    requester: &Pubkey,
+    execution_id: &str,
+    claimer: Option<&Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let (execution_account, _) = execution_address(requester, execution_id.as_bytes());
+    let (claim_account, _) = execution_claim_address(execution_account.as_ref());
+    let mut accounts = vec![
+        AccountMeta::new(*cranker, true),
+        AccountMeta::new(*requester, false),
+        AccountMeta::new(execution_account, false),
+        AccountMeta::new(claim_account, false),
+    ];
+    accounts.extend(claimer_prover_account(claimer));
+    accounts.push(AccountMeta::new(
+        execution_bids_address(execution_account.as_ref()).0,
+        false,
+    ));
+    let mut fbb = FlatBufferBuilder::new();
+    let execution_id = fbb.create_string(execution_id);
+    let fbb_expire = ExpireV1::create(
+        &mut fbb,
+        &ExpireV1Args {
+            execution_id: Some(execution_id),
+        },
+    );
+    fbb.finish(fbb_expire, None);
+    let ix_data = fbb.finished_data();
+    let mut fbb = FlatBufferBuilder::new();
+    let ix = fbb.create_vector(ix_data);
+    let fbb_ix = ChannelInstruction::create(
+        &mut fbb,
+        &ChannelInstructionArgs {
+            ix_type: ChannelInstructionIxType::ExpireV1,
+            expire_v1: Some(ix),
+            ..Default::default()
+        },
+    );
+    fbb.finish(fbb_ix, None);
+    let ix_data = fbb.finished_data();
+    Ok(Instruction::new_with_bytes(crate::ID, ix_data, accounts))
+}
+
+/// The registry account of the prover holding a claim, its missed commitment is recorded there
+fn claimer_prover_account(claimer: Option<&Pubkey>) -> Option<AccountMeta> {
+    claimer.map(|claimer| AccountMeta::new(prover_address(claimer).0, false))
+}
+
+/// Registers `operator` as a prover, or updates its metadata and supported versions. The
+/// registration keeps the prover's claim history.
+pub fn register_prover_v1(
+    operator: &Pubkey,
+    metadata_uri: &str,
+    prover_versions: &[ProverVersion],
+) -> Result<Instruction, ClientError> {
+    let (prover_account, _) = prover_address(operator);
+    let accounts = vec![
+        AccountMeta::new(*operator, true),
+        AccountMeta::new(prover_account, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    let mut fbb = FlatBufferBuilder::new();
+    let metadata_uri = fbb.create_string(metadata_uri);
+    let prover_versions = fbb.create_vector(prover_versions);
+    let fbb_register = RegisterProverV1::create(
+        &mut fbb,
+        &RegisterProverV1Args {
+            metadata_uri: Some(metadata_uri),
+            prover_versions: Some(prover_versions),
+        },
+    );
+    fbb.finish(fbb_register, None);
+    let ix_data = fbb.finished_data();
+    let mut fbb = FlatBufferBuilder::new();
+    let ix = fbb.create_vector(ix_data);
+    let fbb_ix = ChannelInstruction::create(
+        &mut fbb,
+        &ChannelInstructionArgs {
+            ix_type: ChannelInstructionIxType::RegisterProverV1,
+            register_prover_v1: Some(ix),
+            ..Default::default()
+        },
+    );
+    fbb.finish(fbb_ix, None);
+    let ix_data = fbb.finished_data();
+    Ok(Instruction::new_with_bytes(crate::ID, ix_data, accounts))
+}
+
+/// Creates or replaces the allow list `authority` keeps under `id`, execution requests can
+/// reference its address to let these provers claim them
+pub fn set_allow_list_v1(
+    authority: &Pubkey,
+    id: &str,
+    claimers: &[Pubkey],
+) -> Result<Instruction, ClientError> {
+    allow_list_v1(
+        ChannelInstructionIxType::SetAllowListV1,
+        authority,
+        id,
+        claimers,
+    )
+}
+
+/// Closes an allow list and returns its rent to the authority, the requests referencing it can
+/// then only be claimed by the provers they list themselves
+pub fn close_allow_list_v1(authority: &Pubkey, id: &str) -> Result<Instruction, ClientError> {
+    allow_list_v1(
+        ChannelInstructionIxType::CloseAllowListV1,
+        authority,
+        id,
+        &[],
+    )
+}
+
+fn allow_list_v1(
+    ix_type: ChannelInstructionIxType,
+    authority: &Pubkey,
+    id: &str,
+    claimers: &[Pubkey],
+) -> Result<Instruction, ClientError> {
+    if id.is_empty() || id.len() > MAX_ALLOW_LIST_ID_LEN {
+        return Err(ClientError::InvalidInput);
+    }
+    let (allow_list_account, _) = allow_list_address(authority, id);
+    let accounts = vec![
+        AccountMeta::new(*authority, true),
+        AccountMeta::new(allow_list_account, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    let mut fbb = FlatBufferBuilder::new();
+    let owner = fbb.create_vector(authority.as_ref());
+    let id = fbb.create_string(id);
+    let claimers: Vec<Claimer> = claimers
+        .iter()
+        .map(|c| Claimer::new(&c.to_bytes()))
+        .collect();
+    let claimers = fbb.create_vector(&claimers);
+    let fbb_allow_list = AllowListV1::create(
+        &mut fbb,
+        &AllowListV1Args {
+            authority: Some(owner),
+            id: Some(id),
+            claimers: Some(claimers),
+        },
+    );
+    fbb.finish(fbb_allow_list, None);
+    let ix_data = fbb.finished_data();
+    let mut fbb = FlatBufferBuilder::new();
+    let ix = fbb.create_vector(ix_data);
+    let fbb_ix = ChannelInstruction::create(
+        &mut fbb,
+        &ChannelInstructionArgs {
+            ix_type,
+            allow_list_v1: Some(ix),
+            ..Default::default()
+        },
+    );
+    fbb.finish(fbb_ix, None);
+    let ix_data = fbb.finished_data();
+    Ok(Instruction::new_with_bytes(crate::ID, ix_data, accounts))
+}
+
+/// Bids `price` lamports for the claim on an auctioned execution, committing to submit the proof
+/// by `completion_slot` when the bid wins. `claimer_allow_list` is the allow list the execution
+/// restricts its claimers to.
+pub fn bid_v1(
+    bidder: &Pubkey,
+    requester: &Pubkey,
+    execution_id: &str,
+    price: u64,
+    completion_slot: u64,
+    claimer_allow_list: Option<&Pubkey>,
+) -> Result<Instruction, ClientError> {
+    let (execution_account, _) = execution_address(requester, execution_id.as_bytes());
+    let (bids_account, _) = execution_bids_address(execution_account.as_ref());
+    let mut accounts = vec![
+        AccountMeta::new_readonly(execution_account, false),
+        AccountMeta::new_readonly(*requester, false),
+        AccountMeta::new(bids_account, false),
+        AccountMeta::new_readonly(*bidder, true),
+        AccountMeta::new_readonly(prover_address(bidder).0, false),
+    ];
+    if let Some(allow_list) = claimer_allow_list {
+        accounts.push(AccountMeta::new_readonly(*allow_list, false));
+    }
+    let mut fbb = FlatBufferBuilder::new();
+    let execution_id = fbb.create_string(execution_id);
+    let fbb_bid = BidV1::create(
+        &mut fbb,
+        &BidV1Args {
+            execution_id: Some(execution_id),
+            price,
+            completion_slot,
+        },
+    );
+    fbb.finish(fbb_bid, None);
+    let ix_data = fbb.finished_data();
+    let mut fbb = FlatBufferBuilder::new();
+    let ix = fbb.create_vector(ix_data);
+    let fbb_ix = ChannelInstruction::create(
+        &mut fbb,
+        &ChannelInstructionArgs {
+            ix_type: ChannelInstructionIxType::BidV1,
+            bid_v1: Some(ix),
+            ..Default::default()
+        },
+    );
+    fbb.finish(fbb_ix, None);
+    let ix_data = fbb.finished_data();
+    Ok(Instruction::new_with_bytes(crate::ID, ix_data, accounts))
+}
+
+/// Creates or replaces the verifier config of a prover version, `authority` is the upgrade
+/// authority of the program. Proofs of the version are verified with it instead of the verifier
+/// compiled into the program.
+pub fn set_verifier_config_v1(
+    authority: &Pubkey,
+    config: &VerifierConfig,
+) -> Result<Instruction, ClientError> {
+    let (verifier_config_account, _) = verifier_config_address(config.prover_version);
+    let accounts = vec![
+        AccountMeta::new(*authority, true),
+        AccountMeta::new(verifier_config_account, false),
+        AccountMeta::new_readonly(program_data_address(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    let mut fbb = FlatBufferBuilder::new();
+    let control_root = fbb.create_vector(&config.control_root);
+    let bn254_control_id = fbb.create_vector(&config.bn254_control_id);
+    let vk_alpha_g1 = fbb.create_vector(&config.vk_alpha_g1);
+    let vk_beta_g2 = fbb.create_vector(&config.vk_beta_g2);
+    let vk_gamma_g2 = fbb.create_vector(&config.vk_gamma_g2);
+    let vk_delta_g2 = fbb.create_vector(&config.vk_delta_g2);
+    let vk_ic = fbb.create_vector(&config.vk_ic.concat());
+    let batch_image_id = config
+        .batch_image_id
+        .as_ref()
+        .map(|id| fbb.create_string(id));
+    let fbb_config = VerifierConfigV1::create(
+        &mut fbb,
+        &VerifierConfigV1Args {
+            prover_version: config.prover_version,
+            enabled: config.enabled,
+            control_root: Some(control_root),
+            bn254_control_id: Some(bn254_control_id),
+            vk_alpha_g1: Some(vk_alpha_g1),
+            vk_beta_g2: Some(vk_beta_g2),
+            vk_gamma_g2: Some(vk_gamma_g2),
+            vk_delta_g2: Some(vk_delta_g2),
+            vk_ic: Some(vk_ic),
+            batch_image_id,
+        },
+    );
+    fbb.finish(fbb_config, None);
+    let ix_data = fbb.finished_data();
+    let verifier_config =
+        root_as_verifier_config_v1(ix_data).map_err(|_| ClientError::InvalidInput)?;
+    if !valid_verifier_config(&verifier_config) {
+        return Err(ClientError::InvalidInput);
+    }
+    let mut fbb = FlatBufferBuilder::new();
+    let ix = fbb.create_vector(ix_data);
+    let fbb_ix = ChannelInstruction::create(
+        &mut fbb,
+        &ChannelInstructionArgs {
+            ix_type: ChannelInstructionIxType::SetVerifierConfigV1,
+            verifier_config_v1: Some(ix),
+            ..Default::default()
+        },
+    );
+    fbb.finish(fbb_ix, None);
+    let ix_data = fbb.finished_data();
+    Ok(Instruction::new_with_bytes(crate::ID, ix_data, accounts))
+}
@@ -1,10 +1,14 @@
+pub mod allow_list;
+pub mod bid_state;
 #[cfg(feature = "on-chain")]
 pub mod callback;
 pub mod claim_state;
 pub mod error;
 pub mod instructions;
+pub mod prover_state;
 pub mod prover_version;
 pub mod util;
+pub mod verifier_config;
 
 pub use bonsol_schema;
 pub use util::{ID, *};
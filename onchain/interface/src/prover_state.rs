@@ -0,0 +1,183 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::error::ClientError;
+
+#[cfg(feature = "on-chain")]
+use {
+    solana_program::account_info::AccountInfo, solana_program::program_memory::sol_memcpy,
+    solana_program::pubkey::Pubkey,
+};
+
+#[cfg(not(feature = "on-chain"))]
+use solana_sdk::pubkey::Pubkey;
+
+/// Most prover versions a registration can list
+pub const MAX_PROVER_VERSIONS: usize = 7;
+/// Longest metadata uri a registration can hold
+pub const MAX_METADATA_URI_LEN: usize = 128;
+/// Reputation of a prover that proved every claim it settled
+pub const MAX_REPUTATION: u16 = 10_000;
+
+/// Registration of a prover, kept at `prover_address(operator)`. The counters are maintained by
+/// the program as the prover's claims are settled.
+#[repr(C)]
+#[derive(Pod, Copy, Clone, Zeroable)]
+pub struct ProverV1 {
+    pub operator: [u8; 32],
+    pub registered_at: u64,
+    /// Executions claimed
+    pub claims: u64,
+    /// Verified proofs delivered for the prover's own claims
+    pub proofs: u64,
+    /// Claims whose block commitment passed without a proof
+    pub missed_commitments: u64,
+    /// Stakes forfeited, for missed commitments and failed proofs
+    pub slashes: u64,
+    pub prover_versions: [u16; MAX_PROVER_VERSIONS],
+    pub prover_version_count: u16,
+    pub metadata_uri: [u8; MAX_METADATA_URI_LEN],
+}
+
+pub struct ProverHolder {
+    data: Vec<u8>,
+}
+
+impl ProverHolder {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self { data }
+    }
+
+    pub fn prover(&self) -> Result<ProverV1, ClientError> {
+        ProverV1::load_prover_owned(&self.data)
+    }
+}
+
+impl ProverV1 {
+    pub fn load_prover_owned(data: &[u8]) -> Result<Self, ClientError> {
+        bytemuck::try_pod_read_unaligned::<ProverV1>(data)
+            .map_err(|_| ClientError::InvalidProverAccount)
+    }
+
+    pub fn new(operator: &Pubkey, slot: u64) -> Self {
+        ProverV1 {
+            operator: operator.to_bytes(),
+            registered_at: slot,
+            ..Zeroable::zeroed()
+        }
+    }
+
+    /// Replaces the metadata and supported versions, the counters are kept
+    pub fn register(
+        &mut self,
+        metadata_uri: &str,
+        prover_versions: &[u16],
+    ) -> Result<(), ClientError> {
+        if metadata_uri.len() > MAX_METADATA_URI_LEN || prover_versions.len() > MAX_PROVER_VERSIONS
+        {
+            return Err(ClientError::InvalidProverRegistration);
+        }
+        self.metadata_uri = [0; MAX_METADATA_URI_LEN];
+        self.metadata_uri[..metadata_uri.len()].copy_from_slice(metadata_uri.as_bytes());
+        self.prover_versions = [0; MAX_PROVER_VERSIONS];
+        self.prover_versions[..prover_versions.len()].copy_from_slice(prover_versions);
+        self.prover_version_count = prover_versions.len() as u16;
+        Ok(())
+    }
+
+    pub fn metadata_uri(&self) -> &str {
+        let len = self
+            .metadata_uri
+            .iter()
+            .position(|b| *b == 0)
+            .unwrap_or(MAX_METADATA_URI_LEN);
+        std::str::from_utf8(&self.metadata_uri[..len]).unwrap_or_default()
+    }
+
+    pub fn prover_versions(&self) -> &[u16] {
+        let count = (self.prover_version_count as usize).min(MAX_PROVER_VERSIONS);
+        &self.prover_versions[..count]
+    }
+
+    pub fn record_claim(&mut self) {
+        self.claims = self.claims.saturating_add(1);
+    }
+
+    pub fn record_proof(&mut self) {
+        self.proofs = self.proofs.saturating_add(1);
+    }
+
+    /// A failed proof, the stake is slashed
+    pub fn record_slash(&mut self) {
+        self.slashes = self.slashes.saturating_add(1);
+    }
+
+    /// A commitment that passed without a proof, the stake is slashed
+    pub fn record_missed_commitment(&mut self) {
+        self.missed_commitments = self.missed_commitments.saturating_add(1);
+        self.record_slash();
+    }
+
+    /// Share of the settled claims the prover proved, in basis points. Zero until a claim is
+    /// settled.
+    pub fn reputation(&self) -> u16 {
+        let settled = self.proofs as u128 + self.slashes as u128;
+        if settled == 0 {
+            return 0;
+        }
+        (self.proofs as u128 * MAX_REPUTATION as u128 / settled) as u16
+    }
+
+    /// Whether the prover may claim an execution requiring `min_reputation` and `min_proofs`
+    pub fn meets(&self, min_reputation: u16, min_proofs: u64) -> bool {
+        self.reputation() >= min_reputation && self.proofs >= min_proofs
+    }
+
+    #[cfg(feature = "on-chain")]
+    pub fn save_prover(prover: &Self, pa: &AccountInfo) {
+        let prover_data = bytemuck::bytes_of(prover);
+        sol_memcpy(&mut pa.data.borrow_mut(), prover_data, prover_data.len());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reputation_is_the_share_of_settled_claims_proven() {
+        let mut prover = ProverV1::new(&Pubkey::new_unique(), 1);
+        assert_eq!(prover.reputation(), 0);
+        assert!(prover.meets(0, 0));
+        assert!(!prover.meets(1, 0));
+        for _ in 0..3 {
+            prover.record_claim();
+            prover.record_proof();
+        }
+        prover.record_claim();
+        prover.record_missed_commitment();
+        assert_eq!(prover.claims, 4);
+        assert_eq!(prover.missed_commitments, 1);
+        assert_eq!(prover.slashes, 1);
+        assert_eq!(prover.reputation(), 7_500);
+        assert!(prover.meets(7_500, 3));
+        assert!(!prover.meets(7_501, 3));
+        assert!(!prover.meets(0, 4));
+    }
+
+    #[test]
+    fn registration_keeps_the_counters() {
+        let mut prover = ProverV1::new(&Pubkey::new_unique(), 1);
+        prover.record_claim();
+        prover.register("https://prover.example", &[1, 9]).unwrap();
+        assert_eq!(prover.metadata_uri(), "https://prover.example");
+        assert_eq!(prover.prover_versions(), &[1, 9]);
+        prover.register("", &[9]).unwrap();
+        assert_eq!(prover.metadata_uri(), "");
+        assert_eq!(prover.prover_versions(), &[9]);
+        assert_eq!(prover.claims, 1);
+        assert!(prover
+            .register(&"a".repeat(MAX_METADATA_URI_LEN + 1), &[9])
+            .is_err());
+        assert!(prover.register("", &[9; MAX_PROVER_VERSIONS + 1]).is_err());
+    }
+}
@@ -59,6 +59,7 @@ impl TryInto<FBSProverVersion> for ProverVersion {
         #[allow(unreachable_patterns)]
         match self {
             ProverVersion::V1_0_1 { .. } => Ok(FBSProverVersion::V1_0_1),
+            ProverVersion::V1_2_1 { .. } => Ok(FBSProverVersion::V1_2_1),
             _ => Err(ProverVersionError::UnsupportedVersion),
         }
     }
@@ -105,6 +106,12 @@ mod tests {
         assert_eq!(fbs_version.unwrap(), FBSProverVersion::V1_0_1);
     }
 
+    #[test]
+    fn test_try_into_v1_2_1() {
+        let fbs_version: Result<FBSProverVersion, ProverVersionError> = VERSION_V1_2_1.try_into();
+        assert_eq!(fbs_version, Ok(FBSProverVersion::V1_2_1));
+    }
+
     #[test]
     fn test_try_from_unsupported_version() {
         let unsupported_version = FBSProverVersion(u16::MAX);
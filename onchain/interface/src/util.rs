@@ -2,18 +2,30 @@
 use {
     solana_program::declare_id,
     solana_program::pubkey::Pubkey,
-    solana_program::{keccak, keccak::Hash},
+    solana_program::{bpf_loader_upgradeable, hash, keccak, keccak::Hash},
 };
 
 #[cfg(not(feature = "on-chain"))]
 use {
     solana_sdk::declare_id,
     solana_sdk::pubkey::Pubkey,
-    solana_sdk::{keccak, keccak::Hash},
+    solana_sdk::{bpf_loader_upgradeable, hash, keccak, keccak::Hash},
+};
+
+use {
+    crate::verifier_config::verifier_version,
+    bonsol_schema::{parse_prior_execution, ExitCode, ProverVersion},
 };
 
 declare_id!("BoNsHRcyLLNdtnoDf8hiCNZpyehMC4FDMxs6NTxFi3ew");
 
+/// Lamports paid out of an expired execution's tip to whoever closes it with `ExpireV1`
+pub const EXPIRE_CRANK_FEE: u64 = 10_000;
+
+/// Length of the account of a proven execution, its exit code followed by the digest of its
+/// receipt claim. Executions settled otherwise only keep their exit code.
+pub const PROVEN_EXECUTION_LEN: usize = 33;
+
 pub fn execution_address_seeds<'a>(requester: &'a Pubkey, execution_id: &'a [u8]) -> Vec<&'a [u8]> {
     vec!["execution".as_bytes(), requester.as_ref(), execution_id]
 }
@@ -26,6 +38,22 @@ pub fn execution_claim_address_seeds(execution_address: &[u8]) -> Vec<&[u8]> {
     vec!["execution_claim".as_bytes(), execution_address]
 }
 
+pub fn execution_bids_address_seeds(execution_address: &[u8]) -> Vec<&[u8]> {
+    vec!["execution_bids".as_bytes(), execution_address]
+}
+
+pub fn prover_address_seeds(operator: &Pubkey) -> Vec<&[u8]> {
+    vec!["prover".as_bytes(), operator.as_ref()]
+}
+
+pub fn allow_list_address_seeds<'a>(authority: &'a Pubkey, id: &'a str) -> Vec<&'a [u8]> {
+    vec!["allow_list".as_bytes(), authority.as_ref(), id.as_bytes()]
+}
+
+pub fn verifier_config_address_seeds(prover_version: &[u8; 2]) -> Vec<&[u8]> {
+    vec!["verifier_config".as_bytes(), prover_version]
+}
+
 pub fn execution_address(requester: &Pubkey, execution_id: &[u8]) -> (Pubkey, u8) {
     Pubkey::find_program_address(&execution_address_seeds(requester, execution_id), &ID)
 }
@@ -43,6 +71,12 @@ pub fn img_id_hash(image_id: &str) -> Hash {
     keccak::hash(image_id.as_bytes())
 }
 
+/// sha256 of a program elf, deployments carry it so the elf can be checked before it is loaded
+#[inline]
+pub fn image_checksum(elf: &[u8]) -> [u8; 32] {
+    hash::hash(elf).to_bytes()
+}
+
 pub fn deployment_address(image_id: &str) -> (Pubkey, u8) {
     let hsh = img_id_hash(image_id);
     Pubkey::find_program_address(&deployment_address_seeds(&hsh), &ID)
@@ -51,3 +85,47 @@ pub fn deployment_address(image_id: &str) -> (Pubkey, u8) {
 pub fn execution_claim_address(execution_id: &[u8]) -> (Pubkey, u8) {
     Pubkey::find_program_address(&execution_claim_address_seeds(execution_id), &ID)
 }
+
+/// The bids for the claim on an auctioned execution
+pub fn execution_bids_address(execution_address: &[u8]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&execution_bids_address_seeds(execution_address), &ID)
+}
+
+/// The registry account of the prover signing with `operator`
+pub fn prover_address(operator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&prover_address_seeds(operator), &ID)
+}
+
+/// The allow list `authority` keeps under `id`
+pub fn allow_list_address(authority: &Pubkey, id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&allow_list_address_seeds(authority, id), &ID)
+}
+
+/// The verifier config of the version whose verifier checks the proofs of `prover_version`
+pub fn verifier_config_address(prover_version: ProverVersion) -> (Pubkey, u8) {
+    let version = verifier_version(prover_version).0.to_le_bytes();
+    Pubkey::find_program_address(&verifier_config_address_seeds(&version), &ID)
+}
+
+/// The account holding the upgrade authority of the program, it signs verifier config changes
+pub fn program_data_address() -> Pubkey {
+    bpf_loader_upgradeable::get_program_data_address(&ID)
+}
+
+/// The execution account a `PriorExecution` input names, None when it names none
+pub fn prior_execution_address(data: &[u8]) -> Option<Pubkey> {
+    let (requester, execution_id) = parse_prior_execution(data)?;
+    Some(execution_address(&Pubkey::new_from_array(requester), execution_id.as_bytes()).0)
+}
+
+/// The receipt claim digest a settled execution account keeps, None unless it was proven
+pub fn proven_claim_digest(data: &[u8]) -> Option<[u8; 32]> {
+    match data {
+        [exit_code, digest @ ..]
+            if data.len() == PROVEN_EXECUTION_LEN && *exit_code == ExitCode::Success as u8 =>
+        {
+            digest.try_into().ok()
+        }
+        _ => None,
+    }
+}
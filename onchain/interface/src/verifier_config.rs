@@ -0,0 +1,130 @@
+use bonsol_schema::{ProverVersion, VerifierConfigV1};
+use flatbuffers::Vector;
+
+/// Public inputs of a risc0 groth16 proof, the halves of the control root and of the claim
+/// digest and the bn254 control id
+pub const RISC0_PUBLIC_INPUTS: usize = 5;
+
+pub const G1_LEN: usize = 64;
+
+pub const G2_LEN: usize = 128;
+
+/// The groth16 verifier of a prover version, see `set_verifier_config_v1`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifierConfig {
+    pub prover_version: ProverVersion,
+    /// Proofs of a disabled version are rejected
+    pub enabled: bool,
+    /// Recursion control root in the byte order risc0 prints it
+    pub control_root: [u8; 32],
+    /// bn254 identity control id in the byte order the program passes it to the verifier, the
+    /// reverse of how risc0 prints it
+    pub bn254_control_id: [u8; 32],
+    pub vk_alpha_g1: [u8; G1_LEN],
+    pub vk_beta_g2: [u8; G2_LEN],
+    pub vk_gamma_g2: [u8; G2_LEN],
+    pub vk_delta_g2: [u8; G2_LEN],
+    /// One point per public input plus one
+    pub vk_ic: Vec<[u8; G1_LEN]>,
+    /// Hex image id of the image aggregating the proofs of a `StatusBatchV1`, the version
+    /// accepts no batches without it
+    pub batch_image_id: Option<String>,
+}
+
+/// The version whose verifier checks the proofs of `prover_version`, requests on the default
+/// version are proven with the latest one
+pub fn verifier_version(prover_version: ProverVersion) -> ProverVersion {
+    if prover_version == ProverVersion::DEFAULT {
+        ProverVersion::V1_2_1
+    } else {
+        prover_version
+    }
+}
+
+/// Whether every field of the config has the length the program verifies proofs with
+pub fn valid_verifier_config(config: &VerifierConfigV1) -> bool {
+    fn len(bytes: Option<Vector<u8>>) -> usize {
+        bytes.map_or(0, |b| b.len())
+    }
+    config.prover_version() != ProverVersion::DEFAULT
+        && len(config.control_root()) == 32
+        && len(config.bn254_control_id()) == 32
+        && len(config.vk_alpha_g1()) == G1_LEN
+        && len(config.vk_beta_g2()) == G2_LEN
+        && len(config.vk_gamma_g2()) == G2_LEN
+        && len(config.vk_delta_g2()) == G2_LEN
+        && len(config.vk_ic()) == G1_LEN * (RISC0_PUBLIC_INPUTS + 1)
+        && config.batch_image_id().map_or(true, valid_image_id)
+}
+
+fn valid_image_id(image_id: &str) -> bool {
+    image_id.len() == 64 && image_id.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        instructions::set_verifier_config_v1,
+        util::{program_data_address, verifier_config_address},
+    };
+    use bonsol_schema::{parse_ix_data, root_as_verifier_config_v1};
+    use solana_sdk::pubkey::Pubkey;
+
+    fn config(vk_ic: usize) -> VerifierConfig {
+        VerifierConfig {
+            prover_version: ProverVersion::V1_0_1,
+            enabled: true,
+            control_root: [1; 32],
+            bn254_control_id: [2; 32],
+            vk_alpha_g1: [3; G1_LEN],
+            vk_beta_g2: [4; G2_LEN],
+            vk_gamma_g2: [5; G2_LEN],
+            vk_delta_g2: [6; G2_LEN],
+            vk_ic: vec![[7; G1_LEN]; vk_ic],
+            batch_image_id: Some("ab".repeat(32)),
+        }
+    }
+
+    #[test]
+    fn verifier_configs_are_sent_as_set() {
+        let authority = Pubkey::new_unique();
+        let ix = set_verifier_config_v1(&authority, &config(RISC0_PUBLIC_INPUTS + 1)).unwrap();
+        assert_eq!(
+            ix.accounts[1].pubkey,
+            verifier_config_address(ProverVersion::V1_0_1).0
+        );
+        assert_eq!(ix.accounts[2].pubkey, program_data_address());
+        let ix = parse_ix_data(&ix.data).unwrap();
+        let sent = root_as_verifier_config_v1(ix.verifier_config_v1().unwrap().bytes()).unwrap();
+        assert!(valid_verifier_config(&sent));
+        assert_eq!(
+            sent.vk_ic().unwrap().len(),
+            G1_LEN * (RISC0_PUBLIC_INPUTS + 1)
+        );
+    }
+
+    #[test]
+    fn incomplete_verifying_keys_are_rejected() {
+        let authority = Pubkey::new_unique();
+        assert!(set_verifier_config_v1(&authority, &config(RISC0_PUBLIC_INPUTS)).is_err());
+        let default_version = VerifierConfig {
+            prover_version: ProverVersion::DEFAULT,
+            ..config(RISC0_PUBLIC_INPUTS + 1)
+        };
+        assert!(set_verifier_config_v1(&authority, &default_version).is_err());
+        let short_batch_image = VerifierConfig {
+            batch_image_id: Some("ab".to_string()),
+            ..config(RISC0_PUBLIC_INPUTS + 1)
+        };
+        assert!(set_verifier_config_v1(&authority, &short_batch_image).is_err());
+    }
+
+    #[test]
+    fn default_version_uses_the_latest_verifier() {
+        assert_eq!(
+            verifier_config_address(ProverVersion::DEFAULT),
+            verifier_config_address(ProverVersion::V1_2_1)
+        );
+    }
+}
@@ -5,7 +5,7 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use anyhow::Result;
 use arrayref::array_ref;
 use async_trait::async_trait;
-use bonsol_schema::{InputT, InputType, ProgramInputType};
+use bonsol_schema::{parse_prior_execution, InputT, InputType, ProgramInputType};
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
@@ -64,16 +64,26 @@ pub trait InputResolver: Send + Sync {
         &self,
         execution_id: &str,
         inputs: &mut Vec<ProgramInput>,
-        signer: Arc<&(dyn Signer + Send + Sync)>,
+        signer: Arc<dyn Signer + Send + Sync>,
     ) -> Result<(), anyhow::Error>;
 }
 
+/// Where the receipts of settled executions are fetched from, a `PriorExecution` input resolves
+/// to the receipt of the execution it names
+#[async_trait]
+pub trait ReceiptSource: Send + Sync {
+    /// The bincode encoded receipt of the execution `execution_id` of `requester`, None when the
+    /// source has none. Execution ids are only unique per requester.
+    async fn receipt(&self, requester: &Pubkey, execution_id: &str) -> Result<Option<Vec<u8>>>;
+}
+
 // naive resolver that downloads inputs just in time
 pub struct DefaultInputResolver {
     http_client: Arc<reqwest::Client>,
     solana_rpc_client: Arc<solana_rpc_client::nonblocking::rpc_client::RpcClient>,
     max_input_size_mb: u32,
     timeout: Duration,
+    receipt_source: Option<Arc<dyn ReceiptSource>>,
 }
 
 impl DefaultInputResolver {
@@ -86,6 +96,7 @@ impl DefaultInputResolver {
             solana_rpc_client,
             max_input_size_mb: 10,
             timeout: Duration::from_secs(30),
+            receipt_source: None,
         }
     }
 
@@ -100,9 +111,17 @@ impl DefaultInputResolver {
             solana_rpc_client,
             max_input_size_mb: max_input_size_mb.unwrap_or(10),
             timeout: timeout.unwrap_or(Duration::from_secs(30)),
+            receipt_source: None,
         }
     }
 
+    /// Resolves `PriorExecution` inputs with the receipts of `receipt_source`, without one they
+    /// are not supported
+    pub fn with_receipt_source(mut self, receipt_source: Arc<dyn ReceiptSource>) -> Self {
+        self.receipt_source = Some(receipt_source);
+        self
+    }
+
     fn par_resolve_input(
         &self,
         client: Arc<reqwest::Client>,
@@ -185,6 +204,32 @@ impl DefaultInputResolver {
                     input_type: ProgramInputType::Public,
                 }))
             }
+            InputType::PriorExecution => {
+                let receipt_source = self
+                    .receipt_source
+                    .clone()
+                    .ok_or(anyhow::anyhow!("No receipt source for prior executions"))?;
+                let data = input.data.ok_or(anyhow::anyhow!("Invalid data"))?;
+                let (requester, execution_id) = parse_prior_execution(&data)
+                    .ok_or(anyhow::anyhow!("Invalid prior execution"))?;
+                let requester = Pubkey::new_from_array(requester);
+                let mut url: Url = format!("bonsol://{}", requester).parse()?;
+                url.path_segments_mut()
+                    .map_err(|_| anyhow::anyhow!("Invalid prior execution"))?
+                    .push(execution_id);
+                task_set.spawn(fetch_prior_receipt(
+                    receipt_source,
+                    index,
+                    requester,
+                    execution_id.to_string(),
+                    self.max_input_size_mb as usize,
+                ));
+                Ok(ProgramInput::Unresolved(UnresolvedInput {
+                    index,
+                    url,
+                    input_type: ProgramInputType::PriorExecution,
+                }))
+            }
             _ => {
                 // not implemented yet / or unknown
                 Err(anyhow::anyhow!("Invalid input type"))
@@ -202,6 +247,7 @@ impl InputResolver for DefaultInputResolver {
             InputType::PublicAccountData => true,
             InputType::Private => true,
             InputType::PublicProof => true,
+            InputType::PriorExecution => self.receipt_source.is_some(),
             _ => false,
         }
     }
@@ -214,8 +260,7 @@ impl InputResolver for DefaultInputResolver {
         let mut res = vec![ProgramInput::Empty; inputs.len()];
         for (index, input) in inputs.into_iter().enumerate() {
             let client = self.http_client.clone();
-            res[index] =
-                self.par_resolve_input(client, index as u8, input, &mut url_set)?;
+            res[index] = self.par_resolve_input(client, index as u8, input, &mut url_set)?;
         }
         while let Some(url) = url_set.join_next().await {
             match url {
@@ -235,7 +280,7 @@ impl InputResolver for DefaultInputResolver {
         &self,
         execution_id: &str,
         inputs: &mut Vec<ProgramInput>,
-        signer: Arc<&(dyn Signer + Send + Sync)>,
+        signer: Arc<dyn Signer + Send + Sync>,
     ) -> Result<(), anyhow::Error> {
         let mut url_set = JoinSet::new();
         for (index, input) in inputs.iter().enumerate() {
@@ -248,7 +293,14 @@ impl InputResolver for DefaultInputResolver {
                     now_utc: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
                 };
                 let pir_str = serde_json::to_string(&pir)?;
-                let claim_authorization = signer.sign_message(pir_str.as_bytes());
+                // remote signers can fail, the request is abandoned rather than sent unsigned.
+                // They block on their signing service so they sign off the runtime.
+                let claim_authorization = tokio::task::spawn_blocking({
+                    let signer = signer.clone();
+                    let pir_str = pir_str.clone();
+                    move || signer.try_sign_message(pir_str.as_bytes())
+                })
+                .await??;
                 url_set.spawn(download_private_input(
                     client,
                     index as u8,
@@ -350,6 +402,27 @@ async fn download_public_account(
     })
 }
 
+async fn fetch_prior_receipt(
+    receipt_source: Arc<dyn ReceiptSource>,
+    index: u8,
+    requester: Pubkey,
+    execution_id: String,
+    max_size_mb: usize,
+) -> Result<ResolvedInput> {
+    let receipt = receipt_source
+        .receipt(&requester, &execution_id)
+        .await?
+        .ok_or(anyhow::anyhow!("No receipt of {}", execution_id))?;
+    if receipt.len() > max_size_mb * 1024 * 1024 {
+        return Err(anyhow::anyhow!("Max size exceeded"));
+    }
+    Ok(ResolvedInput {
+        index,
+        data: receipt,
+        input_type: ProgramInputType::PriorExecution,
+    })
+}
+
 async fn download_private_input(
     client: Arc<reqwest::Client>,
     index: u8,
@@ -380,8 +453,10 @@ async fn download_private_input(
 #[cfg(test)]
 mod test {
     use super::*;
+    use bonsol_schema::prior_execution_input;
     use mockito::Mock;
     use reqwest::{Client, Url};
+    use solana_rpc_client::nonblocking::rpc_client::RpcClient;
 
     use std::sync::Arc;
 
@@ -458,4 +533,42 @@ mod test {
 
         mock.assert();
     }
+
+    struct PriorReceipts;
+
+    #[async_trait]
+    impl ReceiptSource for PriorReceipts {
+        async fn receipt(&self, requester: &Pubkey, execution_id: &str) -> Result<Option<Vec<u8>>> {
+            let prior = requester.to_bytes() == [1; 32] && execution_id == "prior";
+            Ok(prior.then(|| vec![1, 2, 3]))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prior_executions_resolve_to_their_receipts() {
+        let resolver = DefaultInputResolver::new(
+            Arc::new(Client::new()),
+            Arc::new(RpcClient::new("http://localhost:8899".to_string())),
+        );
+        assert!(!resolver.supports(InputType::PriorExecution));
+        let resolver = resolver.with_receipt_source(Arc::new(PriorReceipts));
+        assert!(resolver.supports(InputType::PriorExecution));
+
+        let prior = InputT::prior_execution(prior_execution_input(&[1; 32], "prior"));
+        let resolved = resolver.resolve_public_inputs(vec![prior]).await.unwrap();
+        assert_eq!(
+            resolved,
+            vec![ProgramInput::Resolved(ResolvedInput {
+                index: 0,
+                data: vec![1, 2, 3],
+                input_type: ProgramInputType::PriorExecution,
+            })]
+        );
+
+        let missing = InputT::prior_execution(prior_execution_input(&[1; 32], "missing"));
+        assert!(resolver.resolve_public_inputs(vec![missing]).await.is_err());
+        // the same execution id of another requester is another execution
+        let other = InputT::prior_execution(prior_execution_input(&[2; 32], "prior"));
+        assert!(resolver.resolve_public_inputs(vec![other]).await.is_err());
+    }
 }
@@ -19,6 +19,11 @@ pub fn new_risc0_exec_env(
                 if ri.input_type == ProgramInputType::PublicProof {
                     let reciept: Receipt = bincode::deserialize(&ri.data)?;
                     env_builder.add_assumption(reciept);
+                } else if ri.input_type == ProgramInputType::PriorExecution {
+                    // the guest reads the prior journal and verifies it against the assumption
+                    let receipt: Receipt = bincode::deserialize(&ri.data)?;
+                    env_builder.write_slice(&receipt.journal.bytes);
+                    env_builder.add_assumption(receipt);
                 } else {
                     env_builder.write_slice(&ri.data);
                 }
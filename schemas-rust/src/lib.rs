@@ -1,20 +1,36 @@
+pub mod allow_list_v1_generated;
+pub mod bid_v1_generated;
+pub mod cancel_v1_generated;
 pub mod channel_instruction_generated;
 pub mod claim_v1_generated;
 pub mod deploy_v1_generated;
 pub mod execution_request_v1_generated;
+pub mod expire_v1_generated;
 pub mod input_type_generated;
+pub mod manage_deployment_v1_generated;
+pub mod prover_v1_generated;
+pub mod status_batch_v1_generated;
 pub mod status_v1_generated;
+pub mod verifier_config_v1_generated;
 use std::fmt::Display;
 
 use error::ChannelSchemaError;
 use num_derive::{FromPrimitive, ToPrimitive};
 pub mod error;
+pub use allow_list_v1_generated::*;
+pub use bid_v1_generated::*;
+pub use cancel_v1_generated::*;
 pub use channel_instruction_generated::*;
 pub use claim_v1_generated::*;
 pub use deploy_v1_generated::*;
 pub use execution_request_v1_generated::*;
+pub use expire_v1_generated::*;
 pub use input_type_generated::*;
+pub use manage_deployment_v1_generated::*;
+pub use prover_v1_generated::*;
+pub use status_batch_v1_generated::*;
 pub use status_v1_generated::*;
+pub use verifier_config_v1_generated::*;
 pub fn parse_ix_data(ix_data: &[u8]) -> Result<ChannelInstruction, ChannelSchemaError> {
     let instruction =
         root_as_channel_instruction(ix_data).map_err(|_| ChannelSchemaError::InvalidInstruction)?;
@@ -29,6 +45,7 @@ pub enum ExitCode {
     ProvingError = 2,
     InputError = 3,
     Expired = 4,
+    Cancelled = 5,
 }
 
 impl Display for ExitCode {
@@ -39,6 +56,7 @@ impl Display for ExitCode {
             ExitCode::ProvingError => write!(f, "ProvingError"),
             ExitCode::InputError => write!(f, "InputError"),
             ExitCode::Expired => write!(f, "Expired"),
+            ExitCode::Cancelled => write!(f, "Cancelled"),
         }
     }
 }
@@ -78,4 +96,26 @@ impl InputT {
             data: Some(data),
         }
     }
+    /// `data` is the requester followed by the execution id, see `prior_execution_input`
+    pub const fn prior_execution(data: Vec<u8>) -> Self {
+        Self {
+            input_type: InputType::PriorExecution,
+            data: Some(data),
+        }
+    }
+}
+
+/// The data of a `PriorExecution` input naming the execution `execution_id` of `requester`
+pub fn prior_execution_input(requester: &[u8; 32], execution_id: &str) -> Vec<u8> {
+    [requester.as_ref(), execution_id.as_bytes()].concat()
+}
+
+/// The requester and execution id a `PriorExecution` input names, None when it names none
+pub fn parse_prior_execution(data: &[u8]) -> Option<([u8; 32], &str)> {
+    if data.len() <= 32 {
+        return None;
+    }
+    let (requester, execution_id) = data.split_at(32);
+    let execution_id = std::str::from_utf8(execution_id).ok()?;
+    Some((requester.try_into().ok()?, execution_id))
 }
@@ -0,0 +1,148 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+import * as flatbuffers from 'flatbuffers';
+
+import { Claimer, ClaimerT } from './claimer.js';
+
+
+export class AllowListV1 implements flatbuffers.IUnpackableObject<AllowListV1T> {
+  bb: flatbuffers.ByteBuffer|null = null;
+  bb_pos = 0;
+  __init(i:number, bb:flatbuffers.ByteBuffer):AllowListV1 {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+}
+
+static getRootAsAllowListV1(bb:flatbuffers.ByteBuffer, obj?:AllowListV1):AllowListV1 {
+  return (obj || new AllowListV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+static getSizePrefixedRootAsAllowListV1(bb:flatbuffers.ByteBuffer, obj?:AllowListV1):AllowListV1 {
+  bb.setPosition(bb.position() + flatbuffers.SIZE_PREFIX_LENGTH);
+  return (obj || new AllowListV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+authority(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+authorityLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+authorityArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+id():string|null
+id(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+id(optionalEncoding?:any):string|Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+}
+
+claimers(index: number, obj?:Claimer):Claimer|null {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? (obj || new Claimer()).__init(this.bb!.__vector(this.bb_pos + offset) + index * 32, this.bb!) : null;
+}
+
+claimersLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+static startAllowListV1(builder:flatbuffers.Builder) {
+  builder.startObject(3);
+}
+
+static addAuthority(builder:flatbuffers.Builder, authorityOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, authorityOffset, 0);
+}
+
+static createAuthorityVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startAuthorityVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addId(builder:flatbuffers.Builder, idOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(1, idOffset, 0);
+}
+
+static addClaimers(builder:flatbuffers.Builder, claimersOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(2, claimersOffset, 0);
+}
+
+static startClaimersVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(32, numElems, 8);
+}
+
+static endAllowListV1(builder:flatbuffers.Builder):flatbuffers.Offset {
+  const offset = builder.endObject();
+  return offset;
+}
+
+static finishAllowListV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset);
+}
+
+static finishSizePrefixedAllowListV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset, undefined, true);
+}
+
+static createAllowListV1(builder:flatbuffers.Builder, authorityOffset:flatbuffers.Offset, idOffset:flatbuffers.Offset, claimersOffset:flatbuffers.Offset):flatbuffers.Offset {
+  AllowListV1.startAllowListV1(builder);
+  AllowListV1.addAuthority(builder, authorityOffset);
+  AllowListV1.addId(builder, idOffset);
+  AllowListV1.addClaimers(builder, claimersOffset);
+  return AllowListV1.endAllowListV1(builder);
+}
+
+unpack(): AllowListV1T {
+  return new AllowListV1T(
+    this.bb!.createScalarList<number>(this.authority.bind(this), this.authorityLength()),
+    this.id(),
+    this.bb!.createObjList<Claimer, ClaimerT>(this.claimers.bind(this), this.claimersLength())
+  );
+}
+
+
+unpackTo(_o: AllowListV1T): void {
+  _o.authority = this.bb!.createScalarList<number>(this.authority.bind(this), this.authorityLength());
+  _o.id = this.id();
+  _o.claimers = this.bb!.createObjList<Claimer, ClaimerT>(this.claimers.bind(this), this.claimersLength());
+}
+}
+
+export class AllowListV1T implements flatbuffers.IGeneratedObject {
+constructor(
+  public authority: (number)[] = [],
+  public id: string|Uint8Array|null = null,
+  public claimers: (ClaimerT)[] = []
+){}
+
+
+pack(builder:flatbuffers.Builder): flatbuffers.Offset {
+  const authority = AllowListV1.createAuthorityVector(builder, this.authority);
+  const id = (this.id !== null ? builder.createString(this.id!) : 0);
+  const claimers = builder.createStructOffsetList(this.claimers, AllowListV1.startClaimersVector);
+
+  return AllowListV1.createAllowListV1(builder,
+    authority,
+    id,
+    claimers
+  );
+}
+}
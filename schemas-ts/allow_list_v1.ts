@@ -0,0 +1,6 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+export { AllowListV1, AllowListV1T } from './allow-list-v1.js';
+export { Claimer, ClaimerT } from './claimer.js';
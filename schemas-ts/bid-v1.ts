@@ -0,0 +1,136 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+import * as flatbuffers from 'flatbuffers';
+
+
+
+export class BidV1 implements flatbuffers.IUnpackableObject<BidV1T> {
+  bb: flatbuffers.ByteBuffer|null = null;
+  bb_pos = 0;
+  __init(i:number, bb:flatbuffers.ByteBuffer):BidV1 {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+}
+
+static getRootAsBidV1(bb:flatbuffers.ByteBuffer, obj?:BidV1):BidV1 {
+  return (obj || new BidV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+static getSizePrefixedRootAsBidV1(bb:flatbuffers.ByteBuffer, obj?:BidV1):BidV1 {
+  bb.setPosition(bb.position() + flatbuffers.SIZE_PREFIX_LENGTH);
+  return (obj || new BidV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+executionId():string|null
+executionId(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+executionId(optionalEncoding?:any):string|Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+}
+
+price():bigint {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.readUint64(this.bb_pos + offset) : BigInt('0');
+}
+
+mutate_price(value:bigint):boolean {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint64(this.bb_pos + offset, value);
+  return true;
+}
+
+completionSlot():bigint {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.readUint64(this.bb_pos + offset) : BigInt('0');
+}
+
+mutate_completion_slot(value:bigint):boolean {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint64(this.bb_pos + offset, value);
+  return true;
+}
+
+static startBidV1(builder:flatbuffers.Builder) {
+  builder.startObject(3);
+}
+
+static addExecutionId(builder:flatbuffers.Builder, executionIdOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, executionIdOffset, 0);
+}
+
+static addPrice(builder:flatbuffers.Builder, price:bigint) {
+  builder.addFieldInt64(1, price, BigInt('0'));
+}
+
+static addCompletionSlot(builder:flatbuffers.Builder, completionSlot:bigint) {
+  builder.addFieldInt64(2, completionSlot, BigInt('0'));
+}
+
+static endBidV1(builder:flatbuffers.Builder):flatbuffers.Offset {
+  const offset = builder.endObject();
+  return offset;
+}
+
+static finishBidV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset);
+}
+
+static finishSizePrefixedBidV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset, undefined, true);
+}
+
+static createBidV1(builder:flatbuffers.Builder, executionIdOffset:flatbuffers.Offset, price:bigint, completionSlot:bigint):flatbuffers.Offset {
+  BidV1.startBidV1(builder);
+  BidV1.addExecutionId(builder, executionIdOffset);
+  BidV1.addPrice(builder, price);
+  BidV1.addCompletionSlot(builder, completionSlot);
+  return BidV1.endBidV1(builder);
+}
+
+unpack(): BidV1T {
+  return new BidV1T(
+    this.executionId(),
+    this.price(),
+    this.completionSlot()
+  );
+}
+
+
+unpackTo(_o: BidV1T): void {
+  _o.executionId = this.executionId();
+  _o.price = this.price();
+  _o.completionSlot = this.completionSlot();
+}
+}
+
+export class BidV1T implements flatbuffers.IGeneratedObject {
+constructor(
+  public executionId: string|Uint8Array|null = null,
+  public price: bigint = BigInt('0'),
+  public completionSlot: bigint = BigInt('0')
+){}
+
+
+pack(builder:flatbuffers.Builder): flatbuffers.Offset {
+  const executionId = (this.executionId !== null ? builder.createString(this.executionId!) : 0);
+
+  return BidV1.createBidV1(builder,
+    executionId,
+    this.price,
+    this.completionSlot
+  );
+}
+}
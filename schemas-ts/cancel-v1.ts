@@ -0,0 +1,86 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+import * as flatbuffers from 'flatbuffers';
+
+
+
+export class CancelV1 implements flatbuffers.IUnpackableObject<CancelV1T> {
+  bb: flatbuffers.ByteBuffer|null = null;
+  bb_pos = 0;
+  __init(i:number, bb:flatbuffers.ByteBuffer):CancelV1 {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+}
+
+static getRootAsCancelV1(bb:flatbuffers.ByteBuffer, obj?:CancelV1):CancelV1 {
+  return (obj || new CancelV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+static getSizePrefixedRootAsCancelV1(bb:flatbuffers.ByteBuffer, obj?:CancelV1):CancelV1 {
+  bb.setPosition(bb.position() + flatbuffers.SIZE_PREFIX_LENGTH);
+  return (obj || new CancelV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+executionId():string|null
+executionId(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+executionId(optionalEncoding?:any):string|Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+}
+
+static startCancelV1(builder:flatbuffers.Builder) {
+  builder.startObject(1);
+}
+
+static addExecutionId(builder:flatbuffers.Builder, executionIdOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, executionIdOffset, 0);
+}
+
+static endCancelV1(builder:flatbuffers.Builder):flatbuffers.Offset {
+  const offset = builder.endObject();
+  return offset;
+}
+
+static finishCancelV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset);
+}
+
+static finishSizePrefixedCancelV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset, undefined, true);
+}
+
+static createCancelV1(builder:flatbuffers.Builder, executionIdOffset:flatbuffers.Offset):flatbuffers.Offset {
+  CancelV1.startCancelV1(builder);
+  CancelV1.addExecutionId(builder, executionIdOffset);
+  return CancelV1.endCancelV1(builder);
+}
+
+unpack(): CancelV1T {
+  return new CancelV1T(
+    this.executionId()
+  );
+}
+
+
+unpackTo(_o: CancelV1T): void {
+  _o.executionId = this.executionId();
+}
+}
+
+export class CancelV1T implements flatbuffers.IGeneratedObject {
+constructor(
+  public executionId: string|Uint8Array|null = null
+){}
+
+
+pack(builder:flatbuffers.Builder): flatbuffers.Offset {
+  const executionId = (this.executionId !== null ? builder.createString(this.executionId!) : 0);
+
+  return CancelV1.createCancelV1(builder,
+    executionId
+  );
+}
+}
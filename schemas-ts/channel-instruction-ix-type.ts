@@ -6,5 +6,16 @@ export enum ChannelInstructionIxType {
   ExecuteV1 = 0,
   StatusV1 = 1,
   DeployV1 = 2,
-  ClaimV1 = 3
+  ClaimV1 = 3,
+  UpdateDeploymentUrlV1 = 5,
+  DeprecateDeploymentV1 = 6,
+  CloseDeploymentV1 = 7,
+  CancelV1 = 8,
+  ExpireV1 = 9,
+  RegisterProverV1 = 10,
+  SetAllowListV1 = 11,
+  CloseAllowListV1 = 12,
+  BidV1 = 13,
+  SetVerifierConfigV1 = 14,
+  StatusBatchV1 = 15
 }
@@ -101,8 +101,128 @@ claimV1Array():Uint8Array|null {
   return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
 }
 
+manageDeploymentV1(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 14);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+manageDeploymentV1Length():number {
+  const offset = this.bb!.__offset(this.bb_pos, 14);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+manageDeploymentV1Array():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 14);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+cancelV1(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 16);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+cancelV1Length():number {
+  const offset = this.bb!.__offset(this.bb_pos, 16);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+cancelV1Array():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 16);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+expireV1(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 18);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+expireV1Length():number {
+  const offset = this.bb!.__offset(this.bb_pos, 18);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+expireV1Array():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 18);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+registerProverV1(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 20);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+registerProverV1Length():number {
+  const offset = this.bb!.__offset(this.bb_pos, 20);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+registerProverV1Array():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 20);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+allowListV1(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 22);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+allowListV1Length():number {
+  const offset = this.bb!.__offset(this.bb_pos, 22);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+allowListV1Array():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 22);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+bidV1(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 24);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+bidV1Length():number {
+  const offset = this.bb!.__offset(this.bb_pos, 24);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+bidV1Array():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 24);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+verifierConfigV1(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 26);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+verifierConfigV1Length():number {
+  const offset = this.bb!.__offset(this.bb_pos, 26);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+verifierConfigV1Array():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 26);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+statusBatchV1(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 28);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+statusBatchV1Length():number {
+  const offset = this.bb!.__offset(this.bb_pos, 28);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+statusBatchV1Array():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 28);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
 static startChannelInstruction(builder:flatbuffers.Builder) {
-  builder.startObject(5);
+  builder.startObject(13);
 }
 
 static addIxType(builder:flatbuffers.Builder, ixType:ChannelInstructionIxType) {
@@ -173,6 +293,134 @@ static startClaimV1Vector(builder:flatbuffers.Builder, numElems:number) {
   builder.startVector(1, numElems, 1);
 }
 
+static addManageDeploymentV1(builder:flatbuffers.Builder, manageDeploymentV1Offset:flatbuffers.Offset) {
+  builder.addFieldOffset(5, manageDeploymentV1Offset, 0);
+}
+
+static createManageDeploymentV1Vector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startManageDeploymentV1Vector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addCancelV1(builder:flatbuffers.Builder, cancelV1Offset:flatbuffers.Offset) {
+  builder.addFieldOffset(6, cancelV1Offset, 0);
+}
+
+static createCancelV1Vector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startCancelV1Vector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addExpireV1(builder:flatbuffers.Builder, expireV1Offset:flatbuffers.Offset) {
+  builder.addFieldOffset(7, expireV1Offset, 0);
+}
+
+static createExpireV1Vector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startExpireV1Vector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addRegisterProverV1(builder:flatbuffers.Builder, registerProverV1Offset:flatbuffers.Offset) {
+  builder.addFieldOffset(8, registerProverV1Offset, 0);
+}
+
+static createRegisterProverV1Vector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startRegisterProverV1Vector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addAllowListV1(builder:flatbuffers.Builder, allowListV1Offset:flatbuffers.Offset) {
+  builder.addFieldOffset(9, allowListV1Offset, 0);
+}
+
+static createAllowListV1Vector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startAllowListV1Vector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addBidV1(builder:flatbuffers.Builder, bidV1Offset:flatbuffers.Offset) {
+  builder.addFieldOffset(10, bidV1Offset, 0);
+}
+
+static createBidV1Vector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startBidV1Vector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addVerifierConfigV1(builder:flatbuffers.Builder, verifierConfigV1Offset:flatbuffers.Offset) {
+  builder.addFieldOffset(11, verifierConfigV1Offset, 0);
+}
+
+static createVerifierConfigV1Vector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startVerifierConfigV1Vector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addStatusBatchV1(builder:flatbuffers.Builder, statusBatchV1Offset:flatbuffers.Offset) {
+  builder.addFieldOffset(12, statusBatchV1Offset, 0);
+}
+
+static createStatusBatchV1Vector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startStatusBatchV1Vector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
 static endChannelInstruction(builder:flatbuffers.Builder):flatbuffers.Offset {
   const offset = builder.endObject();
   return offset;
@@ -186,13 +434,21 @@ static finishSizePrefixedChannelInstructionBuffer(builder:flatbuffers.Builder, o
   builder.finish(offset, undefined, true);
 }
 
-static createChannelInstruction(builder:flatbuffers.Builder, ixType:ChannelInstructionIxType, executeV1Offset:flatbuffers.Offset, statusV1Offset:flatbuffers.Offset, deployV1Offset:flatbuffers.Offset, claimV1Offset:flatbuffers.Offset):flatbuffers.Offset {
+static createChannelInstruction(builder:flatbuffers.Builder, ixType:ChannelInstructionIxType, executeV1Offset:flatbuffers.Offset, statusV1Offset:flatbuffers.Offset, deployV1Offset:flatbuffers.Offset, claimV1Offset:flatbuffers.Offset, manageDeploymentV1Offset:flatbuffers.Offset, cancelV1Offset:flatbuffers.Offset, expireV1Offset:flatbuffers.Offset, registerProverV1Offset:flatbuffers.Offset, allowListV1Offset:flatbuffers.Offset, bidV1Offset:flatbuffers.Offset, verifierConfigV1Offset:flatbuffers.Offset, statusBatchV1Offset:flatbuffers.Offset):flatbuffers.Offset {
   ChannelInstruction.startChannelInstruction(builder);
   ChannelInstruction.addIxType(builder, ixType);
   ChannelInstruction.addExecuteV1(builder, executeV1Offset);
   ChannelInstruction.addStatusV1(builder, statusV1Offset);
   ChannelInstruction.addDeployV1(builder, deployV1Offset);
   ChannelInstruction.addClaimV1(builder, claimV1Offset);
+  ChannelInstruction.addManageDeploymentV1(builder, manageDeploymentV1Offset);
+  ChannelInstruction.addCancelV1(builder, cancelV1Offset);
+  ChannelInstruction.addExpireV1(builder, expireV1Offset);
+  ChannelInstruction.addRegisterProverV1(builder, registerProverV1Offset);
+  ChannelInstruction.addAllowListV1(builder, allowListV1Offset);
+  ChannelInstruction.addBidV1(builder, bidV1Offset);
+  ChannelInstruction.addVerifierConfigV1(builder, verifierConfigV1Offset);
+  ChannelInstruction.addStatusBatchV1(builder, statusBatchV1Offset);
   return ChannelInstruction.endChannelInstruction(builder);
 }
 
@@ -202,7 +458,15 @@ unpack(): ChannelInstructionT {
     this.bb!.createScalarList<number>(this.executeV1.bind(this), this.executeV1Length()),
     this.bb!.createScalarList<number>(this.statusV1.bind(this), this.statusV1Length()),
     this.bb!.createScalarList<number>(this.deployV1.bind(this), this.deployV1Length()),
-    this.bb!.createScalarList<number>(this.claimV1.bind(this), this.claimV1Length())
+    this.bb!.createScalarList<number>(this.claimV1.bind(this), this.claimV1Length()),
+    this.bb!.createScalarList<number>(this.manageDeploymentV1.bind(this), this.manageDeploymentV1Length()),
+    this.bb!.createScalarList<number>(this.cancelV1.bind(this), this.cancelV1Length()),
+    this.bb!.createScalarList<number>(this.expireV1.bind(this), this.expireV1Length()),
+    this.bb!.createScalarList<number>(this.registerProverV1.bind(this), this.registerProverV1Length()),
+    this.bb!.createScalarList<number>(this.allowListV1.bind(this), this.allowListV1Length()),
+    this.bb!.createScalarList<number>(this.bidV1.bind(this), this.bidV1Length()),
+    this.bb!.createScalarList<number>(this.verifierConfigV1.bind(this), this.verifierConfigV1Length()),
+    this.bb!.createScalarList<number>(this.statusBatchV1.bind(this), this.statusBatchV1Length())
   );
 }
 
@@ -213,6 +477,14 @@ unpackTo(_o: ChannelInstructionT): void {
   _o.statusV1 = this.bb!.createScalarList<number>(this.statusV1.bind(this), this.statusV1Length());
   _o.deployV1 = this.bb!.createScalarList<number>(this.deployV1.bind(this), this.deployV1Length());
   _o.claimV1 = this.bb!.createScalarList<number>(this.claimV1.bind(this), this.claimV1Length());
+  _o.manageDeploymentV1 = this.bb!.createScalarList<number>(this.manageDeploymentV1.bind(this), this.manageDeploymentV1Length());
+  _o.cancelV1 = this.bb!.createScalarList<number>(this.cancelV1.bind(this), this.cancelV1Length());
+  _o.expireV1 = this.bb!.createScalarList<number>(this.expireV1.bind(this), this.expireV1Length());
+  _o.registerProverV1 = this.bb!.createScalarList<number>(this.registerProverV1.bind(this), this.registerProverV1Length());
+  _o.allowListV1 = this.bb!.createScalarList<number>(this.allowListV1.bind(this), this.allowListV1Length());
+  _o.bidV1 = this.bb!.createScalarList<number>(this.bidV1.bind(this), this.bidV1Length());
+  _o.verifierConfigV1 = this.bb!.createScalarList<number>(this.verifierConfigV1.bind(this), this.verifierConfigV1Length());
+  _o.statusBatchV1 = this.bb!.createScalarList<number>(this.statusBatchV1.bind(this), this.statusBatchV1Length());
 }
 }
 
@@ -222,7 +494,15 @@ constructor(
   public executeV1: (number)[] = [],
   public statusV1: (number)[] = [],
   public deployV1: (number)[] = [],
-  public claimV1: (number)[] = []
+  public claimV1: (number)[] = [],
+  public manageDeploymentV1: (number)[] = [],
+  public cancelV1: (number)[] = [],
+  public expireV1: (number)[] = [],
+  public registerProverV1: (number)[] = [],
+  public allowListV1: (number)[] = [],
+  public bidV1: (number)[] = [],
+  public verifierConfigV1: (number)[] = [],
+  public statusBatchV1: (number)[] = []
 ){}
 
 
@@ -231,13 +511,29 @@ pack(builder:flatbuffers.Builder): flatbuffers.Offset {
   const statusV1 = ChannelInstruction.createStatusV1Vector(builder, this.statusV1);
   const deployV1 = ChannelInstruction.createDeployV1Vector(builder, this.deployV1);
   const claimV1 = ChannelInstruction.createClaimV1Vector(builder, this.claimV1);
+  const manageDeploymentV1 = ChannelInstruction.createManageDeploymentV1Vector(builder, this.manageDeploymentV1);
+  const cancelV1 = ChannelInstruction.createCancelV1Vector(builder, this.cancelV1);
+  const expireV1 = ChannelInstruction.createExpireV1Vector(builder, this.expireV1);
+  const registerProverV1 = ChannelInstruction.createRegisterProverV1Vector(builder, this.registerProverV1);
+  const allowListV1 = ChannelInstruction.createAllowListV1Vector(builder, this.allowListV1);
+  const bidV1 = ChannelInstruction.createBidV1Vector(builder, this.bidV1);
+  const verifierConfigV1 = ChannelInstruction.createVerifierConfigV1Vector(builder, this.verifierConfigV1);
+  const statusBatchV1 = ChannelInstruction.createStatusBatchV1Vector(builder, this.statusBatchV1);
 
   return ChannelInstruction.createChannelInstruction(builder,
     this.ixType,
     executeV1,
     statusV1,
     deployV1,
-    claimV1
+    claimV1,
+    manageDeploymentV1,
+    cancelV1,
+    expireV1,
+    registerProverV1,
+    allowListV1,
+    bidV1,
+    verifierConfigV1,
+    statusBatchV1
   );
 }
 }
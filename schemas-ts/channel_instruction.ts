@@ -3,14 +3,23 @@
 /* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
 
 export { Account, AccountT } from './account.js';
+export { AllowListV1, AllowListV1T } from './allow-list-v1.js';
+export { BidV1, BidV1T } from './bid-v1.js';
+export { CancelV1, CancelV1T } from './cancel-v1.js';
 export { ChannelInstruction, ChannelInstructionT } from './channel-instruction.js';
 export { ChannelInstructionIxType } from './channel-instruction-ix-type.js';
 export { ClaimV1, ClaimV1T } from './claim-v1.js';
+export { Claimer, ClaimerT } from './claimer.js';
 export { DeployV1, DeployV1T } from './deploy-v1.js';
 export { ExecutionRequestV1, ExecutionRequestV1T } from './execution-request-v1.js';
+export { ExpireV1, ExpireV1T } from './expire-v1.js';
 export { Input, InputT } from './input.js';
 export { InputType } from './input-type.js';
+export { ManageDeploymentV1, ManageDeploymentV1T } from './manage-deployment-v1.js';
 export { ProgramInputType } from './program-input-type.js';
 export { ProverVersion } from './prover-version.js';
+export { RegisterProverV1, RegisterProverV1T } from './register-prover-v1.js';
+export { StatusBatchV1, StatusBatchV1T } from './status-batch-v1.js';
 export { StatusTypes } from './status-types.js';
 export { StatusV1, StatusV1T } from './status-v1.js';
+export { VerifierConfigV1, VerifierConfigV1T } from './verifier-config-v1.js';
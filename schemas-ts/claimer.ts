@@ -0,0 +1,61 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+import * as flatbuffers from 'flatbuffers';
+
+
+
+export class Claimer implements flatbuffers.IUnpackableObject<ClaimerT> {
+  bb: flatbuffers.ByteBuffer|null = null;
+  bb_pos = 0;
+  __init(i:number, bb:flatbuffers.ByteBuffer):Claimer {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+}
+
+pubkey(index: number):number|null {
+    return this.bb!.readUint8(this.bb_pos + 0 + index);
+}
+
+static sizeOf():number {
+  return 32;
+}
+
+static createClaimer(builder:flatbuffers.Builder, pubkey: number[]|null):flatbuffers.Offset {
+  builder.prep(8, 32);
+
+  for (let i = 31; i >= 0; --i) {
+    builder.writeInt8((pubkey?.[i] ?? 0));
+
+  }
+
+  return builder.offset();
+}
+
+
+unpack(): ClaimerT {
+  return new ClaimerT(
+    this.bb!.createScalarList<number>(this.pubkey.bind(this), 32)
+  );
+}
+
+
+unpackTo(_o: ClaimerT): void {
+  _o.pubkey = this.bb!.createScalarList<number>(this.pubkey.bind(this), 32);
+}
+}
+
+export class ClaimerT implements flatbuffers.IGeneratedObject {
+constructor(
+  public pubkey: (number)[] = []
+){}
+
+
+pack(builder:flatbuffers.Builder): flatbuffers.Offset {
+  return Claimer.createClaimer(builder,
+    this.pubkey
+  );
+}
+}
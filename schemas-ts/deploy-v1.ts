@@ -92,8 +92,39 @@ inputsArray():Uint8Array|null {
   return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
 }
 
+imageChecksum(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 16);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+imageChecksumLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 16);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+imageChecksumArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 16);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+deprecated():boolean {
+  const offset = this.bb!.__offset(this.bb_pos, 18);
+  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : false;
+}
+
+mutate_deprecated(value:boolean):boolean {
+  const offset = this.bb!.__offset(this.bb_pos, 18);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeInt8(this.bb_pos + offset, +value);
+  return true;
+}
+
 static startDeployV1(builder:flatbuffers.Builder) {
-  builder.startObject(6);
+  builder.startObject(8);
 }
 
 static addOwner(builder:flatbuffers.Builder, ownerOffset:flatbuffers.Offset) {
@@ -144,6 +175,26 @@ static startInputsVector(builder:flatbuffers.Builder, numElems:number) {
   builder.startVector(1, numElems, 1);
 }
 
+static addImageChecksum(builder:flatbuffers.Builder, imageChecksumOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(6, imageChecksumOffset, 0);
+}
+
+static createImageChecksumVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startImageChecksumVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addDeprecated(builder:flatbuffers.Builder, deprecated:boolean) {
+  builder.addFieldInt8(7, +deprecated, +false);
+}
+
 static endDeployV1(builder:flatbuffers.Builder):flatbuffers.Offset {
   const offset = builder.endObject();
   return offset;
@@ -157,7 +208,7 @@ static finishSizePrefixedDeployV1Buffer(builder:flatbuffers.Builder, offset:flat
   builder.finish(offset, undefined, true);
 }
 
-static createDeployV1(builder:flatbuffers.Builder, ownerOffset:flatbuffers.Offset, imageIdOffset:flatbuffers.Offset, programNameOffset:flatbuffers.Offset, urlOffset:flatbuffers.Offset, size:bigint, inputsOffset:flatbuffers.Offset):flatbuffers.Offset {
+static createDeployV1(builder:flatbuffers.Builder, ownerOffset:flatbuffers.Offset, imageIdOffset:flatbuffers.Offset, programNameOffset:flatbuffers.Offset, urlOffset:flatbuffers.Offset, size:bigint, inputsOffset:flatbuffers.Offset, imageChecksumOffset:flatbuffers.Offset, deprecated:boolean):flatbuffers.Offset {
   DeployV1.startDeployV1(builder);
   DeployV1.addOwner(builder, ownerOffset);
   DeployV1.addImageId(builder, imageIdOffset);
@@ -165,6 +216,8 @@ static createDeployV1(builder:flatbuffers.Builder, ownerOffset:flatbuffers.Offse
   DeployV1.addUrl(builder, urlOffset);
   DeployV1.addSize(builder, size);
   DeployV1.addInputs(builder, inputsOffset);
+  DeployV1.addImageChecksum(builder, imageChecksumOffset);
+  DeployV1.addDeprecated(builder, deprecated);
   return DeployV1.endDeployV1(builder);
 }
 
@@ -175,7 +228,9 @@ unpack(): DeployV1T {
     this.programName(),
     this.url(),
     this.size(),
-    this.bb!.createScalarList<ProgramInputType>(this.inputs.bind(this), this.inputsLength())
+    this.bb!.createScalarList<ProgramInputType>(this.inputs.bind(this), this.inputsLength()),
+    this.bb!.createScalarList<number>(this.imageChecksum.bind(this), this.imageChecksumLength()),
+    this.deprecated()
   );
 }
 
@@ -187,6 +242,8 @@ unpackTo(_o: DeployV1T): void {
   _o.url = this.url();
   _o.size = this.size();
   _o.inputs = this.bb!.createScalarList<ProgramInputType>(this.inputs.bind(this), this.inputsLength());
+  _o.imageChecksum = this.bb!.createScalarList<number>(this.imageChecksum.bind(this), this.imageChecksumLength());
+  _o.deprecated = this.deprecated();
 }
 }
 
@@ -197,7 +254,9 @@ constructor(
   public programName: string|Uint8Array|null = null,
   public url: string|Uint8Array|null = null,
   public size: bigint = BigInt('0'),
-  public inputs: (ProgramInputType)[] = []
+  public inputs: (ProgramInputType)[] = [],
+  public imageChecksum: (number)[] = [],
+  public deprecated: boolean = false
 ){}
 
 
@@ -207,6 +266,7 @@ pack(builder:flatbuffers.Builder): flatbuffers.Offset {
   const programName = (this.programName !== null ? builder.createString(this.programName!) : 0);
   const url = (this.url !== null ? builder.createString(this.url!) : 0);
   const inputs = DeployV1.createInputsVector(builder, this.inputs);
+  const imageChecksum = DeployV1.createImageChecksumVector(builder, this.imageChecksum);
 
   return DeployV1.createDeployV1(builder,
     owner,
@@ -214,7 +274,9 @@ pack(builder:flatbuffers.Builder): flatbuffers.Offset {
     programName,
     url,
     this.size,
-    inputs
+    inputs,
+    imageChecksum,
+    this.deprecated
   );
 }
 }
@@ -5,6 +5,7 @@
 import * as flatbuffers from 'flatbuffers';
 
 import { Account, AccountT } from './account.js';
+import { Claimer, ClaimerT } from './claimer.js';
 import { Input, InputT } from './input.js';
 import { ProverVersion } from './prover-version.js';
 
@@ -186,8 +187,81 @@ mutate_prover_version(value:ProverVersion):boolean {
   return true;
 }
 
+minReputation():number {
+  const offset = this.bb!.__offset(this.bb_pos, 28);
+  return offset ? this.bb!.readUint16(this.bb_pos + offset) : 0;
+}
+
+mutate_min_reputation(value:number):boolean {
+  const offset = this.bb!.__offset(this.bb_pos, 28);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint16(this.bb_pos + offset, value);
+  return true;
+}
+
+minProofs():bigint {
+  const offset = this.bb!.__offset(this.bb_pos, 30);
+  return offset ? this.bb!.readUint64(this.bb_pos + offset) : BigInt('0');
+}
+
+mutate_min_proofs(value:bigint):boolean {
+  const offset = this.bb!.__offset(this.bb_pos, 30);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint64(this.bb_pos + offset, value);
+  return true;
+}
+
+allowedClaimers(index: number, obj?:Claimer):Claimer|null {
+  const offset = this.bb!.__offset(this.bb_pos, 32);
+  return offset ? (obj || new Claimer()).__init(this.bb!.__vector(this.bb_pos + offset) + index * 32, this.bb!) : null;
+}
+
+allowedClaimersLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 32);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+claimerAllowList(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 34);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+claimerAllowListLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 34);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+claimerAllowListArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 34);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+auctionSlots():bigint {
+  const offset = this.bb!.__offset(this.bb_pos, 36);
+  return offset ? this.bb!.readUint64(this.bb_pos + offset) : BigInt('0');
+}
+
+mutate_auction_slots(value:bigint):boolean {
+  const offset = this.bb!.__offset(this.bb_pos, 36);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint64(this.bb_pos + offset, value);
+  return true;
+}
+
 static startExecutionRequestV1(builder:flatbuffers.Builder) {
-  builder.startObject(12);
+  builder.startObject(17);
 }
 
 static addTip(builder:flatbuffers.Builder, tip:bigint) {
@@ -290,6 +364,42 @@ static addProverVersion(builder:flatbuffers.Builder, proverVersion:ProverVersion
   builder.addFieldInt16(11, proverVersion, ProverVersion.DEFAULT);
 }
 
+static addMinReputation(builder:flatbuffers.Builder, minReputation:number) {
+  builder.addFieldInt16(12, minReputation, 0);
+}
+
+static addMinProofs(builder:flatbuffers.Builder, minProofs:bigint) {
+  builder.addFieldInt64(13, minProofs, BigInt('0'));
+}
+
+static addAllowedClaimers(builder:flatbuffers.Builder, allowedClaimersOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(14, allowedClaimersOffset, 0);
+}
+
+static startAllowedClaimersVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(32, numElems, 8);
+}
+
+static addClaimerAllowList(builder:flatbuffers.Builder, claimerAllowListOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(15, claimerAllowListOffset, 0);
+}
+
+static createClaimerAllowListVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startClaimerAllowListVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addAuctionSlots(builder:flatbuffers.Builder, auctionSlots:bigint) {
+  builder.addFieldInt64(16, auctionSlots, BigInt('0'));
+}
+
 static endExecutionRequestV1(builder:flatbuffers.Builder):flatbuffers.Offset {
   const offset = builder.endObject();
   return offset;
@@ -303,7 +413,7 @@ static finishSizePrefixedExecutionRequestV1Buffer(builder:flatbuffers.Builder, o
   builder.finish(offset, undefined, true);
 }
 
-static createExecutionRequestV1(builder:flatbuffers.Builder, tip:bigint, executionIdOffset:flatbuffers.Offset, imageIdOffset:flatbuffers.Offset, callbackProgramIdOffset:flatbuffers.Offset, callbackInstructionPrefixOffset:flatbuffers.Offset, forwardOutput:boolean, verifyInputHash:boolean, inputOffset:flatbuffers.Offset, inputDigestOffset:flatbuffers.Offset, maxBlockHeight:bigint, callbackExtraAccountsOffset:flatbuffers.Offset, proverVersion:ProverVersion):flatbuffers.Offset {
+static createExecutionRequestV1(builder:flatbuffers.Builder, tip:bigint, executionIdOffset:flatbuffers.Offset, imageIdOffset:flatbuffers.Offset, callbackProgramIdOffset:flatbuffers.Offset, callbackInstructionPrefixOffset:flatbuffers.Offset, forwardOutput:boolean, verifyInputHash:boolean, inputOffset:flatbuffers.Offset, inputDigestOffset:flatbuffers.Offset, maxBlockHeight:bigint, callbackExtraAccountsOffset:flatbuffers.Offset, proverVersion:ProverVersion, minReputation:number, minProofs:bigint, allowedClaimersOffset:flatbuffers.Offset, claimerAllowListOffset:flatbuffers.Offset, auctionSlots:bigint):flatbuffers.Offset {
   ExecutionRequestV1.startExecutionRequestV1(builder);
   ExecutionRequestV1.addTip(builder, tip);
   ExecutionRequestV1.addExecutionId(builder, executionIdOffset);
@@ -317,6 +427,11 @@ static createExecutionRequestV1(builder:flatbuffers.Builder, tip:bigint, executi
   ExecutionRequestV1.addMaxBlockHeight(builder, maxBlockHeight);
   ExecutionRequestV1.addCallbackExtraAccounts(builder, callbackExtraAccountsOffset);
   ExecutionRequestV1.addProverVersion(builder, proverVersion);
+  ExecutionRequestV1.addMinReputation(builder, minReputation);
+  ExecutionRequestV1.addMinProofs(builder, minProofs);
+  ExecutionRequestV1.addAllowedClaimers(builder, allowedClaimersOffset);
+  ExecutionRequestV1.addClaimerAllowList(builder, claimerAllowListOffset);
+  ExecutionRequestV1.addAuctionSlots(builder, auctionSlots);
   return ExecutionRequestV1.endExecutionRequestV1(builder);
 }
 
@@ -333,7 +448,12 @@ unpack(): ExecutionRequestV1T {
     this.bb!.createScalarList<number>(this.inputDigest.bind(this), this.inputDigestLength()),
     this.maxBlockHeight(),
     this.bb!.createObjList<Account, AccountT>(this.callbackExtraAccounts.bind(this), this.callbackExtraAccountsLength()),
-    this.proverVersion()
+    this.proverVersion(),
+    this.minReputation(),
+    this.minProofs(),
+    this.bb!.createObjList<Claimer, ClaimerT>(this.allowedClaimers.bind(this), this.allowedClaimersLength()),
+    this.bb!.createScalarList<number>(this.claimerAllowList.bind(this), this.claimerAllowListLength()),
+    this.auctionSlots()
   );
 }
 
@@ -351,6 +471,11 @@ unpackTo(_o: ExecutionRequestV1T): void {
   _o.maxBlockHeight = this.maxBlockHeight();
   _o.callbackExtraAccounts = this.bb!.createObjList<Account, AccountT>(this.callbackExtraAccounts.bind(this), this.callbackExtraAccountsLength());
   _o.proverVersion = this.proverVersion();
+  _o.minReputation = this.minReputation();
+  _o.minProofs = this.minProofs();
+  _o.allowedClaimers = this.bb!.createObjList<Claimer, ClaimerT>(this.allowedClaimers.bind(this), this.allowedClaimersLength());
+  _o.claimerAllowList = this.bb!.createScalarList<number>(this.claimerAllowList.bind(this), this.claimerAllowListLength());
+  _o.auctionSlots = this.auctionSlots();
 }
 }
 
@@ -367,7 +492,12 @@ constructor(
   public inputDigest: (number)[] = [],
   public maxBlockHeight: bigint = BigInt('0'),
   public callbackExtraAccounts: (AccountT)[] = [],
-  public proverVersion: ProverVersion = ProverVersion.DEFAULT
+  public proverVersion: ProverVersion = ProverVersion.DEFAULT,
+  public minReputation: number = 0,
+  public minProofs: bigint = BigInt('0'),
+  public allowedClaimers: (ClaimerT)[] = [],
+  public claimerAllowList: (number)[] = [],
+  public auctionSlots: bigint = BigInt('0')
 ){}
 
 
@@ -379,6 +509,8 @@ pack(builder:flatbuffers.Builder): flatbuffers.Offset {
   const input = ExecutionRequestV1.createInputVector(builder, builder.createObjectOffsetList(this.input));
   const inputDigest = ExecutionRequestV1.createInputDigestVector(builder, this.inputDigest);
   const callbackExtraAccounts = builder.createStructOffsetList(this.callbackExtraAccounts, ExecutionRequestV1.startCallbackExtraAccountsVector);
+  const allowedClaimers = builder.createStructOffsetList(this.allowedClaimers, ExecutionRequestV1.startAllowedClaimersVector);
+  const claimerAllowList = ExecutionRequestV1.createClaimerAllowListVector(builder, this.claimerAllowList);
 
   return ExecutionRequestV1.createExecutionRequestV1(builder,
     this.tip,
@@ -392,7 +524,12 @@ pack(builder:flatbuffers.Builder): flatbuffers.Offset {
     inputDigest,
     this.maxBlockHeight,
     callbackExtraAccounts,
-    this.proverVersion
+    this.proverVersion,
+    this.minReputation,
+    this.minProofs,
+    allowedClaimers,
+    claimerAllowList,
+    this.auctionSlots
   );
 }
 }
@@ -3,6 +3,8 @@
 /* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
 
 export { Account, AccountT } from './account.js';
+export { AllowListV1, AllowListV1T } from './allow-list-v1.js';
+export { Claimer, ClaimerT } from './claimer.js';
 export { ExecutionRequestV1, ExecutionRequestV1T } from './execution-request-v1.js';
 export { Input, InputT } from './input.js';
 export { InputType } from './input-type.js';
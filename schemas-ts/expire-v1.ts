@@ -0,0 +1,86 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+import * as flatbuffers from 'flatbuffers';
+
+
+
+export class ExpireV1 implements flatbuffers.IUnpackableObject<ExpireV1T> {
+  bb: flatbuffers.ByteBuffer|null = null;
+  bb_pos = 0;
+  __init(i:number, bb:flatbuffers.ByteBuffer):ExpireV1 {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+}
+
+static getRootAsExpireV1(bb:flatbuffers.ByteBuffer, obj?:ExpireV1):ExpireV1 {
+  return (obj || new ExpireV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+static getSizePrefixedRootAsExpireV1(bb:flatbuffers.ByteBuffer, obj?:ExpireV1):ExpireV1 {
+  bb.setPosition(bb.position() + flatbuffers.SIZE_PREFIX_LENGTH);
+  return (obj || new ExpireV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+executionId():string|null
+executionId(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+executionId(optionalEncoding?:any):string|Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+}
+
+static startExpireV1(builder:flatbuffers.Builder) {
+  builder.startObject(1);
+}
+
+static addExecutionId(builder:flatbuffers.Builder, executionIdOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, executionIdOffset, 0);
+}
+
+static endExpireV1(builder:flatbuffers.Builder):flatbuffers.Offset {
+  const offset = builder.endObject();
+  return offset;
+}
+
+static finishExpireV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset);
+}
+
+static finishSizePrefixedExpireV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset, undefined, true);
+}
+
+static createExpireV1(builder:flatbuffers.Builder, executionIdOffset:flatbuffers.Offset):flatbuffers.Offset {
+  ExpireV1.startExpireV1(builder);
+  ExpireV1.addExecutionId(builder, executionIdOffset);
+  return ExpireV1.endExpireV1(builder);
+}
+
+unpack(): ExpireV1T {
+  return new ExpireV1T(
+    this.executionId()
+  );
+}
+
+
+unpackTo(_o: ExpireV1T): void {
+  _o.executionId = this.executionId();
+}
+}
+
+export class ExpireV1T implements flatbuffers.IGeneratedObject {
+constructor(
+  public executionId: string|Uint8Array|null = null
+){}
+
+
+pack(builder:flatbuffers.Builder): flatbuffers.Offset {
+  const executionId = (this.executionId !== null ? builder.createString(this.executionId!) : 0);
+
+  return ExpireV1.createExpireV1(builder,
+    executionId
+  );
+}
+}
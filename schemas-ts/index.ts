@@ -4,6 +4,14 @@ export * from './claim_v1';
 export * from './deploy_v1';
 export * from './execution_request_v1';
 export * from './status_v1';
+export * from './manage_deployment_v1';
+export * from './cancel_v1';
+export * from './expire_v1';
+export * from './prover_v1';
+export * from './allow_list_v1';
+export * from './bid_v1';
+export * from './verifier_config_v1';
+export * from './status_batch_v1';
 
 export enum ExitCode {
   Success = 0,
@@ -9,5 +9,6 @@ export enum InputType {
   PublicUrl = 4,
   Private = 5,
   PublicProof = 7,
-  PrivateLocal = 8
+  PrivateLocal = 8,
+  PriorExecution = 9
 }
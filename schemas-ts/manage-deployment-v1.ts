@@ -0,0 +1,103 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+import * as flatbuffers from 'flatbuffers';
+
+
+
+export class ManageDeploymentV1 implements flatbuffers.IUnpackableObject<ManageDeploymentV1T> {
+  bb: flatbuffers.ByteBuffer|null = null;
+  bb_pos = 0;
+  __init(i:number, bb:flatbuffers.ByteBuffer):ManageDeploymentV1 {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+}
+
+static getRootAsManageDeploymentV1(bb:flatbuffers.ByteBuffer, obj?:ManageDeploymentV1):ManageDeploymentV1 {
+  return (obj || new ManageDeploymentV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+static getSizePrefixedRootAsManageDeploymentV1(bb:flatbuffers.ByteBuffer, obj?:ManageDeploymentV1):ManageDeploymentV1 {
+  bb.setPosition(bb.position() + flatbuffers.SIZE_PREFIX_LENGTH);
+  return (obj || new ManageDeploymentV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+imageId():string|null
+imageId(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+imageId(optionalEncoding?:any):string|Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+}
+
+url():string|null
+url(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+url(optionalEncoding?:any):string|Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+}
+
+static startManageDeploymentV1(builder:flatbuffers.Builder) {
+  builder.startObject(2);
+}
+
+static addImageId(builder:flatbuffers.Builder, imageIdOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, imageIdOffset, 0);
+}
+
+static addUrl(builder:flatbuffers.Builder, urlOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(1, urlOffset, 0);
+}
+
+static endManageDeploymentV1(builder:flatbuffers.Builder):flatbuffers.Offset {
+  const offset = builder.endObject();
+  return offset;
+}
+
+static finishManageDeploymentV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset);
+}
+
+static finishSizePrefixedManageDeploymentV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset, undefined, true);
+}
+
+static createManageDeploymentV1(builder:flatbuffers.Builder, imageIdOffset:flatbuffers.Offset, urlOffset:flatbuffers.Offset):flatbuffers.Offset {
+  ManageDeploymentV1.startManageDeploymentV1(builder);
+  ManageDeploymentV1.addImageId(builder, imageIdOffset);
+  ManageDeploymentV1.addUrl(builder, urlOffset);
+  return ManageDeploymentV1.endManageDeploymentV1(builder);
+}
+
+unpack(): ManageDeploymentV1T {
+  return new ManageDeploymentV1T(
+    this.imageId(),
+    this.url()
+  );
+}
+
+
+unpackTo(_o: ManageDeploymentV1T): void {
+  _o.imageId = this.imageId();
+  _o.url = this.url();
+}
+}
+
+export class ManageDeploymentV1T implements flatbuffers.IGeneratedObject {
+constructor(
+  public imageId: string|Uint8Array|null = null,
+  public url: string|Uint8Array|null = null
+){}
+
+
+pack(builder:flatbuffers.Builder): flatbuffers.Offset {
+  const imageId = (this.imageId !== null ? builder.createString(this.imageId!) : 0);
+  const url = (this.url !== null ? builder.createString(this.url!) : 0);
+
+  return ManageDeploymentV1.createManageDeploymentV1(builder,
+    imageId,
+    url
+  );
+}
+}
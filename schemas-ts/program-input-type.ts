@@ -6,5 +6,6 @@ export enum ProgramInputType {
   Unknown = 0,
   Public = 1,
   Private = 2,
-  PublicProof = 3
+  PublicProof = 3,
+  PriorExecution = 4
 }
@@ -0,0 +1,124 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+import * as flatbuffers from 'flatbuffers';
+
+import { ProverVersion } from './prover-version.js';
+
+
+export class RegisterProverV1 implements flatbuffers.IUnpackableObject<RegisterProverV1T> {
+  bb: flatbuffers.ByteBuffer|null = null;
+  bb_pos = 0;
+  __init(i:number, bb:flatbuffers.ByteBuffer):RegisterProverV1 {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+}
+
+static getRootAsRegisterProverV1(bb:flatbuffers.ByteBuffer, obj?:RegisterProverV1):RegisterProverV1 {
+  return (obj || new RegisterProverV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+static getSizePrefixedRootAsRegisterProverV1(bb:flatbuffers.ByteBuffer, obj?:RegisterProverV1):RegisterProverV1 {
+  bb.setPosition(bb.position() + flatbuffers.SIZE_PREFIX_LENGTH);
+  return (obj || new RegisterProverV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+metadataUri():string|null
+metadataUri(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+metadataUri(optionalEncoding?:any):string|Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+}
+
+proverVersions(index: number):ProverVersion|null {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.readUint16(this.bb!.__vector(this.bb_pos + offset) + index * 2) : 0;
+}
+
+proverVersionsLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+proverVersionsArray():Uint16Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? new Uint16Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+static startRegisterProverV1(builder:flatbuffers.Builder) {
+  builder.startObject(2);
+}
+
+static addMetadataUri(builder:flatbuffers.Builder, metadataUriOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, metadataUriOffset, 0);
+}
+
+static addProverVersions(builder:flatbuffers.Builder, proverVersionsOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(1, proverVersionsOffset, 0);
+}
+
+static createProverVersionsVector(builder:flatbuffers.Builder, data:ProverVersion[]):flatbuffers.Offset {
+  builder.startVector(2, data.length, 2);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt16(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startProverVersionsVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(2, numElems, 2);
+}
+
+static endRegisterProverV1(builder:flatbuffers.Builder):flatbuffers.Offset {
+  const offset = builder.endObject();
+  return offset;
+}
+
+static finishRegisterProverV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset);
+}
+
+static finishSizePrefixedRegisterProverV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset, undefined, true);
+}
+
+static createRegisterProverV1(builder:flatbuffers.Builder, metadataUriOffset:flatbuffers.Offset, proverVersionsOffset:flatbuffers.Offset):flatbuffers.Offset {
+  RegisterProverV1.startRegisterProverV1(builder);
+  RegisterProverV1.addMetadataUri(builder, metadataUriOffset);
+  RegisterProverV1.addProverVersions(builder, proverVersionsOffset);
+  return RegisterProverV1.endRegisterProverV1(builder);
+}
+
+unpack(): RegisterProverV1T {
+  return new RegisterProverV1T(
+    this.metadataUri(),
+    this.bb!.createScalarList<ProverVersion>(this.proverVersions.bind(this), this.proverVersionsLength())
+  );
+}
+
+
+unpackTo(_o: RegisterProverV1T): void {
+  _o.metadataUri = this.metadataUri();
+  _o.proverVersions = this.bb!.createScalarList<ProverVersion>(this.proverVersions.bind(this), this.proverVersionsLength());
+}
+}
+
+export class RegisterProverV1T implements flatbuffers.IGeneratedObject {
+constructor(
+  public metadataUri: string|Uint8Array|null = null,
+  public proverVersions: (ProverVersion)[] = []
+){}
+
+
+pack(builder:flatbuffers.Builder): flatbuffers.Offset {
+  const metadataUri = (this.metadataUri !== null ? builder.createString(this.metadataUri!) : 0);
+  const proverVersions = RegisterProverV1.createProverVersionsVector(builder, this.proverVersions);
+
+  return RegisterProverV1.createRegisterProverV1(builder,
+    metadataUri,
+    proverVersions
+  );
+}
+}
@@ -0,0 +1,176 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+import * as flatbuffers from 'flatbuffers';
+
+import { StatusV1, StatusV1T } from './status-v1.js';
+
+
+export class StatusBatchV1 implements flatbuffers.IUnpackableObject<StatusBatchV1T> {
+  bb: flatbuffers.ByteBuffer|null = null;
+  bb_pos = 0;
+  __init(i:number, bb:flatbuffers.ByteBuffer):StatusBatchV1 {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+}
+
+static getRootAsStatusBatchV1(bb:flatbuffers.ByteBuffer, obj?:StatusBatchV1):StatusBatchV1 {
+  return (obj || new StatusBatchV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+static getSizePrefixedRootAsStatusBatchV1(bb:flatbuffers.ByteBuffer, obj?:StatusBatchV1):StatusBatchV1 {
+  bb.setPosition(bb.position() + flatbuffers.SIZE_PREFIX_LENGTH);
+  return (obj || new StatusBatchV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+proof(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+proofLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+proofArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+executionDigest(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+executionDigestLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+executionDigestArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+statuses(index: number, obj?:StatusV1):StatusV1|null {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? (obj || new StatusV1()).__init(this.bb!.__indirect(this.bb!.__vector(this.bb_pos + offset) + index * 4), this.bb!) : null;
+}
+
+statusesLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+static startStatusBatchV1(builder:flatbuffers.Builder) {
+  builder.startObject(3);
+}
+
+static addProof(builder:flatbuffers.Builder, proofOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(0, proofOffset, 0);
+}
+
+static createProofVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startProofVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addExecutionDigest(builder:flatbuffers.Builder, executionDigestOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(1, executionDigestOffset, 0);
+}
+
+static createExecutionDigestVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startExecutionDigestVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addStatuses(builder:flatbuffers.Builder, statusesOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(2, statusesOffset, 0);
+}
+
+static createStatusesVector(builder:flatbuffers.Builder, data:flatbuffers.Offset[]):flatbuffers.Offset {
+  builder.startVector(4, data.length, 4);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addOffset(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startStatusesVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(4, numElems, 4);
+}
+
+static endStatusBatchV1(builder:flatbuffers.Builder):flatbuffers.Offset {
+  const offset = builder.endObject();
+  return offset;
+}
+
+static finishStatusBatchV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset);
+}
+
+static finishSizePrefixedStatusBatchV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset, undefined, true);
+}
+
+static createStatusBatchV1(builder:flatbuffers.Builder, proofOffset:flatbuffers.Offset, executionDigestOffset:flatbuffers.Offset, statusesOffset:flatbuffers.Offset):flatbuffers.Offset {
+  StatusBatchV1.startStatusBatchV1(builder);
+  StatusBatchV1.addProof(builder, proofOffset);
+  StatusBatchV1.addExecutionDigest(builder, executionDigestOffset);
+  StatusBatchV1.addStatuses(builder, statusesOffset);
+  return StatusBatchV1.endStatusBatchV1(builder);
+}
+
+unpack(): StatusBatchV1T {
+  return new StatusBatchV1T(
+    this.bb!.createScalarList<number>(this.proof.bind(this), this.proofLength()),
+    this.bb!.createScalarList<number>(this.executionDigest.bind(this), this.executionDigestLength()),
+    this.bb!.createObjList<StatusV1, StatusV1T>(this.statuses.bind(this), this.statusesLength())
+  );
+}
+
+
+unpackTo(_o: StatusBatchV1T): void {
+  _o.proof = this.bb!.createScalarList<number>(this.proof.bind(this), this.proofLength());
+  _o.executionDigest = this.bb!.createScalarList<number>(this.executionDigest.bind(this), this.executionDigestLength());
+  _o.statuses = this.bb!.createObjList<StatusV1, StatusV1T>(this.statuses.bind(this), this.statusesLength());
+}
+}
+
+export class StatusBatchV1T implements flatbuffers.IGeneratedObject {
+constructor(
+  public proof: (number)[] = [],
+  public executionDigest: (number)[] = [],
+  public statuses: (StatusV1T)[] = []
+){}
+
+
+pack(builder:flatbuffers.Builder): flatbuffers.Offset {
+  const proof = StatusBatchV1.createProofVector(builder, this.proof);
+  const executionDigest = StatusBatchV1.createExecutionDigestVector(builder, this.executionDigest);
+  const statuses = StatusBatchV1.createStatusesVector(builder, builder.createObjectOffsetList(this.statuses));
+
+  return StatusBatchV1.createStatusBatchV1(builder,
+    proof,
+    executionDigest,
+    statuses
+  );
+}
+}
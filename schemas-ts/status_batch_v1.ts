@@ -0,0 +1,7 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+export { StatusBatchV1, StatusBatchV1T } from './status-batch-v1.js';
+export { StatusTypes } from './status-types.js';
+export { StatusV1, StatusV1T } from './status-v1.js';
@@ -0,0 +1,396 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+import * as flatbuffers from 'flatbuffers';
+
+import { ProverVersion } from './prover-version.js';
+
+
+export class VerifierConfigV1 implements flatbuffers.IUnpackableObject<VerifierConfigV1T> {
+  bb: flatbuffers.ByteBuffer|null = null;
+  bb_pos = 0;
+  __init(i:number, bb:flatbuffers.ByteBuffer):VerifierConfigV1 {
+  this.bb_pos = i;
+  this.bb = bb;
+  return this;
+}
+
+static getRootAsVerifierConfigV1(bb:flatbuffers.ByteBuffer, obj?:VerifierConfigV1):VerifierConfigV1 {
+  return (obj || new VerifierConfigV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+static getSizePrefixedRootAsVerifierConfigV1(bb:flatbuffers.ByteBuffer, obj?:VerifierConfigV1):VerifierConfigV1 {
+  bb.setPosition(bb.position() + flatbuffers.SIZE_PREFIX_LENGTH);
+  return (obj || new VerifierConfigV1()).__init(bb.readInt32(bb.position()) + bb.position(), bb);
+}
+
+proverVersion():ProverVersion {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+  return offset ? this.bb!.readUint16(this.bb_pos + offset) : ProverVersion.DEFAULT;
+}
+
+mutate_prover_version(value:ProverVersion):boolean {
+  const offset = this.bb!.__offset(this.bb_pos, 4);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeUint16(this.bb_pos + offset, value);
+  return true;
+}
+
+enabled():boolean {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+  return offset ? !!this.bb!.readInt8(this.bb_pos + offset) : true;
+}
+
+mutate_enabled(value:boolean):boolean {
+  const offset = this.bb!.__offset(this.bb_pos, 6);
+
+  if (offset === 0) {
+    return false;
+  }
+
+  this.bb!.writeInt8(this.bb_pos + offset, +value);
+  return true;
+}
+
+controlRoot(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+controlRootLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+controlRootArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 8);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+bn254ControlId(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+bn254ControlIdLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+bn254ControlIdArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 10);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+vkAlphaG1(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 12);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+vkAlphaG1Length():number {
+  const offset = this.bb!.__offset(this.bb_pos, 12);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+vkAlphaG1Array():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 12);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+vkBetaG2(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 14);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+vkBetaG2Length():number {
+  const offset = this.bb!.__offset(this.bb_pos, 14);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+vkBetaG2Array():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 14);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+vkGammaG2(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 16);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+vkGammaG2Length():number {
+  const offset = this.bb!.__offset(this.bb_pos, 16);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+vkGammaG2Array():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 16);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+vkDeltaG2(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 18);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+vkDeltaG2Length():number {
+  const offset = this.bb!.__offset(this.bb_pos, 18);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+vkDeltaG2Array():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 18);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+vkIc(index: number):number|null {
+  const offset = this.bb!.__offset(this.bb_pos, 20);
+  return offset ? this.bb!.readUint8(this.bb!.__vector(this.bb_pos + offset) + index) : 0;
+}
+
+vkIcLength():number {
+  const offset = this.bb!.__offset(this.bb_pos, 20);
+  return offset ? this.bb!.__vector_len(this.bb_pos + offset) : 0;
+}
+
+vkIcArray():Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 20);
+  return offset ? new Uint8Array(this.bb!.bytes().buffer, this.bb!.bytes().byteOffset + this.bb!.__vector(this.bb_pos + offset), this.bb!.__vector_len(this.bb_pos + offset)) : null;
+}
+
+batchImageId():string|null
+batchImageId(optionalEncoding:flatbuffers.Encoding):string|Uint8Array|null
+batchImageId(optionalEncoding?:any):string|Uint8Array|null {
+  const offset = this.bb!.__offset(this.bb_pos, 22);
+  return offset ? this.bb!.__string(this.bb_pos + offset, optionalEncoding) : null;
+}
+
+static startVerifierConfigV1(builder:flatbuffers.Builder) {
+  builder.startObject(10);
+}
+
+static addProverVersion(builder:flatbuffers.Builder, proverVersion:ProverVersion) {
+  builder.addFieldInt16(0, proverVersion, ProverVersion.DEFAULT);
+}
+
+static addEnabled(builder:flatbuffers.Builder, enabled:boolean) {
+  builder.addFieldInt8(1, +enabled, +true);
+}
+
+static addControlRoot(builder:flatbuffers.Builder, controlRootOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(2, controlRootOffset, 0);
+}
+
+static createControlRootVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startControlRootVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addBn254ControlId(builder:flatbuffers.Builder, bn254ControlIdOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(3, bn254ControlIdOffset, 0);
+}
+
+static createBn254ControlIdVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startBn254ControlIdVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addVkAlphaG1(builder:flatbuffers.Builder, vkAlphaG1Offset:flatbuffers.Offset) {
+  builder.addFieldOffset(4, vkAlphaG1Offset, 0);
+}
+
+static createVkAlphaG1Vector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startVkAlphaG1Vector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addVkBetaG2(builder:flatbuffers.Builder, vkBetaG2Offset:flatbuffers.Offset) {
+  builder.addFieldOffset(5, vkBetaG2Offset, 0);
+}
+
+static createVkBetaG2Vector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startVkBetaG2Vector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addVkGammaG2(builder:flatbuffers.Builder, vkGammaG2Offset:flatbuffers.Offset) {
+  builder.addFieldOffset(6, vkGammaG2Offset, 0);
+}
+
+static createVkGammaG2Vector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startVkGammaG2Vector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addVkDeltaG2(builder:flatbuffers.Builder, vkDeltaG2Offset:flatbuffers.Offset) {
+  builder.addFieldOffset(7, vkDeltaG2Offset, 0);
+}
+
+static createVkDeltaG2Vector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startVkDeltaG2Vector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addVkIc(builder:flatbuffers.Builder, vkIcOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(8, vkIcOffset, 0);
+}
+
+static createVkIcVector(builder:flatbuffers.Builder, data:number[]|Uint8Array):flatbuffers.Offset {
+  builder.startVector(1, data.length, 1);
+  for (let i = data.length - 1; i >= 0; i--) {
+    builder.addInt8(data[i]!);
+  }
+  return builder.endVector();
+}
+
+static startVkIcVector(builder:flatbuffers.Builder, numElems:number) {
+  builder.startVector(1, numElems, 1);
+}
+
+static addBatchImageId(builder:flatbuffers.Builder, batchImageIdOffset:flatbuffers.Offset) {
+  builder.addFieldOffset(9, batchImageIdOffset, 0);
+}
+
+static endVerifierConfigV1(builder:flatbuffers.Builder):flatbuffers.Offset {
+  const offset = builder.endObject();
+  return offset;
+}
+
+static finishVerifierConfigV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset);
+}
+
+static finishSizePrefixedVerifierConfigV1Buffer(builder:flatbuffers.Builder, offset:flatbuffers.Offset) {
+  builder.finish(offset, undefined, true);
+}
+
+static createVerifierConfigV1(builder:flatbuffers.Builder, proverVersion:ProverVersion, enabled:boolean, controlRootOffset:flatbuffers.Offset, bn254ControlIdOffset:flatbuffers.Offset, vkAlphaG1Offset:flatbuffers.Offset, vkBetaG2Offset:flatbuffers.Offset, vkGammaG2Offset:flatbuffers.Offset, vkDeltaG2Offset:flatbuffers.Offset, vkIcOffset:flatbuffers.Offset, batchImageIdOffset:flatbuffers.Offset):flatbuffers.Offset {
+  VerifierConfigV1.startVerifierConfigV1(builder);
+  VerifierConfigV1.addProverVersion(builder, proverVersion);
+  VerifierConfigV1.addEnabled(builder, enabled);
+  VerifierConfigV1.addControlRoot(builder, controlRootOffset);
+  VerifierConfigV1.addBn254ControlId(builder, bn254ControlIdOffset);
+  VerifierConfigV1.addVkAlphaG1(builder, vkAlphaG1Offset);
+  VerifierConfigV1.addVkBetaG2(builder, vkBetaG2Offset);
+  VerifierConfigV1.addVkGammaG2(builder, vkGammaG2Offset);
+  VerifierConfigV1.addVkDeltaG2(builder, vkDeltaG2Offset);
+  VerifierConfigV1.addVkIc(builder, vkIcOffset);
+  VerifierConfigV1.addBatchImageId(builder, batchImageIdOffset);
+  return VerifierConfigV1.endVerifierConfigV1(builder);
+}
+
+unpack(): VerifierConfigV1T {
+  return new VerifierConfigV1T(
+    this.proverVersion(),
+    this.enabled(),
+    this.bb!.createScalarList<number>(this.controlRoot.bind(this), this.controlRootLength()),
+    this.bb!.createScalarList<number>(this.bn254ControlId.bind(this), this.bn254ControlIdLength()),
+    this.bb!.createScalarList<number>(this.vkAlphaG1.bind(this), this.vkAlphaG1Length()),
+    this.bb!.createScalarList<number>(this.vkBetaG2.bind(this), this.vkBetaG2Length()),
+    this.bb!.createScalarList<number>(this.vkGammaG2.bind(this), this.vkGammaG2Length()),
+    this.bb!.createScalarList<number>(this.vkDeltaG2.bind(this), this.vkDeltaG2Length()),
+    this.bb!.createScalarList<number>(this.vkIc.bind(this), this.vkIcLength()),
+    this.batchImageId()
+  );
+}
+
+
+unpackTo(_o: VerifierConfigV1T): void {
+  _o.proverVersion = this.proverVersion();
+  _o.enabled = this.enabled();
+  _o.controlRoot = this.bb!.createScalarList<number>(this.controlRoot.bind(this), this.controlRootLength());
+  _o.bn254ControlId = this.bb!.createScalarList<number>(this.bn254ControlId.bind(this), this.bn254ControlIdLength());
+  _o.vkAlphaG1 = this.bb!.createScalarList<number>(this.vkAlphaG1.bind(this), this.vkAlphaG1Length());
+  _o.vkBetaG2 = this.bb!.createScalarList<number>(this.vkBetaG2.bind(this), this.vkBetaG2Length());
+  _o.vkGammaG2 = this.bb!.createScalarList<number>(this.vkGammaG2.bind(this), this.vkGammaG2Length());
+  _o.vkDeltaG2 = this.bb!.createScalarList<number>(this.vkDeltaG2.bind(this), this.vkDeltaG2Length());
+  _o.vkIc = this.bb!.createScalarList<number>(this.vkIc.bind(this), this.vkIcLength());
+  _o.batchImageId = this.batchImageId();
+}
+}
+
+export class VerifierConfigV1T implements flatbuffers.IGeneratedObject {
+constructor(
+  public proverVersion: ProverVersion = ProverVersion.DEFAULT,
+  public enabled: boolean = true,
+  public controlRoot: (number)[] = [],
+  public bn254ControlId: (number)[] = [],
+  public vkAlphaG1: (number)[] = [],
+  public vkBetaG2: (number)[] = [],
+  public vkGammaG2: (number)[] = [],
+  public vkDeltaG2: (number)[] = [],
+  public vkIc: (number)[] = [],
+  public batchImageId: string|Uint8Array|null = null
+){}
+
+
+pack(builder:flatbuffers.Builder): flatbuffers.Offset {
+  const controlRoot = VerifierConfigV1.createControlRootVector(builder, this.controlRoot);
+  const bn254ControlId = VerifierConfigV1.createBn254ControlIdVector(builder, this.bn254ControlId);
+  const vkAlphaG1 = VerifierConfigV1.createVkAlphaG1Vector(builder, this.vkAlphaG1);
+  const vkBetaG2 = VerifierConfigV1.createVkBetaG2Vector(builder, this.vkBetaG2);
+  const vkGammaG2 = VerifierConfigV1.createVkGammaG2Vector(builder, this.vkGammaG2);
+  const vkDeltaG2 = VerifierConfigV1.createVkDeltaG2Vector(builder, this.vkDeltaG2);
+  const vkIc = VerifierConfigV1.createVkIcVector(builder, this.vkIc);
+  const batchImageId = (this.batchImageId !== null ? builder.createString(this.batchImageId!) : 0);
+
+  return VerifierConfigV1.createVerifierConfigV1(builder,
+    this.proverVersion,
+    this.enabled,
+    controlRoot,
+    bn254ControlId,
+    vkAlphaG1,
+    vkBetaG2,
+    vkGammaG2,
+    vkDeltaG2,
+    vkIc,
+    batchImageId
+  );
+}
+}
@@ -0,0 +1,13 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+/* eslint-disable @typescript-eslint/no-unused-vars, @typescript-eslint/no-explicit-any, @typescript-eslint/no-non-null-assertion */
+
+export { Account, AccountT } from './account.js';
+export { AllowListV1, AllowListV1T } from './allow-list-v1.js';
+export { Claimer, ClaimerT } from './claimer.js';
+export { ExecutionRequestV1, ExecutionRequestV1T } from './execution-request-v1.js';
+export { Input, InputT } from './input.js';
+export { InputType } from './input-type.js';
+export { ProgramInputType } from './program-input-type.js';
+export { ProverVersion } from './prover-version.js';
+export { VerifierConfigV1, VerifierConfigV1T } from './verifier-config-v1.js';
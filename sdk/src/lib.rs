@@ -5,6 +5,8 @@ use anyhow::Result;
 use bytes::Bytes;
 use futures_util::TryFutureExt;
 use num_traits::FromPrimitive;
+use risc0_zkvm::sha::Digestible;
+use risc0_zkvm::Receipt;
 
 use solana_rpc_client::nonblocking::rpc_client::RpcClient;
 use solana_rpc_client_api::config::RpcSendTransactionConfig;
@@ -19,16 +21,23 @@ use solana_sdk::transaction::VersionedTransaction;
 
 use tokio::time::Instant;
 
-use bonsol_interface::bonsol_schema::{root_as_deploy_v1, root_as_execution_request_v1};
+pub use bonsol_interface::bid_state::{BidStateHolder, BidStateV1, PlacedBid};
 pub use bonsol_interface::bonsol_schema::{
-    ClaimV1T, DeployV1T, ExecutionRequestV1T, ExitCode, InputT, InputType, ProgramInputType,
-    StatusTypes,
+    parse_prior_execution, prior_execution_input, AllowListV1T, ClaimV1T, DeployV1T,
+    ExecutionRequestV1T, ExitCode, InputT, InputType, ProgramInputType, StatusTypes,
+    VerifierConfigV1T,
+};
+use bonsol_interface::bonsol_schema::{
+    root_as_allow_list_v1, root_as_deploy_v1, root_as_execution_request_v1,
+    root_as_verifier_config_v1, ProverVersion as FBSProverVersion,
 };
 use bonsol_interface::claim_state::ClaimStateHolder;
+pub use bonsol_interface::prover_state::{ProverHolder, ProverV1};
 use bonsol_interface::prover_version::ProverVersion;
 pub use bonsol_interface::util::*;
-pub use bonsol_interface::{instructions, ID};
+pub use bonsol_interface::{instructions, prover_version, verifier_config, ID};
 use instructions::{CallbackConfig, ExecutionConfig, InputRef};
+use verifier_config::VerifierConfig;
 
 pub use flatbuffers;
 
@@ -73,6 +82,42 @@ impl BonsolClient {
         Ok(deployment.unpack())
     }
 
+    /// The allow list `authority` keeps under `id`
+    pub async fn get_allow_list_v1(&self, authority: &Pubkey, id: &str) -> Result<AllowListV1T> {
+        let (allow_list_account, _) = allow_list_address(authority, id);
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(&allow_list_account, CommitmentConfig::confirmed())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get account: {:?}", e))?
+            .value
+            .ok_or(anyhow::anyhow!("Invalid allow list account"))?;
+        let allow_list = root_as_allow_list_v1(&account.data)
+            .map_err(|_| anyhow::anyhow!("Invalid allow list account"))?;
+        Ok(allow_list.unpack())
+    }
+
+    /// The verifier config of `prover_version`, None when the program verifies its proofs with
+    /// the verifier compiled into it
+    pub async fn get_verifier_config_v1(
+        &self,
+        prover_version: FBSProverVersion,
+    ) -> Result<Option<VerifierConfigV1T>> {
+        let (verifier_config_account, _) = verifier_config_address(prover_version);
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(&verifier_config_account, CommitmentConfig::confirmed())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get account: {:?}", e))?
+            .value;
+        let Some(account) = account else {
+            return Ok(None);
+        };
+        let verifier_config = root_as_verifier_config_v1(&account.data)
+            .map_err(|_| anyhow::anyhow!("Invalid verifier config account"))?;
+        Ok(Some(verifier_config.unpack()))
+    }
+
     pub async fn get_execution_request_v1(
         &self,
         requester_pubkey: &Pubkey,
@@ -86,7 +131,8 @@ impl BonsolClient {
             .map_err(|e| anyhow::anyhow!("Failed to get account: {:?}", e))?
             .value
             .ok_or(anyhow::anyhow!("Invalid execution request account"))?;
-        if account.data.len() == 1 {
+        // settled executions keep their exit code, proven ones the digest of their claim too
+        if account.data.len() <= PROVEN_EXECUTION_LEN {
             let ec =
                 ExitCode::from_u8(account.data[0]).ok_or(anyhow::anyhow!("Invalid exit code"))?;
             return Ok(ExecutionAccountStatus::Completed(ec));
@@ -113,6 +159,41 @@ impl BonsolClient {
         Ok(ClaimStateHolder::new(account.data))
     }
 
+    /// The bids for the claim on an auctioned execution, None once the auction is decided
+    pub async fn get_bids_v1(
+        &self,
+        requester_pubkey: &Pubkey,
+        execution_id: &str,
+    ) -> Result<Option<BidStateV1>> {
+        let (exad, _) = execution_address(requester_pubkey, execution_id.as_bytes());
+        let (bids_account, _) = execution_bids_address(exad.as_ref());
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(&bids_account, CommitmentConfig::confirmed())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get account: {:?}", e))?
+            .value;
+        account
+            .map(|a| BidStateHolder::new(a.data).bids())
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("Invalid bids account: {:?}", e))
+    }
+
+    /// The registration of a prover, None when it never registered
+    pub async fn get_prover_v1(&self, operator: &Pubkey) -> Result<Option<ProverV1>> {
+        let (prover_account, _) = prover_address(operator);
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(&prover_account, CommitmentConfig::confirmed())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get account: {:?}", e))?
+            .value;
+        account
+            .map(|a| ProverHolder::new(a.data).prover())
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("Invalid prover account: {:?}", e))
+    }
+
     pub async fn download_program(&self, image_id: &str) -> Result<Bytes> {
         let deployment = self.get_deployment_v1(image_id).await?;
         let url = deployment
@@ -121,9 +202,59 @@ impl BonsolClient {
         let resp = reqwest::get(url)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to download program: {:?}", e))?;
-        resp.bytes()
+        let bytes = resp
+            .bytes()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to download program: {:?}", e))?;
+        // deployments made before checksums were required can only be checked by their image id
+        if let Some(checksum) = deployment.image_checksum {
+            if image_checksum(&bytes).as_slice() != checksum.as_slice() {
+                return Err(anyhow::anyhow!(
+                    "Downloaded program does not match the checksum of deployment {}",
+                    image_id
+                ));
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// The receipt of a proven execution with its journal, from the receipt api of the node at
+    /// `node_url`. The receipt is checked against the claim digest the program recorded when it
+    /// verified the execution's proof.
+    pub async fn fetch_receipt(
+        &self,
+        node_url: &str,
+        requester_pubkey: &Pubkey,
+        execution_id: &str,
+    ) -> Result<Receipt> {
+        let (er, _) = execution_address(requester_pubkey, execution_id.as_bytes());
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(&er, CommitmentConfig::confirmed())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get account: {:?}", e))?
+            .value
+            .ok_or(anyhow::anyhow!("Invalid execution request account"))?;
+        let claim_digest = proven_claim_digest(&account.data)
+            .ok_or(anyhow::anyhow!("Execution {} was not proven", execution_id))?;
+        let mut url = reqwest::Url::parse(node_url)?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("Invalid node url {}", node_url))?
+            .pop_if_empty()
+            .extend(["receipts", &requester_pubkey.to_string(), execution_id]);
+        let bytes = reqwest::get(url)
+            .and_then(|resp| async move { resp.error_for_status()?.bytes().await })
             .await
-            .map_err(|e| anyhow::anyhow!("Failed to download program: {:?}", e))
+            .map_err(|e| anyhow::anyhow!("Failed to fetch receipt: {:?}", e))?;
+        let receipt: Receipt = bincode::deserialize(&bytes)
+            .map_err(|e| anyhow::anyhow!("Invalid receipt: {:?}", e))?;
+        if receipt.claim()?.digest().as_bytes() != claim_digest.as_slice() {
+            return Err(anyhow::anyhow!(
+                "Receipt does not match the proof of execution {}",
+                execution_id
+            ));
+        }
+        Ok(receipt)
     }
 
     pub async fn get_deployment(&self, image_id: &str) -> Result<Option<Account>> {
@@ -154,18 +285,212 @@ impl BonsolClient {
         signer: &Pubkey,
         image_id: &str,
         image_size: u64,
+        image_checksum: &[u8],
         program_name: &str,
         url: &str,
         inputs: Vec<ProgramInputType>,
     ) -> Result<Vec<Instruction>> {
         let compute_price_val = self.get_fees(signer).await?;
+        let instruction = instructions::deploy_v1(
+            signer,
+            image_id,
+            image_size,
+            image_checksum,
+            program_name,
+            url,
+            inputs,
+        )?;
+        let compute = ComputeBudgetInstruction::set_compute_unit_limit(20_000);
+        let compute_price = ComputeBudgetInstruction::set_compute_unit_price(compute_price_val);
+        Ok(vec![compute, compute_price, instruction])
+    }
+
+    /// Withdraws a pending execution request, refused while a prover holds an active claim on it
+    pub async fn cancel_v1(
+        &self,
+        requester: &Pubkey,
+        execution_id: &str,
+    ) -> Result<Vec<Instruction>> {
+        let mut claimer = None;
+        if let Ok(claim_state) = self.get_claim_state_v1(requester, execution_id).await {
+            let claim = claim_state.claim()?;
+            if self.get_current_slot().await? <= claim.block_commitment {
+                return Err(anyhow::anyhow!(
+                    "Execution {} is claimed until slot {}",
+                    execution_id,
+                    claim.block_commitment
+                ));
+            }
+            claimer = Some(Pubkey::new_from_array(claim.claimer));
+        }
+        let compute_price_val = self.get_fees(requester).await?;
+        let instruction = instructions::cancel_v1(requester, execution_id, claimer.as_ref())?;
+        let compute = ComputeBudgetInstruction::set_compute_unit_limit(20_000);
+        let compute_price = ComputeBudgetInstruction::set_compute_unit_price(compute_price_val);
+        Ok(vec![compute, compute_price, instruction])
+    }
+
+    /// Closes an execution request that passed its max block height without being settled, the
+    /// requester is refunded and `cranker` is paid the crank fee
+    pub async fn expire_v1(
+        &self,
+        cranker: &Pubkey,
+        requester: &Pubkey,
+        execution_id: &str,
+    ) -> Result<Vec<Instruction>> {
+        let claimer = match self.get_claim_state_v1(requester, execution_id).await {
+            Ok(claim_state) => Some(Pubkey::new_from_array(claim_state.claim()?.claimer)),
+            Err(_) => None,
+        };
+        let compute_price_val = self.get_fees(cranker).await?;
         let instruction =
-            instructions::deploy_v1(signer, image_id, image_size, program_name, url, inputs)?;
+            instructions::expire_v1(cranker, requester, execution_id, claimer.as_ref())?;
         let compute = ComputeBudgetInstruction::set_compute_unit_limit(20_000);
         let compute_price = ComputeBudgetInstruction::set_compute_unit_price(compute_price_val);
         Ok(vec![compute, compute_price, instruction])
     }
 
+    /// Bids `price` lamports for the claim on an auctioned execution, committing to prove it by
+    /// `completion_slot` if the bid wins
+    pub async fn bid_v1(
+        &self,
+        bidder: &Pubkey,
+        requester: &Pubkey,
+        execution_id: &str,
+        price: u64,
+        completion_slot: u64,
+    ) -> Result<Vec<Instruction>> {
+        let claimer_allow_list = match self
+            .get_execution_request_v1(requester, execution_id)
+            .await?
+        {
+            ExecutionAccountStatus::Pending(er) => er
+                .claimer_allow_list
+                .and_then(|a| Pubkey::try_from(a.as_slice()).ok()),
+            ExecutionAccountStatus::Completed(_) => {
+                return Err(anyhow::anyhow!("Execution {} is settled", execution_id))
+            }
+        };
+        let compute_price_val = self.get_fees(bidder).await?;
+        let instruction = instructions::bid_v1(
+            bidder,
+            requester,
+            execution_id,
+            price,
+            completion_slot,
+            claimer_allow_list.as_ref(),
+        )?;
+        let compute = ComputeBudgetInstruction::set_compute_unit_limit(40_000);
+        let compute_price = ComputeBudgetInstruction::set_compute_unit_price(compute_price_val);
+        Ok(vec![compute, compute_price, instruction])
+    }
+
+    /// Registers `operator` as a prover or updates its registration, requesters can then require
+    /// a track record from the provers claiming their executions
+    pub async fn register_prover_v1(
+        &self,
+        operator: &Pubkey,
+        metadata_uri: &str,
+        prover_versions: &[ProverVersion],
+    ) -> Result<Vec<Instruction>> {
+        let fbs_versions = prover_versions
+            .iter()
+            .map(|version| {
+                TryInto::<FBSProverVersion>::try_into(version.clone())
+                    .map_err(|_| anyhow::anyhow!("Unsupported prover version {}", version))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let compute_price_val = self.get_fees(operator).await?;
+        let instruction = instructions::register_prover_v1(operator, metadata_uri, &fbs_versions)?;
+        let compute = ComputeBudgetInstruction::set_compute_unit_limit(20_000);
+        let compute_price = ComputeBudgetInstruction::set_compute_unit_price(compute_price_val);
+        Ok(vec![compute, compute_price, instruction])
+    }
+
+    pub async fn set_allow_list_v1(
+        &self,
+        authority: &Pubkey,
+        id: &str,
+        claimers: &[Pubkey],
+    ) -> Result<Vec<Instruction>> {
+        let instruction = instructions::set_allow_list_v1(authority, id, claimers)?;
+        self.with_allow_list_budget(authority, instruction).await
+    }
+
+    pub async fn close_allow_list_v1(
+        &self,
+        authority: &Pubkey,
+        id: &str,
+    ) -> Result<Vec<Instruction>> {
+        let instruction = instructions::close_allow_list_v1(authority, id)?;
+        self.with_allow_list_budget(authority, instruction).await
+    }
+
+    /// Adds, replaces or disables the verifier of a prover version, `authority` must be the
+    /// upgrade authority of the program
+    pub async fn set_verifier_config_v1(
+        &self,
+        authority: &Pubkey,
+        config: &VerifierConfig,
+    ) -> Result<Vec<Instruction>> {
+        let compute_price_val = self.get_fees(authority).await?;
+        let instruction = instructions::set_verifier_config_v1(authority, config)?;
+        let compute = ComputeBudgetInstruction::set_compute_unit_limit(40_000);
+        let compute_price = ComputeBudgetInstruction::set_compute_unit_price(compute_price_val);
+        Ok(vec![compute, compute_price, instruction])
+    }
+
+    async fn with_allow_list_budget(
+        &self,
+        signer: &Pubkey,
+        instruction: Instruction,
+    ) -> Result<Vec<Instruction>> {
+        let compute_price_val = self.get_fees(signer).await?;
+        let compute = ComputeBudgetInstruction::set_compute_unit_limit(30_000);
+        let compute_price = ComputeBudgetInstruction::set_compute_unit_price(compute_price_val);
+        Ok(vec![compute, compute_price, instruction])
+    }
+
+    pub async fn update_deployment_url_v1(
+        &self,
+        owner: &Pubkey,
+        image_id: &str,
+        url: &str,
+    ) -> Result<Vec<Instruction>> {
+        let instruction = instructions::update_deployment_url_v1(owner, image_id, url)?;
+        self.with_manage_deployment_budget(owner, instruction).await
+    }
+
+    pub async fn deprecate_deployment_v1(
+        &self,
+        owner: &Pubkey,
+        image_id: &str,
+    ) -> Result<Vec<Instruction>> {
+        let instruction = instructions::deprecate_deployment_v1(owner, image_id)?;
+        self.with_manage_deployment_budget(owner, instruction).await
+    }
+
+    pub async fn close_deployment_v1(
+        &self,
+        owner: &Pubkey,
+        image_id: &str,
+    ) -> Result<Vec<Instruction>> {
+        let instruction = instructions::close_deployment_v1(owner, image_id)?;
+        self.with_manage_deployment_budget(owner, instruction).await
+    }
+
+    async fn with_manage_deployment_budget(
+        &self,
+        signer: &Pubkey,
+        instruction: Instruction,
+    ) -> Result<Vec<Instruction>> {
+        let compute_price_val = self.get_fees(signer).await?;
+        // rewriting the deployment unpacks and packs the whole flatbuffer
+        let compute = ComputeBudgetInstruction::set_compute_unit_limit(50_000);
+        let compute_price = ComputeBudgetInstruction::set_compute_unit_price(compute_price_val);
+        Ok(vec![compute, compute_price, instruction])
+    }
+
     pub async fn execute_v1<'a>(
         &self,
         signer: &Pubkey,
@@ -180,6 +505,12 @@ impl BonsolClient {
     ) -> Result<Vec<Instruction>> {
         let compute_price_val = self.get_fees(signer).await?;
 
+        // the bids account of an auctioned request is created with it
+        let compute_units = if config.auction_slots > 0 {
+            40_000
+        } else {
+            20_000
+        };
         let fbs_version_or_none = match prover_version {
             Some(version) => {
                 let fbs_version = version.try_into().expect("Unknown prover version");
@@ -200,7 +531,7 @@ impl BonsolClient {
             callback,
             fbs_version_or_none,
         )?;
-        let compute = ComputeBudgetInstruction::set_compute_unit_limit(20_000);
+        let compute = ComputeBudgetInstruction::set_compute_unit_limit(compute_units);
         let compute_price = ComputeBudgetInstruction::set_compute_unit_price(compute_price_val);
         Ok(vec![compute, compute_price, instruction])
     }
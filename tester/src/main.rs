@@ -72,6 +72,7 @@ async fn example_sdk_test(
                 verify_input_hash: true,
                 input_hash: Some(input_hash.as_ref()),
                 forward_output: true,
+                ..Default::default()
             },
             Some(CallbackConfig {
                 program_id: example_program,